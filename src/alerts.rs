@@ -0,0 +1,196 @@
+use std::{fmt, time::Duration};
+
+use iced::{
+    widget::{button, column, pick_list, row, text, text_input, Column},
+    Alignment, Element,
+};
+use notify_rust::Notification;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::Error,
+    fiat::shows_fiat,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+// the wallet is polled this often for balance alerts when auto refresh is off
+pub const ALERT_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlertKind {
+    PriceAbove,
+    PriceBelow,
+    BalanceAbove,
+    BalanceBelow,
+}
+
+impl AlertKind {
+    pub const ALL: [AlertKind; 4] = [
+        AlertKind::PriceAbove,
+        AlertKind::PriceBelow,
+        AlertKind::BalanceAbove,
+        AlertKind::BalanceBelow,
+    ];
+
+    fn is_price(&self) -> bool {
+        matches!(self, AlertKind::PriceAbove | AlertKind::PriceBelow)
+    }
+}
+
+impl fmt::Display for AlertKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlertKind::PriceAbove => write!(f, "{}", tr("SOL price above")),
+            AlertKind::PriceBelow => write!(f, "{}", tr("SOL price below")),
+            AlertKind::BalanceAbove => write!(f, "{}", tr("Balance above (SOL)")),
+            AlertKind::BalanceBelow => write!(f, "{}", tr("Balance below (SOL)")),
+        }
+    }
+}
+
+// price thresholds are in the fiat currency picked in settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub threshold: f64,
+    // set once it fired, cleared when the value is back on the other side so
+    // a value hovering around the threshold doesn't notify on every poll
+    #[serde(default)]
+    pub triggered: bool,
+}
+
+impl Alert {
+    fn describe(&self, currency: &str) -> String {
+        if self.kind.is_price() {
+            format!(
+                "{} {} {}",
+                self.kind,
+                self.threshold,
+                currency.to_uppercase()
+            )
+        } else {
+            format!("{} {}", self.kind, self.threshold)
+        }
+    }
+}
+
+pub fn parse_alert(kind: AlertKind, threshold: &str) -> Result<Alert, Error> {
+    let threshold: f64 = threshold
+        .trim()
+        .parse()
+        .map_err(|_| Error::InvalidAlertThreshold)?;
+    if !threshold.is_finite() || threshold < 0.0 {
+        return Err(Error::InvalidAlertThreshold);
+    }
+    Ok(Alert {
+        kind,
+        threshold,
+        triggered: false,
+    })
+}
+
+pub fn has_balance_alerts(alerts: &[Alert]) -> bool {
+    alerts.iter().any(|alert| !alert.kind.is_price())
+}
+
+// the alerts that just crossed their threshold, as notification texts; a
+// value that isn't known yet leaves its alerts as they are
+pub fn evaluate_alerts(
+    alerts: &mut [Alert],
+    price: Option<f64>,
+    balance_sol: Option<f64>,
+    currency: &str,
+) -> Vec<String> {
+    let mut fired = Vec::new();
+    for alert in alerts.iter_mut() {
+        let value = if alert.kind.is_price() {
+            price
+        } else {
+            balance_sol
+        };
+        let Some(value) = value else {
+            continue;
+        };
+        let crossed = match alert.kind {
+            AlertKind::PriceAbove | AlertKind::BalanceAbove => value > alert.threshold,
+            AlertKind::PriceBelow | AlertKind::BalanceBelow => value < alert.threshold,
+        };
+        if crossed && !alert.triggered {
+            fired.push(tr_args(
+                "{$alert} (now {$value})",
+                &[
+                    ("alert", &alert.describe(currency)),
+                    ("value", &format!("{:.2}", value)),
+                ],
+            ));
+        }
+        alert.triggered = crossed;
+    }
+    fired
+}
+
+pub async fn notify_alert(body: String) -> Result<(), Error> {
+    Notification::new()
+        .summary(tr("Alert"))
+        .body(&body)
+        .appname("Solana Executable Application")
+        .show()
+        .map(|_| ())
+        .map_err(|_| Error::NotificationError)
+}
+
+pub fn display_alerts(
+    alerts: &[Alert],
+    kind: AlertKind,
+    threshold: &str,
+    currency: &str,
+) -> Element<'static, Message> {
+    let title = text(tr("Alerts")).size(14).style(accent_color());
+
+    let new_alert = row![
+        pick_list(&AlertKind::ALL[..], Some(kind), Message::AlertKindChanged),
+        text_input(tr("Threshold"), threshold)
+            .on_input(Message::AlertThresholdChanged)
+            .on_submit(Message::AddAlert),
+        button(tr("Add")).on_press(Message::AddAlert),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let list =
+        alerts
+            .iter()
+            .enumerate()
+            .fold(Column::new().spacing(5), |column, (index, alert)| {
+                let state = if alert.triggered {
+                    tr("triggered")
+                } else {
+                    tr("waiting")
+                };
+                column.push(
+                    row![
+                        text(format!("{}, {}", alert.describe(currency), state)).size(12),
+                        button(text(tr("Remove")).size(12)).on_press(Message::RemoveAlert(index)),
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+                )
+            });
+
+    // price alerts need the price feed, which only runs with a currency set
+    let note = if !shows_fiat(currency) && alerts.iter().any(|alert| alert.kind.is_price()) {
+        text(tr(
+            "Price alerts wait for a fiat currency to be picked in Settings",
+        ))
+        .size(12)
+    } else {
+        text(tr(
+            "Alerts are checked while the app runs and shown as system notifications",
+        ))
+        .size(12)
+    };
+
+    column![title, new_alert, list, note].spacing(10).into()
+}
@@ -0,0 +1,384 @@
+use std::{fs, path::PathBuf, str::FromStr};
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use iced::{
+    widget::{button, column, row, text, text_input},
+    Command, Element,
+};
+use solana_sdk::pubkey::Pubkey;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    encryption::replace_secret,
+    errors::{report, Error},
+    files::app_config_dir,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+pub const ALLOW_LIST_FILE: &str = "allowlist.txt";
+
+// while this file exists value and authority can only move to the listed
+// recipients, the wallet's own address included, and only the listed token
+// mints can be swapped, wrapped or staked into
+#[derive(Debug, Clone)]
+pub struct AllowList {
+    pub password_hash: String,
+    pub recipients: Vec<Pubkey>,
+    pub mints: Vec<Pubkey>,
+}
+
+impl AllowList {
+    pub fn check_recipient(&self, recipient: &Pubkey) -> Result<(), Error> {
+        if self.recipients.contains(recipient) {
+            Ok(())
+        } else {
+            Err(Error::RecipientNotAllowed)
+        }
+    }
+
+    pub fn check_mint(&self, mint: &Pubkey) -> Result<(), Error> {
+        if self.mints.contains(mint) {
+            Ok(())
+        } else {
+            Err(Error::MintNotAllowed)
+        }
+    }
+
+    pub fn verify_password(&self, password: &str) -> Result<(), Error> {
+        let hash =
+            PasswordHash::new(&self.password_hash).map_err(|_| Error::InvalidAdminPassword)?;
+        Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .map_err(|_| Error::InvalidAdminPassword)
+    }
+}
+
+fn allow_list_path() -> PathBuf {
+    app_config_dir().join(ALLOW_LIST_FILE)
+}
+
+// comma separated, the way the lists are typed in
+pub fn list_addresses(addresses: &[Pubkey]) -> String {
+    addresses
+        .iter()
+        .map(Pubkey::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+// the allow list screen's inputs, the saved list itself stays on the app
+#[derive(Debug, Default)]
+pub struct AllowListForm {
+    pub password: String,
+    pub recipients: String,
+    pub mints: String,
+}
+
+impl AllowListForm {
+    pub fn new(allow_list: &Option<AllowList>) -> Self {
+        Self {
+            password: String::new(),
+            recipients: allow_list
+                .as_ref()
+                .map(|allow_list| list_addresses(&allow_list.recipients))
+                .unwrap_or_default(),
+            mints: allow_list
+                .as_ref()
+                .map(|allow_list| list_addresses(&allow_list.mints))
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn wipe(&mut self) {
+        self.password.zeroize();
+    }
+
+    // the password is moved out so it doesn't linger in the form
+    pub fn save(&mut self, current: Option<AllowList>) -> Command<Message> {
+        Command::perform(
+            save_allow_list(
+                current,
+                Zeroizing::new(std::mem::take(&mut self.password)),
+                self.recipients.clone(),
+                self.mints.clone(),
+            ),
+            Message::AllowListSaved,
+        )
+    }
+
+    pub fn disable(&mut self, current: Option<AllowList>) -> Command<Message> {
+        Command::perform(
+            disable_allow_list(current, Zeroizing::new(std::mem::take(&mut self.password))),
+            Message::AllowListSaved,
+        )
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::AllowListPasswordChanged(password) => {
+                replace_secret(&mut self.password, password)
+            }
+            Message::AllowListRecipientsChanged(recipients) => self.recipients = recipients,
+            Message::AllowListMintsChanged(mints) => self.mints = mints,
+            Message::AllowListSaved(Err(error)) => return report(error),
+            _ => {}
+        }
+        Command::none()
+    }
+}
+
+fn parse_addresses(addresses: &str, error: Error) -> Result<Vec<Pubkey>, Error> {
+    addresses
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|address| !address.is_empty())
+        .map(|address| Pubkey::from_str(address).map_err(|_| error.clone()))
+        .collect()
+}
+
+pub fn load_allow_list() -> Option<AllowList> {
+    parse_allow_list(&fs::read_to_string(allow_list_path()).ok()?)
+}
+
+// file layout, one entry per line: "password=<argon2 hash>",
+// "recipient=<pubkey>" and "mint=<pubkey>"
+fn parse_allow_list(content: &str) -> Option<AllowList> {
+    let mut password_hash = None;
+    let mut recipients = Vec::new();
+    let mut mints = Vec::new();
+
+    for line in content.lines().map(str::trim) {
+        match line.split_once('=') {
+            Some(("password", hash)) => password_hash = Some(hash.to_string()),
+            Some(("recipient", address)) => {
+                if let Ok(recipient) = Pubkey::from_str(address) {
+                    recipients.push(recipient);
+                }
+            }
+            Some(("mint", address)) => {
+                if let Ok(mint) = Pubkey::from_str(address) {
+                    mints.push(mint);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // a list without admin password can't be edited, so don't enforce it
+    Some(AllowList {
+        password_hash: password_hash?,
+        recipients,
+        mints,
+    })
+}
+
+fn hash_admin_password(password: &str) -> Result<String, Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(|_| Error::InvalidAdminPassword)?
+        .to_string())
+}
+
+// the admin password is checked against the stored list, or becomes the
+// admin password when the mode is enabled for the first time
+pub async fn save_allow_list(
+    current: Option<AllowList>,
+    password: Zeroizing<String>,
+    recipients: String,
+    mints: String,
+) -> Result<Option<AllowList>, Error> {
+    let password_hash = match current {
+        Some(allow_list) => {
+            allow_list.verify_password(&password)?;
+            allow_list.password_hash
+        }
+        None => {
+            if password.is_empty() {
+                return Err(Error::InvalidAdminPassword);
+            }
+            hash_admin_password(&password)?
+        }
+    };
+
+    let recipients = parse_addresses(&recipients, Error::InvalidPubKeyLen)?;
+    let mints = parse_addresses(&mints, Error::InvalidMint)?;
+
+    let mut content = format!("password={}\n", password_hash);
+    for recipient in &recipients {
+        content.push_str(&format!("recipient={}\n", recipient));
+    }
+    for mint in &mints {
+        content.push_str(&format!("mint={}\n", mint));
+    }
+
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    tokio::fs::write(allow_list_path(), content)
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+
+    Ok(Some(AllowList {
+        password_hash,
+        recipients,
+        mints,
+    }))
+}
+
+pub async fn disable_allow_list(
+    current: Option<AllowList>,
+    password: Zeroizing<String>,
+) -> Result<Option<AllowList>, Error> {
+    if let Some(allow_list) = current {
+        allow_list.verify_password(&password)?;
+        tokio::fs::remove_file(allow_list_path())
+            .await
+            .map_err(|_| Error::SaveFileError)?;
+    }
+    Ok(None)
+}
+
+pub fn display_allow_list(
+    allow_list: &Option<AllowList>,
+    password: &str,
+    recipients: &str,
+    mints: &str,
+) -> Element<'static, Message> {
+    let status = match allow_list {
+        Some(allow_list) => text(tr_args(
+            "Allow-list mode is ON: {$recipients} allowed recipients, {$mints} allowed token mints",
+            &[
+                ("recipients", &allow_list.recipients.len()),
+                ("mints", &allow_list.mints.len()),
+            ],
+        ))
+        .style(accent_color()),
+        None => text(tr("Allow-list mode is OFF: any recipient can be used")),
+    }
+    .size(14);
+
+    let password_input = text_input(tr("Admin password"), password)
+        .on_input(Message::AllowListPasswordChanged)
+        .password();
+
+    let recipients_input = text_input(tr("Allowed recipients (comma separated)"), recipients)
+        .on_input(Message::AllowListRecipientsChanged);
+    let mints_input = text_input(tr("Allowed token mints (comma separated)"), mints)
+        .on_input(Message::AllowListMintsChanged);
+    let note = text(
+        tr("Staking, wrapping, swaps and withdrawals back into this wallet need its own address listed"),
+    )
+    .size(12);
+
+    let actions = match allow_list {
+        Some(_) => row![
+            button(tr("Save allow-list")).on_press(Message::SaveAllowList),
+            button(tr("Disable allow-list")).on_press(Message::DisableAllowList),
+        ],
+        None => row![button(tr("Enable allow-list")).on_press(Message::SaveAllowList)],
+    }
+    .spacing(10);
+
+    column![
+        status,
+        password_input,
+        recipients_input,
+        mints_input,
+        note,
+        actions
+    ]
+    .spacing(10)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECIPIENT: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    #[test]
+    fn parses_recipients_and_mints() {
+        let content = format!(
+            "password=hash\nrecipient={}\nmint={}\n  recipient=not-a-key  \nunknown=1\n",
+            RECIPIENT, USDC
+        );
+        let allow_list = parse_allow_list(&content).unwrap();
+        assert_eq!(allow_list.password_hash, "hash");
+        assert_eq!(
+            allow_list.recipients,
+            vec![Pubkey::from_str(RECIPIENT).unwrap()]
+        );
+        assert_eq!(allow_list.mints, vec![Pubkey::from_str(USDC).unwrap()]);
+    }
+
+    #[test]
+    fn a_list_without_password_is_not_enforced() {
+        assert!(parse_allow_list(&format!("recipient={}\n", RECIPIENT)).is_none());
+    }
+
+    #[test]
+    fn only_listed_entries_pass() {
+        let allow_list =
+            parse_allow_list(&format!("password=hash\nrecipient={}\n", RECIPIENT)).unwrap();
+        assert!(allow_list
+            .check_recipient(&Pubkey::from_str(RECIPIENT).unwrap())
+            .is_ok());
+        assert!(matches!(
+            allow_list.check_recipient(&Pubkey::new_unique()),
+            Err(Error::RecipientNotAllowed)
+        ));
+        // no mint lines, no token is allowed
+        assert!(matches!(
+            allow_list.check_mint(&Pubkey::from_str(USDC).unwrap()),
+            Err(Error::MintNotAllowed)
+        ));
+    }
+
+    #[test]
+    fn address_lists_round_trip() {
+        let typed = format!("{},\n {}", RECIPIENT, USDC);
+        let addresses = parse_addresses(&typed, Error::InvalidPubKeyLen).unwrap();
+        assert_eq!(
+            list_addresses(&addresses),
+            format!("{}, {}", RECIPIENT, USDC)
+        );
+        assert!(matches!(
+            parse_addresses("not-a-key", Error::InvalidMint),
+            Err(Error::InvalidMint)
+        ));
+    }
+
+    #[test]
+    fn verifies_the_admin_password() {
+        let allow_list = AllowList {
+            password_hash: hash_admin_password("correct horse").unwrap(),
+            recipients: Vec::new(),
+            mints: Vec::new(),
+        };
+        assert!(allow_list.verify_password("correct horse").is_ok());
+        assert!(matches!(
+            allow_list.verify_password("wrong horse"),
+            Err(Error::InvalidAdminPassword)
+        ));
+    }
+
+    #[test]
+    fn a_broken_hash_rejects_every_password() {
+        let allow_list = AllowList {
+            password_hash: String::from("hash"),
+            recipients: Vec::new(),
+            mints: Vec::new(),
+        };
+        assert!(matches!(
+            allow_list.verify_password(""),
+            Err(Error::InvalidAdminPassword)
+        ));
+    }
+}
@@ -0,0 +1,518 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use iced::{
+    widget::{button, column, row, text, text_input, Column},
+    Alignment, Command, Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    fee_calculator::DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE, packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+
+use crate::{
+    allowlist::AllowList,
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color},
+    poisoning::find_lookalike,
+    transaction::send_instructions,
+    units::{format_sol, parse_amount},
+    Error, Message,
+};
+use tokio::time;
+
+#[derive(Debug, Clone, Default)]
+pub enum BatchStatus {
+    #[default]
+    Pending,
+    Sending,
+    Sent(String),
+    Failed(Error),
+    Invalid(Error),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchRow {
+    pub address: String,
+    pub amount: String,
+    pub status: BatchStatus,
+}
+
+const MAX_CHUNK_ATTEMPTS: usize = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+// one transaction worth of transfers and the rows they came from
+#[derive(Debug, Clone)]
+pub struct BatchChunk {
+    pub rows: Vec<usize>,
+    pub transfers: Vec<(Pubkey, u64)>,
+}
+
+// rows to look at again before the batch goes out: recipients resembling an
+// address paid before, and amounts over the large transfer threshold
+#[derive(Debug, Clone, Default)]
+pub struct BatchReview {
+    // row index, the typed recipient and the known address it resembles
+    pub lookalikes: Vec<(usize, Pubkey, Pubkey)>,
+    pub large: Vec<(usize, u64)>,
+}
+
+impl BatchReview {
+    pub fn is_empty(&self) -> bool {
+        self.lookalikes.is_empty() && self.large.is_empty()
+    }
+}
+
+// the batch screen: the rows as typed and the chunks still to send
+#[derive(Debug)]
+pub struct BatchForm {
+    pub rows: Vec<BatchRow>,
+    pub queue: Vec<BatchChunk>,
+    // lookalike or large rows waiting for a confirmation before sending
+    pub review: Option<BatchReview>,
+    pub confirmed: bool,
+}
+
+impl Default for BatchForm {
+    fn default() -> Self {
+        Self {
+            rows: vec![BatchRow::default()],
+            queue: Vec::new(),
+            review: None,
+            confirmed: false,
+        }
+    }
+}
+
+impl BatchForm {
+    pub fn is_sending(&self) -> bool {
+        self.rows
+            .iter()
+            .any(|batch_row| matches!(batch_row.status, BatchStatus::Sending))
+    }
+
+    // the payroll rows are validated against the allow list by the app
+    pub fn import(&mut self, rows: Vec<BatchRow>) {
+        self.rows = rows;
+        self.review = None;
+    }
+
+    // the recipients and lamports of the given rows, once their chunk landed
+    pub fn sent(&self, rows: &[usize]) -> (Vec<Pubkey>, u64) {
+        let rows: Vec<&BatchRow> = rows
+            .iter()
+            .filter_map(|&index| self.rows.get(index))
+            .collect();
+        let recipients = rows
+            .iter()
+            .filter_map(|batch_row| Pubkey::from_str(batch_row.address.trim()).ok())
+            .collect();
+        let lamports = rows
+            .iter()
+            .filter_map(|batch_row| parse_amount(&batch_row.amount).ok())
+            .fold(0u64, |total, amount| total.saturating_add(amount));
+        (recipients, lamports)
+    }
+
+    // sends the next queued chunk, None once the queue is empty
+    pub fn next_chunk(
+        &mut self,
+        signer: Arc<Keypair>,
+        fee_payer: Option<Arc<Keypair>>,
+        rpc_client: Arc<RpcClient>,
+    ) -> Option<Command<Message>> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        let chunk = self.queue.remove(0);
+        for &index in &chunk.rows {
+            if let Some(batch_row) = self.rows.get_mut(index) {
+                batch_row.status = BatchStatus::Sending;
+            }
+        }
+
+        let rows = chunk.rows;
+        Some(Command::perform(
+            send_batch_chunk(signer, fee_payer, rpc_client, chunk.transfers),
+            move |result| Message::BatchChunkSent(rows, result),
+        ))
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::AddBatchRow => self.rows.push(BatchRow::default()),
+            Message::RemoveBatchRow(index) => {
+                if index < self.rows.len() && self.queue.is_empty() {
+                    self.rows.remove(index);
+                    self.review = None;
+                }
+            }
+            Message::BatchRowChanged(index, address, amount) => {
+                if let Some(batch_row) = self.rows.get_mut(index) {
+                    batch_row.address = address;
+                    batch_row.amount = amount;
+                    batch_row.status = BatchStatus::Pending;
+                }
+                self.review = None;
+            }
+            Message::ConfirmBatch => self.confirmed = true,
+            Message::CancelBatch => self.review = None,
+            Message::BatchPrepared(Ok(chunks)) => {
+                for batch_row in self.rows.iter_mut().filter(|row| !row.is_sent()) {
+                    batch_row.status = BatchStatus::Pending;
+                }
+                self.queue = chunks;
+            }
+            Message::BatchChunkSent(rows, result) => {
+                for index in rows {
+                    if let Some(batch_row) = self.rows.get_mut(index) {
+                        batch_row.status = match &result {
+                            Ok(signature) => BatchStatus::Sent(signature.clone()),
+                            Err(error) => BatchStatus::Failed(error.clone()),
+                        };
+                    }
+                }
+            }
+            _ => {}
+        }
+        Command::none()
+    }
+}
+
+// a row is sent at most once, anything already sent is skipped on retries
+impl BatchRow {
+    pub fn is_sent(&self) -> bool {
+        matches!(self.status, BatchStatus::Sent(_))
+    }
+}
+
+pub fn parse_row(row: &BatchRow, allow_list: &Option<AllowList>) -> Result<(Pubkey, u64), Error> {
+    let to = Pubkey::from_str(row.address.trim()).map_err(|_| Error::InvalidPubKeyLen)?;
+    let lamports = parse_amount(&row.amount)?;
+    if lamports == 0 {
+        return Err(Error::InvalidAmount);
+    }
+    if let Some(allow_list) = allow_list {
+        allow_list.check_recipient(&to)?;
+    }
+    Ok((to, lamports))
+}
+
+// every unsent row has to be valid before anything is sent, returns the
+// transfers together with their row index
+pub fn parse_rows(
+    rows: &[BatchRow],
+    allow_list: &Option<AllowList>,
+) -> Result<Vec<(usize, Pubkey, u64)>, Error> {
+    rows.iter()
+        .enumerate()
+        .filter(|(_, row)| !row.is_sent())
+        .map(|(index, row)| {
+            let (to, lamports) = parse_row(row, allow_list)?;
+            Ok((index, to, lamports))
+        })
+        .collect()
+}
+
+pub fn review_transfers(
+    transfers: &[(usize, Pubkey, u64)],
+    known_recipients: &[Pubkey],
+    is_large: impl Fn(u64) -> bool,
+) -> BatchReview {
+    let mut review = BatchReview::default();
+    for &(index, to, lamports) in transfers {
+        if let Some(known) = find_lookalike(&to, known_recipients) {
+            review.lookalikes.push((index, to, known));
+        }
+        if is_large(lamports) {
+            review.large.push((index, lamports));
+        }
+    }
+    review
+}
+
+// every chunk is its own transaction signed by the wallet alone, so each one
+// costs a single signature
+pub fn batch_fees(chunks: &[BatchChunk]) -> u64 {
+    (chunks.len() as u64).saturating_mul(DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE)
+}
+
+fn fits_in_packet(from: &Pubkey, payer: &Pubkey, transfers: &[(Pubkey, u64)]) -> bool {
+    let instructions: Vec<_> = transfers
+        .iter()
+        .map(|(to, lamports)| system_instruction::transfer(from, to, *lamports))
+        .collect();
+    let tx = Transaction::new_with_payer(&instructions, Some(payer));
+
+    bincode::serialized_size(&tx)
+        .map(|size| size as usize <= PACKET_DATA_SIZE)
+        .unwrap_or(false)
+}
+
+// greedily packs transfers into as few transactions as fit
+pub fn chunk_transfers(
+    from: &Pubkey,
+    payer: &Pubkey,
+    transfers: &[(usize, Pubkey, u64)],
+) -> Vec<BatchChunk> {
+    let mut chunks: Vec<BatchChunk> = Vec::new();
+    let mut current = BatchChunk {
+        rows: Vec::new(),
+        transfers: Vec::new(),
+    };
+
+    for &(index, to, lamports) in transfers {
+        let transfer = (to, lamports);
+        current.rows.push(index);
+        current.transfers.push(transfer);
+        if current.rows.len() > 1 && !fits_in_packet(from, payer, &current.transfers) {
+            current.rows.pop();
+            current.transfers.pop();
+            chunks.push(std::mem::replace(
+                &mut current,
+                BatchChunk {
+                    rows: vec![index],
+                    transfers: vec![transfer],
+                },
+            ));
+        }
+    }
+    if !current.rows.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+pub async fn send_batch_chunk(
+    signer: Arc<Keypair>,
+    fee_payer: Option<Arc<Keypair>>,
+    rpc_client: Arc<RpcClient>,
+    transfers: Vec<(Pubkey, u64)>,
+) -> Result<String, Error> {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match try_send_batch_chunk(&signer, &fee_payer, &rpc_client, &transfers).await {
+            // nothing was broadcast yet, so trying again can't pay twice
+            Err(Error::FetchBlockhashError) if attempts < MAX_CHUNK_ATTEMPTS => {
+                time::sleep(RETRY_DELAY).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+async fn try_send_batch_chunk(
+    signer: &Keypair,
+    fee_payer: &Option<Arc<Keypair>>,
+    rpc_client: &RpcClient,
+    transfers: &[(Pubkey, u64)],
+) -> Result<String, Error> {
+    let from = signer.pubkey();
+    let instructions: Vec<_> = transfers
+        .iter()
+        .map(|(to, lamports)| system_instruction::transfer(&from, to, *lamports))
+        .collect();
+
+    let signers: Vec<&Keypair> = match fee_payer {
+        Some(fee_payer) => vec![fee_payer.as_ref(), signer],
+        None => vec![signer],
+    };
+
+    send_instructions(rpc_client, &signers, &instructions).await
+}
+
+fn display_review(review: &BatchReview) -> Element<'static, Message> {
+    let lookalikes =
+        review
+            .lookalikes
+            .iter()
+            .fold(Column::new().spacing(2), |column, (index, to, known)| {
+                column.push(
+                    text(tr_args(
+                        "Row {$row}: {$recipient} resembles {$known}, an address you paid before",
+                        &[("row", &(index + 1)), ("recipient", to), ("known", known)],
+                    ))
+                    .size(12)
+                    .style(error_color()),
+                )
+            });
+    let large = review
+        .large
+        .iter()
+        .fold(Column::new().spacing(2), |column, (index, lamports)| {
+            column.push(
+                text(tr_args(
+                    "Row {$row}: {$amount} SOL is over the large transfer threshold",
+                    &[("row", &(index + 1)), ("amount", &format_sol(*lamports))],
+                ))
+                .size(12)
+                .style(error_color()),
+            )
+        });
+
+    column![
+        text(tr("Check these rows before sending"))
+            .size(16)
+            .style(error_color()),
+        lookalikes,
+        large,
+        row![
+            button(tr("Send anyway")).on_press(Message::ConfirmBatch),
+            button(tr("Cancel")).on_press(Message::CancelBatch),
+        ]
+        .spacing(10),
+    ]
+    .spacing(5)
+    .into()
+}
+
+pub fn display_batch(
+    rows: &[BatchRow],
+    is_sending: bool,
+    review: &Option<BatchReview>,
+) -> Element<'static, Message> {
+    let sent = rows.iter().filter(|row| row.is_sent()).count();
+    let failed = rows
+        .iter()
+        .filter(|row| matches!(row.status, BatchStatus::Failed(_)))
+        .count();
+
+    let header = row![
+        button(tr("Add recipient")).on_press(Message::AddBatchRow),
+        button(tr("Import CSV")).on_press(Message::ImportPayroll),
+        if is_sending {
+            button(tr("Sending..."))
+        } else if failed > 0 {
+            button(tr("Retry failed rows")).on_press(Message::SendBatch)
+        } else {
+            button(tr("Send batch")).on_press(Message::SendBatch)
+        },
+        button(tr("Export results")).on_press(Message::ExportPayrollResults),
+    ]
+    .spacing(10);
+
+    let list =
+        rows.iter()
+            .enumerate()
+            .fold(Column::new().spacing(10), |column, (index, batch_row)| {
+                let address = batch_row.address.clone();
+                let amount = batch_row.amount.clone();
+
+                let status = match &batch_row.status {
+                    BatchStatus::Pending => text("").size(1),
+                    BatchStatus::Sending => text(tr("Sending...")).size(12),
+                    BatchStatus::Sent(signature) => {
+                        text(tr_args("Sent: {$signature}", &[("signature", signature)]))
+                            .size(12)
+                            .style(accent_color())
+                    }
+                    BatchStatus::Failed(error) => {
+                        text(tr_args("Failed: {$error}", &[("error", error)]))
+                            .size(12)
+                            .style(error_color())
+                    }
+                    BatchStatus::Invalid(error) => {
+                        text(tr_args("Invalid: {$error}", &[("error", error)]))
+                            .size(12)
+                            .style(error_color())
+                    }
+                };
+
+                column.push(
+                    column![
+                        row![
+                            text_input(tr("Recipient address"), &batch_row.address).on_input({
+                                let amount = amount.clone();
+                                move |value| Message::BatchRowChanged(index, value, amount.clone())
+                            }),
+                            text_input("SOL", &batch_row.amount).on_input(move |value| {
+                                Message::BatchRowChanged(index, address.clone(), value)
+                            }),
+                            if is_sending {
+                                button(tr("Remove"))
+                            } else {
+                                button(tr("Remove")).on_press(Message::RemoveBatchRow(index))
+                            },
+                        ]
+                        .spacing(10)
+                        .align_items(Alignment::Center),
+                        status
+                    ]
+                    .spacing(2),
+                )
+            });
+
+    let total: u64 = rows
+        .iter()
+        .filter_map(|row| parse_amount(&row.amount).ok())
+        .fold(0, |total, amount| total.saturating_add(amount));
+
+    let review = match review {
+        Some(review) => display_review(review),
+        None => text("").size(1).into(),
+    };
+
+    column![
+        header,
+        review,
+        list,
+        text(tr_args(
+            "{$recipients} recipients | Total: {$total} SOL | {$sent} sent, {$failed} failed",
+            &[
+                ("recipients", &rows.len()),
+                ("total", &format_sol(total)),
+                ("sent", &sent),
+                ("failed", &failed),
+            ],
+        ))
+        .size(14)
+    ]
+    .spacing(10)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_large_rows_but_not_recipients_paid_before() {
+        let known = Pubkey::new_unique();
+        let transfers = vec![(0, known, 1_000), (1, Pubkey::new_unique(), 5_000_000)];
+
+        let review = review_transfers(&transfers, &[known], |lamports| lamports > 1_000_000);
+
+        assert!(review.lookalikes.is_empty());
+        assert_eq!(review.large, vec![(1, 5_000_000)]);
+        assert!(review_transfers(&transfers, &[known], |_| false).is_empty());
+    }
+
+    #[test]
+    fn every_chunk_pays_a_signature_fee() {
+        let chunk = BatchChunk {
+            rows: vec![0],
+            transfers: vec![(Pubkey::new_unique(), 1)],
+        };
+        assert_eq!(batch_fees(&[]), 0);
+        assert_eq!(
+            batch_fees(&[chunk.clone(), chunk]),
+            2 * DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE
+        );
+    }
+
+    #[test]
+    fn a_landed_chunk_marks_only_its_rows_sent() {
+        let mut form = BatchForm {
+            rows: vec![BatchRow::default(), BatchRow::default()],
+            ..BatchForm::default()
+        };
+
+        let _ = form.update(Message::BatchChunkSent(vec![1], Ok(String::from("sig"))));
+
+        assert!(!form.rows[0].is_sent());
+        assert!(form.rows[1].is_sent());
+    }
+}
@@ -0,0 +1,98 @@
+// keeps the window out of screenshots and screen recordings while a secret is
+// on screen. Windows and macOS can do it, X11 and Wayland have no equivalent,
+// there the hold to reveal masking is all there is
+pub fn exclude_from_capture(title: &str, excluded: bool) -> bool {
+    platform::set_excluded(title, excluded)
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::{ffi::c_void, iter::once, ptr::null};
+
+    const WDA_NONE: u32 = 0x00;
+    // windows 10 2004 and later, older versions refuse it and nothing changes
+    const WDA_EXCLUDEFROMCAPTURE: u32 = 0x11;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn FindWindowW(class_name: *const u16, window_name: *const u16) -> *mut c_void;
+        fn SetWindowDisplayAffinity(window: *mut c_void, affinity: u32) -> i32;
+    }
+
+    // another process' window with the same title can't have its affinity
+    // changed from here, so it's never touched
+    pub fn set_excluded(title: &str, excluded: bool) -> bool {
+        let title: Vec<u16> = title.encode_utf16().chain(once(0)).collect();
+        let affinity = if excluded {
+            WDA_EXCLUDEFROMCAPTURE
+        } else {
+            WDA_NONE
+        };
+        unsafe {
+            let window = FindWindowW(null(), title.as_ptr());
+            !window.is_null() && SetWindowDisplayAffinity(window, affinity) != 0
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::{ffi::c_char, ffi::c_void, mem::transmute};
+
+    type Id = *mut c_void;
+
+    const NS_WINDOW_SHARING_NONE: usize = 0;
+    const NS_WINDOW_SHARING_READ_ONLY: usize = 1;
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> Id;
+        fn sel_registerName(name: *const c_char) -> Id;
+        fn objc_msgSend();
+    }
+
+    // the app only has its main window, all of them are set. Called from
+    // update, which runs on the main thread as appkit requires
+    pub fn set_excluded(_title: &str, excluded: bool) -> bool {
+        let sharing_type = if excluded {
+            NS_WINDOW_SHARING_NONE
+        } else {
+            NS_WINDOW_SHARING_READ_ONLY
+        };
+        unsafe {
+            let send: unsafe extern "C" fn(Id, Id) -> Id =
+                transmute(objc_msgSend as unsafe extern "C" fn());
+            let send_count: unsafe extern "C" fn(Id, Id) -> usize =
+                transmute(objc_msgSend as unsafe extern "C" fn());
+            let send_index: unsafe extern "C" fn(Id, Id, usize) -> Id =
+                transmute(objc_msgSend as unsafe extern "C" fn());
+            let send_sharing: unsafe extern "C" fn(Id, Id, usize) =
+                transmute(objc_msgSend as unsafe extern "C" fn());
+
+            let app_class = objc_getClass(c"NSApplication".as_ptr());
+            if app_class.is_null() {
+                return false;
+            }
+            let app = send(app_class, sel_registerName(c"sharedApplication".as_ptr()));
+            let windows = send(app, sel_registerName(c"windows".as_ptr()));
+            let total = send_count(windows, sel_registerName(c"count".as_ptr()));
+            for index in 0..total {
+                let window =
+                    send_index(windows, sel_registerName(c"objectAtIndex:".as_ptr()), index);
+                send_sharing(
+                    window,
+                    sel_registerName(c"setSharingType:".as_ptr()),
+                    sharing_type,
+                );
+            }
+            total > 0
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    pub fn set_excluded(_title: &str, _excluded: bool) -> bool {
+        false
+    }
+}
@@ -0,0 +1,313 @@
+use std::{
+    fmt, fs,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use iced::{
+    mouse,
+    widget::{
+        canvas::{self, Frame, Geometry, Path, Stroke, Text},
+        column, pick_list, row, text, Canvas,
+    },
+    Alignment, Color, Element, Length, Point, Rectangle, Renderer, Theme,
+};
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    errors::Error,
+    files::app_config_dir,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+const CHART_FILE: &str = "chart_points.csv";
+// one point per quarter hour is plenty for trends and keeps the file small
+pub const CHART_RECORD_INTERVAL: Duration = Duration::from_secs(15 * 60);
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// a balance and the sol price seen at the same moment, either may be missing
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartPoint {
+    // unix seconds
+    pub time: u64,
+    pub wallet: Pubkey,
+    pub lamports: Option<u64>,
+    pub price: Option<f64>,
+    pub currency: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartSeries {
+    Balance,
+    Price,
+}
+
+impl ChartSeries {
+    pub const ALL: [ChartSeries; 2] = [ChartSeries::Balance, ChartSeries::Price];
+}
+
+impl fmt::Display for ChartSeries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartSeries::Balance => write!(f, "{}", tr("Wallet balance (SOL)")),
+            ChartSeries::Price => write!(f, "{}", tr("SOL price")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartRange {
+    Day,
+    Week,
+    Month,
+    All,
+}
+
+impl ChartRange {
+    pub const ALL: [ChartRange; 4] = [
+        ChartRange::Day,
+        ChartRange::Week,
+        ChartRange::Month,
+        ChartRange::All,
+    ];
+
+    fn seconds(&self) -> Option<u64> {
+        match self {
+            ChartRange::Day => Some(SECONDS_PER_DAY),
+            ChartRange::Week => Some(7 * SECONDS_PER_DAY),
+            ChartRange::Month => Some(30 * SECONDS_PER_DAY),
+            ChartRange::All => None,
+        }
+    }
+}
+
+impl fmt::Display for ChartRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartRange::Day => write!(f, "{}", tr("24 hours")),
+            ChartRange::Week => write!(f, "{}", tr("7 days")),
+            ChartRange::Month => write!(f, "{}", tr("30 days")),
+            ChartRange::All => write!(f, "{}", tr("All")),
+        }
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+// time,wallet,lamports,price,currency with empty fields for what wasn't known
+fn parse_point(line: &str) -> Option<ChartPoint> {
+    let fields: Vec<&str> = line.split(',').collect();
+    let [time, wallet, lamports, price, currency] = fields[..] else {
+        return None;
+    };
+    Some(ChartPoint {
+        time: time.parse().ok()?,
+        wallet: wallet.parse().ok()?,
+        lamports: lamports.parse().ok(),
+        price: price.parse().ok(),
+        currency: currency.to_string(),
+    })
+}
+
+// a damaged line is skipped, the rest of the history still draws
+pub fn load_chart_points() -> Vec<ChartPoint> {
+    fs::read_to_string(app_config_dir().join(CHART_FILE))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(parse_point)
+        .collect()
+}
+
+pub async fn record_chart_point(point: ChartPoint) -> Result<ChartPoint, Error> {
+    let line = format!(
+        "{},{},{},{},{}\n",
+        point.time,
+        point.wallet,
+        point
+            .lamports
+            .map(|lamports| lamports.to_string())
+            .unwrap_or_default(),
+        point
+            .price
+            .map(|price| price.to_string())
+            .unwrap_or_default(),
+        point.currency
+    );
+
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(app_config_dir().join(CHART_FILE))
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    file.write_all(line.as_bytes())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(point)
+}
+
+// the balance of `wallet`, or the price in `currency` of any wallet's points
+fn series_values(
+    points: &[ChartPoint],
+    series: ChartSeries,
+    range: ChartRange,
+    wallet: Option<Pubkey>,
+    currency: &str,
+) -> Vec<(u64, f64)> {
+    let since = range
+        .seconds()
+        .map_or(0, |seconds| unix_now().saturating_sub(seconds));
+    let mut values: Vec<(u64, f64)> = points
+        .iter()
+        .filter(|point| point.time >= since)
+        .filter_map(|point| match series {
+            ChartSeries::Balance if Some(point.wallet) == wallet => point
+                .lamports
+                .map(|lamports| (point.time, lamports as f64 / LAMPORTS_PER_SOL as f64)),
+            ChartSeries::Price if point.currency == currency => {
+                point.price.map(|price| (point.time, price))
+            }
+            _ => None,
+        })
+        .collect();
+    values.sort_by_key(|(time, _)| *time);
+    values.dedup_by_key(|(time, _)| *time);
+    values
+}
+
+struct LineChart {
+    values: Vec<(u64, f64)>,
+    color: Color,
+}
+
+impl canvas::Program<Message> for LineChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let label_color = theme.palette().text;
+
+        let (Some(first), Some(last)) = (self.values.first(), self.values.last()) else {
+            return vec![frame.into_geometry()];
+        };
+        let (min, max) = self
+            .values
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(min, max), (_, value)| {
+                (min.min(*value), max.max(*value))
+            });
+        // a flat line sits in the middle instead of dividing by zero
+        let spread = if max > min { max - min } else { 1.0 };
+        let duration = (last.0 - first.0).max(1) as f32;
+        let margin = 40.0;
+        let width = frame.width() - margin;
+        let height = frame.height() - margin;
+
+        let to_point = |(time, value): &(u64, f64)| {
+            Point::new(
+                margin + (*time - first.0) as f32 / duration * width,
+                margin / 2.0 + (1.0 - ((value - min) / spread) as f32) * height,
+            )
+        };
+
+        let line = Path::new(|path| {
+            let mut points = self.values.iter().map(to_point);
+            if let Some(start) = points.next() {
+                path.move_to(start);
+                points.for_each(|point| path.line_to(point));
+            }
+        });
+        frame.stroke(
+            &line,
+            Stroke::default().with_color(self.color).with_width(2.0),
+        );
+
+        for (value, y) in [(max, margin / 2.0), (min, margin / 2.0 + height)] {
+            frame.fill_text(Text {
+                content: format!("{:.2}", value),
+                position: Point::new(0.0, y - 6.0),
+                color: label_color,
+                size: 12.0.into(),
+                ..Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+pub fn display_chart(
+    points: &[ChartPoint],
+    series: ChartSeries,
+    range: ChartRange,
+    wallet: Option<Pubkey>,
+    currency: &str,
+) -> Element<'static, Message> {
+    let controls = row![
+        text(tr("Chart")).size(14).style(accent_color()),
+        pick_list(
+            &ChartSeries::ALL[..],
+            Some(series),
+            Message::ChartSeriesChanged
+        ),
+        pick_list(
+            &ChartRange::ALL[..],
+            Some(range),
+            Message::ChartRangeChanged
+        ),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let values = series_values(points, series, range, wallet, currency);
+    if values.len() < 2 {
+        return column![
+            controls,
+            text(tr_args(
+                "Not enough history yet, a point is recorded every {$minutes} minutes while the app runs",
+                &[("minutes", &(CHART_RECORD_INTERVAL.as_secs() / 60))],
+            ))
+            .size(12),
+        ]
+        .spacing(10)
+        .into();
+    }
+
+    let latest = values[values.len() - 1].1;
+    let change = latest - values[0].1;
+    let summary = text(tr_args(
+        "Latest {$latest}, {$change} over the range, {$points} points",
+        &[
+            ("latest", &format!("{:.4}", latest)),
+            ("change", &format!("{:+.4}", change)),
+            ("points", &values.len()),
+        ],
+    ))
+    .size(12);
+
+    let chart = Canvas::new(LineChart {
+        values,
+        color: accent_color(),
+    })
+    .width(Length::Fill)
+    .height(Length::Fixed(300.0));
+
+    column![controls, summary, chart].spacing(10).into()
+}
@@ -0,0 +1,122 @@
+use iced::{
+    widget::{button, column, row, text, text_input, Column},
+    Element,
+};
+
+use crate::{i18n::tr, quick_actions::quick_action_from_query, Message};
+
+// every user action the palette can run, widgets dispatch these as well
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppCommand {
+    LoadWallet,
+    CreateWallet,
+    RefreshBalance,
+    OpenSendForm { address: String, amount: String },
+    ClearSendForm,
+    TopUpWallets,
+}
+
+pub struct CommandInfo {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+}
+
+pub const REGISTRY: &[CommandInfo] = &[
+    CommandInfo {
+        name: "load-wallet",
+        usage: "load-wallet",
+        description: "Pick a keypair file and load it",
+    },
+    CommandInfo {
+        name: "new-wallet",
+        usage: "new-wallet",
+        description: "Generate a new keypair, save it to a file and load it",
+    },
+    CommandInfo {
+        name: "refresh-balance",
+        usage: "refresh-balance",
+        description: "Fetch the balance of the loaded wallet again",
+    },
+    CommandInfo {
+        name: "send",
+        usage: "send <address> <amount>",
+        description: "Open the send form prefilled with a recipient and amount",
+    },
+    CommandInfo {
+        name: "clear-form",
+        usage: "clear-form",
+        description: "Clear the send form",
+    },
+    CommandInfo {
+        name: "top-up",
+        usage: "top-up",
+        description: "Request devnet airdrops up to the faucet target",
+    },
+];
+
+// parses "<name> [args...]" into a command
+pub fn parse_command(input: &str) -> Option<AppCommand> {
+    let mut parts = input.split_whitespace();
+    let name = parts.next()?;
+
+    match name {
+        "load-wallet" => Some(AppCommand::LoadWallet),
+        "new-wallet" => Some(AppCommand::CreateWallet),
+        "refresh-balance" => Some(AppCommand::RefreshBalance),
+        "send" => Some(AppCommand::OpenSendForm {
+            address: parts.next().unwrap_or_default().to_string(),
+            amount: parts.next().unwrap_or_default().to_string(),
+        }),
+        "clear-form" => Some(AppCommand::ClearSendForm),
+        "top-up" => Some(AppCommand::TopUpWallets),
+        _ => None,
+    }
+}
+
+pub fn matching_commands(query: &str) -> impl Iterator<Item = &'static CommandInfo> + '_ {
+    let name = query
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    REGISTRY.iter().filter(move |info| {
+        info.name.contains(&name) || tr(info.description).to_lowercase().contains(&name)
+    })
+}
+
+pub fn display_palette(query: &str) -> Element<'static, Message> {
+    let input = text_input(tr("Type a command, e.g. send <address> <amount>"), query)
+        .on_input(Message::PaletteQueryChanged)
+        .on_submit(Message::PaletteSubmitted);
+
+    let commands = matching_commands(query).fold(Column::new().spacing(5), |column, info| {
+        // commands with arguments take them from the typed query
+        let command_line = if query.trim_start().starts_with(info.name) {
+            query.to_string()
+        } else {
+            info.name.to_string()
+        };
+
+        let entry = button(text(format!("{}  -  {}", info.usage, tr(info.description))).size(14));
+        match parse_command(&command_line) {
+            Some(command) => column.push(entry.on_press(Message::RunCommand(command))),
+            None => column.push(entry),
+        }
+    });
+
+    // "label: command" pins the command under a custom label
+    let pin = if quick_action_from_query(query).is_some() {
+        button(tr("Pin as quick action")).on_press(Message::PinQuickAction)
+    } else {
+        button(tr("Pin as quick action"))
+    };
+
+    column![
+        row![input, pin].spacing(10),
+        text(tr("Prefix with \"label:\" to name a pinned action")).size(12),
+        commands
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,327 @@
+use std::{fmt, fs, time::Duration};
+
+use iced::{
+    widget::{column, pick_list, row, text, text_input},
+    window, Alignment, Element,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    alerts::Alert,
+    dca::DcaSchedule,
+    errors::Error,
+    fiat::CURRENCIES,
+    files::app_config_dir,
+    i18n::{tr, tr_args, Language},
+    jito::{DEFAULT_BLOCK_ENGINE, DEFAULT_JITO_TIP},
+    palette::accent_color,
+    permissions::NetworkPermissions,
+    settings::PREFERENCES_FILE,
+    swap::DEFAULT_SLIPPAGE_BPS,
+    Message,
+};
+
+const CONFIG_FILE: &str = "config.toml";
+// a file that no longer parses is kept next to the fresh one
+const BROKEN_CONFIG_FILE: &str = "config.toml.bak";
+// bump together with a step in `migrate`
+const CONFIG_VERSION: u32 = 1;
+// resizes and moves come in bursts, they're written once things settle
+pub const WINDOW_STATE_SAVE_DELAY: Duration = Duration::from_secs(2);
+// how often the os setting is looked at while following it
+pub const SYSTEM_THEME_POLL_INTERVAL: Duration = Duration::from_secs(5);
+pub const THEMES: [&str; 3] = ["dark", "light", "system"];
+
+// a theme or currency as it's saved, listed under its translated name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Choice(&'static str);
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", tr(self.0))
+    }
+}
+// offered in settings, the text size of every screen follows the scale
+pub const UI_SCALES: [u32; 8] = [75, 90, 100, 110, 125, 150, 175, 200];
+pub const EXPLORERS: [&str; 3] = ["solana-explorer", "solscan", "solanafm"];
+
+// the app's own settings file, meant to be readable and editable by hand
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub version: u32,
+    // used when no profile is active, empty to follow the solana cli
+    pub rpc_url: String,
+    // "en" or "es"
+    pub language: String,
+    // dark, light or system
+    pub theme: String,
+    // hex like "#30cbf2", empty for the built-in color
+    pub accent_color: String,
+    pub success_color: String,
+    pub error_color: String,
+    // percent applied to the whole ui on top of the os scaling
+    pub ui_scale: u32,
+    // processed, confirmed or finalized, empty for finalized
+    pub commitment: String,
+    // priority fees in micro-lamports per compute unit offered as one click
+    pub fee_presets: Vec<u64>,
+    pub explorer: String,
+    // balances and amounts are also shown in this currency, "none" to hide
+    pub fiat_currency: String,
+    // "sol" or "lamports", what the send amount is typed in
+    pub amount_unit: String,
+    // opened on launch when no default keypair is set
+    pub last_keypair_path: String,
+    // 0 until the window was resized once
+    pub window_width: u32,
+    pub window_height: u32,
+    pub window_x: Option<i32>,
+    pub window_y: Option<i32>,
+    // the tab that was open when the app was closed
+    pub last_screen: String,
+    pub alerts: Vec<Alert>,
+    // basis points a swap may pay out less than quoted, 50 is 0.5%
+    pub swap_slippage_bps: u16,
+    pub dca_schedules: Vec<DcaSchedule>,
+    // bundles are posted to <jito_block_engine>/api/v1/bundles
+    pub jito_block_engine: String,
+    pub jito_tip_lamports: u64,
+    // third-party hosts the app may contact, all off until allowed
+    pub network_access: NetworkPermissions,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            rpc_url: String::new(),
+            language: String::from("en"),
+            theme: String::from("dark"),
+            accent_color: String::new(),
+            success_color: String::new(),
+            error_color: String::new(),
+            ui_scale: 100,
+            commitment: String::new(),
+            fee_presets: vec![1_000, 10_000, 100_000],
+            explorer: String::from("solana-explorer"),
+            fiat_currency: String::from("none"),
+            amount_unit: String::from("sol"),
+            last_keypair_path: String::new(),
+            window_width: 0,
+            window_height: 0,
+            window_x: None,
+            window_y: None,
+            last_screen: String::new(),
+            alerts: Vec::new(),
+            swap_slippage_bps: DEFAULT_SLIPPAGE_BPS,
+            dca_schedules: Vec::new(),
+            jito_block_engine: String::from(DEFAULT_BLOCK_ENGINE),
+            jito_tip_lamports: DEFAULT_JITO_TIP,
+            network_access: NetworkPermissions::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    // a hand-edited value is kept within what stays usable
+    pub fn scale_factor(&self) -> f64 {
+        f64::from(
+            self.ui_scale
+                .clamp(UI_SCALES[0], UI_SCALES[UI_SCALES.len() - 1]),
+        ) / 100.0
+    }
+
+    pub fn follows_system_theme(&self) -> bool {
+        self.theme == "system"
+    }
+
+    // `system_dark` is the last os setting seen, only used in system mode
+    pub fn is_dark(&self, system_dark: bool) -> bool {
+        match self.theme.as_str() {
+            "light" => false,
+            "system" => system_dark,
+            _ => true,
+        }
+    }
+    // link to a transaction on the chosen explorer, for the cluster the url is on
+    pub fn explorer_url(&self, signature: &str, cluster: &str) -> String {
+        let cluster = match cluster {
+            "mainnet-beta" | "custom" => String::new(),
+            cluster => format!("?cluster={}", cluster),
+        };
+        match self.explorer.as_str() {
+            "solscan" => format!("https://solscan.io/tx/{}{}", signature, cluster),
+            "solanafm" => format!("https://solana.fm/tx/{}{}", signature, cluster),
+            _ => format!("https://explorer.solana.com/tx/{}{}", signature, cluster),
+        }
+    }
+}
+
+// an os that doesn't say is treated as dark, the app's original look
+pub fn system_prefers_dark() -> bool {
+    !matches!(dark_light::detect(), dark_light::Mode::Light)
+}
+
+// size and position the window had last time, iced's defaults otherwise
+pub fn window_settings(config: &AppConfig) -> window::Settings {
+    let defaults = window::Settings::default();
+    window::Settings {
+        size: if config.window_width > 0 && config.window_height > 0 {
+            (config.window_width, config.window_height)
+        } else {
+            defaults.size
+        },
+        position: match (config.window_x, config.window_y) {
+            (Some(x), Some(y)) => window::Position::Specific(x, y),
+            _ => defaults.position,
+        },
+        ..defaults
+    }
+}
+
+// before the toml file the commitment lived with the preferences
+fn from_preferences() -> AppConfig {
+    let content = fs::read_to_string(app_config_dir().join(PREFERENCES_FILE)).unwrap_or_default();
+    let mut config = AppConfig::default();
+    for line in content.lines() {
+        if let Some(("commitment", level)) = line.split_once('=') {
+            config.commitment = level.to_string();
+        }
+    }
+    config
+}
+
+// older files only miss fields, serde fills them with defaults; steps that
+// rename or reinterpret a field go here
+fn migrate(mut config: AppConfig) -> AppConfig {
+    if config.version > CONFIG_VERSION {
+        // written by a newer build, keep what this one understands
+        return config;
+    }
+    config.version = CONFIG_VERSION;
+    config
+}
+
+pub fn load_config() -> AppConfig {
+    let path = app_config_dir().join(CONFIG_FILE);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return from_preferences();
+    };
+    match toml::from_str(&content) {
+        Ok(config) => migrate(config),
+        Err(_) => {
+            fs::rename(&path, app_config_dir().join(BROKEN_CONFIG_FILE)).ok();
+            AppConfig::default()
+        }
+    }
+}
+
+pub async fn save_config(config: AppConfig) -> Result<(), Error> {
+    let content = toml::to_string(&config).map_err(|_| Error::SaveFileError)?;
+
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    tokio::fs::write(app_config_dir().join(CONFIG_FILE), content)
+        .await
+        .map_err(|_| Error::SaveFileError)
+}
+
+pub fn display_config(config: &AppConfig) -> Element<'static, Message> {
+    let title = text(tr("Connection and appearance"))
+        .size(14)
+        .style(accent_color());
+
+    let rpc_url = text_input(
+        tr("RPC URL when no profile is active, press Enter to apply (empty to follow the Solana CLI)"),
+        &config.rpc_url,
+    )
+    .on_input(Message::RpcUrlChanged)
+    .on_submit(Message::RpcUrlSubmitted);
+
+    let language = row![
+        text(tr("Language")).size(14),
+        pick_list(
+            &Language::ALL[..],
+            Some(Language::from_code(&config.language)),
+            Message::LanguageChanged,
+        ),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let selected_theme = THEMES
+        .into_iter()
+        .find(|theme| *theme == config.theme)
+        .unwrap_or(THEMES[0]);
+    let theme = row![
+        text(tr("Theme")).size(14),
+        pick_list(
+            THEMES.map(Choice).to_vec(),
+            Some(Choice(selected_theme)),
+            |theme| Message::ThemeChanged(theme.0.to_string())
+        ),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let scale = row![
+        text(tr("UI scale (%)")).size(14),
+        pick_list(
+            &UI_SCALES[..],
+            Some(config.ui_scale),
+            Message::UiScaleChanged
+        ),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let colors = row![
+        text_input(tr("Accent color, e.g. #30cbf2"), &config.accent_color)
+            .on_input(Message::AccentColorChanged),
+        text_input(tr("Success color, e.g. #14f195"), &config.success_color)
+            .on_input(Message::SuccessColorChanged),
+        text_input(tr("Error color, e.g. #ff0000"), &config.error_color)
+            .on_input(Message::ErrorColorChanged),
+    ]
+    .spacing(10);
+
+    let selected = EXPLORERS
+        .into_iter()
+        .find(|explorer| *explorer == config.explorer)
+        .unwrap_or(EXPLORERS[0]);
+    let explorer = row![
+        text(tr("Explorer")).size(14),
+        pick_list(&EXPLORERS[..], Some(selected), |explorer| {
+            Message::ExplorerChanged(explorer.to_string())
+        }),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let selected_currency = CURRENCIES
+        .into_iter()
+        .find(|currency| *currency == config.fiat_currency)
+        .unwrap_or(CURRENCIES[0]);
+    let currency = row![
+        text(tr("Fiat currency")).size(14),
+        pick_list(
+            CURRENCIES.map(Choice).to_vec(),
+            Some(Choice(selected_currency)),
+            |currency| Message::FiatCurrencyChanged(currency.0.to_string())
+        ),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let file = text(tr_args(
+        "Fee presets and the rest live in {$path}",
+        &[("path", &app_config_dir().join(CONFIG_FILE).display())],
+    ))
+    .size(12);
+
+    column![title, rpc_url, language, theme, scale, colors, explorer, currency, file]
+        .spacing(10)
+        .into()
+}
@@ -0,0 +1,334 @@
+use std::{fmt, fs, sync::Arc, time::Duration};
+
+use iced::{
+    widget::{button, column, pick_list, row, text, Column},
+    Alignment, Element,
+};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::signature::Keypair;
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    chart::unix_now,
+    errors::Error,
+    files::app_config_dir,
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color, success_color},
+    permissions::NetworkPermissions,
+    swap::{execute_swap, fetch_swap_quote, SwapReceipt, SwapToken, SWAP_TOKENS},
+    units::parse_amount,
+    Message,
+};
+
+const DCA_HISTORY_FILE: &str = "dca_history.csv";
+// how often due schedules are looked for, runs happen within this of their time
+pub const DCA_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+// newest first, older runs stay in the file
+const SHOWN_RUNS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DcaInterval {
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl DcaInterval {
+    pub const ALL: [DcaInterval; 3] =
+        [DcaInterval::Hourly, DcaInterval::Daily, DcaInterval::Weekly];
+
+    fn seconds(&self) -> u64 {
+        match self {
+            DcaInterval::Hourly => 60 * 60,
+            DcaInterval::Daily => 24 * 60 * 60,
+            DcaInterval::Weekly => 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+impl fmt::Display for DcaInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DcaInterval::Hourly => write!(f, "{}", tr("Every hour")),
+            DcaInterval::Daily => write!(f, "{}", tr("Every day")),
+            DcaInterval::Weekly => write!(f, "{}", tr("Every week")),
+        }
+    }
+}
+
+// a swap repeated while the app runs, tokens are kept by symbol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DcaSchedule {
+    pub id: u64,
+    pub input: String,
+    pub output: String,
+    pub amount: String,
+    pub interval: DcaInterval,
+    // unix seconds
+    pub next_run: u64,
+    pub paused: bool,
+}
+
+impl DcaSchedule {
+    pub fn new(
+        id: u64,
+        input: SwapToken,
+        output: SwapToken,
+        amount: String,
+        interval: DcaInterval,
+    ) -> Self {
+        Self {
+            id,
+            input: input.symbol.to_string(),
+            output: output.symbol.to_string(),
+            amount,
+            interval,
+            // the first swap goes out on the next check
+            next_run: unix_now(),
+            paused: false,
+        }
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        !self.paused && self.next_run <= now
+    }
+
+    // runs missed while the app was closed are skipped, not made up in a burst
+    pub fn advance(&mut self, now: u64) {
+        self.next_run = (self.next_run + self.interval.seconds()).max(now + 1);
+    }
+
+    // SOL the swap takes from the wallet, held against the spend limit while
+    // it runs
+    pub fn sol_amount(&self) -> Result<u64, Error> {
+        if self.input == SWAP_TOKENS[0].symbol {
+            parse_amount(&self.amount)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn describe(&self) -> String {
+        tr_args(
+            "{$amount} {$input} to {$output}",
+            &[
+                ("amount", &self.amount),
+                ("input", &self.input),
+                ("output", &self.output),
+            ],
+        )
+    }
+}
+
+pub fn swap_token(symbol: &str) -> Option<SwapToken> {
+    SWAP_TOKENS.into_iter().find(|token| token.symbol == symbol)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DcaRun {
+    pub time: u64,
+    pub schedule: u64,
+    pub summary: String,
+    // SOL the swap took, only known for runs of this session
+    pub lamports: u64,
+    pub result: Result<String, String>,
+}
+
+// a skipped run (locked wallet, wrong cluster...) is recorded like a failed one
+pub fn skipped_run(schedule: &DcaSchedule, error: Error) -> DcaRun {
+    DcaRun {
+        time: unix_now(),
+        schedule: schedule.id,
+        summary: schedule.describe(),
+        lamports: 0,
+        result: Err(error.to_string()),
+    }
+}
+
+pub async fn run_dca(
+    schedule: DcaSchedule,
+    slippage_bps: u16,
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+    permissions: NetworkPermissions,
+) -> DcaRun {
+    let swap = async {
+        let (Some(input), Some(output)) =
+            (swap_token(&schedule.input), swap_token(&schedule.output))
+        else {
+            return Err(Error::SwapQuoteError);
+        };
+        let quote = fetch_swap_quote(
+            input,
+            output,
+            schedule.amount.clone(),
+            slippage_bps,
+            permissions,
+        )
+        .await?;
+        // a scheduled buy never goes through a route a person would be asked about
+        if quote.needs_confirmation() {
+            return Err(Error::SwapQuoteError);
+        }
+        execute_swap(quote, signer, rpc_client, permissions).await
+    };
+
+    let result = swap.await;
+    DcaRun {
+        time: unix_now(),
+        schedule: schedule.id,
+        summary: schedule.describe(),
+        lamports: result.as_ref().map_or(0, SwapReceipt::sol_spent),
+        result: result
+            .map(|receipt| receipt.signature)
+            .map_err(|error| error.to_string()),
+    }
+}
+
+// time,schedule,summary,signature,error
+fn parse_run(line: &str) -> Option<DcaRun> {
+    let fields: Vec<&str> = line.split(',').collect();
+    let [time, schedule, summary, signature, error] = fields[..] else {
+        return None;
+    };
+    Some(DcaRun {
+        time: time.parse().ok()?,
+        schedule: schedule.parse().ok()?,
+        summary: summary.to_string(),
+        lamports: 0,
+        result: if error.is_empty() {
+            Ok(signature.to_string())
+        } else {
+            Err(error.to_string())
+        },
+    })
+}
+
+pub fn load_dca_history() -> Vec<DcaRun> {
+    fs::read_to_string(app_config_dir().join(DCA_HISTORY_FILE))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(parse_run)
+        .collect()
+}
+
+pub async fn save_dca_run(run: DcaRun) -> Result<(), Error> {
+    // the error is shown as is, its commas would split the line
+    let (signature, error) = match &run.result {
+        Ok(signature) => (signature.clone(), String::new()),
+        Err(error) => (String::new(), error.replace(',', ";")),
+    };
+    let line = format!(
+        "{},{},{},{},{}\n",
+        run.time, run.schedule, run.summary, signature, error
+    );
+
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(app_config_dir().join(DCA_HISTORY_FILE))
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    file.write_all(line.as_bytes())
+        .await
+        .map_err(|_| Error::SaveFileError)
+}
+
+fn minutes_until(time: u64) -> String {
+    match time.saturating_sub(unix_now()) / 60 {
+        0 => String::from(tr("due now")),
+        minutes @ 1..=119 => tr_args("in {$minutes}m", &[("minutes", &minutes)]),
+        minutes => tr_args("in {$hours}h", &[("hours", &(minutes / 60))]),
+    }
+}
+
+pub fn display_dca(
+    schedules: &[DcaSchedule],
+    history: &[DcaRun],
+    interval: DcaInterval,
+) -> Element<'static, Message> {
+    let title = text(tr("Recurring swaps")).size(14).style(accent_color());
+
+    let add = row![
+        pick_list(
+            &DcaInterval::ALL[..],
+            Some(interval),
+            Message::DcaIntervalChanged
+        ),
+        button(tr("Repeat this swap")).on_press(Message::AddDcaSchedule),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let list = schedules
+        .iter()
+        .fold(Column::new().spacing(5), |column, schedule| {
+            let state = if schedule.paused {
+                String::from(tr("paused"))
+            } else {
+                tr_args(
+                    "next {$when}",
+                    &[("when", &minutes_until(schedule.next_run))],
+                )
+            };
+            column.push(
+                row![
+                    text(format!(
+                        "{}, {}, {}",
+                        schedule.describe(),
+                        schedule.interval.to_string().to_lowercase(),
+                        state
+                    ))
+                    .size(12),
+                    button(text(tr(if schedule.paused { "Resume" } else { "Pause" })).size(12))
+                        .on_press(Message::ToggleDcaSchedule(schedule.id)),
+                    button(text(tr("Remove")).size(12))
+                        .on_press(Message::RemoveDcaSchedule(schedule.id)),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            )
+        });
+
+    let runs =
+        history
+            .iter()
+            .rev()
+            .take(SHOWN_RUNS)
+            .fold(Column::new().spacing(2), |column, run| {
+                let minutes_ago = unix_now().saturating_sub(run.time) / 60;
+                let (outcome, color) = match &run.result {
+                    Ok(signature) => (signature, success_color()),
+                    Err(error) => (error, error_color()),
+                };
+                let line = text(tr_args(
+                    "{$minutes}m ago, {$summary}: {$outcome}",
+                    &[
+                        ("minutes", &minutes_ago),
+                        ("summary", &run.summary),
+                        ("outcome", outcome),
+                    ],
+                ))
+                .size(12)
+                .style(color);
+                column.push(line)
+            });
+
+    column![
+        title,
+        text(tr(
+            "Swaps run while the app is open, runs missed while it was closed are skipped"
+        ))
+        .size(12),
+        add,
+        list,
+        text(tr("Last runs")).size(14).style(accent_color()),
+        runs,
+    ]
+    .spacing(10)
+    .into()
+}
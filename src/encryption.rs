@@ -0,0 +1,250 @@
+use std::{path::PathBuf, sync::Arc};
+
+use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+use argon2::{
+    password_hash::rand_core::{OsRng, RngCore},
+    Argon2,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use iced::{
+    widget::{button, column, text, text_input},
+    Element,
+};
+use solana_sdk::signature::Keypair;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    errors::Error,
+    files::save_file,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+const HEADER: &str = "solana-exec-app encrypted keypair v1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+// argon2id with the crate defaults turns the passphrase into an aes-256 key
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Aes256Gcm, Error> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|_| Error::EncryptionError)?;
+    Aes256Gcm::new_from_slice(key.as_ref()).map_err(|_| Error::EncryptionError)
+}
+
+// file layout: the header line, then "salt=", "nonce=" and "ciphertext="
+// lines with base64 values
+pub fn encrypt_secret(secret: &[u8], passphrase: &str) -> Result<String, Error> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let ciphertext = derive_key(passphrase, &salt)?
+        .encrypt(Nonce::from_slice(&nonce), secret)
+        .map_err(|_| Error::EncryptionError)?;
+
+    Ok(format!(
+        "{}\nsalt={}\nnonce={}\nciphertext={}\n",
+        HEADER,
+        STANDARD.encode(salt),
+        STANDARD.encode(nonce),
+        STANDARD.encode(ciphertext)
+    ))
+}
+
+// a wrong passphrase and a tampered file look the same to aes-gcm
+pub fn decrypt_secret(content: &str, passphrase: &str) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let mut lines = content.lines().map(str::trim);
+    if lines.next() != Some(HEADER) {
+        return Err(Error::InvalidEncryptedFile);
+    }
+
+    let (mut salt, mut nonce, mut ciphertext) = (None, None, None);
+    for line in lines {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let decoded = STANDARD.decode(value).ok();
+        match key {
+            "salt" => salt = decoded,
+            "nonce" => nonce = decoded,
+            "ciphertext" => ciphertext = decoded,
+            _ => {}
+        }
+    }
+
+    let (Some(salt), Some(nonce), Some(ciphertext)) = (salt, nonce, ciphertext) else {
+        return Err(Error::InvalidEncryptedFile);
+    };
+    if nonce.len() != NONCE_LEN {
+        return Err(Error::InvalidEncryptedFile);
+    }
+
+    derive_key(passphrase, &salt)?
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map(Zeroizing::new)
+        .map_err(|_| Error::WrongPassphrase)
+}
+
+// text inputs hand over a fresh String on every keystroke, wipe the old one
+pub fn replace_secret(slot: &mut String, value: String) {
+    slot.zeroize();
+    *slot = value;
+}
+
+pub fn check_new_passphrase(passphrase: &str, confirmation: &str) -> Result<(), Error> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(Error::WeakPassphrase);
+    }
+    if passphrase != confirmation {
+        return Err(Error::PassphraseMismatch);
+    }
+    Ok(())
+}
+
+pub async fn export_encrypted_keypair(
+    keypair: Arc<Keypair>,
+    passphrase: Zeroizing<String>,
+) -> Result<PathBuf, Error> {
+    let secret = Zeroizing::new(keypair.to_bytes());
+    let content = encrypt_secret(secret.as_ref(), &passphrase)?;
+
+    let path = save_file("wallet.enc").await?;
+    if path.exists() {
+        return Err(Error::FileAlreadyExists);
+    }
+    tokio::fs::write(&path, content)
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(path)
+}
+
+pub fn display_encrypted_export(
+    passphrase: &str,
+    confirmation: &str,
+    exported: &Option<PathBuf>,
+) -> Element<'static, Message> {
+    let title = text(tr("Encrypted backup")).size(14).style(accent_color());
+
+    let passphrase_input = text_input(tr("Backup passphrase (at least 8 characters)"), passphrase)
+        .on_input(Message::BackupPassphraseChanged)
+        .password();
+    let confirmation_input = text_input(tr("Repeat the passphrase"), confirmation)
+        .on_input(Message::BackupConfirmationChanged)
+        .password();
+
+    let export = button(tr("Export encrypted keypair")).on_press(Message::ExportEncryptedKeypair);
+
+    let exported = match exported {
+        Some(path) => text(tr_args(
+            "Backup written to {$path}",
+            &[("path", &path.display())],
+        ))
+        .size(12),
+        None => text(tr(
+            "The passphrase can't be recovered, without it the backup is useless",
+        ))
+        .size(12),
+    };
+
+    column![
+        title,
+        passphrase_input,
+        confirmation_input,
+        export,
+        exported
+    ]
+    .spacing(10)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"a keypair's 64 secret bytes, or anything else";
+
+    #[test]
+    fn round_trips_with_the_right_passphrase() {
+        let content = encrypt_secret(SECRET, "passphrase").unwrap();
+        assert!(content.starts_with(HEADER));
+        assert_eq!(
+            decrypt_secret(&content, "passphrase").unwrap().as_slice(),
+            SECRET
+        );
+    }
+
+    #[test]
+    fn each_file_gets_its_own_salt_and_nonce() {
+        assert_ne!(
+            encrypt_secret(SECRET, "passphrase").unwrap(),
+            encrypt_secret(SECRET, "passphrase").unwrap()
+        );
+    }
+
+    #[test]
+    fn a_wrong_passphrase_is_rejected() {
+        let content = encrypt_secret(SECRET, "passphrase").unwrap();
+        assert!(matches!(
+            decrypt_secret(&content, "passphrasf"),
+            Err(Error::WrongPassphrase)
+        ));
+    }
+
+    #[test]
+    fn a_tampered_ciphertext_is_rejected() {
+        let content = encrypt_secret(SECRET, "passphrase").unwrap();
+        let tampered: String = content
+            .lines()
+            .map(|line| match line.strip_prefix("ciphertext=") {
+                Some(encoded) => {
+                    let mut bytes = STANDARD.decode(encoded).unwrap();
+                    bytes[0] ^= 1;
+                    format!("ciphertext={}\n", STANDARD.encode(bytes))
+                }
+                None => format!("{}\n", line),
+            })
+            .collect();
+        assert!(matches!(
+            decrypt_secret(&tampered, "passphrase"),
+            Err(Error::WrongPassphrase)
+        ));
+    }
+
+    #[test]
+    fn a_file_missing_fields_is_not_an_encrypted_keypair() {
+        let content = encrypt_secret(SECRET, "passphrase").unwrap();
+        let without_nonce: String = content
+            .lines()
+            .filter(|line| !line.starts_with("nonce="))
+            .map(|line| format!("{}\n", line))
+            .collect();
+        assert!(matches!(
+            decrypt_secret(&without_nonce, "passphrase"),
+            Err(Error::InvalidEncryptedFile)
+        ));
+        assert!(matches!(
+            decrypt_secret("[1,2,3]", "passphrase"),
+            Err(Error::InvalidEncryptedFile)
+        ));
+    }
+
+    #[test]
+    fn new_passphrases_need_eight_characters_typed_twice() {
+        assert!(check_new_passphrase("12345678", "12345678").is_ok());
+        // counted in characters, not bytes
+        assert!(check_new_passphrase("ñññññññ", "ñññññññ").is_err());
+        assert!(matches!(
+            check_new_passphrase("1234567", "1234567"),
+            Err(Error::WeakPassphrase)
+        ));
+        assert!(matches!(
+            check_new_passphrase("12345678", "12345679"),
+            Err(Error::PassphraseMismatch)
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+use std::{sync::Arc, time::Duration};
+
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use crate::{errors::Error, network::fetch_slot_time};
+
+// the slot subscription moves the progress between refreshes
+pub const EPOCH_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy)]
+pub struct EpochProgress {
+    pub epoch: u64,
+    // slot the epoch info was taken at and its index inside the epoch
+    pub absolute_slot: u64,
+    pub slot_index: u64,
+    pub slots_in_epoch: u64,
+    // average seconds per slot from the recent performance samples
+    pub slot_time: f64,
+}
+
+impl EpochProgress {
+    // how far into the epoch `slot` is, clamped to the epoch's last slot
+    fn index_at(&self, slot: Option<u64>) -> u64 {
+        let elapsed = slot
+            .map(|slot| slot.saturating_sub(self.absolute_slot))
+            .unwrap_or(0);
+        (self.slot_index + elapsed).min(self.slots_in_epoch)
+    }
+
+    pub fn percent(&self, slot: Option<u64>) -> f64 {
+        if self.slots_in_epoch == 0 {
+            return 0.0;
+        }
+        self.index_at(slot) as f64 / self.slots_in_epoch as f64 * 100.0
+    }
+
+    pub fn remaining(&self, slot: Option<u64>) -> Duration {
+        let slots_left = self.slots_in_epoch - self.index_at(slot);
+        Duration::from_secs_f64(slots_left as f64 * self.slot_time)
+    }
+}
+
+pub async fn fetch_epoch_progress(rpc_client: Arc<RpcClient>) -> Result<EpochProgress, Error> {
+    let info = rpc_client
+        .get_epoch_info()
+        .await
+        .map_err(|_| Error::FetchNetworkInfoError)?;
+    let slot_time = fetch_slot_time(rpc_client).await?;
+
+    Ok(EpochProgress {
+        epoch: info.epoch,
+        absolute_slot: info.absolute_slot,
+        slot_index: info.slot_index,
+        slots_in_epoch: info.slots_in_epoch,
+        slot_time,
+    })
+}
+
+// "1d 4h", "3h 12m" or "45m", an epoch is about two days on mainnet
+pub fn format_countdown(remaining: Duration) -> String {
+    let minutes = remaining.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
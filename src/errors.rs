@@ -1,3 +1,12 @@
+use std::fmt;
+
+use iced::Command;
+
+use crate::{
+    i18n::{tr, tr_args},
+    Message,
+};
+
 #[derive(Debug, Clone)]
 pub enum Error {
     DialogClosed,
@@ -7,5 +16,178 @@ pub enum Error {
     TransactionError,
     InvalidAmount,
     InvalidPubKeyLen,
-    InsufficientBalance
+    InsufficientBalance,
+    FetchFeeError,
+    InvalidPriorityFee,
+    FetchReceiptsError,
+    SimulationError(String),
+    MemoRequired,
+    BelowMinimumDeposit,
+    BlockhashExpired,
+    NonceAdvanced,
+    FetchLookupTablesError,
+    RecipientNotAllowed,
+    MintNotAllowed,
+    InvalidAdminPassword,
+    SaveFileError,
+    FetchNonceError,
+    InvalidNonceAccount,
+    InvalidTransactionFile,
+    SignerNotRequired,
+    MissingSignatures,
+    FetchHistoryError,
+    InvalidPayrollFile,
+    AirdropError,
+    FaucetRateLimited,
+    InvalidTestSetLabel,
+    InvalidTestWalletCount,
+    FetchTransactionError,
+    FetchAccountError,
+    InvalidMint,
+    OwnerOffCurve,
+    OwnerNotSystemAccount,
+    SpendLimitReached,
+    NoWalletPassword,
+    InvalidKeypairFile,
+    FileAlreadyExists,
+    InvalidMnemonic,
+    FetchNetworkInfoError,
+    EncryptionError,
+    InvalidEncryptedFile,
+    WrongPassphrase,
+    WeakPassphrase,
+    PassphraseMismatch,
+    KeychainError,
+    WatchOnlyWallet,
+    InvalidProfileName,
+    InvalidKeypairJson,
+    InvalidVanityPattern,
+    VanityCancelled,
+    NotificationError,
+    TrayError,
+    NoRpcEndpointAnswered,
+    ClusterMismatch,
+    FetchPriceError,
+    FetchTokenListError,
+    InvalidAlertThreshold,
+    InvalidSwapAmount,
+    SwapQuoteError,
+    SwapError,
+    SwapPayerMismatch,
+    SwapOutflowMismatch,
+    InvalidSlippage,
+    NoKeypairLoaded,
+    NoWrappedSol,
+    InvalidVoteAccount,
+    StakeBelowMinimum,
+    StakeStillActive,
+    StakeNotMergeable,
+    FetchValidatorsError,
+    FetchRewardsError,
+    StakePoolNotFound,
+    StakePoolOutOfDate,
+    BundleRejected,
+    JitoTipTooLow,
+    ServiceNotAllowed,
+}
+
+// what the user is shown, `Debug` stays for logs and tests
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Error::SimulationError(reason) => {
+                return write!(
+                    f,
+                    "{}",
+                    tr_args("The simulation failed: {$reason}", &[("reason", reason)])
+                )
+            }
+            Error::DialogClosed => tr("No file was picked"),
+            Error::FetchBalanceError => tr("Couldn't fetch the balance"),
+            Error::InvalidFileType => tr("That file type isn't supported"),
+            Error::FetchBlockhashError => tr("Couldn't fetch a recent blockhash"),
+            Error::TransactionError => tr("The transaction failed"),
+            Error::InvalidAmount => tr("Invalid amount"),
+            Error::InvalidPubKeyLen => tr("Invalid address"),
+            Error::InsufficientBalance => tr("Insufficient balance"),
+            Error::FetchFeeError => tr("Couldn't estimate the fee"),
+            Error::InvalidPriorityFee => tr("Invalid priority fee"),
+            Error::FetchReceiptsError => tr("Couldn't fetch the receipts"),
+            Error::MemoRequired => tr("This exchange needs a memo with the deposit"),
+            Error::BelowMinimumDeposit => tr("The amount is below the exchange's minimum deposit"),
+            Error::BlockhashExpired => tr("The transaction expired before it landed, nothing was sent"),
+            Error::NonceAdvanced => tr("The nonce account moved on before the transaction landed, nothing was sent"),
+            Error::FetchLookupTablesError => tr("Couldn't fetch the lookup tables"),
+            Error::RecipientNotAllowed => tr("The recipient isn't on the allow-list"),
+            Error::MintNotAllowed => tr("The token mint isn't on the allow-list"),
+            Error::InvalidAdminPassword => tr("Wrong admin password"),
+            Error::SaveFileError => tr("Couldn't save the file"),
+            Error::FetchNonceError => tr("Couldn't fetch the nonce account"),
+            Error::InvalidNonceAccount => tr("That isn't an initialized nonce account"),
+            Error::InvalidTransactionFile => tr("Invalid transaction file"),
+            Error::SignerNotRequired => tr("The loaded wallet doesn't need to sign this transaction"),
+            Error::MissingSignatures => tr("The transaction is still missing signatures"),
+            Error::FetchHistoryError => tr("Couldn't fetch the history"),
+            Error::InvalidPayrollFile => tr("Invalid payroll file, each line needs an address and an amount"),
+            Error::AirdropError => tr("The airdrop failed"),
+            Error::FaucetRateLimited => tr("The faucet is rate limiting this address, try again later"),
+            Error::InvalidTestSetLabel => tr("Invalid label or a test set with that label already exists, use letters, numbers, - and _"),
+            Error::InvalidTestWalletCount => tr("Invalid number of wallets"),
+            Error::FetchTransactionError => tr("Couldn't fetch the transaction"),
+            Error::FetchAccountError => tr("Couldn't fetch the account"),
+            Error::InvalidMint => tr("Invalid token mint"),
+            Error::OwnerOffCurve => tr("The owner is a program address, it can't own an associated token account this way"),
+            Error::OwnerNotSystemAccount => tr("The owner isn't a wallet address"),
+            Error::SpendLimitReached => tr("This would go over the session spend limit"),
+            Error::NoWalletPassword => tr("No wallet password is set, add a startup password or move the wallet to the keystore first"),
+            Error::InvalidKeypairFile => tr("Invalid keypair file"),
+            Error::FileAlreadyExists => tr("The file already exists"),
+            Error::InvalidMnemonic => tr("Invalid seed phrase"),
+            Error::FetchNetworkInfoError => tr("Couldn't fetch the network info"),
+            Error::EncryptionError => tr("Encryption failed"),
+            Error::InvalidEncryptedFile => tr("Invalid encrypted file"),
+            Error::WrongPassphrase => tr("Wrong passphrase"),
+            Error::WeakPassphrase => tr("The passphrase needs at least 8 characters"),
+            Error::PassphraseMismatch => tr("The passphrases don't match"),
+            Error::KeychainError => tr("The OS keychain couldn't be used"),
+            Error::WatchOnlyWallet => tr("A watched address can't sign, load its keypair to send"),
+            Error::InvalidProfileName => tr("Invalid profile name"),
+            Error::InvalidKeypairJson => tr("That isn't a keypair, paste the [12,34,...] array from a keypair file"),
+            Error::InvalidVanityPattern => tr("Type a prefix or suffix using only base58 characters"),
+            Error::VanityCancelled => tr("The search was stopped"),
+            Error::NotificationError => tr("Couldn't show the notification"),
+            Error::TrayError => tr("Couldn't create the tray icon"),
+            Error::NoRpcEndpointAnswered => tr("No RPC endpoint answered"),
+            Error::ClusterMismatch => tr("The RPC endpoint is on another cluster than the one this was prepared for"),
+            Error::FetchPriceError => tr("Couldn't fetch prices"),
+            Error::FetchTokenListError => tr("Couldn't fetch Jupiter's verified token list"),
+            Error::InvalidAlertThreshold => tr("Invalid alert threshold"),
+            Error::InvalidSwapAmount => tr("Invalid swap amount"),
+            Error::SwapQuoteError => tr("Couldn't get a swap quote"),
+            Error::SwapError => tr("The swap failed"),
+            Error::SwapPayerMismatch => tr("Jupiter's transaction charges another wallet, it wasn't signed"),
+            Error::SwapOutflowMismatch => tr("Jupiter's transaction would take more than the quote, it wasn't signed"),
+            Error::InvalidSlippage => tr("Invalid slippage, use 1 to 5000 bps"),
+            Error::NoKeypairLoaded => tr("No keypair loaded"),
+            Error::NoWrappedSol => tr("This wallet has no wrapped SOL"),
+            Error::InvalidVoteAccount => tr("Invalid vote account"),
+            Error::StakeBelowMinimum => tr("The amount is below the minimum stake"),
+            Error::StakeStillActive => tr("The stake is still active, deactivate it and wait for the epoch to end"),
+            Error::StakeNotMergeable => tr("These stake accounts can't be merged"),
+            Error::FetchValidatorsError => tr("Couldn't fetch the validators"),
+            Error::FetchRewardsError => tr("Couldn't fetch the staking rewards"),
+            Error::StakePoolNotFound => tr("Stake pool not found"),
+            Error::StakePoolOutOfDate => tr("The stake pool hasn't been updated this epoch yet, try again later"),
+            Error::BundleRejected => tr("The Jito block engine rejected the bundle"),
+            Error::JitoTipTooLow => tr("The Jito tip is invalid or below the minimum"),
+            Error::ServiceNotAllowed => tr("That service isn't allowed in Network access"),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+// the feature modules' update has no toasts of its own, their errors go
+// back through the app
+pub fn report(error: Error) -> Command<Message> {
+    Command::perform(async { error }, Message::ShowError)
 }
@@ -0,0 +1,51 @@
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey, pubkey::Pubkey};
+
+use crate::errors::Error;
+
+pub struct ExchangeRule {
+    pub name: &'static str,
+    pub address: Pubkey,
+    pub requires_memo: bool,
+    pub min_deposit: u64,
+}
+
+// shared deposit addresses, keep them in sync with each exchange's deposit page
+pub const EXCHANGE_RULES: &[ExchangeRule] = &[
+    ExchangeRule {
+        name: "Binance",
+        address: pubkey!("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+        requires_memo: true,
+        min_deposit: LAMPORTS_PER_SOL / 100,
+    },
+    ExchangeRule {
+        name: "Binance",
+        address: pubkey!("5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9"),
+        requires_memo: true,
+        min_deposit: LAMPORTS_PER_SOL / 100,
+    },
+    ExchangeRule {
+        name: "Coinbase",
+        address: pubkey!("H8sMJSCQxfKiFTCfDR3DUMLPwcRbM61LGFJ8N4dK3WjS"),
+        requires_memo: false,
+        min_deposit: 0,
+    },
+];
+
+pub fn find_exchange(address: &Pubkey) -> Option<&'static ExchangeRule> {
+    EXCHANGE_RULES.iter().find(|rule| &rule.address == address)
+}
+
+// run before broadcasting anything to a known exchange address
+pub fn check_deposit(to: &Pubkey, lamports: u64, memo: &str) -> Result<(), Error> {
+    let Some(rule) = find_exchange(to) else {
+        return Ok(());
+    };
+
+    if rule.requires_memo && memo.trim().is_empty() {
+        return Err(Error::MemoRequired);
+    }
+    if lamports < rule.min_deposit {
+        return Err(Error::BelowMinimumDeposit);
+    }
+    Ok(())
+}
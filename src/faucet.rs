@@ -0,0 +1,233 @@
+use std::{sync::Arc, time::Duration};
+
+use iced::{
+    widget::{button, checkbox, column, row, text, text_input, Column},
+    Alignment, Command, Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use tokio::time;
+
+use crate::{
+    i18n::tr,
+    palette::{accent_color, error_color},
+    units::{format_sol, parse_amount},
+    Error, Message,
+};
+
+// the public faucets hand out at most this much per request
+const MAX_AIRDROP: u64 = 2 * LAMPORTS_PER_SOL;
+// pause between requests so we stay under the faucet rate limits
+pub const AIRDROP_COOLDOWN: Duration = Duration::from_secs(10);
+// how often the automatic top up runs while it's enabled
+pub const AUTO_TOP_UP_INTERVAL: Duration = Duration::from_secs(300);
+const CONFIRM_ATTEMPTS: usize = 30;
+
+// only devnet and testnet (and a local validator) have a faucet
+pub fn has_faucet(rpc_url: &str) -> bool {
+    ["devnet", "testnet", "localhost", "127.0.0.1"]
+        .iter()
+        .any(|cluster| rpc_url.contains(cluster))
+}
+
+fn airdrop_error(error: impl ToString) -> Error {
+    let message = error.to_string().to_lowercase();
+    if message.contains("429") || message.contains("rate limit") {
+        Error::FaucetRateLimited
+    } else {
+        Error::AirdropError
+    }
+}
+
+async fn airdrop(address: &Pubkey, lamports: u64, rpc_client: &RpcClient) -> Result<(), Error> {
+    let signature = rpc_client
+        .request_airdrop(address, lamports)
+        .await
+        .map_err(airdrop_error)?;
+
+    for _ in 0..CONFIRM_ATTEMPTS {
+        if rpc_client
+            .confirm_transaction(&signature)
+            .await
+            .map_err(airdrop_error)?
+        {
+            return Ok(());
+        }
+        time::sleep(Duration::from_secs(1)).await;
+    }
+
+    Err(Error::AirdropError)
+}
+
+// requests airdrops until the address holds `target` lamports, returns how
+// much was airdropped
+pub async fn top_up(
+    address: Pubkey,
+    target: u64,
+    rpc_client: Arc<RpcClient>,
+) -> Result<u64, Error> {
+    let mut airdropped = 0;
+
+    loop {
+        let balance = rpc_client
+            .get_balance(&address)
+            .await
+            .map_err(|_| Error::FetchBalanceError)?;
+        if balance >= target {
+            return Ok(airdropped);
+        }
+
+        if airdropped > 0 {
+            time::sleep(AIRDROP_COOLDOWN).await;
+        }
+
+        let lamports = (target - balance).min(MAX_AIRDROP);
+        airdrop(&address, lamports, &rpc_client).await?;
+        airdropped += lamports;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TopUpResult {
+    pub address: Pubkey,
+    pub result: Result<u64, Error>,
+}
+
+// tops up every wallet one after the other, stops early once the faucet
+// starts rate limiting since the remaining requests would fail too
+pub async fn top_up_all(
+    addresses: Vec<Pubkey>,
+    target: u64,
+    rpc_client: Arc<RpcClient>,
+) -> Vec<TopUpResult> {
+    let mut results = Vec::new();
+
+    for (index, address) in addresses.iter().enumerate() {
+        if index > 0 {
+            time::sleep(AIRDROP_COOLDOWN).await;
+        }
+
+        let result = top_up(*address, target, rpc_client.clone()).await;
+        let rate_limited = matches!(result, Err(Error::FaucetRateLimited));
+        results.push(TopUpResult {
+            address: *address,
+            result,
+        });
+        if rate_limited {
+            break;
+        }
+    }
+
+    results
+}
+
+// the faucet screen, the app picks which wallets get topped up
+#[derive(Debug)]
+pub struct Faucet {
+    pub target: String,
+    pub auto_top_up: bool,
+    pub running: bool,
+    pub results: Vec<TopUpResult>,
+}
+
+impl Default for Faucet {
+    fn default() -> Self {
+        Self {
+            target: String::from("1"),
+            auto_top_up: false,
+            running: false,
+            results: Vec::new(),
+        }
+    }
+}
+
+impl Faucet {
+    pub fn target(&self) -> Result<u64, Error> {
+        parse_amount(&self.target)
+    }
+
+    pub fn start(
+        &mut self,
+        addresses: Vec<Pubkey>,
+        target: u64,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        self.running = true;
+        Command::perform(
+            top_up_all(addresses, target, rpc_client),
+            Message::WalletsToppedUp,
+        )
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::FaucetTargetChanged(target) => self.target = target,
+            Message::AutoTopUpToggled(auto_top_up) => self.auto_top_up = auto_top_up,
+            Message::WalletsToppedUp(results) => {
+                self.running = false;
+                self.results = results;
+            }
+            _ => {}
+        }
+        Command::none()
+    }
+}
+
+pub fn display_faucet(
+    rpc_url: &str,
+    target: &str,
+    auto_top_up: bool,
+    is_running: bool,
+    results: &[TopUpResult],
+) -> Element<'static, Message> {
+    if !has_faucet(rpc_url) {
+        return text(tr("The faucet is only available on devnet and testnet"))
+            .size(14)
+            .into();
+    }
+
+    let target_input =
+        text_input(tr("Target balance (SOL)"), target).on_input(Message::FaucetTargetChanged);
+
+    let top_up_button = if is_running {
+        button(tr("Requesting airdrops..."))
+    } else {
+        button(tr("Top up now")).on_press(Message::TopUpWallets)
+    };
+
+    let auto = checkbox(
+        tr("Keep wallets topped up during this session"),
+        auto_top_up,
+        Message::AutoTopUpToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    let results_list = results
+        .iter()
+        .fold(Column::new().spacing(5), |column, top_up| {
+            let status = match &top_up.result {
+                Ok(0) => text(tr("already at target")).size(12),
+                Ok(lamports) => text(format!("+{} SOL", format_sol(*lamports)))
+                    .size(12)
+                    .style(accent_color()),
+                Err(error) => text(error.to_string()).size(12).style(error_color()),
+            };
+            column.push(
+                row![text(top_up.address.to_string()).size(12), status]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            )
+        });
+
+    column![
+        text(tr("Devnet faucet")).size(14).style(accent_color()),
+        target_input,
+        row![top_up_button, auto]
+            .spacing(20)
+            .align_items(Alignment::Center),
+        results_list
+    ]
+    .spacing(10)
+    .into()
+}
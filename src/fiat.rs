@@ -0,0 +1,15 @@
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+// "none" turns the fiat values and the price polling off
+pub const CURRENCIES: [&str; 7] = ["none", "usd", "eur", "gbp", "jpy", "cad", "ars"];
+
+pub fn shows_fiat(currency: &str) -> bool {
+    CURRENCIES.contains(&currency) && currency != "none"
+}
+
+// "≈ 12.34 USD", yen has no cents
+pub fn format_fiat(lamports: u64, price: f64, currency: &str) -> String {
+    let value = lamports as f64 / LAMPORTS_PER_SOL as f64 * price;
+    let decimals = if currency == "jpy" { 0 } else { 2 };
+    format!("≈ {:.*} {}", decimals, value, currency.to_uppercase())
+}
@@ -1,21 +1,154 @@
-use std::{env, path::PathBuf};
+use std::{env, path::PathBuf, sync::OnceLock};
 
-use crate::errors::Error;
+use crate::{config::load_config, errors::Error, i18n::tr, settings::load_preferences};
 use rfd::AsyncFileDialog;
-pub const DEFAULT_LOCATION: &str = ".config/solana/id.json";
+use solana_cli_config::{Config, CONFIG_FILE};
+pub const DEFAULT_LOCATION: &str = "solana/id.json";
+pub const APP_CONFIG_LOCATION: &str = "solana-exec-app";
+// next to the executable, enables portable mode when present
+pub const PORTABLE_MARKER: &str = "portable";
+pub const PORTABLE_DATA_LOCATION: &str = "solana-exec-app-data";
 
-pub fn default_file() -> PathBuf {
-    let home_dir = env::var("HOME") // mac users
+static APP_CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
+static CLI_CONFIG: OnceLock<Option<Config>> = OnceLock::new();
+
+fn home_dir() -> PathBuf {
+    env::var("HOME") // mac users
         .or_else(|_| env::var("USERPROFILE")) // windows users
-        .expect("Cannot find home directory");
-    let mut path = PathBuf::from(home_dir);
-    path.push(DEFAULT_LOCATION);
-    path
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
+// $XDG_CONFIG_HOME when set, ~/.config otherwise
+fn config_homes() -> Vec<PathBuf> {
+    let mut homes = Vec::new();
+    if let Some(xdg) = env::var_os("XDG_CONFIG_HOME").map(PathBuf::from) {
+        if xdg.is_absolute() {
+            homes.push(xdg);
+        }
+    }
+    homes.push(home_dir().join(".config"));
+    homes
+}
+
+// the first candidate that already exists keeps working after XDG_CONFIG_HOME
+// gets set, a fresh install uses the preferred one
+fn config_path(location: &str) -> PathBuf {
+    let candidates: Vec<PathBuf> = config_homes()
+        .into_iter()
+        .map(|home| home.join(location))
+        .collect();
+    candidates
+        .iter()
+        .find(|path| path.exists())
+        .unwrap_or(&candidates[0])
+        .clone()
+}
+
+// what `solana config get` reports, None when the cli was never configured
+pub fn solana_cli_config() -> Option<&'static Config> {
+    CLI_CONFIG
+        .get_or_init(|| {
+            let path = CONFIG_FILE.as_ref()?;
+            Config::load(path).ok()
+        })
+        .as_ref()
+}
+
+// the file picked in settings, then the cli's keypair_path, then id.json
+// where the cli puts it by default
+pub fn default_file() -> PathBuf {
+    let configured = load_preferences().default_keypair_path;
+    if !configured.trim().is_empty() {
+        return PathBuf::from(configured.trim());
+    }
+    if let Some(config) = solana_cli_config() {
+        if !config.keypair_path.is_empty() {
+            return PathBuf::from(&config.keypair_path);
+        }
+    }
+    config_path(DEFAULT_LOCATION)
+}
+
+// what's opened on launch: the keypair set in settings, otherwise the one
+// opened last if it's still there, then the cli's
+pub fn startup_file() -> PathBuf {
+    let last = PathBuf::from(load_config().last_keypair_path.trim());
+    if load_preferences().default_keypair_path.trim().is_empty()
+        && !last.as_os_str().is_empty()
+        && last.exists()
+    {
+        return last;
+    }
+    default_file()
+}
+
+// where the solana cli keeps id.json and, usually, the user's other keypairs
+pub fn solana_config_dir() -> PathBuf {
+    default_file()
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(home_dir)
+}
+
+fn executable_dir() -> Option<PathBuf> {
+    env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(PathBuf::from))
+}
+
+pub fn is_portable() -> bool {
+    env::args().any(|arg| arg == "--portable")
+        || executable_dir()
+            .map(|dir| dir.join(PORTABLE_MARKER).exists())
+            .unwrap_or(false)
+}
+
+fn data_dir_override() -> Option<PathBuf> {
+    let mut args = env::args();
+    args.find(|arg| arg == "--data-dir")?;
+    args.next().map(PathBuf::from)
+}
+
+// where the app keeps its own files: `--data-dir <path>`, then portable mode,
+// then the user's config directory
+pub fn app_config_dir() -> PathBuf {
+    APP_CONFIG_DIR
+        .get_or_init(|| {
+            if let Some(path) = data_dir_override() {
+                return path;
+            }
+            if is_portable() {
+                if let Some(dir) = executable_dir() {
+                    return dir.join(PORTABLE_DATA_LOCATION);
+                }
+            }
+            config_path(APP_CONFIG_LOCATION)
+        })
+        .clone()
+}
+
+// takes effect on the next launch
+pub async fn set_portable_mode(portable: bool) -> Result<bool, Error> {
+    let marker = executable_dir()
+        .ok_or(Error::SaveFileError)?
+        .join(PORTABLE_MARKER);
+
+    if portable {
+        tokio::fs::write(marker, "")
+            .await
+            .map_err(|_| Error::SaveFileError)?;
+    } else if marker.exists() {
+        tokio::fs::remove_file(marker)
+            .await
+            .map_err(|_| Error::SaveFileError)?;
+    }
+    Ok(portable)
 }
 
 pub async fn pick_file() -> Result<PathBuf, Error> {
     let handle = AsyncFileDialog::new()
-        .set_title("Choose a valid json solana keypair")
+        .set_title(tr("Choose a valid json solana keypair"))
         .pick_file()
         .await
         .ok_or(Error::DialogClosed)?;
@@ -26,3 +159,37 @@ pub async fn pick_file() -> Result<PathBuf, Error> {
     Ok(handle.path().to_owned())
 }
 
+pub async fn pick_transaction_file() -> Result<PathBuf, Error> {
+    let handle = AsyncFileDialog::new()
+        .set_title(tr("Choose a base64 transaction file"))
+        .pick_file()
+        .await
+        .ok_or(Error::DialogClosed)?;
+
+    Ok(handle.path().to_owned())
+}
+
+pub async fn pick_csv_file() -> Result<PathBuf, Error> {
+    let handle = AsyncFileDialog::new()
+        .set_title(tr("Choose a csv file of address,amount rows"))
+        .add_filter("CSV", &["csv"])
+        .pick_file()
+        .await
+        .ok_or(Error::DialogClosed)?;
+
+    if handle.path().extension().and_then(|ext| ext.to_str()) != Some("csv") {
+        return Err(Error::InvalidFileType);
+    }
+    Ok(handle.path().to_owned())
+}
+
+pub async fn save_file(file_name: &str) -> Result<PathBuf, Error> {
+    let handle = AsyncFileDialog::new()
+        .set_title(tr("Choose where to save the file"))
+        .set_file_name(file_name)
+        .save_file()
+        .await
+        .ok_or(Error::DialogClosed)?;
+
+    Ok(handle.path().to_owned())
+}
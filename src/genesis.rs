@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use iced::{
+    widget::{column, text},
+    Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use crate::{
+    errors::Error,
+    i18n::{tr, tr_args},
+    palette::error_color,
+    rpc::cluster_name,
+    Message,
+};
+
+const MAINNET_GENESIS: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+const DEVNET_GENESIS: &str = "EtWTRABZaYq6iMfeYKouRu166VL2xqa1wcaWoxPkrZBG";
+const TESTNET_GENESIS: &str = "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY";
+
+// the node says it's on `actual` (None for a chain that isn't public) while
+// the profile or url says `expected`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClusterMismatch {
+    pub expected: &'static str,
+    pub actual: Option<&'static str>,
+}
+
+fn cluster_for_genesis(hash: &str) -> Option<&'static str> {
+    match hash {
+        MAINNET_GENESIS => Some("mainnet-beta"),
+        DEVNET_GENESIS => Some("devnet"),
+        TESTNET_GENESIS => Some("testnet"),
+        _ => None,
+    }
+}
+
+pub fn genesis_hash(cluster: &str) -> Option<&'static str> {
+    match cluster {
+        "mainnet-beta" => Some(MAINNET_GENESIS),
+        "devnet" => Some(DEVNET_GENESIS),
+        "testnet" => Some(TESTNET_GENESIS),
+        _ => None,
+    }
+}
+
+// a profile named after a cluster wins over the url, which is often a
+// provider's endpoint that doesn't say which cluster it serves
+pub fn expected_cluster(profile_name: &str, rpc_url: &str) -> Option<&'static str> {
+    [profile_name.to_lowercase(), rpc_url.to_string()]
+        .iter()
+        .map(|hint| match cluster_name(hint) {
+            "mainnet-beta" => Some("mainnet-beta"),
+            "devnet" => Some("devnet"),
+            "testnet" => Some("testnet"),
+            _ => None,
+        })
+        .find(Option::is_some)
+        .flatten()
+}
+
+pub async fn verify_cluster(
+    expected: Option<&'static str>,
+    rpc_client: Arc<RpcClient>,
+) -> Result<Option<ClusterMismatch>, Error> {
+    let Some(expected) = expected else {
+        return Ok(None);
+    };
+    let genesis_hash = rpc_client
+        .get_genesis_hash()
+        .await
+        .map_err(|_| Error::FetchNetworkInfoError)?;
+
+    let actual = cluster_for_genesis(&genesis_hash.to_string());
+    Ok((actual != Some(expected)).then_some(ClusterMismatch { expected, actual }))
+}
+
+pub fn display_cluster_warning(mismatch: &ClusterMismatch) -> Element<'static, Message> {
+    let actual = mismatch.actual.unwrap_or(tr("an unknown cluster"));
+    column![
+        text(tr_args(
+            "Cluster mismatch: expected {$expected} but the RPC node is on {$actual}",
+            &[("expected", &mismatch.expected), ("actual", &actual)],
+        ))
+        .size(14)
+        .style(error_color()),
+        text(tr(
+            "Sending is disabled until the profile or RPC URL is fixed"
+        ))
+        .size(12),
+    ]
+    .spacing(5)
+    .into()
+}
@@ -0,0 +1,291 @@
+use std::{path::PathBuf, str::FromStr, sync::Arc};
+
+use iced::{
+    widget::{button, column, row, text, Column},
+    Alignment, Element,
+};
+use serde_json::{json, Value};
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::RpcTransactionConfig, rpc_response::RpcConfirmedTransactionStatusWithSignature,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiMessage, UiTransactionEncoding,
+};
+
+use crate::{
+    errors::Error,
+    files::save_file,
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color},
+    rpc::history_commitment,
+    Message,
+};
+
+// getSignaturesForAddress returns at most this many entries per call
+const SIGNATURES_PAGE: usize = 1000;
+
+// which endpoint an entry came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistorySource {
+    Primary,
+    Archival,
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub memo: Option<String>,
+    pub failed: bool,
+    pub source: HistorySource,
+}
+
+// the rpc prefixes every memo with its length: "[5] hello"
+fn clean_memo(memo: &str) -> String {
+    memo.split("; ")
+        .map(|part| match part.split_once("] ") {
+            Some((prefix, memo)) if prefix.starts_with('[') => memo,
+            _ => part,
+        })
+        .collect::<Vec<&str>>()
+        .join("; ")
+}
+
+fn history_entries(
+    signatures: Vec<RpcConfirmedTransactionStatusWithSignature>,
+    source: HistorySource,
+) -> Vec<HistoryEntry> {
+    signatures
+        .into_iter()
+        .map(|status| HistoryEntry {
+            signature: status.signature,
+            slot: status.slot,
+            block_time: status.block_time,
+            memo: status.memo.as_deref().map(clean_memo),
+            failed: status.err.is_some(),
+            source,
+        })
+        .collect()
+}
+
+// a node that pruned its ledger only knows what happened after its first block
+async fn is_pruned(rpc_client: &RpcClient) -> bool {
+    rpc_client
+        .get_first_available_block()
+        .await
+        .map(|slot| slot > 0)
+        .unwrap_or(false)
+}
+
+// when the primary node ran out of history before a full page and it
+// doesn't keep the whole ledger, older entries come from the archival node
+pub async fn fetch_history(
+    owner: Pubkey,
+    rpc_client: Arc<RpcClient>,
+    archival_client: Option<Arc<RpcClient>>,
+) -> Result<Vec<HistoryEntry>, Error> {
+    let commitment = Some(history_commitment(&rpc_client));
+    let signatures = rpc_client
+        .get_signatures_for_address_with_config(
+            &owner,
+            GetConfirmedSignaturesForAddress2Config {
+                commitment,
+                ..GetConfirmedSignaturesForAddress2Config::default()
+            },
+        )
+        .await
+        .map_err(|_| Error::FetchHistoryError)?;
+    let mut entries = history_entries(signatures, HistorySource::Primary);
+
+    let Some(archival_client) = archival_client else {
+        return Ok(entries);
+    };
+    if entries.len() >= SIGNATURES_PAGE || !is_pruned(&rpc_client).await {
+        return Ok(entries);
+    }
+
+    let before = entries
+        .last()
+        .and_then(|entry| Signature::from_str(&entry.signature).ok());
+    let older = archival_client
+        .get_signatures_for_address_with_config(
+            &owner,
+            GetConfirmedSignaturesForAddress2Config {
+                before,
+                until: None,
+                limit: Some(SIGNATURES_PAGE - entries.len()),
+                commitment,
+            },
+        )
+        .await
+        .map_err(|_| Error::FetchHistoryError)?;
+    entries.extend(history_entries(older, HistorySource::Archival));
+
+    Ok(entries)
+}
+
+// fresh entries replace cached ones with the same signature, cached entries
+// the endpoints didn't return this time are kept
+pub fn merge_history(cache: &mut Vec<HistoryEntry>, fresh: Vec<HistoryEntry>) {
+    cache.retain(|cached| {
+        !fresh
+            .iter()
+            .any(|entry| entry.signature == cached.signature)
+    });
+    cache.extend(fresh);
+    cache.sort_by(|a, b| b.slot.cmp(&a.slot));
+}
+
+// pruned nodes don't have old transactions, ask the archival node for those
+pub async fn fetch_transaction(
+    signature: &str,
+    encoding: UiTransactionEncoding,
+    rpc_client: &RpcClient,
+    archival_client: Option<&RpcClient>,
+) -> Result<EncodedConfirmedTransactionWithStatusMeta, Error> {
+    let signature = Signature::from_str(signature).map_err(|_| Error::FetchTransactionError)?;
+    let config = RpcTransactionConfig {
+        encoding: Some(encoding),
+        commitment: Some(history_commitment(rpc_client)),
+        max_supported_transaction_version: Some(0),
+    };
+
+    match rpc_client
+        .get_transaction_with_config(&signature, config)
+        .await
+    {
+        Ok(confirmed) => Ok(confirmed),
+        Err(_) => match archival_client {
+            Some(archival_client) => archival_client
+                .get_transaction_with_config(&signature, config)
+                .await
+                .map_err(|_| Error::FetchTransactionError),
+            None => Err(Error::FetchTransactionError),
+        },
+    }
+}
+
+// parsed instructions, every account with its role and balance change, logs
+fn decoded_transaction_json(
+    signature: &str,
+    confirmed: EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<Value, Error> {
+    let meta = confirmed
+        .transaction
+        .meta
+        .ok_or(Error::FetchTransactionError)?;
+    let EncodedTransaction::Json(transaction) = confirmed.transaction.transaction else {
+        return Err(Error::FetchTransactionError);
+    };
+    let UiMessage::Parsed(message) = transaction.message else {
+        return Err(Error::FetchTransactionError);
+    };
+
+    let accounts: Vec<Value> = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .map(|(index, account)| {
+            let pre_balance = meta.pre_balances.get(index).copied().unwrap_or(0);
+            let post_balance = meta.post_balances.get(index).copied().unwrap_or(0);
+            json!({
+                "pubkey": account.pubkey,
+                "signer": account.signer,
+                "writable": account.writable,
+                "pre_balance": pre_balance,
+                "post_balance": post_balance,
+                "balance_change": post_balance as i128 - pre_balance as i128,
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "signature": signature,
+        "slot": confirmed.slot,
+        "block_time": confirmed.block_time,
+        "fee": meta.fee,
+        "error": meta.err.map(|err| err.to_string()),
+        "recent_blockhash": message.recent_blockhash,
+        "accounts": accounts,
+        "instructions": message.instructions,
+        "inner_instructions": meta.inner_instructions,
+        "logs": meta.log_messages,
+    }))
+}
+
+pub async fn export_decoded_transaction(
+    signature: String,
+    rpc_client: Arc<RpcClient>,
+    archival_client: Option<Arc<RpcClient>>,
+) -> Result<PathBuf, Error> {
+    let confirmed = fetch_transaction(
+        &signature,
+        UiTransactionEncoding::JsonParsed,
+        &rpc_client,
+        archival_client.as_deref(),
+    )
+    .await?;
+
+    let content = serde_json::to_string_pretty(&decoded_transaction_json(&signature, confirmed)?)
+        .map_err(|_| Error::SaveFileError)?;
+
+    let path = save_file(&format!("{}.json", signature)).await?;
+    tokio::fs::write(&path, content)
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(path)
+}
+
+pub fn display_history(entries: &[HistoryEntry]) -> Element<'static, Message> {
+    let refresh = button(tr("Refresh history")).on_press(Message::RefreshHistory);
+
+    let list = entries
+        .iter()
+        .fold(Column::new().spacing(10), |column, entry| {
+            let status = if entry.failed {
+                text(tr("Failed")).size(12).style(error_color())
+            } else {
+                text(tr("Success")).size(12).style(accent_color())
+            };
+
+            let memo = match &entry.memo {
+                Some(memo) => text(tr_args("Memo: {$memo}", &[("memo", memo)])).size(12),
+                None => text("").size(1),
+            };
+
+            column.push(
+                column![
+                    text(entry.signature.clone()).size(14),
+                    text(match entry.block_time {
+                        Some(block_time) => tr_args(
+                            "Slot {$slot} | unix time {$time}",
+                            &[("slot", &entry.slot), ("time", &block_time)],
+                        ),
+                        None => tr_args("Slot {$slot}", &[("slot", &entry.slot)]),
+                    })
+                    .size(12),
+                    row![
+                        status,
+                        if entry.source == HistorySource::Archival {
+                            text(tr("from the archival node")).size(12)
+                        } else {
+                            text("").size(1)
+                        },
+                        button(text(tr("Export decoded JSON")).size(12))
+                            .on_press(Message::ExportDecodedTransaction(entry.signature.clone())),
+                        button(text(tr("Replay")).size(12))
+                            .on_press(Message::ReplayTransaction(entry.signature.clone())),
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+                    memo
+                ]
+                .spacing(2),
+            )
+        });
+
+    column![refresh, list].spacing(10).into()
+}
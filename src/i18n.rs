@@ -0,0 +1,727 @@
+use std::{fmt, sync::RwLock};
+
+// the english text is the key, a string without a translation is shown as is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "es" => Language::Spanish,
+            _ => Language::English,
+        }
+    }
+}
+
+// each language is listed under its own name in the picker
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::English => write!(f, "English"),
+            Language::Spanish => write!(f, "Español"),
+        }
+    }
+}
+
+static LANGUAGE: RwLock<Language> = RwLock::new(Language::English);
+
+pub fn set_language(language: Language) {
+    *LANGUAGE.write().unwrap() = language;
+}
+
+pub fn tr(text: &'static str) -> &'static str {
+    match *LANGUAGE.read().unwrap() {
+        Language::English => text,
+        Language::Spanish => spanish(text).unwrap_or(text),
+    }
+}
+
+// fluent-style messages: `{$name}` placeholders are filled in after the
+// lookup, so each language puts the values where its grammar wants them
+pub fn tr_args(text: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    args.iter()
+        .fold(tr(text).to_string(), |message, (name, value)| {
+            message.replace(&format!("{{${}}}", name), &value.to_string())
+        })
+}
+
+fn spanish(text: &str) -> Option<&'static str> {
+    Some(match text {
+        // header
+        "SOL Balance: " => "Saldo SOL: ",
+        "Hidden, click or Ctrl+H to reveal" => "Oculto, haz clic o pulsa Ctrl+H para mostrarlo",
+        "Loading balance..." => "Cargando saldo...",
+        "Balance hidden" => "Saldo oculto",
+        "Path of your keypair:" => "Ruta de tu keypair:",
+        "No keypair loaded" => "Ningún keypair cargado",
+        "No wallet loaded" => "Ninguna wallet cargada",
+        "Load keypair" => "Cargar keypair",
+        "Create new wallet" => "Crear wallet nueva",
+        "Create with seed phrase" => "Crear con frase semilla",
+        "Paste keypair" => "Pegar keypair",
+        "Commands (Ctrl+K)" => "Comandos (Ctrl+K)",
+        "Profile" => "Perfil",
+        "Lock" => "Bloquear",
+        "Watch an address (read-only)" => "Observar una dirección (solo lectura)",
+        "Watch" => "Observar",
+        "Watch-only: balances and history are shown, sending is disabled" => {
+            "Solo lectura: se muestran saldos e historial, los envíos están desactivados"
+        }
+        "Load a keypair to send from this wallet" => {
+            "Carga un keypair para enviar desde esta wallet"
+        }
+        // tabs
+        "Send SOL" => "Enviar SOL",
+        "Batch send" => "Envío por lotes",
+        "Swap" => "Intercambio",
+        "Validators" => "Validadores",
+        "Network access" => "Acceso a la red",
+        "Import seed phrase" => "Importar frase semilla",
+        "Portfolio" => "Cartera",
+        "History" => "Historial",
+        "Chart" => "Gráfico",
+        "Alerts" => "Alertas",
+        "Network" => "Red",
+        "Test wallets" => "Wallets de prueba",
+        "Vanity address" => "Dirección personalizada",
+        "Token accounts" => "Cuentas de tokens",
+        "Lookup tables" => "Tablas de búsqueda",
+        "Allow-list" => "Lista permitida",
+        "Nonce account" => "Cuenta nonce",
+        "Offline signing" => "Firma sin conexión",
+        "Settings" => "Ajustes",
+        // send form
+        "Send SOL to any wallet!!! LFG" => "¡¡¡Envía SOL a cualquier wallet!!! LFG",
+        "Put receiver address" => "Dirección del destinatario",
+        "Amount to send" => "Cantidad a enviar",
+        "Max" => "Máx",
+        "Keep the rent-exempt minimum when sending max" => {
+            "Conservar el mínimo exento de renta al enviar el máximo"
+        }
+        "Memo (optional)" => "Memo (opcional)",
+        "memo required, " => "memo obligatorio, ",
+        "Priority fee (micro-lamports per compute unit)" => {
+            "Comisión de prioridad (micro-lamports por unidad de cómputo)"
+        }
+        "Notify recipient (memo ping with the payment signature)" => {
+            "Avisar al destinatario (memo con la firma del pago)"
+        }
+        "Use my wallet" => "Usar mi wallet",
+        "Use a separate fee payer keypair" => "Usar otro keypair para pagar las comisiones",
+        "Unwrap my wSOL if my SOL balance can't cover the transfer" => {
+            "Convertir mi wSOL si mi saldo de SOL no cubre el envío"
+        }
+        "Use the durable nonce account (transaction doesn't expire)" => {
+            "Usar la cuenta nonce duradera (la transacción no expira)"
+        }
+        "Send" => "Enviar",
+        "Large transfer, please confirm" => "Envío grande, confírmalo",
+        "Yes, send it" => "Sí, enviar",
+        "Cancel" => "Cancelar",
+        "Simulation preview" => "Vista previa de la simulación",
+        "Program logs:" => "Logs del programa:",
+        "Confirm and send" => "Confirmar y enviar",
+        "ETA: unknown" => "Tiempo estimado: desconocido",
+        "Estimating fee..." => "Calculando comisión...",
+        "Unlock spending" => "Desbloquear gastos",
+        "Wallet password" => "Contraseña de la billetera",
+        "unknown" => "desconocido",
+        // settings
+        "Connection and appearance" => "Conexión y apariencia",
+        "Language" => "Idioma",
+        "Theme" => "Tema",
+        "UI scale (%)" => "Escala de la interfaz (%)",
+        "Explorer" => "Explorador",
+        "Fiat currency" => "Moneda fiat",
+        // network access
+        "Besides the RPC nodes the app only contacts the services allowed below" => {
+            "Además de los nodos RPC, la app solo contacta los servicios permitidos abajo"
+        }
+        "CoinGecko (api.coingecko.com): fiat prices" => {
+            "CoinGecko (api.coingecko.com): precios en fiat"
+        }
+        "Pyth (hermes.pyth.network): SOL/USD when CoinGecko fails" => {
+            "Pyth (hermes.pyth.network): SOL/USD cuando CoinGecko falla"
+        }
+        "Jupiter (jup.ag): swap quotes, swaps, DCA and verified tokens" => {
+            "Jupiter (jup.ag): cotizaciones, intercambios, DCA y tokens verificados"
+        }
+        "Jito block engine: sending transfers as bundles" => {
+            "Block engine de Jito: enviar transferencias como bundles"
+        }
+        "Block engine:" => "Block engine:",
+        // send form
+        "This address has no account yet: send at least {$minimum} SOL (the rent-exempt minimum) or the transfer will fail" => "Esta dirección aún no tiene cuenta: envía al menos {$minimum} SOL (el mínimo exento de renta) o el envío fallará",
+        "{$exchange} deposit address: {$memo}minimum deposit {$minimum} SOL" => "Dirección de depósito de {$exchange}: {$memo}depósito mínimo {$minimum} SOL",
+        "Fee payer: {$address}" => "Pagador de comisiones: {$address}",
+        "Recipient: {$address}" => "Destinatario: {$address}",
+        "Amount: {$amount} SOL" => "Cantidad: {$amount} SOL",
+        "Fee: {$fee} SOL" => "Comisión: {$fee} SOL",
+        "Fee: {$fee}" => "Comisión: {$fee}",
+        "Fee: {$fee} SOL | Total: {$total} SOL" => "Comisión: {$fee} SOL | Total: {$total} SOL",
+        "Your SOL doesn't cover this transfer: your whole wSOL account ({$amount} SOL) will be unwrapped in the same transaction" => "Tu SOL no cubre este envío: toda tu cuenta wSOL ({$amount} SOL) se convertirá en la misma transacción",
+        "Your balance change: {$change} SOL" => "Cambio en tu saldo: {$change} SOL",
+        "Recipient balance change: {$change} SOL" => "Cambio en el saldo del destinatario: {$change} SOL",
+        "Compute units consumed: {$units}" => "Unidades de cómputo consumidas: {$units}",
+        "ETA: ~{$confirmed}s to confirmed, ~{$finalized}s to finalized" => "Tiempo estimado: ~{$confirmed}s hasta confirmada, ~{$finalized}s hasta finalizada",
+        "Confirming... {$elapsed}s elapsed (~{$finalized}s to finalized)" => "Confirmando... {$elapsed}s transcurridos (~{$finalized}s hasta finalizada)",
+        "Confirming... {$elapsed}s elapsed" => "Confirmando... {$elapsed}s transcurridos",
+        "Spent this session: {$spent} of {$limit} SOL" => "Gastado en esta sesión: {$spent} de {$limit} SOL",
+        "Payment acknowledged: {$signature}" => "Pago confirmado por el destinatario: {$signature}",
+        "Amount" => "Cantidad",
+        // toasts
+        "Transaction confirmed" => "Transacción confirmada",
+        "Copy signature" => "Copiar firma",
+        "Swap confirmed" => "Intercambio confirmado",
+        "Recurring swap done: {$summary}" => "Intercambio recurrente hecho: {$summary}",
+        "Recurring swap failed: {$summary} ({$error})" => "Falló el intercambio recurrente: {$summary} ({$error})",
+        "Stake account {$address} created and delegated" => "Cuenta de stake {$address} creada y delegada",
+        "Split off into stake account {$address}" => "Separado en la cuenta de stake {$address}",
+        "Using {$url}" => "Usando {$url}",
+        "Wallet moved to the encrypted keystore" => "Wallet movida al almacén cifrado",
+        "Encrypted backup written to {$path}" => "Copia cifrada guardada en {$path}",
+        "Alert: {$alert}" => "Alerta: {$alert}",
+        // offline signing
+        "Saved to {$path}" => "Guardado en {$path}",
+        "Fully signed, saved to {$path}" => "Firmada por completo, guardada en {$path}",
+        "Partially signed, saved to {$path}. Still missing: {$missing}" => "Firmada en parte, guardada en {$path}. Faltan: {$missing}",
+        "Broadcasted: {$signature}" => "Enviada: {$signature}",
+        // keystore and backups
+        "Unlock your wallet" => "Desbloquea tu wallet",
+        "Keystore passphrase" => "Frase de contraseña del almacén",
+        "Unlock" => "Desbloquear",
+        "Encrypted keystore" => "Almacén cifrado",
+        "Remember the passphrase in the OS keychain and unlock on startup (saved the next time it's typed)" => "Recordar la frase en el llavero del sistema y desbloquear al iniciar (se guarda la próxima vez que se escriba)",
+        "The loaded wallet is now in the encrypted keystore. The plain keypair file it came from was kept, delete it once you've checked the keystore unlocks." => "La wallet cargada está ahora en el almacén cifrado. Se conservó el archivo de keypair original, bórralo cuando compruebes que el almacén se desbloquea.",
+        "The app keeps its wallet in an encrypted keystore and asks for the passphrase on startup." => "La app guarda su wallet en un almacén cifrado y pide la frase al iniciar.",
+        "Keystore passphrase (at least 8 characters)" => "Frase de contraseña del almacén (al menos 8 caracteres)",
+        "Repeat the passphrase" => "Repite la frase",
+        "Move the loaded keypair (e.g. a plain id.json) into an encrypted keystore that is unlocked on startup" => "Mover el keypair cargado (p. ej. un id.json sin cifrar) a un almacén cifrado que se desbloquea al iniciar",
+        "Move to keystore" => "Mover al almacén",
+        "Encrypted backup" => "Copia de seguridad cifrada",
+        "Backup passphrase (at least 8 characters)" => "Frase de la copia (al menos 8 caracteres)",
+        "Export encrypted keypair" => "Exportar keypair cifrado",
+        "Backup written to {$path}" => "Copia guardada en {$path}",
+        "The passphrase can't be recovered, without it the backup is useless" => "La frase no se puede recuperar, sin ella la copia no sirve",
+        // startup password
+        "Enter the startup password" => "Escribe la contraseña de inicio",
+        "Startup password" => "Contraseña de inicio",
+        "The default keypair is loaded once the password is accepted" => "El keypair predeterminado se carga cuando se acepta la contraseña",
+        "The default keypair is only loaded after the startup password is typed" => "El keypair predeterminado solo se carga después de escribir la contraseña de inicio",
+        "Remove startup password" => "Quitar la contraseña de inicio",
+        "Repeat the password" => "Repite la contraseña",
+        "Ask for a password before the default keypair is loaded at startup" => "Pedir una contraseña antes de cargar el keypair predeterminado al iniciar",
+        "Require at startup" => "Pedir al iniciar",
+        // allow-list
+        "Allow-list mode is ON: {$recipients} allowed recipients, {$mints} allowed token mints" => "Lista permitida ACTIVADA: {$recipients} destinatarios permitidos, {$mints} mints de tokens permitidos",
+        "Allow-list mode is OFF: any recipient can be used" => "Lista permitida DESACTIVADA: se puede usar cualquier destinatario",
+        "Admin password" => "Contraseña de administrador",
+        "Allowed recipients (comma separated)" => "Destinatarios permitidos (separados por comas)",
+        "Allowed token mints (comma separated)" => "Mints de tokens permitidos (separados por comas)",
+        "Staking, wrapping, swaps and withdrawals back into this wallet need its own address listed" => "Staking, wrap, intercambios y retiros a esta wallet necesitan su propia dirección en la lista",
+        "Save allow-list" => "Guardar lista permitida",
+        "Disable allow-list" => "Desactivar lista permitida",
+        "Enable allow-list" => "Activar lista permitida",
+        // liquid staking
+        "Liquid staking (JitoSOL)" => "Staking líquido (JitoSOL)",
+        "The JitoSOL pool only exists on mainnet" => "El pool de JitoSOL solo existe en mainnet",
+        "Load pool" => "Cargar pool",
+        "1 JitoSOL = {$rate} SOL, balance {$balance} JitoSOL (about {$sol} SOL)" => "1 JitoSOL = {$rate} SOL, saldo {$balance} JitoSOL (unos {$sol} SOL)",
+        "Staking {$amount} SOL gives about {$tokens} JitoSOL, unstaking {$amount} JitoSOL gives about {$sol} SOL, before pool fees" => "Hacer stake de {$amount} SOL da unos {$tokens} JitoSOL, retirar {$amount} JitoSOL da unos {$sol} SOL, antes de las comisiones del pool",
+        "Stake SOL" => "Hacer stake de SOL",
+        "Unstake JitoSOL" => "Retirar JitoSOL",
+        "Unstaking is paid from the pool's reserve right away, larger amounts are better swapped" => "El retiro se paga al momento desde la reserva del pool, para cantidades grandes conviene un intercambio",
+        // history
+        "Refresh history" => "Actualizar historial",
+        "Failed" => "Fallida",
+        "Success" => "Correcta",
+        "Slot {$slot} | unix time {$time}" => "Slot {$slot} | hora unix {$time}",
+        "from the archival node" => "del nodo de archivo",
+        "Export decoded JSON" => "Exportar JSON decodificado",
+        "Replay" => "Reproducir",
+        // recurring swaps
+        "{$amount} {$input} to {$output}" => "{$amount} {$input} a {$output}",
+        "Every hour" => "Cada hora",
+        "Every day" => "Cada día",
+        "Every week" => "Cada semana",
+        "due now" => "ahora",
+        "in {$minutes}m" => "en {$minutes}m",
+        "in {$hours}h" => "en {$hours}h",
+        "Recurring swaps" => "Intercambios recurrentes",
+        "Repeat this swap" => "Repetir este intercambio",
+        "paused" => "en pausa",
+        "next {$when}" => "próximo {$when}",
+        "Resume" => "Reanudar",
+        "Pause" => "Pausar",
+        "Remove" => "Quitar",
+        "{$minutes}m ago, {$summary}: {$outcome}" => "hace {$minutes}m, {$summary}: {$outcome}",
+        "Swaps run while the app is open, runs missed while it was closed are skipped" => "Los intercambios se hacen con la app abierta, los que tocaban mientras estaba cerrada se omiten",
+        "Last runs" => "Últimas ejecuciones",
+        // faucet
+        "The faucet is only available on devnet and testnet" => "El faucet solo está disponible en devnet y testnet",
+        "Target balance (SOL)" => "Saldo objetivo (SOL)",
+        "Requesting airdrops..." => "Pidiendo airdrops...",
+        "Top up now" => "Recargar ahora",
+        "Keep wallets topped up during this session" => "Mantener las wallets recargadas durante esta sesión",
+        "already at target" => "ya en el objetivo",
+        "Devnet faucet" => "Faucet de devnet",
+        // rpc endpoints
+        "RPC endpoints" => "Endpoints RPC",
+        "Testing..." => "Probando...",
+        "Test endpoints" => "Probar endpoints",
+        "Fallback RPC URLs, comma separated (only those on the primary's cluster are used)" => "URLs RPC de respaldo, separadas por comas (solo se usan las del clúster del principal)",
+        "Send-only RPC URL, e.g. a protected relay (transactions go there, reads stay on the nodes below)" => "URL RPC solo de envío, p. ej. un relay protegido (las transacciones van ahí, las lecturas siguen en los nodos de abajo)",
+        "Requests per second (default {$default}, 0 for no limit)" => "Peticiones por segundo (por defecto {$default}, 0 sin límite)",
+        "Commitment" => "Nivel de confirmación",
+        "in use" => "en uso",
+        "checking cluster" => "comprobando el clúster",
+        "other cluster, not used" => "otro clúster, no se usa",
+        "standby" => "en reserva",
+        "failing ({$failures} errors in a row)" => "fallando ({$failures} errores seguidos)",
+        "test failed" => "la prueba falló",
+        "send only" => "solo envío",
+        "The send-only URL is for another cluster, transactions go to the nodes above" => "La URL solo de envío es de otro clúster, las transacciones van a los nodos de arriba",
+        // errors
+        "No file was picked" => "No se eligió ningún archivo",
+        "Couldn't fetch the balance" => "No se pudo obtener el saldo",
+        "That file type isn't supported" => "Ese tipo de archivo no es compatible",
+        "Couldn't fetch a recent blockhash" => "No se pudo obtener un blockhash reciente",
+        "The transaction failed" => "La transacción falló",
+        "Invalid amount" => "Cantidad no válida",
+        "Invalid address" => "Dirección no válida",
+        "Insufficient balance" => "Saldo insuficiente",
+        "Couldn't estimate the fee" => "No se pudo calcular la comisión",
+        "Invalid priority fee" => "Comisión de prioridad no válida",
+        "Couldn't fetch the receipts" => "No se pudieron obtener los recibos",
+        "This exchange needs a memo with the deposit" => "Este exchange necesita un memo con el depósito",
+        "The amount is below the exchange's minimum deposit" => "La cantidad es menor que el depósito mínimo del exchange",
+        "The transaction expired before it landed, nothing was sent" => "La transacción caducó antes de confirmarse, no se envió nada",
+        "The nonce account moved on before the transaction landed, nothing was sent" => "La cuenta nonce avanzó antes de que la transacción se confirmara, no se envió nada",
+        "Couldn't fetch the lookup tables" => "No se pudieron obtener las tablas de búsqueda",
+        "The recipient isn't on the allow-list" => "El destinatario no está en la lista permitida",
+        "The token mint isn't on the allow-list" => "El mint del token no está en la lista permitida",
+        "Wrong admin password" => "Contraseña de administrador incorrecta",
+        "Couldn't save the file" => "No se pudo guardar el archivo",
+        "Couldn't fetch the nonce account" => "No se pudo obtener la cuenta nonce",
+        "That isn't an initialized nonce account" => "Esa no es una cuenta nonce inicializada",
+        "Invalid transaction file" => "Archivo de transacción no válido",
+        "The loaded wallet doesn't need to sign this transaction" => "La wallet cargada no necesita firmar esta transacción",
+        "The transaction is still missing signatures" => "A la transacción aún le faltan firmas",
+        "Couldn't fetch the history" => "No se pudo obtener el historial",
+        "Invalid payroll file, each line needs an address and an amount" => "Archivo de nómina no válido, cada línea necesita una dirección y una cantidad",
+        "The airdrop failed" => "El airdrop falló",
+        "The faucet is rate limiting this address, try again later" => "El faucet está limitando esta dirección, inténtalo más tarde",
+        "Invalid label or a test set with that label already exists, use letters, numbers, - and _" => "Etiqueta no válida o ya existe un conjunto con esa etiqueta, usa letras, números, - y _",
+        "Invalid number of wallets" => "Número de wallets no válido",
+        "Couldn't fetch the transaction" => "No se pudo obtener la transacción",
+        "Couldn't fetch the account" => "No se pudo obtener la cuenta",
+        "Invalid token mint" => "Mint de token no válido",
+        "The owner is a program address, it can't own an associated token account this way" => "El propietario es una dirección de programa, no puede tener una cuenta de token asociada así",
+        "The owner isn't a wallet address" => "El propietario no es una dirección de wallet",
+        "This would go over the session spend limit" => "Esto superaría el límite de gasto de la sesión",
+        "No wallet password is set, add a startup password or move the wallet to the keystore first" => "No hay contraseña de wallet, añade una contraseña de inicio o mueve la wallet al almacén primero",
+        "Invalid keypair file" => "Archivo de keypair no válido",
+        "The file already exists" => "El archivo ya existe",
+        "Invalid seed phrase" => "Frase semilla no válida",
+        "Couldn't fetch the network info" => "No se pudo obtener la información de la red",
+        "Encryption failed" => "El cifrado falló",
+        "Invalid encrypted file" => "Archivo cifrado no válido",
+        "Wrong passphrase" => "Frase de contraseña incorrecta",
+        "The passphrase needs at least 8 characters" => "La frase de contraseña necesita al menos 8 caracteres",
+        "The passphrases don't match" => "Las frases de contraseña no coinciden",
+        "The OS keychain couldn't be used" => "No se pudo usar el llavero del sistema",
+        "A watched address can't sign, load its keypair to send" => "Una dirección observada no puede firmar, carga su keypair para enviar",
+        "Invalid profile name" => "Nombre de perfil no válido",
+        "That isn't a keypair, paste the [12,34,...] array from a keypair file" => "Eso no es un keypair, pega el arreglo [12,34,...] de un archivo de keypair",
+        "Type a prefix or suffix using only base58 characters" => "Escribe un prefijo o sufijo con solo caracteres base58",
+        "The search was stopped" => "La búsqueda se detuvo",
+        "Couldn't show the notification" => "No se pudo mostrar la notificación",
+        "Couldn't create the tray icon" => "No se pudo crear el icono de la bandeja",
+        "No RPC endpoint answered" => "Ningún endpoint RPC respondió",
+        "The RPC endpoint is on another cluster than the one this was prepared for" => "El endpoint RPC está en otro clúster que aquel para el que se preparó esto",
+        "Couldn't fetch prices" => "No se pudieron obtener los precios",
+        "Couldn't fetch Jupiter's verified token list" => "No se pudo obtener la lista de tokens verificados de Jupiter",
+        "Invalid alert threshold" => "Umbral de alerta no válido",
+        "Invalid swap amount" => "Cantidad de intercambio no válida",
+        "Couldn't get a swap quote" => "No se pudo obtener una cotización",
+        "The swap failed" => "El intercambio falló",
+        "Jupiter's transaction charges another wallet, it wasn't signed" => "La transacción de Jupiter cobra a otra wallet, no se firmó",
+        "Jupiter's transaction would take more than the quote, it wasn't signed" => "La transacción de Jupiter tomaría más que la cotización, no se firmó",
+        "Invalid slippage, use 1 to 5000 bps" => "Deslizamiento no válido, usa de 1 a 5000 bps",
+        "This wallet has no wrapped SOL" => "Esta wallet no tiene SOL envuelto",
+        "Invalid vote account" => "Cuenta de voto no válida",
+        "The amount is below the minimum stake" => "La cantidad es menor que el stake mínimo",
+        "The stake is still active, deactivate it and wait for the epoch to end" => "El stake sigue activo, desactívalo y espera a que termine la época",
+        "These stake accounts can't be merged" => "Estas cuentas de stake no se pueden fusionar",
+        "Couldn't fetch the validators" => "No se pudieron obtener los validadores",
+        "Couldn't fetch the staking rewards" => "No se pudieron obtener las recompensas de staking",
+        "Stake pool not found" => "No se encontró el stake pool",
+        "The stake pool hasn't been updated this epoch yet, try again later" => "El stake pool aún no se actualizó en esta época, inténtalo más tarde",
+        "The Jito block engine rejected the bundle" => "El block engine de Jito rechazó el bundle",
+        "The Jito tip is invalid or below the minimum" => "La propina de Jito no es válida o es menor que el mínimo",
+        "That service isn't allowed in Network access" => "Ese servicio no está permitido en Acceso a la red",
+        "The simulation failed: {$reason}" => "La simulación falló: {$reason}",
+        "RPC request failed" => "La petición RPC falló",
+        // alerts
+        "SOL price above" => "Precio de SOL por encima de",
+        "SOL price below" => "Precio de SOL por debajo de",
+        "Balance above (SOL)" => "Saldo por encima de (SOL)",
+        "Balance below (SOL)" => "Saldo por debajo de (SOL)",
+        "{$alert} (now {$value})" => "{$alert} (ahora {$value})",
+        "Alert" => "Alerta",
+        "Threshold" => "Umbral",
+        "Add" => "Añadir",
+        "triggered" => "disparada",
+        "waiting" => "en espera",
+        "Price alerts wait for a fiat currency to be picked in Settings" => "Las alertas de precio esperan a que se elija una moneda fiat en Ajustes",
+        "Alerts are checked while the app runs and shown as system notifications" => "Las alertas se comprueban mientras la app está abierta y se muestran como notificaciones del sistema",
+        // batch send
+        "Add recipient" => "Añadir destinatario",
+        "Import CSV" => "Importar CSV",
+        "Sending..." => "Enviando...",
+        "Retry failed rows" => "Reintentar las filas fallidas",
+        "Send batch" => "Enviar lote",
+        "Export results" => "Exportar resultados",
+        "Sent: {$signature}" => "Enviada: {$signature}",
+        "Failed: {$error}" => "Falló: {$error}",
+        "Invalid: {$error}" => "No válida: {$error}",
+        "Recipient address" => "Dirección del destinatario",
+        "{$recipients} recipients | Total: {$total} SOL | {$sent} sent, {$failed} failed" => "{$recipients} destinatarios | Total: {$total} SOL | {$sent} enviadas, {$failed} fallidas",
+        // chart
+        "Wallet balance (SOL)" => "Saldo de la wallet (SOL)",
+        "SOL price" => "Precio de SOL",
+        "24 hours" => "24 horas",
+        "7 days" => "7 días",
+        "30 days" => "30 días",
+        "All" => "Todo",
+        "Not enough history yet, a point is recorded every {$minutes} minutes while the app runs" => "Aún no hay suficiente historial, se guarda un punto cada {$minutes} minutos mientras la app está abierta",
+        "Latest {$latest}, {$change} over the range, {$points} points" => "Último {$latest}, {$change} en el periodo, {$points} puntos",
+        // command palette
+        "Pick a keypair file and load it" => "Elegir un archivo de keypair y cargarlo",
+        "Generate a new keypair, save it to a file and load it" => "Generar un keypair nuevo, guardarlo en un archivo y cargarlo",
+        "Fetch the balance of the loaded wallet again" => "Volver a obtener el saldo de la wallet cargada",
+        "Open the send form prefilled with a recipient and amount" => "Abrir el formulario de envío con un destinatario y una cantidad",
+        "Clear the send form" => "Vaciar el formulario de envío",
+        "Request devnet airdrops up to the faucet target" => "Pedir airdrops de devnet hasta el objetivo del faucet",
+        "Type a command, e.g. send <address> <amount>" => "Escribe un comando, p. ej. send <dirección> <cantidad>",
+        "Pin as quick action" => "Fijar como acción rápida",
+        "Prefix with \"label:\" to name a pinned action" => "Antepón \"etiqueta:\" para dar nombre a una acción fijada",
+        // connection and appearance
+        "RPC URL when no profile is active, press Enter to apply (empty to follow the Solana CLI)" => "URL RPC cuando no hay un perfil activo, pulsa Enter para aplicarla (vacía para seguir a la CLI de Solana)",
+        "dark" => "oscuro",
+        "light" => "claro",
+        "system" => "sistema",
+        "Accent color, e.g. #30cbf2" => "Color de acento, p. ej. #30cbf2",
+        "Success color, e.g. #14f195" => "Color de éxito, p. ej. #14f195",
+        "Error color, e.g. #ff0000" => "Color de error, p. ej. #ff0000",
+        "Fee presets and the rest live in {$path}" => "Los ajustes de comisiones y el resto están en {$path}",
+        // files and cluster
+        "none" => "ninguna",
+        "Choose a valid json solana keypair" => "Elige un keypair de solana en json válido",
+        "Choose a base64 transaction file" => "Elige un archivo de transacción en base64",
+        "Choose a csv file of address,amount rows" => "Elige un archivo csv con filas dirección,cantidad",
+        "Choose where to save the file" => "Elige dónde guardar el archivo",
+        "an unknown cluster" => "un clúster desconocido",
+        "Cluster mismatch: expected {$expected} but the RPC node is on {$actual}" => "El clúster no coincide: se esperaba {$expected} pero el nodo RPC está en {$actual}",
+        "Sending is disabled until the profile or RPC URL is fixed" => "Los envíos están desactivados hasta corregir el perfil o la URL RPC",
+        // wallet and jito
+        "Wallet address: " => "Dirección de la wallet: ",
+        "Jito bundles are only available on mainnet" => "Los bundles de Jito solo están disponibles en mainnet",
+        "Send as a Jito bundle (more reliable during congestion)" => "Enviar como bundle de Jito (más fiable con congestión)",
+        "Tip (SOL)" => "Propina (SOL)",
+        // lookup tables
+        "Refresh tables" => "Actualizar tablas",
+        "Create table" => "Crear tabla",
+        "Addresses to add (comma separated)" => "Direcciones a añadir (separadas por comas)",
+        "Extend" => "Ampliar",
+        "Deactivate" => "Desactivar",
+        "Close" => "Cerrar",
+        "{$count} addresses | {$state}" => "{$count} direcciones | {$state}",
+        "active" => "activa",
+        "deactivated at slot {$slot}" => "desactivada en el slot {$slot}",
+        "No lookup tables owned by this keypair" => "Este keypair no tiene tablas de búsqueda",
+        // history and tabs
+        "Memo: {$memo}" => "Memo: {$memo}",
+        "Slot {$slot}" => "Slot {$slot}",
+        "Staking" => "Staking",
+        "Faucet" => "Faucet",
+        // seed phrases
+        "New wallet from a seed phrase" => "Wallet nueva a partir de una frase semilla",
+        "Screenshots and screen recordings leave this window out while the phrase is on screen" => "Las capturas y grabaciones de pantalla no incluyen esta ventana mientras la frase está visible",
+        "This system can't keep the window out of screenshots, make sure nothing is recording the screen" => "Este sistema no puede excluir la ventana de las capturas, asegúrate de que nada esté grabando la pantalla",
+        "Write these words down in order and keep them offline. Anyone with them controls the wallet." => "Anota estas palabras en orden y guárdalas sin conexión. Quien las tenga controla la wallet.",
+        "Hold the mouse button down on the words to reveal them" => "Mantén pulsado el botón del ratón sobre las palabras para verlas",
+        "I wrote them down" => "Ya las anoté",
+        "Generate another" => "Generar otra",
+        "Word #{$position}" => "Palabra n.º {$position}",
+        "Create wallet" => "Crear wallet",
+        "Type the requested words to confirm your backup" => "Escribe las palabras pedidas para confirmar tu copia",
+        "Show the words again" => "Mostrar las palabras otra vez",
+        "12 or 24 word seed phrase" => "Frase semilla de 12 o 24 palabras",
+        "BIP39 passphrase (optional)" => "Frase de contraseña BIP39 (opcional)",
+        "Import" => "Importar",
+        "Derive addresses" => "Derivar direcciones",
+        // network performance
+        "Network performance" => "Rendimiento de la red",
+        "Refresh" => "Actualizar",
+        "Loading..." => "Cargando...",
+        "{$tps} TPS ({$non_vote} excluding votes)" => "{$tps} TPS ({$non_vote} sin contar votos)",
+        "Degraded: slots take {$millis}ms, consider a higher priority fee" => "Degradada: los slots tardan {$millis}ms, considera una comisión de prioridad más alta",
+        "Healthy: slots take {$millis}ms" => "Normal: los slots tardan {$millis}ms",
+        "Priority fees over recent slots: median {$median} / average {$average} micro-lamports per CU" => "Comisiones de prioridad en los últimos slots: mediana {$median} / media {$average} micro-lamports por CU",
+        "Use median priority fee" => "Usar la comisión de prioridad mediana",
+        // nonce accounts
+        "Your nonce accounts" => "Tus cuentas nonce",
+        "No nonce accounts with this wallet as authority" => "No hay cuentas nonce con esta wallet como autoridad",
+        "{$address}: {$balance} SOL, blockhash {$blockhash}" => "{$address}: {$balance} SOL, blockhash {$blockhash}",
+        "Use" => "Usar",
+        "Nonce account address" => "Dirección de la cuenta nonce",
+        "Create nonce account" => "Crear cuenta nonce",
+        "Advance" => "Avanzar",
+        "Authority: {$authority}" => "Autoridad: {$authority}",
+        "Stored blockhash: {$blockhash}" => "Blockhash guardado: {$blockhash}",
+        "Balance: {$balance} SOL" => "Saldo: {$balance} SOL",
+        "No nonce account loaded" => "Ninguna cuenta nonce cargada",
+        "SOL to withdraw" => "SOL a retirar",
+        "Withdraw" => "Retirar",
+        "New authority address" => "Dirección de la nueva autoridad",
+        "Transfer authority" => "Transferir autoridad",
+        // notifications
+        "From {$sender}" => "De {$sender}",
+        "To {$owner}" => "Para {$owner}",
+        "Received {$amount} {$asset}" => "Recibido {$amount} {$asset}",
+        // offline
+        "Signing-only mode: the app won't contact any RPC" => "Modo solo firma: la app no contactará ningún RPC",
+        "Start the app with --offline on the air-gapped machine" => "Inicia la app con --offline en la máquina aislada",
+        "1. Connected machine: export the send form as an unsigned transaction" => "1. Máquina conectada: exporta el formulario de envío como transacción sin firmar",
+        "Export unsigned transaction" => "Exportar transacción sin firmar",
+        "2. Air-gapped machine: sign the exported file with the loaded keypair" => "2. Máquina aislada: firma el archivo exportado con el keypair cargado",
+        "   Partially signed files can be signed again by every other signer" => "   Cada uno de los demás firmantes puede volver a firmar los archivos firmados parcialmente",
+        "Sign transaction file" => "Firmar archivo de transacción",
+        "3. Connected machine: broadcast the signed file" => "3. Máquina conectada: difunde el archivo firmado",
+        "Broadcast signed transaction" => "Difundir transacción firmada",
+        // paste keypair
+        "[12,34,...] keypair bytes" => "[12,34,...] bytes del keypair",
+        "{$pubkey} is loaded from memory only, it's gone once the app closes unless it's moved to the keystore below" => "{$pubkey} está cargada solo en memoria, desaparece al cerrar la app salvo que se mueva al almacén de abajo",
+        "Nothing is written to disk, the text is wiped once it's imported" => "No se escribe nada en disco, el texto se borra al importarlo",
+        // poisoning
+        "Possible address poisoning!" => "¡Posible envenenamiento de direcciones!",
+        "You typed:   {$recipient}" => "Escribiste:   {$recipient}",
+        "You paid before: {$lookalike}" => "Pagaste antes a: {$lookalike}",
+        "Both start and end the same but they are different addresses. Lookalike addresses are planted in wallet history to be copied by mistake, compare every character before sending." => "Ambas empiezan y terminan igual pero son direcciones distintas. Las direcciones parecidas se plantan en el historial de la wallet para que se copien por error, compara cada carácter antes de enviar.",
+        "Use the address I paid before" => "Usar la dirección a la que pagué antes",
+        "I checked it, continue" => "Lo comprobé, continuar",
+        // portfolio
+        "Total: {$total}" => "Total: {$total}",
+        "Asset" => "Activo",
+        "Status" => "Estado",
+        "Allocation" => "Distribución",
+        "Value ({$currency})" => "Valor ({$currency})",
+        "Pick a fiat currency in Settings to see values" => "Elige una moneda fiat en Ajustes para ver valores",
+        "{$count} asset(s) have no price and aren't part of the total" => "{$count} activo(s) no tienen precio y no cuentan en el total",
+        "{$mint} isn't the real {$symbol}, don't send it, swap it or follow links that come with it" => "{$mint} no es el {$symbol} real, no lo envíes, no lo intercambies ni sigas los enlaces que lo acompañan",
+        // prices
+        "{$provider}, {$seconds}s ago" => "{$provider}, hace {$seconds}s",
+        // profiles
+        "Profiles" => "Perfiles",
+        "{$name} (active)" => "{$name} (activo)",
+        "current wallet" => "wallet actual",
+        "Delete" => "Eliminar",
+        "Profile name, e.g. Dev (devnet, test key)" => "Nombre del perfil, p. ej. Dev (devnet, clave de prueba)",
+        "Save current setup as profile" => "Guardar la configuración actual como perfil",
+        // refresh
+        "Not updated yet" => "Aún sin actualizar",
+        "Updated just now" => "Actualizado ahora mismo",
+        "Updated {$seconds}s ago" => "Actualizado hace {$seconds}s",
+        "Updated {$minutes}m ago" => "Actualizado hace {$minutes}m",
+        "Updated {$hours}h ago" => "Actualizado hace {$hours}h",
+        "Tokens" => "Tokens",
+        // settings
+        "Privacy" => "Privacidad",
+        "Privacy mode: hide balances until revealed with Ctrl+H" => "Modo privacidad: oculta los saldos hasta mostrarlos con Ctrl+H",
+        "Transfers" => "Transferencias",
+        "Ask for confirmation above this amount in SOL (empty to disable)" => "Pedir confirmación por encima de esta cantidad en SOL (vacío para desactivar)",
+        "Ask for confirmation above this % of the balance (empty to disable)" => "Pedir confirmación por encima de este % del saldo (vacío para desactivar)",
+        "Attach a default memo to outgoing payments (can be edited per send)" => "Adjuntar un memo por defecto a los pagos salientes (editable en cada envío)",
+        "Default memo, e.g. an invoice prefix or team name" => "Memo por defecto, p. ej. un prefijo de factura o el nombre del equipo",
+        "Balances" => "Saldos",
+        "Re-poll the balance and token accounts every N seconds (empty to rely on live updates)" => "Volver a consultar el saldo y las cuentas de tokens cada N segundos (vacío para depender de las actualizaciones en vivo)",
+        "Mute desktop notifications for incoming transfers" => "Silenciar las notificaciones de escritorio de transferencias entrantes",
+        "Keep running in the system tray when the window is closed (applies on restart)" => "Seguir en la bandeja del sistema al cerrar la ventana (se aplica al reiniciar)",
+        "Archival RPC URL for history older than the main node keeps (empty to disable)" => "URL de RPC de archivo para el historial más antiguo que guarda el nodo principal (vacío para desactivar)",
+        "Bookkeeping" => "Contabilidad",
+        "Folder for daily end-of-day balance CSVs (empty to disable)" => "Carpeta para los CSV diarios de saldos al cierre del día (vacío para desactivar)",
+        "Export balances now" => "Exportar saldos ahora",
+        "Run with --export-balances <folder> to export without opening the window" => "Ejecuta con --export-balances <carpeta> para exportar sin abrir la ventana",
+        "Storage" => "Almacenamiento",
+        "Keypair loaded on startup (empty to follow the Solana CLI config)" => "Par de claves cargado al iniciar (vacío para seguir la configuración de la CLI de Solana)",
+        "Browse" => "Examinar",
+        "Portable mode: keep app data next to the executable (applies on restart)" => "Modo portable: guarda los datos de la app junto al ejecutable (se aplica al reiniciar)",
+        "Use --data-dir <path> to store app data somewhere else" => "Usa --data-dir <ruta> para guardar los datos de la app en otro lugar",
+        "Spend limit" => "Límite de gasto",
+        "Set a startup password or move the wallet to a keystore to use a spend limit" => "Configura una contraseña de inicio o mueve la wallet a un almacén para usar un límite de gasto",
+        "No limit set" => "Sin límite configurado",
+        "New limit in SOL (empty to disable)" => "Nuevo límite en SOL (vacío para desactivar)",
+        "Apply" => "Aplicar",
+        "App data directory: {$path}" => "Directorio de datos de la app: {$path}",
+        "Current limit: {$limit} SOL per session" => "Límite actual: {$limit} SOL por sesión",
+        // replay
+        "{$title}: failed with {$error}" => "{$title}: falló con {$error}",
+        "{$title}: success" => "{$title}: éxito",
+        "then: {$then}  now: {$now}" => "entonces: {$then}  ahora: {$now}",
+        " (data changes)" => " (cambian datos)",
+        "Replay of {$signature}" => "Repetición de {$signature}",
+        "Balance changes in lamports, when it landed vs against the current state" => "Cambios de saldo en lamports, cuando se confirmó frente al estado actual",
+        "Original run" => "Ejecución original",
+        "Close replay" => "Cerrar repetición",
+        "Replay ({$units} compute units)" => "Repetición ({$units} unidades de cómputo)",
+        // rewards
+        "Load rewards" => "Cargar recompensas",
+        "Export CSV" => "Exportar CSV",
+        "Staking rewards" => "Recompensas de staking",
+        "Rewards of the last {$epochs} epochs for the accounts above" => "Recompensas de las últimas {$epochs} épocas para las cuentas de arriba",
+        "Epoch {$epoch}: +{$amount} SOL across {$accounts} account(s)" => "Época {$epoch}: +{$amount} SOL en {$accounts} cuenta(s)",
+        "Total over {$epochs} epochs: +{$amount} SOL" => "Total en {$epochs} épocas: +{$amount} SOL",
+        // stake
+        "Stake accounts" => "Cuentas de stake",
+        "No stake accounts for this wallet" => "No hay cuentas de stake para esta wallet",
+        "Amount to split off (SOL)" => "Cantidad a separar (SOL)",
+        "Pick the account to merge into, only compatible accounts can be picked" => "Elige la cuenta en la que fusionar, solo se pueden elegir cuentas compatibles",
+        "Merging needs both accounts inactive, or delegated to the same validator" => "Fusionar requiere que ambas cuentas estén inactivas o delegadas al mismo validador",
+        "not delegated" => "sin delegar",
+        "activating" => "activándose",
+        "inactive" => "inactiva",
+        "Split" => "Dividir",
+        "Cancel merge" => "Cancelar fusión",
+        "Validator vote account" => "Cuenta de voto del validador",
+        "Browse validators" => "Explorar validadores",
+        "Amount to stake (SOL, rent included)" => "Cantidad a delegar (SOL, renta incluida)",
+        "Review" => "Revisar",
+        "Staking..." => "Haciendo stake...",
+        "Large stake, please confirm" => "Stake grande, por favor confirma",
+        "Yes, stake it" => "Sí, hacer stake",
+        "Confirm and stake" => "Confirmar y hacer stake",
+        "Merge here" => "Fusionar aquí",
+        "Merge" => "Fusionar",
+        "Current epoch {$epoch}" => "Época actual {$epoch}",
+        "cooling down, withdrawable from epoch {$epoch}" => "enfriándose, retirable desde la época {$epoch}",
+        ", validator {$voter}" => ", validador {$voter}",
+        "Validator: {$vote_account}" => "Validador: {$vote_account}",
+        "Delegated: {$delegated} SOL (minimum {$minimum} SOL)" => "Delegado: {$delegated} SOL (mínimo {$minimum} SOL)",
+        "Stake account rent: {$rent} SOL, fee: {$fee} SOL" => "Renta de la cuenta de stake: {$rent} SOL, comisión: {$fee} SOL",
+        // status bar
+        "Offline mode, no network connection is used" => "Modo sin conexión, no se usa ninguna conexión de red",
+        "RPC healthy" => "RPC en buen estado",
+        "RPC unhealthy" => "RPC con problemas",
+        "RPC checking" => "Comprobando RPC",
+        "WebSocket connected" => "WebSocket conectado",
+        "WebSocket disconnected" => "WebSocket desconectado",
+        "Slot -" => "Slot -",
+        "Epoch -" => "Época -",
+        "Epoch {$epoch} {$percent}%, next in {$countdown}" => "Época {$epoch} {$percent}%, la siguiente en {$countdown}",
+        "Throttled, {$queued} requests queued" => "Limitado, {$queued} solicitudes en cola",
+        "Prices: {$source}" => "Precios: {$source}",
+        // test wallets
+        "Set label" => "Etiqueta del conjunto",
+        "Wallets" => "Billeteras",
+        "Airdrop to each" => "Airdrop a cada una",
+        "Generate" => "Generar",
+        "Active" => "Activa",
+        "Delete set" => "Eliminar conjunto",
+        "Test set \"{$label}\" ({$count} wallets)" => "Conjunto de prueba \"{$label}\" ({$count} wallets)",
+        // token accounts
+        "Owner wallet address" => "Dirección de la wallet propietaria",
+        "Token mint address" => "Dirección del mint del token",
+        "The owner is a program derived address (PDA), I know it can't sign" => "El propietario es una dirección derivada de programa (PDA), sé que no puede firmar",
+        "Associated token account preview" => "Vista previa de la cuenta de token asociada",
+        "Owner is off curve (PDA)" => "El propietario está fuera de la curva (PDA)",
+        "Owner is a regular wallet" => "El propietario es una wallet normal",
+        "This account already exists" => "Esta cuenta ya existe",
+        "Preview address" => "Previsualizar dirección",
+        "Create account" => "Crear cuenta",
+        "Address: {$address}" => "Dirección: {$address}",
+        "Token program: {$program}" => "Programa de tokens: {$program}",
+        // swap
+        "Swap with Jupiter" => "Intercambiar con Jupiter",
+        "Jupiter only routes on mainnet-beta, switch the RPC or profile to swap" => "Jupiter solo enruta en mainnet-beta, cambia el RPC o el perfil para intercambiar",
+        "Slippage (bps)" => "Deslizamiento (bps)",
+        "Getting quote..." => "Obteniendo cotización...",
+        "Get quote" => "Obtener cotización",
+        "Swapping..." => "Intercambiando...",
+        "Swap anyway" => "Intercambiar de todos modos",
+        "direct" => "directa",
+        "Minimum received: {$amount} {$symbol}" => "Mínimo recibido: {$amount} {$symbol}",
+        "Price impact: {$impact}%, slippage tolerance: {$slippage}%" => "Impacto en el precio: {$impact}%, tolerancia de deslizamiento: {$slippage}%",
+        "Route: {$route}" => "Ruta: {$route}",
+        "Amount of {$symbol} to swap" => "Cantidad de {$symbol} a intercambiar",
+        "{$symbol} ({$mint}) isn't on Jupiter's verified list and poses as {$imitated}" => "{$symbol} ({$mint}) no está en la lista verificada de Jupiter y se hace pasar por {$imitated}",
+        "{$symbol} ({$mint}) couldn't be checked against Jupiter's verified list" => "{$symbol} ({$mint}) no se pudo comprobar contra la lista verificada de Jupiter",
+        "{$symbol} ({$mint}) isn't on Jupiter's verified list" => "{$symbol} ({$mint}) no está en la lista verificada de Jupiter",
+        "This route moves the price by {$impact}%, you may get much less than the market rate" => "Esta ruta mueve el precio un {$impact}%, podrías recibir mucho menos que el precio de mercado",
+        "Swap confirmed: {$signature}" => "Intercambio confirmado: {$signature}",
+        // token list
+        "verified" => "verificado",
+        "unverified" => "no verificado",
+        "unchecked" => "sin comprobar",
+        "unverified, posing as {$symbol}" => "no verificado, se hace pasar por {$symbol}",
+        // tray
+        "Open" => "Abrir",
+        "Quit" => "Salir",
+        // validators
+        "Most stake" => "Más stake",
+        "Highest APY" => "Mayor APY",
+        "Lowest commission" => "Menor comisión",
+        "Name" => "Nombre",
+        "Search by name or address" => "Buscar por nombre o dirección",
+        "Fetching vote accounts..." => "Obteniendo cuentas de voto...",
+        "No validators loaded" => "No hay validadores cargados",
+        "Unnamed" => "Sin nombre",
+        "Stake with" => "Hacer stake con",
+        "{$shown} of {$total} validators, showing the first {$limit}. APY is an estimate from inflation, commission and last epoch's vote credits" => "{$shown} de {$total} validadores, se muestran los primeros {$limit}. El APY es una estimación a partir de la inflación, la comisión y los créditos de voto de la última época",
+        "{$name} ({$vote_account}): {$commission}% commission, {$stake} SOL staked, ~{$apy}% APY" => "{$name} ({$vote_account}): {$commission}% de comisión, {$stake} SOL en stake, ~{$apy}% APY",
+        // vanity
+        "Prefix" => "Prefijo",
+        "Suffix" => "Sufijo",
+        "Ignore case (matches sooner)" => "Ignorar mayúsculas (coincide antes)",
+        "Search again" => "Buscar de nuevo",
+        "Every extra character makes the search about 58 times longer" => "Cada carácter extra hace la búsqueda unas 58 veces más larga",
+        "Start" => "Iniciar",
+        "{$attempts} attempts, {$rate} per second" => "{$attempts} intentos, {$rate} por segundo",
+        "Found {$pubkey}, it's loaded from memory only, move it to the keystore below to keep it" => "Encontrada {$pubkey}, está cargada solo en memoria, muévela al almacén de abajo para conservarla",
+        // wallets
+        "Set up a wallet" => "Configura una wallet",
+        "Or watch an address above to look at a wallet without its key" => "O vigila una dirección arriba para ver una wallet sin su clave",
+        "A keypair stored somewhere else can be loaded on every start from" => "Un keypair guardado en otro lugar se puede cargar en cada inicio desde",
+        "In the Solana config directory" => "En el directorio de configuración de Solana",
+        "No keypair was found at {$path}" => "No se encontró ningún keypair en {$path}",
+        "{$address} (watch)" => "{$address} (vigilada)",
+        // wsol
+        "No wrapped SOL" => "Sin SOL envuelto",
+        "Checking wrapped SOL..." => "Comprobando SOL envuelto...",
+        "Amount of SOL to wrap" => "Cantidad de SOL a envolver",
+        "Wrapped SOL (wSOL)" => "SOL envuelto (wSOL)",
+        "Wrap" => "Envolver",
+        "Unwrap all" => "Desenvolver todo",
+        "Unwrapping returns {$amount} SOL (wrapped amount plus the account rent)" => "Desenvolver devuelve {$amount} SOL (la cantidad envuelta más la renta de la cuenta)",
+        // errors
+        "Error: {$error}" => "Error: {$error}",
+        // batch review
+        "Row {$row}: {$recipient} resembles {$known}, an address you paid before" => "Fila {$row}: {$recipient} se parece a {$known}, una dirección a la que pagaste antes",
+        "Row {$row}: {$amount} SOL is over the large transfer threshold" => "Fila {$row}: {$amount} SOL supera el umbral de transferencia grande",
+        "Check these rows before sending" => "Revisa estas filas antes de enviar",
+        "Send anyway" => "Enviar de todos modos",
+        _ => return None,
+    })
+}
@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use iced::{
+    widget::{checkbox, row, text, text_input},
+    Alignment, Element,
+};
+use rand::seq::SliceRandom;
+use serde_json::{json, Value};
+use solana_client::rpc_client::SerializableTransaction;
+use solana_sdk::{instruction::Instruction, pubkey, pubkey::Pubkey, system_instruction};
+
+use crate::{errors::Error, i18n::tr, Message};
+
+pub const DEFAULT_BLOCK_ENGINE: &str = "https://mainnet.block-engine.jito.wtf";
+// the block engine drops bundles tipping less than this
+pub const MIN_JITO_TIP: u64 = 1_000;
+pub const DEFAULT_JITO_TIP: u64 = 10_000;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+// any of them works, picking one at random spreads the write locks
+const TIP_ACCOUNTS: [Pubkey; 8] = [
+    pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+    pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+    pubkey!("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+    pubkey!("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+    pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+    pubkey!("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+    pubkey!("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+    pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+];
+
+// where a transfer goes when it's sent as a bundle instead of through the rpc
+#[derive(Debug, Clone)]
+pub struct JitoSettings {
+    pub block_engine: String,
+    pub tip_lamports: u64,
+}
+
+// the tip is a plain transfer inside the same transaction, so it's only paid
+// when the transaction lands
+pub fn tip_instruction(payer: &Pubkey, settings: &JitoSettings) -> Instruction {
+    let tip_account = TIP_ACCOUNTS
+        .choose(&mut rand::thread_rng())
+        .unwrap_or(&TIP_ACCOUNTS[0]);
+    system_instruction::transfer(payer, tip_account, settings.tip_lamports)
+}
+
+// a bundle of one; the block engine answers with a bundle id right away and
+// the transaction is confirmed through the rpc like any other
+pub async fn send_bundle(
+    settings: &JitoSettings,
+    tx: &impl SerializableTransaction,
+) -> Result<String, Error> {
+    let bytes = bincode::serialize(tx).map_err(|_| Error::TransactionError)?;
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sendBundle",
+        "params": [[STANDARD.encode(bytes)], { "encoding": "base64" }],
+    });
+    let body = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|_| Error::BundleRejected)?
+        .post(format!(
+            "{}/api/v1/bundles",
+            settings.block_engine.trim_end_matches('/')
+        ))
+        .header("Content-Type", "application/json")
+        .body(request.to_string())
+        .send()
+        .await
+        .map_err(|_| Error::BundleRejected)?
+        .text()
+        .await
+        .map_err(|_| Error::BundleRejected)?;
+    let response: Value = serde_json::from_str(&body).map_err(|_| Error::BundleRejected)?;
+
+    response["result"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or(Error::BundleRejected)
+}
+
+pub fn display_jito_option(enabled: bool, tip: &str, mainnet: bool) -> Element<'static, Message> {
+    if !mainnet {
+        return text(tr("Jito bundles are only available on mainnet"))
+            .size(12)
+            .into();
+    }
+    row![
+        checkbox(
+            tr("Send as a Jito bundle (more reliable during congestion)"),
+            enabled,
+            Message::UseJitoToggled,
+        )
+        .size(16)
+        .text_size(14),
+        text_input(tr("Tip (SOL)"), tip).on_input(Message::JitoTipChanged),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center)
+    .into()
+}
@@ -0,0 +1,175 @@
+use std::{path::PathBuf, sync::Arc};
+
+use iced::{
+    widget::{button, checkbox, column, text, text_input},
+    Element,
+};
+use keyring::Entry;
+use solana_sdk::signature::Keypair;
+use zeroize::Zeroizing;
+
+use crate::{
+    encryption::{check_new_passphrase, decrypt_secret, encrypt_secret},
+    errors::Error,
+    files::app_config_dir,
+    i18n::tr,
+    palette::accent_color,
+    Message,
+};
+
+const KEYSTORE_FILE: &str = "keystore.enc";
+// the passphrase lives in the os credential store under this service/user
+const KEYCHAIN_SERVICE: &str = "solana-exec-app";
+const KEYCHAIN_USER: &str = "keystore-passphrase";
+
+pub fn keystore_path() -> PathBuf {
+    app_config_dir().join(KEYSTORE_FILE)
+}
+
+// when this exists the app never reads a plaintext keypair on startup
+pub fn has_keystore() -> bool {
+    keystore_path().exists()
+}
+
+fn keychain_entry() -> Result<Entry, Error> {
+    Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_USER).map_err(|_| Error::KeychainError)
+}
+
+// only a passphrase that just unlocked the keystore is worth remembering
+fn remember_passphrase(passphrase: &str) -> Result<(), Error> {
+    keychain_entry()?
+        .set_password(passphrase)
+        .map_err(|_| Error::KeychainError)
+}
+
+pub async fn forget_keychain_passphrase() -> Result<(), Error> {
+    match keychain_entry()?.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(_) => Err(Error::KeychainError),
+    }
+}
+
+pub async fn unlock_keystore(
+    passphrase: Zeroizing<String>,
+    remember: bool,
+) -> Result<Arc<Keypair>, Error> {
+    let content = tokio::fs::read_to_string(keystore_path())
+        .await
+        .map_err(|_| Error::InvalidEncryptedFile)?;
+    let secret = decrypt_secret(&content, &passphrase)?;
+    let keypair = Keypair::from_bytes(&secret).map_err(|_| Error::InvalidEncryptedFile)?;
+
+    // a keychain that refuses the entry only costs the automatic unlock
+    if remember {
+        remember_passphrase(&passphrase).ok();
+    }
+    Ok(Arc::new(keypair))
+}
+
+// startup unlock without typing, falls back to the passphrase prompt on error
+pub async fn unlock_with_keychain() -> Result<Arc<Keypair>, Error> {
+    let passphrase = keychain_entry()?
+        .get_password()
+        .map(Zeroizing::new)
+        .map_err(|_| Error::KeychainError)?;
+    unlock_keystore(passphrase, false).await
+}
+
+// encrypts the loaded keypair into the store, the plaintext file it came
+// from is left alone so a failed migration never loses the key
+pub async fn migrate_to_keystore(
+    keypair: Arc<Keypair>,
+    passphrase: Zeroizing<String>,
+    confirmation: Zeroizing<String>,
+    remember: bool,
+) -> Result<Arc<Keypair>, Error> {
+    check_new_passphrase(&passphrase, &confirmation)?;
+    if has_keystore() {
+        return Err(Error::FileAlreadyExists);
+    }
+
+    let secret = Zeroizing::new(keypair.to_bytes());
+    let content = encrypt_secret(secret.as_ref(), &passphrase)?;
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    tokio::fs::write(keystore_path(), content)
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+
+    if remember {
+        remember_passphrase(&passphrase).ok();
+    }
+    Ok(keypair)
+}
+
+pub fn display_unlock(passphrase: &str) -> Element<'static, Message> {
+    let title = text(tr("Unlock your wallet"))
+        .size(14)
+        .style(accent_color());
+
+    let passphrase_input = text_input(tr("Keystore passphrase"), passphrase)
+        .on_input(Message::KeystorePassphraseChanged)
+        .on_submit(Message::UnlockKeystore)
+        .password();
+
+    column![
+        title,
+        passphrase_input,
+        button(tr("Unlock")).on_press(Message::UnlockKeystore),
+    ]
+    .spacing(10)
+    .into()
+}
+
+pub fn display_keystore_migration(
+    passphrase: &str,
+    confirmation: &str,
+    migrated: bool,
+    keychain_unlock: bool,
+) -> Element<'static, Message> {
+    let title = text(tr("Encrypted keystore"))
+        .size(14)
+        .style(accent_color());
+
+    let keychain = checkbox(
+        tr("Remember the passphrase in the OS keychain and unlock on startup (saved the next time it's typed)"),
+        keychain_unlock,
+        Message::KeychainUnlockToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    if has_keystore() {
+        let status = if migrated {
+            tr("The loaded wallet is now in the encrypted keystore. The plain keypair file it came from was kept, delete it once you've checked the keystore unlocks.")
+        } else {
+            tr("The app keeps its wallet in an encrypted keystore and asks for the passphrase on startup.")
+        };
+        return column![title, text(status).size(12), keychain]
+            .spacing(10)
+            .into();
+    }
+
+    let passphrase_input = text_input(
+        tr("Keystore passphrase (at least 8 characters)"),
+        passphrase,
+    )
+    .on_input(Message::KeystorePassphraseChanged)
+    .password();
+    let confirmation_input = text_input(tr("Repeat the passphrase"), confirmation)
+        .on_input(Message::KeystoreConfirmationChanged)
+        .password();
+
+    column![
+        title,
+        text(tr("Move the loaded keypair (e.g. a plain id.json) into an encrypted keystore that is unlocked on startup"))
+            .size(12),
+        passphrase_input,
+        confirmation_input,
+        keychain,
+        button(tr("Move to keystore")).on_press(Message::MigrateToKeystore)
+    ]
+    .spacing(10)
+    .into()
+}
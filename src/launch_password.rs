@@ -0,0 +1,170 @@
+use std::{fs, path::PathBuf, sync::Arc};
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use iced::{
+    widget::{button, column, text, text_input},
+    Element,
+};
+use solana_sdk::signature::Keypair;
+use zeroize::Zeroizing;
+
+use crate::{
+    encryption::check_new_passphrase,
+    errors::Error,
+    files::app_config_dir,
+    i18n::tr,
+    keystore::{has_keystore, unlock_keystore},
+    loaders::load_keypair_from_file,
+    palette::accent_color,
+    Message,
+};
+
+const LAUNCH_PASSWORD_FILE: &str = "launch-password.txt";
+
+fn launch_password_path() -> PathBuf {
+    app_config_dir().join(LAUNCH_PASSWORD_FILE)
+}
+
+// the file holds a single argon2 hash, the default keypair isn't read
+// until a password matching it is typed
+pub fn load_launch_password() -> Option<String> {
+    let hash = fs::read_to_string(launch_password_path()).ok()?;
+    let hash = hash.trim();
+    (!hash.is_empty()).then(|| hash.to_string())
+}
+
+fn verify_launch_password(hash: &str, password: &str) -> Result<(), Error> {
+    let hash = PasswordHash::new(hash).map_err(|_| Error::WrongPassphrase)?;
+    Argon2::default()
+        .verify_password(password.as_bytes(), &hash)
+        .map_err(|_| Error::WrongPassphrase)
+}
+
+// argon2 takes long enough to stall the window, so the check and the load of
+// the startup keypair run off the ui thread
+pub async fn unlock_launch(
+    hash: String,
+    password: Zeroizing<String>,
+    path: PathBuf,
+) -> Result<Arc<Keypair>, Error> {
+    verify_launch_password(&hash, &password)?;
+    load_keypair_from_file(path).map(Arc::new)
+}
+
+// the password guarding the wallet: the keystore passphrase when there's a
+// keystore, otherwise the startup password
+pub async fn verify_wallet_password(
+    launch_hash: Option<String>,
+    password: Zeroizing<String>,
+) -> Result<(), Error> {
+    if has_keystore() {
+        return unlock_keystore(password, false).await.map(|_| ());
+    }
+    match launch_hash {
+        Some(hash) => verify_launch_password(&hash, &password),
+        None => Err(Error::NoWalletPassword),
+    }
+}
+
+pub async fn set_launch_password(
+    password: Zeroizing<String>,
+    confirmation: Zeroizing<String>,
+) -> Result<Option<String>, Error> {
+    check_new_passphrase(&password, &confirmation)?;
+
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(|_| Error::EncryptionError)?
+        .to_string();
+
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    tokio::fs::write(launch_password_path(), &hash)
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(Some(hash))
+}
+
+// only someone who knows the current password can turn it off
+pub async fn remove_launch_password(
+    hash: String,
+    password: Zeroizing<String>,
+) -> Result<Option<String>, Error> {
+    verify_launch_password(&hash, &password)?;
+    tokio::fs::remove_file(launch_password_path())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(None)
+}
+
+pub fn display_launch_lock(password: &str) -> Element<'static, Message> {
+    let title = text(tr("Enter the startup password"))
+        .size(14)
+        .style(accent_color());
+
+    let password_input = text_input(tr("Startup password"), password)
+        .on_input(Message::LaunchPasswordChanged)
+        .on_submit(Message::UnlockLaunch)
+        .password();
+
+    column![
+        title,
+        password_input,
+        button(tr("Unlock")).on_press(Message::UnlockLaunch),
+        text(tr(
+            "The default keypair is loaded once the password is accepted"
+        ))
+        .size(12)
+    ]
+    .spacing(10)
+    .into()
+}
+
+pub fn display_launch_password_settings(
+    hash: &Option<String>,
+    password: &str,
+    confirmation: &str,
+) -> Element<'static, Message> {
+    let title = text(tr("Startup password")).size(14).style(accent_color());
+
+    let password_input = text_input(tr("Startup password"), password)
+        .on_input(Message::LaunchPasswordChanged)
+        .password();
+
+    match hash {
+        Some(_) => column![
+            title,
+            text(tr(
+                "The default keypair is only loaded after the startup password is typed"
+            ))
+            .size(12),
+            password_input,
+            button(tr("Remove startup password")).on_press(Message::RemoveLaunchPassword)
+        ]
+        .spacing(10)
+        .into(),
+        None => {
+            let confirmation_input = text_input(tr("Repeat the password"), confirmation)
+                .on_input(Message::LaunchConfirmationChanged)
+                .password();
+
+            column![
+                title,
+                text(tr(
+                    "Ask for a password before the default keypair is loaded at startup"
+                ))
+                .size(12),
+                password_input,
+                confirmation_input,
+                button(tr("Require at startup")).on_press(Message::SetLaunchPassword)
+            ]
+            .spacing(10)
+            .into()
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use std::sync::Arc;
+
+use iced::{
+    widget::{button, column, row, text, text_input},
+    Alignment, Command, Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    clock::Epoch,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake, system_program, sysvar,
+};
+
+use crate::{
+    errors::{report, Error},
+    i18n::{tr, tr_args},
+    palette::{accent_color, warning_color},
+    token_accounts::{associated_token_address, create_associated_token_account, AtaPreview},
+    transaction::send_instructions,
+    units::{format_sol, parse_amount},
+    Message,
+};
+
+pub const STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+// JitoSOL, a plain spl stake pool; only deployed on mainnet
+pub const JITO_STAKE_POOL: Pubkey = pubkey!("Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb");
+
+// stake pool instruction indexes
+const DEPOSIT_SOL: u8 = 14;
+const WITHDRAW_SOL: u8 = 16;
+
+// fixed offsets in the stake pool account, everything read here comes before
+// the first variable length field
+const RESERVE_STAKE_OFFSET: usize = 130;
+const POOL_MINT_OFFSET: usize = 162;
+const MANAGER_FEE_OFFSET: usize = 194;
+const TOKEN_PROGRAM_OFFSET: usize = 226;
+const TOTAL_LAMPORTS_OFFSET: usize = 258;
+const POOL_TOKEN_SUPPLY_OFFSET: usize = 266;
+const LAST_UPDATE_EPOCH_OFFSET: usize = 274;
+// amount field of a token account
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct StakePool {
+    pub address: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub pool_mint: Pubkey,
+    pub manager_fee_account: Pubkey,
+    pub token_program: Pubkey,
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+    pub last_update_epoch: Epoch,
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Option<Pubkey> {
+    Pubkey::try_from(data.get(offset..offset + 32)?).ok()
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(offset..offset + 8)?.try_into().ok()?,
+    ))
+}
+
+impl StakePool {
+    fn parse(address: Pubkey, data: &[u8]) -> Option<Self> {
+        Some(Self {
+            address,
+            reserve_stake: read_pubkey(data, RESERVE_STAKE_OFFSET)?,
+            pool_mint: read_pubkey(data, POOL_MINT_OFFSET)?,
+            manager_fee_account: read_pubkey(data, MANAGER_FEE_OFFSET)?,
+            token_program: read_pubkey(data, TOKEN_PROGRAM_OFFSET)?,
+            total_lamports: read_u64(data, TOTAL_LAMPORTS_OFFSET)?,
+            pool_token_supply: read_u64(data, POOL_TOKEN_SUPPLY_OFFSET)?,
+            last_update_epoch: read_u64(data, LAST_UPDATE_EPOCH_OFFSET)?,
+        })
+    }
+
+    fn withdraw_authority(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[self.address.as_ref(), b"withdraw"],
+            &STAKE_POOL_PROGRAM_ID,
+        )
+        .0
+    }
+
+    // SOL one pool token is worth, which only grows as rewards come in
+    pub fn sol_per_token(&self) -> f64 {
+        if self.pool_token_supply == 0 {
+            return 1.0;
+        }
+        self.total_lamports as f64 / self.pool_token_supply as f64
+    }
+
+    // before the pool's deposit and withdrawal fees
+    pub fn tokens_for(&self, lamports: u64) -> u64 {
+        (lamports as f64 / self.sol_per_token()) as u64
+    }
+
+    pub fn lamports_for(&self, tokens: u64) -> u64 {
+        (tokens as f64 * self.sol_per_token()) as u64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LiquidStake {
+    pub pool: StakePool,
+    // pool tokens in the wallet's associated account
+    pub balance: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LiquidForm {
+    pub amount: String,
+    pub stake: Option<LiquidStake>,
+    // lamports of the deposit in flight, counted against the spend limit once it lands
+    pub depositing: Option<u64>,
+    pub busy: bool,
+    pub signature: String,
+}
+
+impl LiquidForm {
+    pub fn refresh(&self, owner: Pubkey, rpc_client: Arc<RpcClient>) -> Command<Message> {
+        Command::perform(
+            fetch_liquid_stake(owner, rpc_client),
+            Message::LiquidStakeLoaded,
+        )
+    }
+
+    pub fn deposit(
+        &mut self,
+        signer: Arc<Keypair>,
+        pool: StakePool,
+        lamports: u64,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        self.depositing = Some(lamports);
+        self.busy = true;
+        self.signature = String::new();
+        Command::perform(
+            deposit_sol(signer, pool, lamports, rpc_client),
+            Message::LiquidStakeUpdated,
+        )
+    }
+
+    pub fn withdraw(
+        &mut self,
+        signer: Arc<Keypair>,
+        pool: StakePool,
+        tokens: u64,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        self.busy = true;
+        self.signature = String::new();
+        Command::perform(
+            withdraw_sol(signer, pool, tokens, rpc_client),
+            Message::LiquidStakeUpdated,
+        )
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::LiquidStakeLoaded(result) => match result {
+                Ok(stake) => self.stake = Some(stake),
+                Err(error) => return report(error),
+            },
+            Message::LiquidAmountChanged(amount) => self.amount = amount,
+            Message::LiquidStakeUpdated(result) => {
+                self.busy = false;
+                self.depositing = None;
+                match result {
+                    Ok(signature) => {
+                        self.signature = signature;
+                        self.amount = String::new();
+                    }
+                    Err(error) => return report(error),
+                }
+            }
+            _ => {}
+        }
+        Command::none()
+    }
+}
+
+fn pool_token_account(owner: &Pubkey, pool: &StakePool) -> Pubkey {
+    associated_token_address(owner, &pool.pool_mint, &pool.token_program)
+}
+
+pub async fn fetch_liquid_stake(
+    owner: Pubkey,
+    rpc_client: Arc<RpcClient>,
+) -> Result<LiquidStake, Error> {
+    let pool = rpc_client
+        .get_account_with_commitment(&JITO_STAKE_POOL, rpc_client.commitment())
+        .await
+        .map_err(|_| Error::FetchAccountError)?
+        .value
+        .filter(|account| account.owner == STAKE_POOL_PROGRAM_ID)
+        .and_then(|account| StakePool::parse(JITO_STAKE_POOL, &account.data))
+        .ok_or(Error::StakePoolNotFound)?;
+
+    let balance = rpc_client
+        .get_account_with_commitment(&pool_token_account(&owner, &pool), rpc_client.commitment())
+        .await
+        .map_err(|_| Error::FetchAccountError)?
+        .value
+        .and_then(|account| read_u64(&account.data, TOKEN_AMOUNT_OFFSET))
+        .unwrap_or(0);
+
+    Ok(LiquidStake { pool, balance })
+}
+
+// the pool refuses deposits and withdrawals until its epoch update has been
+// cranked, which usually happens within minutes of the epoch changing
+async fn check_pool_updated(pool: &StakePool, rpc_client: &RpcClient) -> Result<(), Error> {
+    let epoch = rpc_client
+        .get_epoch_info()
+        .await
+        .map_err(|_| Error::FetchNetworkInfoError)?
+        .epoch;
+    if pool.last_update_epoch < epoch {
+        return Err(Error::StakePoolOutOfDate);
+    }
+    Ok(())
+}
+
+// SOL goes into the pool's reserve and pool tokens are minted to the wallet's
+// associated account, created when missing
+pub async fn deposit_sol(
+    signer: Arc<Keypair>,
+    pool: StakePool,
+    lamports: u64,
+    rpc_client: Arc<RpcClient>,
+) -> Result<String, Error> {
+    check_pool_updated(&pool, &rpc_client).await?;
+    let owner = signer.pubkey();
+    let token_account = pool_token_account(&owner, &pool);
+    let create_account = create_associated_token_account(
+        &owner,
+        &AtaPreview {
+            owner,
+            mint: pool.pool_mint,
+            token_program: pool.token_program,
+            address: token_account,
+            exists: false,
+            owner_off_curve: false,
+        },
+    );
+
+    let mut data = vec![DEPOSIT_SOL];
+    data.extend_from_slice(&lamports.to_le_bytes());
+    let deposit = Instruction::new_with_bytes(
+        STAKE_POOL_PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new_readonly(pool.withdraw_authority(), false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(pool.manager_fee_account, false),
+            // no referrer, the referral share comes back to the wallet
+            AccountMeta::new(token_account, false),
+            AccountMeta::new(pool.pool_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(pool.token_program, false),
+        ],
+    );
+    send_instructions(&rpc_client, &[&*signer], &[create_account, deposit]).await
+}
+
+// pool tokens are burned for SOL out of the reserve; a withdrawal larger than
+// the reserve fails, swapping is the way out for those
+pub async fn withdraw_sol(
+    signer: Arc<Keypair>,
+    pool: StakePool,
+    tokens: u64,
+    rpc_client: Arc<RpcClient>,
+) -> Result<String, Error> {
+    check_pool_updated(&pool, &rpc_client).await?;
+    let owner = signer.pubkey();
+
+    let mut data = vec![WITHDRAW_SOL];
+    data.extend_from_slice(&tokens.to_le_bytes());
+    let withdraw = Instruction::new_with_bytes(
+        STAKE_POOL_PROGRAM_ID,
+        &data,
+        vec![
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new_readonly(pool.withdraw_authority(), false),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(pool_token_account(&owner, &pool), false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new(owner, false),
+            AccountMeta::new(pool.manager_fee_account, false),
+            AccountMeta::new(pool.pool_mint, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+            AccountMeta::new_readonly(stake::program::id(), false),
+            AccountMeta::new_readonly(pool.token_program, false),
+        ],
+    );
+    send_instructions(&rpc_client, &[&*signer], &[withdraw]).await
+}
+
+pub fn display_liquid_stake(form: &LiquidForm, mainnet: bool) -> Element<'static, Message> {
+    let title = text(tr("Liquid staking (JitoSOL)"))
+        .size(14)
+        .style(accent_color());
+
+    if !mainnet {
+        return column![
+            title,
+            text(tr("The JitoSOL pool only exists on mainnet")).size(12)
+        ]
+        .spacing(10)
+        .into();
+    }
+
+    let Some(stake) = &form.stake else {
+        return column![
+            title,
+            button(text(tr("Load pool")).size(12)).on_press(Message::RefreshLiquidStake),
+        ]
+        .spacing(10)
+        .into();
+    };
+
+    let rate = text(tr_args(
+        "1 JitoSOL = {$rate} SOL, balance {$balance} JitoSOL (about {$sol} SOL)",
+        &[
+            ("rate", &format!("{:.6}", stake.pool.sol_per_token())),
+            ("balance", &format_sol(stake.balance)),
+            ("sol", &format_sol(stake.pool.lamports_for(stake.balance))),
+        ],
+    ))
+    .size(14);
+
+    // both directions, the buttons say which one applies
+    let estimate = match parse_amount(&form.amount) {
+        Ok(amount) if amount > 0 => tr_args(
+            "Staking {$amount} SOL gives about {$tokens} JitoSOL, unstaking {$amount} JitoSOL gives about {$sol} SOL, before pool fees",
+            &[
+                ("amount", &form.amount.trim()),
+                ("tokens", &format_sol(stake.pool.tokens_for(amount))),
+                ("sol", &format_sol(stake.pool.lamports_for(amount))),
+            ],
+        ),
+        _ => String::new(),
+    };
+
+    let actions = row![
+        text_input(tr("Amount"), &form.amount).on_input(Message::LiquidAmountChanged),
+        if form.busy {
+            button(tr("Stake SOL"))
+        } else {
+            button(tr("Stake SOL")).on_press(Message::DepositLiquid)
+        },
+        if form.busy || stake.balance == 0 {
+            button(tr("Unstake JitoSOL"))
+        } else {
+            button(tr("Unstake JitoSOL")).on_press(Message::WithdrawLiquid)
+        },
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    column![
+        title,
+        rate,
+        actions,
+        text(estimate).size(12),
+        text(tr("Unstaking is paid from the pool's reserve right away, larger amounts are better swapped"))
+            .size(12)
+            .style(warning_color()),
+        text(form.signature.clone()).size(14),
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,57 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::{SinkExt, StreamExt};
+use iced::{subscription, Subscription};
+use solana_account_decoder::UiAccountEncoding;
+use solana_cli_config::Config;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcAccountInfoConfig};
+use solana_pubsub_client::nonblocking::pubsub_client::PubsubClient;
+use solana_sdk::pubkey::Pubkey;
+use tokio::time;
+
+use crate::{loaders::display_balance, Message};
+
+// how long a dropped socket waits before reconnecting, endpoints without
+// websockets are polled at the same pace instead
+const RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+// pushes the active wallet's balance whenever the account changes, incoming
+// transfers and spends from other apps included; a new pubkey or endpoint
+// restarts it
+pub fn balance_updates(pubkey: Pubkey, rpc_client: Arc<RpcClient>) -> Subscription<Message> {
+    subscription::channel(
+        (pubkey, rpc_client.url()),
+        16,
+        move |mut output| async move {
+            let ws_url = Config::compute_websocket_url(&rpc_client.url());
+            let config = RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(rpc_client.commitment()),
+                ..RpcAccountInfoConfig::default()
+            };
+
+            loop {
+                // a change between the last fetch and the subscription would
+                // otherwise go unnoticed
+                if let Ok(balance) = display_balance(pubkey, rpc_client.clone()).await {
+                    output.send(Message::BalanceLoaded(Ok(balance))).await.ok();
+                }
+
+                if let Ok(pubsub_client) = PubsubClient::new(&ws_url).await {
+                    if let Ok((mut notifications, _unsubscribe)) = pubsub_client
+                        .account_subscribe(&pubkey, Some(config.clone()))
+                        .await
+                    {
+                        while let Some(response) = notifications.next().await {
+                            output
+                                .send(Message::BalanceLoaded(Ok(response.value.lamports)))
+                                .await
+                                .ok();
+                        }
+                    }
+                }
+                time::sleep(RECONNECT_DELAY).await;
+            }
+        },
+    )
+}
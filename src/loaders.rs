@@ -1,37 +1,50 @@
 use std::{path::PathBuf, sync::Arc};
 
-use crate::{errors::Error, Message};
+use crate::{errors::Error, files::save_file, i18n::tr, palette::accent_color, rpc::with_retry, Message};
 use iced::{
-    color, widget::{column, text}, Element
+    widget::{column, text}, Element
 };
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
-    signature::{read_keypair_file, Keypair},
-    signer::Signer,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, write_keypair_file, Keypair},
 };
 
-pub fn display_pubkey(file_path: PathBuf) -> Element<'static, Message> {
-    let keypair = load_keypair_from_file(file_path);
-
-    let label = text(format!("Wallet address: ",))
+pub fn display_pubkey(pubkey: Option<Pubkey>) -> Element<'static, Message> {
+    let label = text(tr("Wallet address: "))
         .size(14)
-        .style(color!(0x30cbf2));
+        .style(accent_color());
 
-    let value = text(keypair.pubkey().to_string()).size(14);
+    let value = match pubkey {
+        Some(pubkey) => text(pubkey.to_string()).size(14),
+        None => text(tr("No wallet loaded")).size(14),
+    };
 
     let pubkey_container = column![label, value];
     pubkey_container.into()
 }
 
-pub fn load_keypair_from_file(path: PathBuf) -> Keypair {
-    let keypair = read_keypair_file(path).unwrap_or(Keypair::new());
-    keypair
+pub fn load_keypair_from_file(path: PathBuf) -> Result<Keypair, Error> {
+    read_keypair_file(path).map_err(|_| Error::InvalidKeypairFile)
+}
+
+// never overwrites an existing file, that could be another wallet's only copy
+pub async fn save_keypair_file(keypair: Keypair) -> Result<PathBuf, Error> {
+    let path = save_file("wallet.json").await?;
+    if path.exists() {
+        return Err(Error::FileAlreadyExists);
+    }
+
+    write_keypair_file(&keypair, &path).map_err(|_| Error::SaveFileError)?;
+    Ok(path)
+}
+
+pub async fn create_keypair_file() -> Result<PathBuf, Error> {
+    save_keypair_file(Keypair::new()).await
 }
 
-pub async fn display_balance(path: PathBuf, rpc_client: Arc<RpcClient>) -> Result<u64, Error> {
-    let keypair = load_keypair_from_file(path);
-    rpc_client
-        .get_balance(&keypair.pubkey())
+pub async fn display_balance(pubkey: Pubkey, rpc_client: Arc<RpcClient>) -> Result<u64, Error> {
+    with_retry(|| rpc_client.get_balance(&pubkey))
         .await
         .map_err(|_| Error::FetchBalanceError)
 }
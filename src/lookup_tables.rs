@@ -0,0 +1,296 @@
+use std::{str::FromStr, sync::Arc};
+
+use iced::{
+    widget::{button, column, row, text, text_input, Column},
+    Command, Element,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    address_lookup_table::{self, state::AddressLookupTable},
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use crate::{
+    errors::{report, Error},
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    transaction::send_instructions,
+    Message,
+};
+
+// offset of the authority pubkey inside the lookup table meta:
+// discriminator (4) + deactivation slot (8) + last extended slot (8) +
+// start index (1) + option tag (1)
+const AUTHORITY_OFFSET: usize = 22;
+
+#[derive(Debug, Clone)]
+pub struct LookupTableInfo {
+    pub address: Pubkey,
+    pub addresses: Vec<Pubkey>,
+    pub deactivation_slot: u64,
+}
+
+impl LookupTableInfo {
+    pub fn is_active(&self) -> bool {
+        self.deactivation_slot == u64::MAX
+    }
+}
+
+// the lookup tables screen: the wallet's tables and the addresses to extend with
+#[derive(Debug, Default)]
+pub struct LookupTableList {
+    pub tables: Vec<LookupTableInfo>,
+    pub new_addresses: String,
+}
+
+impl LookupTableList {
+    pub fn refresh(&self, authority: Pubkey, rpc_client: Arc<RpcClient>) -> Command<Message> {
+        Command::perform(
+            fetch_lookup_tables(authority, rpc_client),
+            Message::LookupTablesLoaded,
+        )
+    }
+
+    // create, extend, deactivate and close, signed by the wallet
+    pub fn submit(
+        &self,
+        action: Message,
+        signer: Arc<Keypair>,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        match action {
+            Message::CreateLookupTable => Command::perform(
+                create_lookup_table(signer, rpc_client),
+                Message::LookupTableUpdated,
+            ),
+            Message::ExtendLookupTable(table) => Command::perform(
+                extend_lookup_table(signer, rpc_client, table, self.new_addresses.clone()),
+                Message::LookupTableUpdated,
+            ),
+            Message::DeactivateLookupTable(table) => Command::perform(
+                deactivate_lookup_table(signer, rpc_client, table),
+                Message::LookupTableUpdated,
+            ),
+            Message::CloseLookupTable(table) => Command::perform(
+                close_lookup_table(signer, rpc_client, table),
+                Message::LookupTableUpdated,
+            ),
+            _ => Command::none(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::LookupTablesLoaded(result) => match result {
+                Ok(tables) => {
+                    self.tables = tables;
+                    Command::none()
+                }
+                Err(error) => report(error),
+            },
+            Message::LookupTableAddressesChanged(addresses) => {
+                self.new_addresses = addresses;
+                Command::none()
+            }
+            Message::LookupTableUpdated(result) => match result {
+                Ok(_) => {
+                    self.new_addresses = String::new();
+                    Command::none()
+                }
+                Err(error) => report(error),
+            },
+            _ => Command::none(),
+        }
+    }
+}
+
+pub async fn fetch_lookup_tables(
+    authority: Pubkey,
+    rpc_client: Arc<RpcClient>,
+) -> Result<Vec<LookupTableInfo>, Error> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            AUTHORITY_OFFSET,
+            &authority.to_bytes(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    let accounts = rpc_client
+        .get_program_accounts_with_config(&address_lookup_table::program::id(), config)
+        .await
+        .map_err(|_| Error::FetchLookupTablesError)?;
+
+    let tables = accounts
+        .into_iter()
+        .filter_map(|(address, account)| {
+            let table = AddressLookupTable::deserialize(&account.data).ok()?;
+            Some(LookupTableInfo {
+                address,
+                addresses: table.addresses.to_vec(),
+                deactivation_slot: table.meta.deactivation_slot,
+            })
+        })
+        .collect();
+
+    Ok(tables)
+}
+
+pub async fn create_lookup_table(
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+) -> Result<String, Error> {
+    // the derivation slot must be a recent, rooted slot
+    let recent_slot = rpc_client
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+        .await
+        .map_err(|_| Error::TransactionError)?;
+
+    let (create_ix, _) = address_lookup_table::instruction::create_lookup_table(
+        signer.pubkey(),
+        signer.pubkey(),
+        recent_slot,
+    );
+
+    send_instructions(&rpc_client, &[&*signer], &[create_ix]).await
+}
+
+// addresses are separated by commas or whitespace
+pub async fn extend_lookup_table(
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+    table: Pubkey,
+    new_addresses: String,
+) -> Result<String, Error> {
+    let new_addresses = new_addresses
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|address| !address.is_empty())
+        .map(|address| Pubkey::from_str(address).map_err(|_| Error::InvalidPubKeyLen))
+        .collect::<Result<Vec<Pubkey>, Error>>()?;
+
+    if new_addresses.is_empty() {
+        return Err(Error::InvalidPubKeyLen);
+    }
+
+    let extend_ix = address_lookup_table::instruction::extend_lookup_table(
+        table,
+        signer.pubkey(),
+        Some(signer.pubkey()),
+        new_addresses,
+    );
+
+    send_instructions(&rpc_client, &[&*signer], &[extend_ix]).await
+}
+
+pub async fn deactivate_lookup_table(
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+    table: Pubkey,
+) -> Result<String, Error> {
+    let deactivate_ix =
+        address_lookup_table::instruction::deactivate_lookup_table(table, signer.pubkey());
+
+    send_instructions(&rpc_client, &[&*signer], &[deactivate_ix]).await
+}
+
+// only works once the table has been deactivated for a full slot hashes window
+pub async fn close_lookup_table(
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+    table: Pubkey,
+) -> Result<String, Error> {
+    let close_ix = address_lookup_table::instruction::close_lookup_table(
+        table,
+        signer.pubkey(),
+        signer.pubkey(),
+    );
+
+    send_instructions(&rpc_client, &[&*signer], &[close_ix]).await
+}
+
+pub fn display_lookup_tables(
+    tables: &[LookupTableInfo],
+    new_addresses: &str,
+) -> Element<'static, Message> {
+    let header = row![
+        button(tr("Refresh tables")).on_press(Message::RefreshLookupTables),
+        button(tr("Create table")).on_press(Message::CreateLookupTable),
+    ]
+    .spacing(10);
+
+    let addresses_input = text_input(tr("Addresses to add (comma separated)"), new_addresses)
+        .on_input(Message::LookupTableAddressesChanged);
+
+    let tables_list = tables
+        .iter()
+        .fold(Column::new().spacing(15), |column, table| {
+            let addresses = table
+                .addresses
+                .iter()
+                .fold(Column::new().spacing(2), |column, address| {
+                    column.push(text(address.to_string()).size(12))
+                });
+
+            let actions = if table.is_active() {
+                row![
+                    button(tr("Extend")).on_press(Message::ExtendLookupTable(table.address)),
+                    button(tr("Deactivate"))
+                        .on_press(Message::DeactivateLookupTable(table.address)),
+                ]
+            } else {
+                row![button(tr("Close")).on_press(Message::CloseLookupTable(table.address))]
+            }
+            .spacing(10);
+
+            column.push(
+                column![
+                    text(table.address.to_string())
+                        .size(14)
+                        .style(accent_color()),
+                    text(tr_args(
+                        "{$count} addresses | {$state}",
+                        &[
+                            ("count", &table.addresses.len()),
+                            (
+                                "state",
+                                &if table.is_active() {
+                                    String::from(tr("active"))
+                                } else {
+                                    tr_args(
+                                        "deactivated at slot {$slot}",
+                                        &[("slot", &table.deactivation_slot)],
+                                    )
+                                },
+                            ),
+                        ],
+                    ))
+                    .size(14),
+                    addresses,
+                    actions
+                ]
+                .spacing(5),
+            )
+        });
+
+    let empty_hint = if tables.is_empty() {
+        text(tr("No lookup tables owned by this keypair")).size(14)
+    } else {
+        text("").size(1)
+    };
+
+    column![header, addresses_input, empty_hint, tables_list]
+        .spacing(10)
+        .into()
+}
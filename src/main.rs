@@ -1,58 +1,803 @@
 use std::{
+    collections::HashMap,
+    env,
     path::{Path, PathBuf},
+    str::FromStr,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use iced::{
-    color, executor,
+    clipboard, executor, keyboard, subscription,
     theme::Theme,
-    widget::{button, column, container, row, text, text_input, Column, Image, Space},
-    Application, Command, Element, Settings, Subscription,
+    widget::{
+        button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Column,
+        Image, Space,
+    },
+    window, Alignment, Application, Command, Element, Event, Length, Settings, Subscription,
 };
 use solana_client::nonblocking::rpc_client::RpcClient;
-use solana_sdk::{native_token::LAMPORTS_PER_SOL, signature::Keypair};
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+};
 use tokio::time;
+use zeroize::{Zeroize, Zeroizing};
+mod alerts;
+mod allowlist;
+mod batch;
+mod capture;
+mod chart;
+mod commands;
+mod config;
+mod dca;
+mod encryption;
+mod epoch;
 mod errors;
+mod exchanges;
+mod faucet;
+mod fiat;
 mod files;
+mod genesis;
+mod history;
+mod i18n;
+mod jito;
+mod keystore;
+mod launch_password;
+mod liquid;
+mod live_balance;
 mod loaders;
+mod lookup_tables;
+mod mnemonic;
+mod network;
+mod nonce;
+mod notifications;
+mod offline;
+mod palette;
+mod paste_keypair;
+mod payroll;
+mod permissions;
+mod poisoning;
+mod portfolio;
+mod prices;
+mod profiles;
+mod quick_actions;
+mod receipts;
+mod refresh;
+mod replay;
+mod rewards;
+mod rpc;
+mod settings;
+mod snapshots;
+mod stake;
+mod status_bar;
+mod swap;
+mod test_wallets;
+mod toasts;
+mod token_accounts;
+mod token_list;
 mod transaction;
+mod tray;
+mod units;
+mod validators;
+mod vanity;
+mod wallets;
+mod wsol;
 
+use alerts::{
+    display_alerts, evaluate_alerts, has_balance_alerts, notify_alert, parse_alert, AlertKind,
+    ALERT_CHECK_INTERVAL,
+};
+use allowlist::{display_allow_list, list_addresses, load_allow_list, AllowList, AllowListForm};
+use batch::{
+    batch_fees, chunk_transfers, display_batch, parse_rows, review_transfers, BatchChunk,
+    BatchForm, BatchRow,
+};
+use capture::exclude_from_capture;
+use chart::{
+    display_chart, load_chart_points, record_chart_point, unix_now, ChartPoint, ChartRange,
+    ChartSeries, CHART_RECORD_INTERVAL,
+};
+use commands::{display_palette, parse_command, AppCommand};
+use config::{
+    display_config, load_config, save_config, system_prefers_dark, window_settings, AppConfig,
+    SYSTEM_THEME_POLL_INTERVAL, WINDOW_STATE_SAVE_DELAY,
+};
+use dca::{
+    display_dca, load_dca_history, run_dca, save_dca_run, skipped_run, swap_token, DcaInterval,
+    DcaRun, DcaSchedule, DCA_CHECK_INTERVAL,
+};
+use encryption::{
+    check_new_passphrase, display_encrypted_export, export_encrypted_keypair, replace_secret,
+};
+use epoch::{fetch_epoch_progress, EpochProgress, EPOCH_REFRESH_INTERVAL};
 use errors::Error;
-use files::{default_file, pick_file, DEFAULT_LOCATION};
-use loaders::{display_balance, display_pubkey, load_keypair_from_file};
-use transaction::transfer_sol;
+use exchanges::find_exchange;
+use faucet::{display_faucet, has_faucet, Faucet, TopUpResult, AUTO_TOP_UP_INTERVAL};
+use fiat::{format_fiat, shows_fiat};
+use files::{
+    default_file, is_portable, pick_file, set_portable_mode, startup_file, DEFAULT_LOCATION,
+};
+use genesis::{display_cluster_warning, expected_cluster, verify_cluster, ClusterMismatch};
+use history::{
+    display_history, export_decoded_transaction, fetch_history, merge_history, HistoryEntry,
+};
+use i18n::{set_language, tr, tr_args, Language};
+use jito::{display_jito_option, JitoSettings, MIN_JITO_TIP};
+use keystore::{
+    display_keystore_migration, display_unlock, forget_keychain_passphrase, has_keystore,
+    keystore_path, migrate_to_keystore, unlock_keystore, unlock_with_keychain,
+};
+use launch_password::{
+    display_launch_lock, display_launch_password_settings, load_launch_password,
+    remove_launch_password, set_launch_password, unlock_launch, verify_wallet_password,
+};
+use liquid::{display_liquid_stake, LiquidForm, LiquidStake};
+use live_balance::balance_updates;
+use loaders::{create_keypair_file, display_pubkey, load_keypair_from_file};
+use lookup_tables::{display_lookup_tables, LookupTableInfo, LookupTableList};
+use mnemonic::{
+    create_mnemonic_wallet, derive_accounts, display_mnemonic_backup, display_mnemonic_import,
+    import_derived_account, DerivedAccount, MnemonicBackup,
+};
+use network::{
+    display_network_performance, estimate_confirmation_eta, fetch_network_performance,
+    ConfirmationEta, NetworkPerformance,
+};
+use nonce::{display_nonce_account, NonceAccountInfo, NonceForm};
+use notifications::{incoming_tokens, notify_incoming, Incoming};
+use offline::{
+    broadcast_transaction_file, display_offline, export_unsigned_transfer, sign_transaction_file,
+};
+use palette::{accent_color, error_color, set_palette, Palette};
+use paste_keypair::{display_paste_keypair, keypair_from_json};
+use payroll::{export_payroll_results, read_payroll_file, validate_rows};
+use permissions::{display_permissions, Service};
+use poisoning::{
+    display_poisoning_warning, find_lookalike, load_known_recipients, remember_recipients,
+    save_known_recipients,
+};
+use portfolio::{display_portfolio, holdings};
+use prices::{fetch_prices, PriceCache, Prices, PRICE_REFRESH_INTERVAL};
+use profiles::{display_profiles, load_profiles, save_profiles, valid_profile_name, Profile};
+use quick_actions::{
+    display_quick_actions, load_quick_actions, quick_action_from_query, save_quick_actions,
+    QuickAction,
+};
+use receipts::{fetch_receipt_pings, send_receipt_ping};
+use refresh::{display_token_balances, refresh_interval, refresh_wallet, WalletRefresh};
+use replay::{display_replay, replay_transaction, ReplayReport};
+use rewards::{display_rewards, export_stake_rewards, fetch_stake_rewards, StakeReward};
+use rpc::{
+    archival_rpc_client, benchmark_endpoints, cluster_name, configured_rpc_url, default_rpc_url,
+    display_rpc_endpoints, match_clusters, new_rpc_pool, RpcEndpoints,
+};
+use settings::{
+    display_settings, display_spend_limit, load_preferences, save_preferences, Preferences,
+};
+use snapshots::{
+    export_snapshot, headless_export_folder, last_closed_day, run_headless_export, snapshot_path,
+    snapshot_wallets, SnapshotRow, SNAPSHOT_CHECK_INTERVAL,
+};
+use stake::{
+    display_stake_accounts, display_stake_form, fetch_stake_accounts, StakeAccount, StakeForm,
+    StakePreview,
+};
+use status_bar::{
+    check_rpc_health, display_status_bar, slot_updates, ConnectionStatus, HEALTH_CHECK_INTERVAL,
+};
+use swap::{
+    display_swap, parse_slippage, parse_token_amount, SwapForm, SwapQuote, SwapReceipt, SwapToken,
+    SWAP_TOKENS,
+};
+use test_wallets::{display_test_wallets, TestSet, TestWallets};
+use toasts::{display_toasts, ToastAction, ToastLevel, Toasts};
+use token_accounts::{display_token_accounts, AtaForm, AtaPreview};
+use token_list::{fetch_token_list, fetch_token_symbols, MintStatus, TokenVerification};
+use transaction::{
+    estimate_fee, max_sendable, recipient_rent_shortfall, simulate_transfer, transfer_sol,
+    SentTransfer, SimulationPreview, TransferValues,
+};
+use tray::{tray_actions, Tray, TrayAction, TRAY_POLL_INTERVAL};
+use units::{format_sol, parse_amount, AmountUnit};
+use validators::{display_validators, Validator, ValidatorList, ValidatorSort};
+use vanity::{display_vanity, VanityGrinder};
+use wallets::{discover_keypairs, display_wallets, display_welcome, remember_wallet, LoadedWallet};
+use wsol::{display_wsol, WsolForm, NATIVE_MINT};
 
 fn main() -> iced::Result {
-    SolExecApp::run(Settings::default())
+    if let Some(folder) = headless_export_folder() {
+        match run_headless_export(folder, &default_rpc_url()) {
+            Ok(path) => println!("Balances written to {}", path.display()),
+            Err(error) => {
+                eprintln!("Error: {}", error);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+    // with the tray enabled closing the window only hides it
+    SolExecApp::run(Settings {
+        exit_on_close_request: !load_preferences().minimize_to_tray,
+        window: window_settings(&load_config()),
+        ..Settings::default()
+    })
 }
 
 struct SolExecApp {
-    pub signer: Arc<Keypair>,
+    // None until a keypair is loaded, and while a watched address is active
+    pub signer: Option<Arc<Keypair>>,
     pub rpc_client: Arc<RpcClient>,
+    // the nodes behind rpc_client and how they've been answering
+    pub rpc_endpoints: Arc<RpcEndpoints>,
+    pub testing_endpoints: bool,
+    // set while the node's genesis hash contradicts the profile or url
+    pub cluster_mismatch: Option<ClusterMismatch>,
+    pub config: AppConfig,
+    // geometry or tab changed and isn't written to the config yet
+    pub window_state_changed: bool,
+    // the tab from the last session, opened once the first wallet loads
+    pub restore_screen: Option<Screen>,
+    // price of one SOL in the configured fiat currency
+    pub prices: PriceCache,
+    // last dark/light setting read from the os
+    pub system_dark: bool,
+    pub archival_rpc_client: Option<Arc<RpcClient>>,
     pub path: Option<PathBuf>,
-    pub error: Option<Error>,
+    pub toasts: Toasts,
+    // cluster tip, rpc health and websocket state for the status bar
+    pub connection: ConnectionStatus,
+    pub network_performance: Option<NetworkPerformance>,
+    pub chart_points: Vec<ChartPoint>,
+    pub chart_series: ChartSeries,
+    pub chart_range: ChartRange,
+    // a point is recorded at most once per interval
+    pub chart_recorded: Option<Instant>,
+    pub alert_kind: AlertKind,
+    pub alert_threshold: String,
+    pub swap: SwapForm,
+    pub dca_interval: DcaInterval,
+    pub dca_history: Vec<DcaRun>,
+    // schedules with a swap in flight and the SOL each holds against the
+    // spend limit, a slow one isn't started twice
+    pub dca_running: Vec<(u64, u64)>,
+    pub stake: StakeForm,
+    pub stake_accounts: Vec<StakeAccount>,
+    pub stake_epoch: Option<u64>,
+    pub stake_rewards: Vec<StakeReward>,
+    pub liquid: LiquidForm,
+    pub stake_rewards_loading: bool,
+    pub validators: ValidatorList,
     pub balance: Option<u64>,
     pub receiver_value: (String, String),
+    pub amount_unit: AmountUnit,
     pub signature: String,
     pub is_loading: bool,
     pub current_frame: usize,
+    pub priority_fee: String,
+    pub fee: Option<u64>,
+    pub notify_recipient: bool,
+    pub acknowledged_payments: Vec<String>,
+    pub simulation: Option<SimulationPreview>,
+    pub memo: String,
+    pub eta: Option<ConfirmationEta>,
+    pub sent_at: Option<Instant>,
+    pub palette_open: bool,
+    pub palette_query: String,
+    pub screen: Screen,
+    pub lookup_tables: LookupTableList,
+    pub allow_list: Option<AllowList>,
+    pub allow_list_form: AllowListForm,
+    pub nonce: NonceForm,
+    pub use_nonce: bool,
+    pub use_jito: bool,
+    pub jito_tip: String,
+    pub offline: bool,
+    pub offline_status: String,
+    pub portable: bool,
+    pub privacy_mode: bool,
+    pub balances_revealed: bool,
+    pub fee_payer: Option<Arc<Keypair>>,
+    pub fee_payer_path: Option<PathBuf>,
+    pub history: Vec<HistoryEntry>,
+    pub batch: BatchForm,
+    pub faucet: Faucet,
+    pub test_wallets: TestWallets,
+    pub keep_rent_exempt: bool,
+    pub rent_warning: Option<u64>,
+    pub ata: AtaForm,
+    pub known_recipients: Vec<Pubkey>,
+    pub poisoning_warning: Option<Pubkey>,
+    pub poisoning_acknowledged: bool,
+    pub quick_actions: Vec<QuickAction>,
+    pub large_transfer_sol: String,
+    pub large_transfer_percent: String,
+    pub confirming_large_transfer: bool,
+    pub large_transfer_confirmed: bool,
+    pub spend_limit_input: String,
+    // keystore or startup password typed to change the limit or lift it
+    pub spend_password: String,
+    pub session_spent: u64,
+    pub replay: Option<ReplayReport>,
+    pub unwrap_wsol: bool,
+    pub wsol: WsolForm,
+    pub mnemonic: String,
+    pub mnemonic_passphrase: String,
+    pub derived_accounts: Vec<DerivedAccount>,
+    pub mnemonic_backup: Option<MnemonicBackup>,
+    // the seed phrase words only show while they're held down
+    pub secret_revealed: bool,
+    pub secrets_on_screen: bool,
+    // whether the OS keeps the window out of screen captures right now
+    pub capture_excluded: bool,
+    pub backup_passphrase: String,
+    pub backup_confirmation: String,
+    pub encrypted_backup: Option<PathBuf>,
+    pub keystore_passphrase: String,
+    pub keystore_confirmation: String,
+    pub keystore_migrated: bool,
+    // argon2 hash of the startup password, if one is required
+    pub launch_password: Option<String>,
+    // the keypair opened on startup: the startup profile's, or the default
+    // one when the profile doesn't pick one
+    pub startup_file: PathBuf,
+    // a followed address without a private key, nothing can be signed for it
+    pub watch_only: Option<Pubkey>,
+    pub watch_address: String,
+    // every keypair or watched address loaded this session, the active one is
+    // mirrored into signer/path/watch_only
+    pub wallets: Vec<LoadedWallet>,
+    // keypair files found in the solana config directory at startup
+    pub discovered_keypairs: Vec<(PathBuf, Pubkey)>,
+    pub profiles: Vec<Profile>,
+    pub profile_name: String,
+    // "[12,34,...]" bytes pasted in, wiped as soon as they're parsed
+    pub pasted_keypair: String,
+    // token accounts of the active wallet, filled by the periodic refresh
+    pub token_balances: Vec<SnapshotRow>,
+    // held and swapped mints checked against jupiter's verified list
+    pub token_verification: TokenVerification,
+    pub balance_updated: Option<Instant>,
+    // created on the first poll once the event loop runs, None if the
+    // desktop has no tray
+    pub tray: Option<Tray>,
+    pub tray_started: bool,
+    pub vanity: VanityGrinder,
+    pub launch_password_input: String,
+    pub launch_password_confirmation: String,
+    pub preferences: Preferences,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    Send,
+    LookupTables,
+    AllowList,
+    Nonce,
+    Offline,
+    Settings,
+    History,
+    Batch,
+    Faucet,
+    TestWallets,
+    TokenAccounts,
+    ImportMnemonic,
+    NewMnemonic,
+    Unlock,
+    LaunchLock,
+    Welcome,
+    PasteKeypair,
+    Vanity,
+    Network,
+    Portfolio,
+    Chart,
+    Alerts,
+    Swap,
+    Staking,
+    Validators,
+    Permissions,
+}
+
+impl Screen {
+    // tabs worth reopening on the next launch, by their saved name
+    fn restorable(name: &str) -> Option<Screen> {
+        [
+            Screen::Send,
+            Screen::LookupTables,
+            Screen::AllowList,
+            Screen::Nonce,
+            Screen::Settings,
+            Screen::History,
+            Screen::Batch,
+            Screen::Faucet,
+            Screen::TestWallets,
+            Screen::TokenAccounts,
+            Screen::Vanity,
+            Screen::Network,
+            Screen::Portfolio,
+            Screen::Chart,
+            Screen::Alerts,
+            Screen::Swap,
+            Screen::Staking,
+            Screen::Validators,
+            Screen::Permissions,
+        ]
+        .into_iter()
+        .find(|screen| format!("{:?}", screen) == name)
+    }
 }
 
 #[derive(Debug, Clone)]
 enum Message {
     FileOpened(Result<PathBuf, Error>),
     Open,
+    CreateWallet,
     BalanceLoaded(Result<u64, Error>),
-    ErrorCleared,
+    DismissToast(u64),
+    // an error from a feature module's update, shown as a toast
+    ShowError(Error),
+    SlotUpdated(u64),
+    WebsocketStateChanged(bool),
+    CheckRpcHealth,
+    RpcHealthChecked(bool),
+    RefreshNetworkPerformance,
+    NetworkPerformanceLoaded(Result<NetworkPerformance, Error>),
+    ChartSeriesChanged(ChartSeries),
+    ChartRangeChanged(ChartRange),
+    ChartPointRecorded(Result<ChartPoint, Error>),
+    AlertKindChanged(AlertKind),
+    AlertThresholdChanged(String),
+    AddAlert,
+    RemoveAlert(usize),
+    AlertNotified(Result<(), Error>),
+    SwapInputChanged(SwapToken),
+    SwapOutputChanged(SwapToken),
+    FlipSwap,
+    SwapAmountChanged(String),
+    SwapSlippageChanged(String),
+    CancelSwap,
+    GetSwapQuote,
+    SwapQuoteLoaded(Result<SwapQuote, Error>),
+    ExecuteSwap,
+    SwapExecuted(Result<SwapReceipt, Error>),
+    DcaIntervalChanged(DcaInterval),
+    AddDcaSchedule,
+    ToggleDcaSchedule(u64),
+    RemoveDcaSchedule(u64),
+    CheckDca,
+    DcaRan(DcaRun),
+    DcaRunSaved(Result<(), Error>),
+    StakeVoteAccountChanged(String),
+    StakeAmountChanged(String),
+    PreviewStake,
+    StakePreviewed(Result<StakePreview, Error>),
+    ConfirmStake,
+    CancelStake,
+    StakeCreated(Result<(Pubkey, String), Error>),
+    RefreshStakeAccounts,
+    StakeAccountsLoaded(Result<(Vec<StakeAccount>, u64), Error>),
+    DeactivateStake(Pubkey),
+    WithdrawStake(Pubkey),
+    StakeAccountUpdated(Result<String, Error>),
+    StakeSplitAmountChanged(String),
+    SplitStake(Pubkey),
+    StakeSplit(Result<(Pubkey, String), Error>),
+    PickMergeSource(Pubkey),
+    CancelMergeStake,
+    MergeStake(Pubkey),
+    RefreshLiquidStake,
+    LiquidStakeLoaded(Result<LiquidStake, Error>),
+    LiquidAmountChanged(String),
+    DepositLiquid,
+    WithdrawLiquid,
+    LiquidStakeUpdated(Result<String, Error>),
+    RefreshStakeRewards,
+    StakeRewardsLoaded(Result<Vec<StakeReward>, Error>),
+    ExportStakeRewards,
+    StakeRewardsExported(Result<PathBuf, Error>),
+    RefreshValidators,
+    ValidatorsLoaded(Result<Vec<Validator>, Error>),
+    ValidatorSearchChanged(String),
+    ValidatorSortChanged(ValidatorSort),
+    PickValidator(Pubkey),
+    RefreshEpoch,
+    EpochLoaded(Result<EpochProgress, Error>),
+    CopyToClipboard(String),
     TxValuesHandler((String, String)),
+    SimulateTransaction,
+    SimulationCompleted(Result<SimulationPreview, Error>),
+    SimulationCancelled,
+    EtaEstimated(Result<ConfirmationEta, Error>),
     ExecuteTransaction,
-    TransactionExecuted(Result<String, Error>),
+    TransactionExecuted(Result<SentTransfer, Error>),
+    PriorityFeeChanged(String),
+    MemoChanged(String),
+    FeeEstimated(Result<u64, Error>),
+    NotifyRecipientToggled(bool),
+    ReceiptSent(Result<String, Error>),
+    ReceiptsLoaded(Result<Vec<String>, Error>),
+    TogglePalette,
+    PaletteQueryChanged(String),
+    PaletteSubmitted,
+    RunCommand(AppCommand),
+    ShowScreen(Screen),
+    RefreshLookupTables,
+    LookupTablesLoaded(Result<Vec<LookupTableInfo>, Error>),
+    LookupTableAddressesChanged(String),
+    CreateLookupTable,
+    ExtendLookupTable(Pubkey),
+    DeactivateLookupTable(Pubkey),
+    CloseLookupTable(Pubkey),
+    LookupTableUpdated(Result<String, Error>),
+    AllowListPasswordChanged(String),
+    AllowListRecipientsChanged(String),
+    AllowListMintsChanged(String),
+    SaveAllowList,
+    DisableAllowList,
+    AllowListSaved(Result<Option<AllowList>, Error>),
+    NonceAddressChanged(String),
+    RefreshNonceAccount,
+    NonceAccountLoaded(Result<NonceAccountInfo, Error>),
+    CreateNonceAccount,
+    NonceAccountCreated(Result<(String, Pubkey), Error>),
+    AdvanceNonceAccount,
+    NonceWithdrawAmountChanged(String),
+    WithdrawNonceAccount,
+    NonceAccountUpdated(Result<String, Error>),
+    RefreshNonceAccounts,
+    NonceAccountsLoaded(Result<Vec<NonceAccountInfo>, Error>),
+    SelectNonceAccount(Pubkey),
+    NonceAuthorityChanged(String),
+    AuthorizeNonceAccount,
+    UseNonceToggled(bool),
+    UseJitoToggled(bool),
+    NetworkPermissionToggled(Service, bool),
+    JitoTipChanged(String),
+    ExportUnsignedTransaction,
+    SignTransactionFile,
+    BroadcastTransactionFile,
+    OfflineFileWritten(Result<PathBuf, Error>),
+    TransactionFileSigned(Result<(PathBuf, Vec<Pubkey>), Error>),
+    OfflineTransactionBroadcasted(Result<String, Error>),
+    PortableModeToggled(bool),
+    PortableModeSaved(Result<bool, Error>),
+    PrivacyModeToggled(bool),
+    ToggleBalances,
+    OpenFeePayer,
+    FeePayerOpened(Result<PathBuf, Error>),
+    ClearFeePayer,
+    RefreshHistory,
+    HistoryLoaded(Result<Vec<HistoryEntry>, Error>),
+    AddBatchRow,
+    RemoveBatchRow(usize),
+    BatchRowChanged(usize, String, String),
+    SendBatch,
+    ConfirmBatch,
+    CancelBatch,
+    BatchPrepared(Result<Vec<BatchChunk>, Error>),
+    BatchChunkSent(Vec<usize>, Result<String, Error>),
+    ImportPayroll,
+    PayrollImported(Result<Vec<BatchRow>, Error>),
+    ExportPayrollResults,
+    PayrollResultsExported(Result<PathBuf, Error>),
+    FaucetTargetChanged(String),
+    AutoTopUpToggled(bool),
+    TopUpWallets,
+    WalletsToppedUp(Vec<TopUpResult>),
+    TestSetLabelChanged(String),
+    TestWalletCountChanged(String),
+    TestWalletAirdropToggled(bool),
+    GenerateTestSet,
+    TestSetGenerated(Result<TestSet, Error>),
+    DeleteTestSet(String),
+    TestSetDeleted(Result<String, Error>),
+    SendMax,
+    AmountUnitChanged(AmountUnit),
+    MaxAmountComputed(Result<u64, Error>),
+    KeepRentExemptToggled(bool),
+    RentWarningChecked(String, Result<Option<u64>, Error>),
+    ExportDecodedTransaction(String),
+    AtaOwnerChanged(String),
+    AtaMintChanged(String),
+    AtaOffCurveToggled(bool),
+    PreviewAta,
+    AtaPreviewed(Result<AtaPreview, Error>),
+    AtaPreviewCancelled,
+    CreateAta,
+    AtaCreated(Result<String, Error>),
+    UseKnownRecipient(Pubkey),
+    PoisoningAcknowledged,
+    PoisoningCancelled,
+    KnownRecipientsSaved(Result<(), Error>),
+    PinQuickAction,
+    UnpinQuickAction(usize),
+    RunQuickAction(usize),
+    QuickActionsSaved(Result<(), Error>),
+    LargeTransferSolChanged(String),
+    LargeTransferPercentChanged(String),
+    LargeTransferConfirmed,
+    LargeTransferCancelled,
+    SpendLimitChanged(String),
+    SpendPasswordChanged(String),
+    ApplySpendLimit,
+    SpendLimitApplied(Result<String, Error>),
+    UnlockSpending,
+    SpendingUnlocked(Result<(), Error>),
+    ReplayTransaction(String),
+    TransactionReplayed(Result<ReplayReport, Error>),
+    CloseReplay,
+    UnwrapWsolToggled(bool),
+    RefreshWsol,
+    WsolLoaded(Result<u64, Error>),
+    WrapAmountChanged(String),
+    WrapSol,
+    UnwrapSol,
+    WsolUpdated(Result<String, Error>),
+    MnemonicChanged(String),
+    MnemonicPassphraseChanged(String),
+    DeriveAccounts,
+    GenerateMnemonic,
+    MnemonicBackupWritten,
+    MnemonicBackupShown,
+    MnemonicBackupAnswerChanged(usize, String),
+    SecretHeld(bool),
+    CreateMnemonicWallet,
+    AccountsDerived(Result<Vec<DerivedAccount>, Error>),
+    ImportDerivedAccount(u32),
+    DefaultMemoChanged(String),
+    DefaultMemoToggled(bool),
+    ArchivalRpcUrlChanged(String),
+    FallbackRpcUrlsChanged(String),
+    RpcRateLimitChanged(String),
+    BroadcastRpcUrlChanged(String),
+    CommitmentChanged(String),
+    RpcUrlChanged(String),
+    RpcUrlSubmitted,
+    ExplorerChanged(String),
+    ConfigSaved(Result<(), Error>),
+    WindowResized(u32, u32),
+    WindowMoved(i32, i32),
+    SaveWindowState,
+    ThemeChanged(String),
+    UiScaleChanged(u32),
+    LanguageChanged(Language),
+    FiatCurrencyChanged(String),
+    RefreshPrices,
+    PricesLoaded(Result<Prices, Error>),
+    RefreshTokenList,
+    TokenListLoaded(Result<HashMap<String, String>, Error>),
+    TokenSymbolsLoaded(Result<Vec<(String, String)>, Error>),
+    AccentColorChanged(String),
+    SuccessColorChanged(String),
+    ErrorColorChanged(String),
+    CheckSystemTheme,
+    ClusterVerified(Result<Option<ClusterMismatch>, Error>),
+    TestRpcEndpoints,
+    RpcEndpointsTested(Option<String>),
+    RpcClustersMatched(()),
+    SnapshotFolderChanged(String),
+    DefaultKeypairPathChanged(String),
+    PastedKeypairChanged(String),
+    RefreshWallet,
+    WalletRefreshed(Result<WalletRefresh, Error>),
+    BalanceRefreshChanged(String),
+    MuteIncomingToggled(bool),
+    MinimizeToTrayToggled(bool),
+    PollTray,
+    CloseRequested,
+    IncomingNotified(Result<(), Error>),
+    VanityPrefixChanged(String),
+    VanitySuffixChanged(String),
+    VanityIgnoreCaseToggled(bool),
+    StartVanity,
+    CancelVanity,
+    VanityGround(Result<Arc<Keypair>, Error>),
+    ImportPastedKeypair,
+    PickDefaultKeypair,
+    DefaultKeypairPicked(Result<PathBuf, Error>),
+    BackupPassphraseChanged(String),
+    BackupConfirmationChanged(String),
+    ExportEncryptedKeypair,
+    EncryptedKeypairExported(Result<PathBuf, Error>),
+    KeystorePassphraseChanged(String),
+    KeystoreConfirmationChanged(String),
+    UnlockKeystore,
+    KeystoreUnlocked(Result<Arc<Keypair>, Error>),
+    MigrateToKeystore,
+    KeystoreMigrated(Result<Arc<Keypair>, Error>),
+    KeychainUnlockToggled(bool),
+    LockWallet,
+    WatchAddressChanged(String),
+    WatchAddress,
+    SelectWallet(usize),
+    ProfileSelected(String),
+    ProfileNameChanged(String),
+    SaveProfile,
+    DeleteProfile(String),
+    ProfilesSaved(Result<(), Error>),
+    RemoveWallet(usize),
+    LaunchPasswordChanged(String),
+    LaunchConfirmationChanged(String),
+    UnlockLaunch,
+    LaunchUnlocked(Result<Arc<Keypair>, Error>),
+    SetLaunchPassword,
+    RemoveLaunchPassword,
+    LaunchPasswordSaved(Result<Option<String>, Error>),
+    ExportSnapshot,
+    CheckSnapshot,
+    SnapshotExported(Result<PathBuf, Error>),
+    PreferencesSaved(Result<(), Error>),
+    DecodedTransactionExported(Result<PathBuf, Error>),
     // for ./gif_animation/loader animation
     NextFrame,
 }
 
-const RPC_URL: &str = "https://api.devnet.solana.com";
+impl Message {
+    // ways to bring in or switch to a wallet, or to leave the current screen;
+    // a locked app ignores them until its password is typed
+    fn loads_wallet(&self) -> bool {
+        matches!(
+            self,
+            Message::Open
+                | Message::CreateWallet
+                | Message::FileOpened(Ok(_))
+                | Message::OpenFeePayer
+                | Message::PickDefaultKeypair
+                | Message::RunCommand(_)
+                | Message::RunQuickAction(_)
+                | Message::TogglePalette
+                | Message::PaletteSubmitted
+                | Message::ShowScreen(_)
+                | Message::GenerateMnemonic
+                | Message::CreateMnemonicWallet
+                | Message::DeriveAccounts
+                | Message::ImportDerivedAccount(_)
+                | Message::ImportPastedKeypair
+                | Message::StartVanity
+                | Message::VanityGround(_)
+                | Message::GenerateTestSet
+                | Message::WatchAddress
+                | Message::SelectWallet(_)
+                | Message::ProfileSelected(_)
+        )
+    }
+
+    // actions that sign with the loaded keypair, refused for watch-only wallets
+    fn needs_signer(&self) -> bool {
+        matches!(
+            self,
+            Message::SimulateTransaction
+                | Message::ExecuteTransaction
+                | Message::LargeTransferConfirmed
+                | Message::ExportUnsignedTransaction
+                | Message::SignTransactionFile
+                | Message::SendBatch
+                | Message::ConfirmBatch
+                | Message::CreateLookupTable
+                | Message::ExtendLookupTable(_)
+                | Message::DeactivateLookupTable(_)
+                | Message::CloseLookupTable(_)
+                | Message::CreateNonceAccount
+                | Message::AdvanceNonceAccount
+                | Message::WithdrawNonceAccount
+                | Message::AuthorizeNonceAccount
+                | Message::CreateAta
+                | Message::ExportEncryptedKeypair
+                | Message::MigrateToKeystore
+                | Message::ExecuteSwap
+                | Message::WrapSol
+                | Message::UnwrapSol
+                | Message::ConfirmStake
+                | Message::DeactivateStake(_)
+                | Message::WithdrawStake(_)
+                | Message::SplitStake(_)
+                | Message::MergeStake(_)
+                | Message::DepositLiquid
+                | Message::WithdrawLiquid
+                | Message::AddDcaSchedule
+                | Message::CheckDca
+        )
+    }
+}
 
 impl Application for SolExecApp {
     type Message = Message;
@@ -64,19 +809,216 @@ impl Application for SolExecApp {
     type Flags = ();
 
     fn new(_flags: Self::Flags) -> (Self, Command<Message>) {
+        // signing-only mode for air-gapped machines
+        let offline = env::args().any(|arg| arg == "--offline");
+        let allow_list = load_allow_list();
+        let preferences = load_preferences();
+        let config = load_config();
+        set_palette(Palette::from_config(&config));
+        set_language(Language::from_code(&config.language));
+        let keystore = has_keystore();
+        let keychain_unlock = preferences.keychain_unlock;
+        let launch_password = load_launch_password();
+        let launch_locked = !keystore && launch_password.is_some();
+        let profiles = load_profiles();
+        // the last used profile picks the endpoint, fees and wallet again
+        let startup_profile = profiles
+            .iter()
+            .find(|profile| profile.name == preferences.active_profile)
+            .cloned();
+        let startup_file = startup_profile
+            .as_ref()
+            .and_then(|profile| profile.keypair_path.clone())
+            .unwrap_or_else(startup_file);
+        let (rpc_client, rpc_endpoints) = new_rpc_pool(
+            &startup_profile
+                .as_ref()
+                .map(|profile| profile.rpc_url.clone())
+                .unwrap_or_else(|| configured_rpc_url(&config.rpc_url)),
+            &preferences,
+            &config,
+        );
+        let expected = expected_cluster(&preferences.active_profile, rpc_endpoints.primary_url());
+        let verify = if offline {
+            Command::none()
+        } else {
+            Command::batch(vec![
+                Command::perform(
+                    verify_cluster(expected, Arc::clone(&rpc_client)),
+                    Message::ClusterVerified,
+                ),
+                Command::perform(
+                    match_clusters(Arc::clone(&rpc_endpoints), expected),
+                    Message::RpcClustersMatched,
+                ),
+            ])
+        };
+        let price = if offline
+            || !shows_fiat(&config.fiat_currency)
+            || !config.network_access.allows_prices(&config.fiat_currency)
+        {
+            Command::none()
+        } else {
+            Command::perform(
+                fetch_prices(
+                    config.fiat_currency.clone(),
+                    Vec::new(),
+                    config.network_access,
+                ),
+                Message::PricesLoaded,
+            )
+        };
         (
             Self {
-                path: Some(default_file()),
-                error: None,
+                // set once the startup keypair is actually read
+                path: None,
+                toasts: Toasts::default(),
+                connection: ConnectionStatus::default(),
+                network_performance: None,
+                chart_points: load_chart_points(),
+                chart_series: ChartSeries::Balance,
+                chart_range: ChartRange::Week,
+                chart_recorded: None,
+                alert_kind: AlertKind::PriceAbove,
+                alert_threshold: String::new(),
+                swap: SwapForm::new(config.swap_slippage_bps),
+                dca_interval: DcaInterval::Daily,
+                dca_history: load_dca_history(),
+                dca_running: Vec::new(),
+                stake: StakeForm::default(),
+                stake_accounts: Vec::new(),
+                stake_epoch: None,
+                stake_rewards: Vec::new(),
+                liquid: LiquidForm::default(),
+                stake_rewards_loading: false,
+                validators: ValidatorList::default(),
                 balance: None,
-                rpc_client: Arc::new(RpcClient::new(RPC_URL.to_string())),
-                signer: Keypair::new().into(),
+                rpc_client,
+                rpc_endpoints,
+                testing_endpoints: false,
+                cluster_mismatch: None,
+                archival_rpc_client: archival_rpc_client(
+                    &preferences.archival_rpc_url,
+                    &preferences,
+                    &config,
+                ),
+                signer: None,
                 receiver_value: (String::new(), String::new()),
+                amount_unit: AmountUnit::from_code(&config.amount_unit),
                 signature: String::new(),
                 is_loading: false,
                 current_frame: 0,
+                priority_fee: startup_profile
+                    .as_ref()
+                    .map(|profile| profile.priority_fee.clone())
+                    .unwrap_or_default(),
+                fee: None,
+                notify_recipient: false,
+                acknowledged_payments: Vec::new(),
+                simulation: None,
+                memo: preferences.initial_memo(),
+                eta: None,
+                sent_at: None,
+                palette_open: false,
+                palette_query: String::new(),
+                screen: if keystore {
+                    Screen::Unlock
+                } else if launch_locked {
+                    Screen::LaunchLock
+                } else if offline {
+                    Screen::Offline
+                } else if !startup_file.exists() {
+                    Screen::Welcome
+                } else {
+                    Screen::Send
+                },
+                lookup_tables: LookupTableList::default(),
+                allow_list_form: AllowListForm::new(&allow_list),
+                allow_list,
+                nonce: NonceForm::default(),
+                use_nonce: false,
+                use_jito: false,
+                jito_tip: format_sol(config.jito_tip_lamports),
+                offline,
+                offline_status: String::new(),
+                portable: is_portable(),
+                privacy_mode: false,
+                balances_revealed: false,
+                fee_payer: None,
+                fee_payer_path: None,
+                history: Vec::new(),
+                batch: BatchForm::default(),
+                faucet: Faucet::default(),
+                test_wallets: TestWallets::default(),
+                keep_rent_exempt: true,
+                rent_warning: None,
+                ata: AtaForm::default(),
+                known_recipients: load_known_recipients(),
+                poisoning_warning: None,
+                poisoning_acknowledged: false,
+                quick_actions: load_quick_actions(),
+                large_transfer_sol: String::from("1"),
+                large_transfer_percent: String::from("50"),
+                confirming_large_transfer: false,
+                large_transfer_confirmed: false,
+                spend_limit_input: preferences.spend_limit.clone(),
+                spend_password: String::new(),
+                session_spent: 0,
+                replay: None,
+                unwrap_wsol: false,
+                wsol: WsolForm::default(),
+                mnemonic: String::new(),
+                mnemonic_passphrase: String::new(),
+                derived_accounts: Vec::new(),
+                mnemonic_backup: None,
+                secret_revealed: false,
+                secrets_on_screen: false,
+                capture_excluded: false,
+                backup_passphrase: String::new(),
+                backup_confirmation: String::new(),
+                encrypted_backup: None,
+                keystore_passphrase: String::new(),
+                keystore_confirmation: String::new(),
+                keystore_migrated: false,
+                launch_password,
+                startup_file: startup_file.clone(),
+                watch_only: None,
+                watch_address: String::new(),
+                wallets: Vec::new(),
+                discovered_keypairs: discover_keypairs(),
+                profiles,
+                profile_name: String::new(),
+                pasted_keypair: String::new(),
+                token_balances: Vec::new(),
+                token_verification: TokenVerification::default(),
+                balance_updated: None,
+                tray: None,
+                tray_started: false,
+                vanity: VanityGrinder::default(),
+                launch_password_input: String::new(),
+                launch_password_confirmation: String::new(),
+                preferences,
+                restore_screen: Screen::restorable(&config.last_screen),
+                window_state_changed: false,
+                system_dark: system_prefers_dark(),
+                prices: PriceCache::default(),
+                config,
             },
-            Command::perform(async { Ok(default_file()) }, Message::FileOpened),
+            // with a keystore or a startup password the wallet waits for the
+            // user, otherwise there's nothing to load on a first run
+            Command::batch(vec![
+                verify,
+                price,
+                if keystore && keychain_unlock {
+                    Command::perform(unlock_with_keychain(), Message::KeystoreUnlocked)
+                } else if keystore || launch_locked {
+                    Command::none()
+                } else if startup_file.exists() {
+                    Command::perform(async { Ok(startup_file) }, Message::FileOpened)
+                } else {
+                    Command::none()
+                },
+            ]),
         )
     }
 
@@ -85,116 +1027,137 @@ impl Application for SolExecApp {
     }
 
     fn update(&mut self, message: Message) -> Command<Message> {
-        match message {
-            Message::Open => Command::perform(pick_file(), Message::FileOpened),
-            Message::FileOpened(Ok(path)) => {
-                self.path = Some(path.to_path_buf());
-                self.signer = load_keypair_from_file(path.to_path_buf()).into();
-                Command::perform(
-                    display_balance(path, self.rpc_client.clone()),
-                    Message::BalanceLoaded,
-                )
-            }
-            Message::FileOpened(Err(error)) => {
-                self.error = Some(error);
-                Command::perform(async { time::sleep(Duration::from_secs(5)).await }, |_| {
-                    Message::ErrorCleared
-                })
-            }
-            Message::BalanceLoaded(Ok(balance)) => {
-                self.balance = Some(balance);
-                Command::none()
-            }
-            Message::BalanceLoaded(Err(error)) => {
-                self.error = Some(error);
-                Command::none()
-            }
-            Message::ExecuteTransaction => {
-                self.signature = String::new();
-                self.is_loading = true;
-                let values = SolExecApp {
-                    signer: Arc::clone(&self.signer),
-                    rpc_client: Arc::clone(&self.rpc_client),
-                    path: self.path.clone(),
-                    error: self.error.clone(),
-                    balance: self.balance.clone(),
-                    receiver_value: self.receiver_value.clone(),
-                    signature: self.signature.clone(),
-                    is_loading: self.is_loading,
-                    current_frame: self.current_frame,
-                };
-                Command::perform(transfer_sol(values), Message::TransactionExecuted)
-            }
-            Message::TransactionExecuted(Ok(signature)) => {
-                self.signature = signature;
-                let path = self
-                    .path
-                    .clone()
-                    .unwrap_or_else(|| default_file().to_path_buf());
-                self.is_loading = false;
-                Command::perform(
-                    display_balance(path, self.rpc_client.clone()),
-                    Message::BalanceLoaded,
-                )
-            }
-            Message::TransactionExecuted(Err(error)) => {
-                self.error = Some(error);
-                self.is_loading = false;
-                Command::perform(async { time::sleep(Duration::from_secs(5)).await }, |_| {
-                    Message::ErrorCleared
-                })
-            }
-            Message::TxValuesHandler((address, amount)) => {
-                self.receiver_value = (address, amount);
-                Command::none()
-            }
-            Message::ErrorCleared => {
-                self.error = None;
-                Command::none()
-            }
-            Message::NextFrame => {
-                self.current_frame = (self.current_frame + 1) % 21;
-                Command::none()
-            }
-        }
+        let command = self.handle_message(message);
+        self.sync_capture_exclusion();
+        command
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        iced::time::every(Duration::from_millis(75)).map(|_| Message::NextFrame)
+        Subscription::batch(vec![
+            iced::time::every(Duration::from_millis(75)).map(|_| Message::NextFrame),
+            if self.faucet.auto_top_up && has_faucet(self.rpc_endpoints.primary_url()) {
+                iced::time::every(AUTO_TOP_UP_INTERVAL).map(|_| Message::TopUpWallets)
+            } else {
+                Subscription::none()
+            },
+            match self.loaded_pubkey() {
+                Some(pubkey) if !self.offline => {
+                    balance_updates(pubkey, Arc::clone(&self.rpc_client))
+                }
+                _ => Subscription::none(),
+            },
+            if self.offline {
+                Subscription::none()
+            } else {
+                Subscription::batch(vec![
+                    slot_updates(Arc::clone(&self.rpc_client)),
+                    iced::time::every(HEALTH_CHECK_INTERVAL).map(|_| Message::CheckRpcHealth),
+                    iced::time::every(EPOCH_REFRESH_INTERVAL).map(|_| Message::RefreshEpoch),
+                ])
+            },
+            // polling on top of the websocket, for endpoints that drop it
+            match refresh_interval(&self.preferences.balance_refresh_secs) {
+                Some(interval) if self.loaded_pubkey().is_some() && !self.offline => {
+                    iced::time::every(interval).map(|_| Message::RefreshWallet)
+                }
+                _ => Subscription::none(),
+            },
+            if self.preferences.snapshot_folder.trim().is_empty() || self.offline {
+                Subscription::none()
+            } else {
+                iced::time::every(SNAPSHOT_CHECK_INTERVAL).map(|_| Message::CheckSnapshot)
+            },
+            if shows_fiat(&self.config.fiat_currency)
+                && self
+                    .config
+                    .network_access
+                    .allows_prices(&self.config.fiat_currency)
+                && !self.offline
+            {
+                iced::time::every(PRICE_REFRESH_INTERVAL).map(|_| Message::RefreshPrices)
+            } else {
+                Subscription::none()
+            },
+            // ticks without a keypair would only be refused
+            if self.config.dca_schedules.is_empty() || self.offline || !self.has_signer() {
+                Subscription::none()
+            } else {
+                iced::time::every(DCA_CHECK_INTERVAL).map(|_| Message::CheckDca)
+            },
+            // balance alerts need polling even with auto refresh turned off
+            if has_balance_alerts(&self.config.alerts)
+                && refresh_interval(&self.preferences.balance_refresh_secs).is_none()
+                && self.loaded_pubkey().is_some()
+                && !self.offline
+            {
+                iced::time::every(ALERT_CHECK_INTERVAL).map(|_| Message::RefreshWallet)
+            } else {
+                Subscription::none()
+            },
+            if self.config.follows_system_theme() {
+                iced::time::every(SYSTEM_THEME_POLL_INTERVAL).map(|_| Message::CheckSystemTheme)
+            } else {
+                Subscription::none()
+            },
+            if self.window_state_changed {
+                iced::time::every(WINDOW_STATE_SAVE_DELAY).map(|_| Message::SaveWindowState)
+            } else {
+                Subscription::none()
+            },
+            if self.preferences.minimize_to_tray {
+                iced::time::every(TRAY_POLL_INTERVAL).map(|_| Message::PollTray)
+            } else {
+                Subscription::none()
+            },
+            // ctrl/cmd + k opens the command palette
+            subscription::events_with(|event, _status| match event {
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code: keyboard::KeyCode::K,
+                    modifiers,
+                }) if modifiers.command() => Some(Message::TogglePalette),
+                // ctrl/cmd + h reveals or hides balances in privacy mode
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code: keyboard::KeyCode::H,
+                    modifiers,
+                }) if modifiers.command() => Some(Message::ToggleBalances),
+                Event::Window(window::Event::CloseRequested) => Some(Message::CloseRequested),
+                Event::Window(window::Event::Resized { width, height }) => {
+                    Some(Message::WindowResized(width, height))
+                }
+                Event::Window(window::Event::Moved { x, y }) => Some(Message::WindowMoved(x, y)),
+                // a held seed phrase is hidden again when the window loses focus
+                Event::Window(window::Event::Unfocused) => Some(Message::SecretHeld(false)),
+                _ => None,
+            }),
+        ])
     }
 
     fn view(&self) -> Element<'_, Message> {
-        let image_path = match self.current_frame {
-            0 => "./gif_animation/loader1.png",
-            1 => "./gif_animation/loader2.png",
-            2 => "./gif_animation/loader3.png",
-            3 => "./gif_animation/loader4.png",
-            4 => "./gif_animation/loader5.png",
-            5 => "./gif_animation/loader6.png",
-            6 => "./gif_animation/loader7.png",
-            7 => "./gif_animation/loader7.png",
-            8 => "./gif_animation/loader7.png",
-            9 => "./gif_animation/loader7.png",
-            10 => "./gif_animation/loader11.png",
-            11 => "./gif_animation/loader12.png",
-            12 => "./gif_animation/loader13.png",
-            13 => "./gif_animation/loader14.png",
-            14 => "./gif_animation/loader15.png",
-            15 => "./gif_animation/loader16.png",
-            16 => "./gif_animation/loader7.png",
-            17 => "./gif_animation/loader7.png",
-            18 => "./gif_animation/loader7.png",
-            19 => "./gif_animation/loader7.png",
-            20 => "./gif_animation/loader7.png",
-            _ => "./gif_animation/loader1.png",
-        };
+        // a locked app shows its prompt and nothing that would load a wallet
+        // around it
+        if self.is_locked() {
+            let prompt = if self.screen == Screen::LaunchLock {
+                display_launch_lock(&self.launch_password_input)
+            } else {
+                display_unlock(&self.keystore_passphrase)
+            };
+            return container(column![prompt, display_toasts(&self.toasts)].spacing(10))
+                .padding(25)
+                .into();
+        }
 
         let balance_text = match self.balance {
+            Some(_) if self.balances_hidden() => column![
+                text(tr("SOL Balance: ")).style(accent_color()).size(14),
+                button(text(tr("Hidden, click or Ctrl+H to reveal")).size(12))
+                    .on_press(Message::ToggleBalances)
+            ],
             Some(balance) => column![
-                text("SOL Balance: ").style(color!(0x30cbf2)).size(14),
-                text(format!(" {:.3}", balance as f32 / LAMPORTS_PER_SOL as f32)).size(14)
+                text(tr("SOL Balance: ")).style(accent_color()).size(14),
+                text(format!(" {:.3}", balance as f32 / LAMPORTS_PER_SOL as f32)).size(14),
+                text(self.fiat_value(balance)).size(12)
             ],
-            None => column![text("Loading balance...").size(14)],
+            None => column![text(tr("Loading balance...")).size(14)],
         };
 
         let file_path = self
@@ -203,65 +1166,403 @@ impl Application for SolExecApp {
             .map(Path::to_path_buf)
             .unwrap_or_else(|| PathBuf::from(""));
 
-        let file_path_indicator = text("Path of your keypair:")
+        let file_path_indicator = text(tr("Path of your keypair:"))
             .size(14)
-            .style(color!(0x30cbf2));
-        let file_path_name = text(file_path.to_str().unwrap_or(DEFAULT_LOCATION)).size(14);
+            .style(accent_color());
+        let file_path_name = match self.path {
+            Some(_) => text(file_path.to_str().unwrap_or(DEFAULT_LOCATION)).size(14),
+            None => text(tr("No keypair loaded")).size(14),
+        };
 
         let display_path = column![file_path_indicator, file_path_name];
 
-        let display_pkey = display_pubkey(file_path.to_path_buf());
+        let display_pkey = display_pubkey(self.loaded_pubkey());
 
         // display the pubkey of the keypair & SOL balance
 
-        let wallet_info = row![display_pkey, balance_text].spacing(100);
-
-        let load_keypair = button("Load keypair").on_press(Message::Open);
+        let wallet_info = row![
+            display_pkey,
+            balance_text,
+            display_token_balances(
+                &self.token_balances,
+                self.balance_updated,
+                self.balances_hidden()
+            )
+        ]
+        .spacing(100);
 
-        // Solana sender
+        let mut load_keypair = row![
+            button(tr("Load keypair")).on_press(Message::RunCommand(AppCommand::LoadWallet)),
+            button(tr("Create new wallet")).on_press(Message::RunCommand(AppCommand::CreateWallet)),
+            button(tr("Create with seed phrase")).on_press(Message::GenerateMnemonic),
+            button(tr("Paste keypair")).on_press(Message::ShowScreen(Screen::PasteKeypair)),
+            button(tr("Commands (Ctrl+K)")).on_press(Message::TogglePalette),
+            pick_list(
+                self.profiles
+                    .iter()
+                    .map(|profile| profile.name.clone())
+                    .collect::<Vec<String>>(),
+                (!self.preferences.active_profile.is_empty())
+                    .then(|| self.preferences.active_profile.clone()),
+                Message::ProfileSelected,
+            )
+            .placeholder(tr("Profile")),
+        ]
+        .spacing(10);
+        if self.loaded_pubkey().is_some() && self.can_lock() {
+            load_keypair = load_keypair.push(button(tr("Lock")).on_press(Message::LockWallet));
+        }
 
-        let some_h2 = Column::new().push(Space::with_height(20)).push(
-            text("Send SOL to any wallet!!! LFG")
-                .style(color!(0x30cbf2))
-                .size(14),
-        );
+        let watch = row![
+            text_input(tr("Watch an address (read-only)"), &self.watch_address)
+                .on_input(Message::WatchAddressChanged)
+                .on_submit(Message::WatchAddress),
+            button(tr("Watch")).on_press(Message::WatchAddress),
+        ]
+        .spacing(10);
 
-        let address_input = text_input("Put receiver address", &self.receiver_value.0)
-            .on_input(|value| Message::TxValuesHandler((value, self.receiver_value.1.to_string())));
+        let watch_notice = if self.watch_only.is_some() {
+            text(tr(
+                "Watch-only: balances and history are shown, sending is disabled",
+            ))
+            .size(14)
+            .style(accent_color())
+        } else {
+            text("").size(1)
+        };
 
-        let amount_input = text_input("Lamports to send", &self.receiver_value.1.to_string())
-            .on_input(|value| Message::TxValuesHandler((self.receiver_value.0.clone(), value)));
+        let quick_actions = display_quick_actions(&self.quick_actions);
 
-        let send_lamports_btn: Element<'_, Message> = if self.is_loading {
-            Image::new(image_path).width(64).height(40).into()
+        let palette: Element<'_, Message> = if self.palette_open {
+            display_palette(&self.palette_query)
         } else {
-            button("Send lamports")
-                .on_press(Message::ExecuteTransaction)
-                .into()
+            Space::with_height(0).into()
         };
 
-        let signature = text(&self.signature).size(14);
+        let tabs = row![
+            button(tr("Send SOL")).on_press(Message::ShowScreen(Screen::Send)),
+            button(tr("Portfolio")).on_press(Message::ShowScreen(Screen::Portfolio)),
+            button(tr("Batch send")).on_press(Message::ShowScreen(Screen::Batch)),
+            button(tr("Swap")).on_press(Message::ShowScreen(Screen::Swap)),
+            button(tr("Staking")).on_press(Message::ShowScreen(Screen::Staking)),
+            button(tr("Validators")).on_press(Message::ShowScreen(Screen::Validators)),
+            button(tr("Import seed phrase")).on_press(Message::ShowScreen(Screen::ImportMnemonic)),
+            button(tr("History")).on_press(Message::ShowScreen(Screen::History)),
+            button(tr("Faucet")).on_press(Message::ShowScreen(Screen::Faucet)),
+            button(tr("Chart")).on_press(Message::ShowScreen(Screen::Chart)),
+            button(tr("Alerts")).on_press(Message::ShowScreen(Screen::Alerts)),
+            button(tr("Network")).on_press(Message::ShowScreen(Screen::Network)),
+            button(tr("Network access")).on_press(Message::ShowScreen(Screen::Permissions)),
+            button(tr("Test wallets")).on_press(Message::ShowScreen(Screen::TestWallets)),
+            button(tr("Vanity address")).on_press(Message::ShowScreen(Screen::Vanity)),
+            button(tr("Token accounts")).on_press(Message::ShowScreen(Screen::TokenAccounts)),
+            button(tr("Lookup tables")).on_press(Message::ShowScreen(Screen::LookupTables)),
+            button(tr("Allow-list")).on_press(Message::ShowScreen(Screen::AllowList)),
+            button(tr("Nonce account")).on_press(Message::ShowScreen(Screen::Nonce)),
+            button(tr("Offline signing")).on_press(Message::ShowScreen(Screen::Offline)),
+            button(tr("Settings")).on_press(Message::ShowScreen(Screen::Settings)),
+        ]
+        .spacing(10);
 
-        // if there's some error, display it
-        let info_message = if let Some(ref error) = &self.error {
-            text(format!("Error: {:?}", error))
-                .size(14)
-                .style(color!(0xFF0000))
-        } else {
-            text("").size(1)
+        let info_message = match &self.cluster_mismatch {
+            Some(mismatch) => column![
+                display_cluster_warning(mismatch),
+                display_toasts(&self.toasts)
+            ]
+            .spacing(10)
+            .into(),
+            None => display_toasts(&self.toasts),
+        };
+
+        let content = match self.screen {
+            Screen::Send | Screen::Batch | Screen::Nonce | Screen::Offline | Screen::Swap
+                if self.watch_only.is_some() =>
+            {
+                text(tr("Load a keypair to send from this wallet"))
+                    .size(14)
+                    .into()
+            }
+            Screen::Send => self.send_view(),
+            Screen::LookupTables => display_lookup_tables(
+                &self.lookup_tables.tables,
+                &self.lookup_tables.new_addresses,
+            ),
+            Screen::Nonce => display_nonce_account(
+                &self.nonce.address,
+                &self.nonce.account,
+                &self.nonce.withdraw_amount,
+                &self.nonce.new_authority,
+                &self.nonce.accounts,
+            ),
+            Screen::Offline => display_offline(&self.offline_status, self.offline),
+            Screen::History => match &self.replay {
+                Some(report) => display_replay(report),
+                None => display_history(&self.history),
+            },
+            // drawn on their own at the top of the view
+            Screen::Unlock | Screen::LaunchLock => Space::with_height(0).into(),
+            Screen::Welcome => display_welcome(&self.startup_file),
+            // a key that only lives in memory is offered the keystore
+            Screen::PasteKeypair => {
+                let pasted = self
+                    .loaded_pubkey()
+                    .filter(|_| self.path.is_none() && self.watch_only.is_none());
+                let mut view =
+                    column![display_paste_keypair(&self.pasted_keypair, pasted)].spacing(20);
+                if pasted.is_some() {
+                    view = view.push(display_keystore_migration(
+                        &self.keystore_passphrase,
+                        &self.keystore_confirmation,
+                        self.keystore_migrated,
+                        self.preferences.keychain_unlock,
+                    ));
+                }
+                view.into()
+            }
+            // the match stays in memory until it's moved to the keystore
+            Screen::Vanity => {
+                let found = self
+                    .vanity
+                    .found
+                    .filter(|pubkey| self.loaded_pubkey() == Some(*pubkey) && self.path.is_none());
+                let mut view = column![display_vanity(
+                    &self.vanity.pattern,
+                    &self.vanity.search,
+                    found
+                )]
+                .spacing(20);
+                if found.is_some() {
+                    view = view.push(display_keystore_migration(
+                        &self.keystore_passphrase,
+                        &self.keystore_confirmation,
+                        self.keystore_migrated,
+                        self.preferences.keychain_unlock,
+                    ));
+                    // the keystore holds a single wallet, an encrypted backup
+                    // keeps the match when it's taken
+                    if has_keystore() {
+                        view = view.push(display_encrypted_export(
+                            &self.backup_passphrase,
+                            &self.backup_confirmation,
+                            &self.encrypted_backup,
+                        ));
+                    }
+                }
+                view.into()
+            }
+            Screen::NewMnemonic => match &self.mnemonic_backup {
+                Some(backup) => {
+                    display_mnemonic_backup(backup, self.secret_revealed, self.capture_excluded)
+                }
+                None => Space::with_height(0).into(),
+            },
+            Screen::ImportMnemonic => display_mnemonic_import(
+                &self.mnemonic,
+                &self.mnemonic_passphrase,
+                &self.derived_accounts,
+            ),
+            Screen::TokenAccounts => column![
+                display_token_accounts(
+                    &self.ata.owner,
+                    &self.ata.mint,
+                    self.ata.allow_off_curve,
+                    &self.ata.preview,
+                    &self.ata.signature,
+                ),
+                display_wsol(
+                    self.wsol.lamports,
+                    &self.wsol.amount,
+                    self.wsol.busy,
+                    &self.wsol.signature,
+                ),
+            ]
+            .spacing(20)
+            .into(),
+            Screen::TestWallets => display_test_wallets(
+                &self.test_wallets.sets,
+                &self.test_wallets.label,
+                &self.test_wallets.count,
+                self.test_wallets.airdrop,
+                self.path.as_ref(),
+            ),
+            Screen::Network => display_network_performance(&self.network_performance),
+            Screen::Swap => column![
+                display_swap(
+                    &self.swap,
+                    cluster_name(self.rpc_endpoints.primary_url()) == "mainnet-beta",
+                    &self.swap_unverified(),
+                ),
+                display_dca(
+                    &self.config.dca_schedules,
+                    &self.dca_history,
+                    self.dca_interval
+                ),
+            ]
+            .spacing(20)
+            .into(),
+            Screen::Staking => column![
+                display_stake_form(&self.stake),
+                display_stake_accounts(&self.stake_accounts, self.stake_epoch, &self.stake),
+                display_rewards(&self.stake_rewards, self.stake_rewards_loading),
+                display_liquid_stake(
+                    &self.liquid,
+                    cluster_name(self.rpc_endpoints.primary_url()) == "mainnet-beta",
+                ),
+            ]
+            .spacing(20)
+            .into(),
+            Screen::Permissions => {
+                display_permissions(&self.config.network_access, &self.config.jito_block_engine)
+            }
+            Screen::Validators => display_validators(
+                &self.validators.validators,
+                &self.validators.search,
+                self.validators.sort,
+                self.validators.loading,
+            ),
+            Screen::Alerts => display_alerts(
+                &self.config.alerts,
+                self.alert_kind,
+                &self.alert_threshold,
+                &self.config.fiat_currency,
+            ),
+            Screen::Chart => display_chart(
+                &self.chart_points,
+                self.chart_series,
+                self.chart_range,
+                self.loaded_pubkey(),
+                &self.config.fiat_currency,
+            ),
+            Screen::Portfolio => display_portfolio(
+                &holdings(
+                    self.balance.unwrap_or(0),
+                    &self.token_balances,
+                    &self.prices,
+                    &self.config.fiat_currency,
+                ),
+                &self.token_verification,
+                &self.config.fiat_currency,
+                self.balances_hidden(),
+            ),
+            Screen::Faucet => display_faucet(
+                self.rpc_endpoints.primary_url(),
+                &self.faucet.target,
+                self.faucet.auto_top_up,
+                self.faucet.running,
+                &self.faucet.results,
+            ),
+            Screen::Batch => display_batch(
+                &self.batch.rows,
+                self.batch.is_sending(),
+                &self.batch.review,
+            ),
+            Screen::Settings => {
+                let settings = column![
+                    display_settings(
+                        self.portable,
+                        self.privacy_mode,
+                        &self.large_transfer_sol,
+                        &self.large_transfer_percent,
+                        &self.preferences,
+                    ),
+                    display_spend_limit(
+                        &self.preferences.spend_limit,
+                        &self.spend_limit_input,
+                        &self.spend_password,
+                        self.can_lock(),
+                    ),
+                    display_rpc_endpoints(
+                        &self.rpc_endpoints,
+                        &self.preferences,
+                        &self.config.commitment,
+                        self.testing_endpoints,
+                    ),
+                    display_config(&self.config),
+                ]
+                .spacing(10);
+                // moving or backing up a key needs one to be loaded
+                let settings = if self.has_signer() {
+                    settings.push(display_keystore_migration(
+                        &self.keystore_passphrase,
+                        &self.keystore_confirmation,
+                        self.keystore_migrated,
+                        self.preferences.keychain_unlock,
+                    ))
+                } else {
+                    settings
+                };
+                let settings = settings
+                    .push(display_profiles(
+                        &self.profiles,
+                        &self.preferences.active_profile,
+                        &self.profile_name,
+                    ))
+                    .push(display_launch_password_settings(
+                        &self.launch_password,
+                        &self.launch_password_input,
+                        &self.launch_password_confirmation,
+                    ));
+                if self.has_signer() {
+                    settings
+                        .push(display_encrypted_export(
+                            &self.backup_passphrase,
+                            &self.backup_confirmation,
+                            &self.encrypted_backup,
+                        ))
+                        .into()
+                } else {
+                    settings.into()
+                }
+            }
+            Screen::AllowList => display_allow_list(
+                &self.allow_list,
+                &self.allow_list_form.password,
+                &self.allow_list_form.recipients,
+                &self.allow_list_form.mints,
+            ),
         };
 
+        let sidebar = display_wallets(
+            &self.wallets,
+            &self.discovered_keypairs,
+            self.loaded_pubkey(),
+            self.balances_hidden(),
+        );
+
+        // kept outside the scrollable so it's always visible
+        let status_bar = display_status_bar(
+            &self.rpc_client.url(),
+            &self.connection,
+            self.rpc_endpoints.queued(),
+            shows_fiat(&self.config.fiat_currency)
+                .then(|| self.prices.source(&self.config.fiat_currency))
+                .flatten(),
+            self.offline,
+        );
+
         container(
             column![
-                wallet_info,
-                display_path,
-                load_keypair,
-                info_message,
-                some_h2,
-                address_input,
-                amount_input,
-                send_lamports_btn,
-                signature
+                scrollable(
+                    row![
+                        sidebar,
+                        column![
+                            wallet_info,
+                            watch_notice,
+                            display_path,
+                            load_keypair,
+                            watch,
+                            quick_actions,
+                            palette,
+                            info_message,
+                            tabs,
+                            content
+                        ]
+                        .spacing(10),
+                    ]
+                    .spacing(20),
+                )
+                .height(Length::Fill),
+                status_bar
             ]
             .spacing(10),
         )
@@ -269,7 +1570,3124 @@ impl Application for SolExecApp {
         .into()
     }
 
+    fn scale_factor(&self) -> f64 {
+        self.config.scale_factor()
+    }
+
     fn theme(&self) -> Theme {
-        Theme::Dark
+        Palette::from_config(&self.config).theme(self.config.is_dark(self.system_dark))
+    }
+}
+
+impl SolExecApp {
+    fn handle_message(&mut self, message: Message) -> Command<Message> {
+        if self.watch_only.is_some() && message.needs_signer() {
+            return self.show_error(Error::WatchOnlyWallet);
+        }
+        // only the unlock itself brings a wallet in while the prompt is up
+        if self.is_locked() && (message.loads_wallet() || message.needs_signer()) {
+            return Command::none();
+        }
+        match message {
+            Message::Open => Command::perform(pick_file(), Message::FileOpened),
+            Message::CreateWallet => Command::perform(create_keypair_file(), Message::FileOpened),
+            Message::FileOpened(Ok(path)) => {
+                let keypair = match load_keypair_from_file(path.to_path_buf()) {
+                    Ok(keypair) => keypair,
+                    Err(error) => return self.update(Message::FileOpened(Err(error))),
+                };
+                self.wallet_loaded(keypair.into(), path)
+            }
+            Message::FileOpened(Err(error)) => self.show_error(error),
+            Message::BalanceLoaded(Ok(balance)) => {
+                // only a balance already fetched this session counts, not
+                // the cached one a wallet switch starts from
+                let received = match self.balance {
+                    Some(previous) if self.balance_updated.is_some() => {
+                        balance.saturating_sub(previous)
+                    }
+                    _ => 0,
+                };
+                self.balance = Some(balance);
+                self.balance_updated = Some(Instant::now());
+                self.update_tray();
+                let active = self.loaded_pubkey();
+                if let Some(wallet) = self.wallets.iter_mut().find(|w| Some(w.pubkey) == active) {
+                    wallet.balance = Some(balance);
+                }
+                let estimate = self.estimate_send_fee();
+                let record = self.record_chart_point();
+                let alerts = self.check_alerts();
+                if received == 0 {
+                    return Command::batch(vec![estimate, record, alerts]);
+                }
+                Command::batch(vec![
+                    estimate,
+                    record,
+                    alerts,
+                    self.notify(vec![Incoming::sol(received)]),
+                ])
+            }
+            Message::BalanceLoaded(Err(error)) => self.show_error(error),
+            Message::SimulateTransaction => {
+                // a lookalike of an address paid before needs an explicit confirmation
+                if !self.poisoning_acknowledged {
+                    if let Some(lookalike) = Pubkey::from_str(&self.receiver_value.0)
+                        .ok()
+                        .and_then(|to| find_lookalike(&to, &self.known_recipients))
+                    {
+                        self.poisoning_warning = Some(lookalike);
+                        return Command::none();
+                    }
+                }
+                if let Err(error) = self
+                    .check_cluster()
+                    .and_then(|_| self.amount_unit.parse(&self.receiver_value.1))
+                    .and_then(|amount| self.check_spend_limit(amount))
+                    .and_then(|_| self.check_jito_tip())
+                {
+                    return self.update(Message::SimulationCompleted(Err(error)));
+                }
+                let values = match self.transfer_values() {
+                    Ok(values) => values,
+                    Err(error) => return self.update(Message::SimulationCompleted(Err(error))),
+                };
+                self.signature = String::new();
+                self.simulation = None;
+                self.confirming_large_transfer = false;
+                self.is_loading = true;
+                Command::batch(vec![
+                    Command::perform(simulate_transfer(values), Message::SimulationCompleted),
+                    Command::perform(
+                        estimate_confirmation_eta(self.rpc_client.clone()),
+                        Message::EtaEstimated,
+                    ),
+                ])
+            }
+            Message::SimulationCompleted(Ok(preview)) => {
+                self.simulation = Some(preview);
+                self.is_loading = false;
+                Command::none()
+            }
+            Message::SimulationCompleted(Err(error)) => {
+                self.is_loading = false;
+                self.show_error(error)
+            }
+            Message::SimulationCancelled => {
+                self.simulation = None;
+                Command::none()
+            }
+            Message::EtaEstimated(Ok(eta)) => {
+                self.eta = Some(eta);
+                Command::none()
+            }
+            Message::EtaEstimated(Err(_)) => {
+                self.eta = None;
+                Command::none()
+            }
+            Message::ExecuteTransaction => {
+                // the limit may have been lowered or used up since the preview
+                if let Err(error) = self
+                    .check_cluster()
+                    .and_then(|_| self.amount_unit.parse(&self.receiver_value.1))
+                    .and_then(|amount| self.check_spend_limit(amount))
+                    .and_then(|_| self.check_jito_tip())
+                {
+                    return self.show_error(error);
+                }
+                // big transfers need a second, explicit confirmation
+                if self.is_large_transfer() && !self.large_transfer_confirmed {
+                    self.confirming_large_transfer = true;
+                    return Command::none();
+                }
+                let values = match self.transfer_values() {
+                    Ok(values) => values,
+                    Err(error) => return self.show_error(error),
+                };
+                self.large_transfer_confirmed = false;
+                self.confirming_large_transfer = false;
+                self.simulation = None;
+                self.signature = String::new();
+                self.is_loading = true;
+                self.sent_at = Some(Instant::now());
+                Command::perform(transfer_sol(values), Message::TransactionExecuted)
+            }
+            Message::LargeTransferConfirmed => {
+                self.large_transfer_confirmed = true;
+                self.update(Message::ExecuteTransaction)
+            }
+            Message::LargeTransferCancelled => {
+                self.confirming_large_transfer = false;
+                self.simulation = None;
+                Command::none()
+            }
+            Message::SpendLimitChanged(spend_limit) => {
+                self.spend_limit_input = spend_limit;
+                Command::none()
+            }
+            Message::SpendPasswordChanged(password) => {
+                replace_secret(&mut self.spend_password, password);
+                Command::none()
+            }
+            Message::ApplySpendLimit => {
+                let limit = self.spend_limit_input.trim().to_string();
+                if !limit.is_empty() {
+                    if let Err(error) = parse_amount(&limit) {
+                        return self.show_error(error);
+                    }
+                }
+                // setting a first limit only makes sending harder
+                if self.preferences.spend_limit.trim().is_empty() {
+                    return self.update(Message::SpendLimitApplied(Ok(limit)));
+                }
+                let verify = verify_wallet_password(
+                    self.launch_password.clone(),
+                    Zeroizing::new(std::mem::take(&mut self.spend_password)),
+                );
+                Command::perform(
+                    async move { verify.await.map(|()| limit) },
+                    Message::SpendLimitApplied,
+                )
+            }
+            Message::SpendLimitApplied(Ok(limit)) => {
+                self.spend_password.zeroize();
+                self.preferences.spend_limit = limit;
+                Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                )
+            }
+            // the wallet's password starts a new spending window
+            Message::UnlockSpending => Command::perform(
+                verify_wallet_password(
+                    self.launch_password.clone(),
+                    Zeroizing::new(std::mem::take(&mut self.spend_password)),
+                ),
+                Message::SpendingUnlocked,
+            ),
+            Message::SpendingUnlocked(Ok(())) => {
+                self.session_spent = 0;
+                Command::none()
+            }
+            Message::LargeTransferSolChanged(amount) => {
+                self.large_transfer_sol = amount;
+                Command::none()
+            }
+            Message::LargeTransferPercentChanged(percent) => {
+                self.large_transfer_percent = percent;
+                Command::none()
+            }
+            Message::TransactionExecuted(Ok(sent)) => {
+                self.signature = sent.signature;
+                self.sent_at = None;
+                self.is_loading = false;
+                // the balance follows through the account subscription
+                let mut commands = vec![self.toast(
+                    ToastLevel::Success,
+                    String::from(tr("Transaction confirmed")),
+                    Some(ToastAction {
+                        label: String::from(tr("Copy signature")),
+                        message: Message::CopyToClipboard(self.signature.clone()),
+                    }),
+                )];
+                self.session_spent = self.session_spent.saturating_add(sent.lamports);
+                remember_recipients(&mut self.known_recipients, &[sent.recipient]);
+                commands.push(Command::perform(
+                    save_known_recipients(self.known_recipients.clone()),
+                    Message::KnownRecipientsSaved,
+                ));
+                // the ping has to come from the wallet that paid
+                if let Some(signer) = self
+                    .signer
+                    .clone()
+                    .filter(|signer| self.notify_recipient && signer.pubkey() == sent.sender)
+                {
+                    commands.push(Command::perform(
+                        send_receipt_ping(
+                            signer,
+                            Arc::clone(&self.rpc_client),
+                            sent.recipient,
+                            self.signature.clone(),
+                        ),
+                        Message::ReceiptSent,
+                    ));
+                }
+                Command::batch(commands)
+            }
+            Message::TransactionExecuted(Err(error)) => {
+                self.sent_at = None;
+                self.is_loading = false;
+                self.show_error(error)
+            }
+            Message::TxValuesHandler((address, amount)) => {
+                self.receiver_value = (address, amount);
+                self.simulation = None;
+                self.rent_warning = None;
+                self.poisoning_warning = None;
+                self.poisoning_acknowledged = false;
+                // pre-flight lookup, sending less than the rent-exempt minimum to
+                // an account that doesn't exist yet fails
+                match (
+                    Pubkey::from_str(&self.receiver_value.0),
+                    self.amount_unit.parse(&self.receiver_value.1),
+                ) {
+                    (Ok(to), Ok(lamports)) if !self.offline => {
+                        let address = self.receiver_value.0.clone();
+                        Command::perform(
+                            recipient_rent_shortfall(to, lamports, self.rpc_client.clone()),
+                            move |result| Message::RentWarningChecked(address, result),
+                        )
+                    }
+                    _ => Command::none(),
+                }
+            }
+            Message::UseKnownRecipient(address) => self.update(Message::TxValuesHandler((
+                address.to_string(),
+                self.receiver_value.1.clone(),
+            ))),
+            Message::PoisoningAcknowledged => {
+                self.poisoning_warning = None;
+                self.poisoning_acknowledged = true;
+                self.update(Message::SimulateTransaction)
+            }
+            Message::PoisoningCancelled => {
+                self.poisoning_warning = None;
+                Command::none()
+            }
+            Message::KnownRecipientsSaved(Ok(_)) => Command::none(),
+            Message::RentWarningChecked(address, result) => {
+                if address == self.receiver_value.0 {
+                    self.rent_warning = result.ok().flatten();
+                }
+                Command::none()
+            }
+            Message::SendMax => match self.transfer_values() {
+                Ok(values) => Command::perform(max_sendable(values), Message::MaxAmountComputed),
+                Err(error) => self.show_error(error),
+            },
+            Message::MaxAmountComputed(Ok(amount)) => self.update(Message::TxValuesHandler((
+                self.receiver_value.0.clone(),
+                self.amount_unit.format(amount),
+            ))),
+            // the typed amount is carried over, so 1.5 SOL becomes 1500000000 lamports
+            Message::AmountUnitChanged(unit) => {
+                if let Ok(lamports) = self.amount_unit.parse(&self.receiver_value.1) {
+                    self.receiver_value.1 = unit.format(lamports);
+                }
+                self.amount_unit = unit;
+                self.config.amount_unit = unit.code().to_string();
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::KeepRentExemptToggled(keep_rent_exempt) => {
+                self.keep_rent_exempt = keep_rent_exempt;
+                Command::none()
+            }
+            Message::PriorityFeeChanged(priority_fee) => {
+                self.priority_fee = priority_fee;
+                self.simulation = None;
+                self.estimate_send_fee()
+            }
+            Message::MemoChanged(memo) => {
+                self.memo = memo;
+                self.simulation = None;
+                Command::none()
+            }
+            Message::FeeEstimated(Ok(fee)) => {
+                self.fee = Some(fee);
+                Command::none()
+            }
+            Message::FeeEstimated(Err(_)) => {
+                self.fee = None;
+                Command::none()
+            }
+            Message::NotifyRecipientToggled(notify_recipient) => {
+                self.notify_recipient = notify_recipient;
+                Command::none()
+            }
+            Message::ReceiptSent(Ok(_)) => Command::none(),
+            Message::ReceiptSent(Err(error)) => self.show_error(error),
+            Message::ReceiptsLoaded(Ok(acknowledged_payments)) => {
+                self.acknowledged_payments = acknowledged_payments;
+                Command::none()
+            }
+            Message::ReceiptsLoaded(Err(error)) => self.show_error(error),
+            Message::SlotUpdated(slot) => {
+                // the first slot from an endpoint also fetches its epoch
+                let first = self.connection.slot.is_none();
+                self.connection.slot = Some(slot);
+                if first {
+                    return self.update(Message::RefreshEpoch);
+                }
+                Command::none()
+            }
+            Message::WebsocketStateChanged(connected) => {
+                self.connection.websocket_connected = connected;
+                Command::none()
+            }
+            Message::CheckRpcHealth => Command::perform(
+                check_rpc_health(self.rpc_client.clone()),
+                Message::RpcHealthChecked,
+            ),
+            Message::RefreshNetworkPerformance => Command::perform(
+                fetch_network_performance(self.rpc_client.clone()),
+                Message::NetworkPerformanceLoaded,
+            ),
+            Message::NetworkPerformanceLoaded(Ok(performance)) => {
+                self.network_performance = Some(performance);
+                Command::none()
+            }
+            Message::RefreshEpoch => Command::perform(
+                fetch_epoch_progress(self.rpc_client.clone()),
+                Message::EpochLoaded,
+            ),
+            Message::EpochLoaded(Ok(epoch)) => {
+                self.connection.epoch = Some(epoch);
+                Command::none()
+            }
+            // the status bar keeps the last known epoch
+            Message::EpochLoaded(Err(_)) => Command::none(),
+            Message::RpcHealthChecked(healthy) => {
+                self.connection.rpc_healthy = Some(healthy);
+                Command::none()
+            }
+            Message::DismissToast(id) => {
+                self.toasts.dismiss(id);
+                Command::none()
+            }
+            Message::ShowError(error) => self.show_error(error),
+            Message::CopyToClipboard(value) => clipboard::write(value),
+            Message::NextFrame => {
+                self.current_frame = (self.current_frame + 1) % 21;
+                Command::none()
+            }
+            Message::TogglePalette => {
+                self.palette_open = !self.palette_open;
+                self.palette_query = String::new();
+                Command::none()
+            }
+            Message::PaletteQueryChanged(query) => {
+                self.palette_query = query;
+                Command::none()
+            }
+            Message::PaletteSubmitted => match parse_command(&self.palette_query) {
+                Some(command) => self.update(Message::RunCommand(command)),
+                None => Command::none(),
+            },
+            Message::PinQuickAction => match quick_action_from_query(&self.palette_query) {
+                Some(action) => {
+                    if !self.quick_actions.contains(&action) {
+                        self.quick_actions.push(action);
+                    }
+                    self.palette_open = false;
+                    self.palette_query = String::new();
+                    Command::perform(
+                        save_quick_actions(self.quick_actions.clone()),
+                        Message::QuickActionsSaved,
+                    )
+                }
+                None => Command::none(),
+            },
+            Message::UnpinQuickAction(index) => {
+                if index < self.quick_actions.len() {
+                    self.quick_actions.remove(index);
+                }
+                Command::perform(
+                    save_quick_actions(self.quick_actions.clone()),
+                    Message::QuickActionsSaved,
+                )
+            }
+            Message::RunQuickAction(index) => match self
+                .quick_actions
+                .get(index)
+                .and_then(|action| parse_command(&action.command_line))
+            {
+                Some(command) => self.run_command(command),
+                None => Command::none(),
+            },
+            Message::QuickActionsSaved(Ok(_)) => Command::none(),
+            Message::RunCommand(command) => {
+                self.palette_open = false;
+                self.palette_query = String::new();
+                self.run_command(command)
+            }
+            Message::ShowScreen(screen) => {
+                self.screen = screen;
+                if Screen::restorable(&format!("{:?}", screen)).is_some() {
+                    self.config.last_screen = format!("{:?}", screen);
+                    self.window_state_changed = true;
+                }
+                match screen {
+                    Screen::LookupTables => self.update(Message::RefreshLookupTables),
+                    Screen::History => self.update(Message::RefreshHistory),
+                    Screen::Network => self.update(Message::RefreshNetworkPerformance),
+                    Screen::Staking
+                        if cluster_name(self.rpc_endpoints.primary_url()) == "mainnet-beta" =>
+                    {
+                        Command::batch(vec![
+                            self.update(Message::RefreshStakeAccounts),
+                            self.update(Message::RefreshLiquidStake),
+                        ])
+                    }
+                    Screen::Staking => self.update(Message::RefreshStakeAccounts),
+                    // thousands of accounts, fetched once and on Refresh
+                    Screen::Validators if self.validators.validators.is_empty() => {
+                        self.update(Message::RefreshValidators)
+                    }
+                    Screen::Portfolio => Command::batch(vec![
+                        self.update(Message::RefreshWallet),
+                        self.update(Message::RefreshPrices),
+                        self.update(Message::RefreshTokenList),
+                    ]),
+                    Screen::Swap => self.update(Message::RefreshTokenList),
+                    Screen::TokenAccounts => {
+                        if self.ata.owner.is_empty() {
+                            self.ata.owner = self
+                                .loaded_pubkey()
+                                .map(|pubkey| pubkey.to_string())
+                                .unwrap_or_default();
+                        }
+                        self.update(Message::RefreshWsol)
+                    }
+                    Screen::Nonce if !self.nonce.address.is_empty() => Command::batch(vec![
+                        self.update(Message::RefreshNonceAccounts),
+                        self.update(Message::RefreshNonceAccount),
+                    ]),
+                    Screen::Nonce => self.update(Message::RefreshNonceAccounts),
+                    _ => Command::none(),
+                }
+            }
+            Message::RefreshLookupTables => match self.loaded_pubkey() {
+                Some(pubkey) => self.lookup_tables.refresh(pubkey, self.rpc_client.clone()),
+                None => Command::none(),
+            },
+            Message::CreateLookupTable
+            | Message::ExtendLookupTable(_)
+            | Message::DeactivateLookupTable(_)
+            | Message::CloseLookupTable(_) => match self.signer() {
+                Ok(signer) => self
+                    .lookup_tables
+                    .submit(message, signer, self.rpc_client.clone()),
+                Err(error) => self.show_error(error),
+            },
+            Message::LookupTableUpdated(Ok(ref signature)) => {
+                self.signature = signature.clone();
+                Command::batch(vec![
+                    self.lookup_tables.update(message),
+                    self.update(Message::RefreshLookupTables),
+                ])
+            }
+            Message::LookupTablesLoaded(_)
+            | Message::LookupTableAddressesChanged(_)
+            | Message::LookupTableUpdated(Err(_)) => self.lookup_tables.update(message),
+            Message::SaveAllowList => self.allow_list_form.save(self.allow_list.clone()),
+            Message::DisableAllowList => self.allow_list_form.disable(self.allow_list.clone()),
+            Message::AllowListSaved(Ok(allow_list)) => {
+                self.allow_list = allow_list;
+                Command::none()
+            }
+            Message::AllowListPasswordChanged(_)
+            | Message::AllowListRecipientsChanged(_)
+            | Message::AllowListMintsChanged(_)
+            | Message::AllowListSaved(Err(_)) => self.allow_list_form.update(message),
+            Message::RefreshNonceAccount => self.nonce.refresh(self.rpc_client.clone()),
+            Message::RefreshNonceAccounts => match self.loaded_pubkey() {
+                Some(pubkey) => self.nonce.refresh_accounts(pubkey, self.rpc_client.clone()),
+                None => Command::none(),
+            },
+            Message::CreateNonceAccount
+            | Message::AdvanceNonceAccount
+            | Message::WithdrawNonceAccount
+            | Message::AuthorizeNonceAccount => {
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                let recipients = match message {
+                    Message::AdvanceNonceAccount => Vec::new(),
+                    // an address that doesn't parse is refused by the authorize itself
+                    Message::AuthorizeNonceAccount => {
+                        Pubkey::from_str(self.nonce.new_authority.trim())
+                            .into_iter()
+                            .collect()
+                    }
+                    // the wallet funds the account and stays its authority, or
+                    // gets the withdrawn lamports back
+                    _ => vec![signer.pubkey()],
+                };
+                if let Err(error) = self.check_allow_list(&recipients, &[]) {
+                    return self.show_error(error);
+                }
+                self.nonce.submit(message, signer, self.rpc_client.clone())
+            }
+            Message::NonceAccountCreated(Ok((ref signature, _)))
+            | Message::NonceAccountUpdated(Ok(ref signature)) => {
+                self.signature = signature.clone();
+                Command::batch(vec![
+                    self.nonce.update(message),
+                    self.update(Message::RefreshNonceAccount),
+                    self.update(Message::RefreshNonceAccounts),
+                ])
+            }
+            Message::SelectNonceAccount(_) => Command::batch(vec![
+                self.nonce.update(message),
+                self.update(Message::RefreshNonceAccount),
+            ]),
+            Message::NonceAddressChanged(_)
+            | Message::NonceAccountLoaded(_)
+            | Message::NonceAccountsLoaded(_)
+            | Message::NonceWithdrawAmountChanged(_)
+            | Message::NonceAuthorityChanged(_)
+            | Message::NonceAccountCreated(Err(_))
+            | Message::NonceAccountUpdated(Err(_)) => self.nonce.update(message),
+            Message::UseNonceToggled(use_nonce) => {
+                self.use_nonce = use_nonce;
+                self.simulation = None;
+                Command::none()
+            }
+            Message::UseJitoToggled(use_jito) => {
+                self.use_jito = use_jito;
+                self.simulation = None;
+                Command::none()
+            }
+            Message::NetworkPermissionToggled(service, allowed) => {
+                self.config.network_access.set(service, allowed);
+                self.simulation = None;
+                if !self
+                    .config
+                    .network_access
+                    .allows_prices(&self.config.fiat_currency)
+                {
+                    self.prices.clear();
+                }
+                Command::batch(vec![
+                    Command::perform(save_config(self.config.clone()), Message::ConfigSaved),
+                    self.update(Message::RefreshPrices),
+                ])
+            }
+            Message::JitoTipChanged(tip) => {
+                self.jito_tip = tip;
+                self.simulation = None;
+                match parse_amount(&self.jito_tip) {
+                    Ok(lamports) if lamports >= MIN_JITO_TIP => {
+                        self.config.jito_tip_lamports = lamports;
+                        Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+                    }
+                    // kept as typed, sending refuses it meanwhile
+                    _ => Command::none(),
+                }
+            }
+            Message::ExportUnsignedTransaction => match self.transfer_values() {
+                Ok(values) => Command::perform(
+                    export_unsigned_transfer(values),
+                    Message::OfflineFileWritten,
+                ),
+                Err(error) => self.show_error(error),
+            },
+            // a transaction built elsewhere can move anything, it's not
+            // signed while the allow-list is on
+            Message::SignTransactionFile if self.allow_list.is_some() => {
+                self.show_error(Error::RecipientNotAllowed)
+            }
+            Message::SignTransactionFile => match self.signer() {
+                Ok(signer) => Command::perform(
+                    sign_transaction_file(signer),
+                    Message::TransactionFileSigned,
+                ),
+                Err(error) => self.show_error(error),
+            },
+            Message::BroadcastTransactionFile => Command::perform(
+                broadcast_transaction_file(self.rpc_client.clone()),
+                Message::OfflineTransactionBroadcasted,
+            ),
+            Message::OfflineFileWritten(Ok(path)) => {
+                self.offline_status = tr_args("Saved to {$path}", &[("path", &path.display())]);
+                Command::none()
+            }
+            Message::TransactionFileSigned(Ok((path, missing))) => {
+                self.offline_status = if missing.is_empty() {
+                    tr_args(
+                        "Fully signed, saved to {$path}",
+                        &[("path", &path.display())],
+                    )
+                } else {
+                    tr_args(
+                        "Partially signed, saved to {$path}. Still missing: {$missing}",
+                        &[
+                            ("path", &path.display()),
+                            ("missing", &list_addresses(&missing)),
+                        ],
+                    )
+                };
+                Command::none()
+            }
+            Message::OfflineTransactionBroadcasted(Ok(signature)) => {
+                self.offline_status =
+                    tr_args("Broadcasted: {$signature}", &[("signature", &signature)]);
+                self.signature = signature;
+                self.update(Message::RunCommand(AppCommand::RefreshBalance))
+            }
+            Message::PortableModeToggled(portable) => {
+                Command::perform(set_portable_mode(portable), Message::PortableModeSaved)
+            }
+            Message::PortableModeSaved(Ok(portable)) => {
+                self.portable = portable;
+                Command::none()
+            }
+            Message::PrivacyModeToggled(privacy_mode) => {
+                self.privacy_mode = privacy_mode;
+                self.balances_revealed = false;
+                Command::none()
+            }
+            Message::ToggleBalances => {
+                self.balances_revealed = !self.balances_revealed;
+                Command::none()
+            }
+            Message::RefreshHistory => match self.loaded_pubkey() {
+                Some(pubkey) => Command::perform(
+                    fetch_history(
+                        pubkey,
+                        self.rpc_client.clone(),
+                        self.archival_rpc_client.clone(),
+                    ),
+                    Message::HistoryLoaded,
+                ),
+                None => Command::none(),
+            },
+            Message::HistoryLoaded(Ok(history)) => {
+                merge_history(&mut self.history, history);
+                Command::none()
+            }
+            Message::ExportDecodedTransaction(signature) => Command::perform(
+                export_decoded_transaction(
+                    signature,
+                    self.rpc_client.clone(),
+                    self.archival_rpc_client.clone(),
+                ),
+                Message::DecodedTransactionExported,
+            ),
+            Message::DecodedTransactionExported(Ok(_)) => Command::none(),
+            Message::ReplayTransaction(signature) => Command::perform(
+                replay_transaction(
+                    signature,
+                    self.rpc_client.clone(),
+                    self.archival_rpc_client.clone(),
+                ),
+                Message::TransactionReplayed,
+            ),
+            Message::TransactionReplayed(Ok(report)) => {
+                self.replay = Some(report);
+                Command::none()
+            }
+            Message::UnwrapWsolToggled(unwrap_wsol) => {
+                self.unwrap_wsol = unwrap_wsol;
+                self.simulation = None;
+                Command::none()
+            }
+            Message::MnemonicChanged(mnemonic) => {
+                replace_secret(&mut self.mnemonic, mnemonic);
+                self.derived_accounts = Vec::new();
+                Command::none()
+            }
+            Message::MnemonicPassphraseChanged(passphrase) => {
+                replace_secret(&mut self.mnemonic_passphrase, passphrase);
+                self.derived_accounts = Vec::new();
+                Command::none()
+            }
+            Message::DeriveAccounts => Command::perform(
+                derive_accounts(
+                    Zeroizing::new(self.mnemonic.clone()),
+                    Zeroizing::new(self.mnemonic_passphrase.clone()),
+                    self.rpc_client.clone(),
+                ),
+                Message::AccountsDerived,
+            ),
+            Message::AccountsDerived(Ok(accounts)) => {
+                self.derived_accounts = accounts;
+                Command::none()
+            }
+            Message::GenerateMnemonic => {
+                self.mnemonic_backup = Some(MnemonicBackup::generate());
+                self.secret_revealed = false;
+                self.screen = Screen::NewMnemonic;
+                Command::none()
+            }
+            Message::MnemonicBackupWritten => {
+                if let Some(backup) = self.mnemonic_backup.as_mut() {
+                    backup.written_down = true;
+                }
+                self.secret_revealed = false;
+                Command::none()
+            }
+            Message::MnemonicBackupShown => {
+                if let Some(backup) = self.mnemonic_backup.as_mut() {
+                    backup.written_down = false;
+                    backup.answers.iter_mut().for_each(Zeroize::zeroize);
+                }
+                Command::none()
+            }
+            Message::MnemonicBackupAnswerChanged(index, answer) => {
+                if let Some(answer_slot) = self
+                    .mnemonic_backup
+                    .as_mut()
+                    .and_then(|backup| backup.answers.get_mut(index))
+                {
+                    replace_secret(answer_slot, answer);
+                }
+                Command::none()
+            }
+            Message::SecretHeld(held) => {
+                self.secret_revealed = held;
+                Command::none()
+            }
+            // the keypair only exists once the backup has been confirmed
+            Message::CreateMnemonicWallet => match self.mnemonic_backup.take() {
+                Some(backup) if backup.is_confirmed() => {
+                    self.screen = Screen::Send;
+                    Command::perform(create_mnemonic_wallet(backup.phrase()), Message::FileOpened)
+                }
+                backup => {
+                    self.mnemonic_backup = backup;
+                    Command::none()
+                }
+            },
+            // the phrase is dropped from memory once the chosen key is saved
+            Message::ImportDerivedAccount(index) => {
+                let phrase = Zeroizing::new(std::mem::take(&mut self.mnemonic));
+                let passphrase = Zeroizing::new(std::mem::take(&mut self.mnemonic_passphrase));
+                self.derived_accounts = Vec::new();
+                Command::perform(
+                    import_derived_account(phrase, passphrase, index),
+                    Message::FileOpened,
+                )
+            }
+            Message::DefaultMemoChanged(memo) => {
+                // keep the send form in sync while it still holds the old default
+                if self.memo == self.preferences.initial_memo() {
+                    self.memo = memo.clone();
+                }
+                self.preferences.default_memo = memo;
+                Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                )
+            }
+            Message::DefaultMemoToggled(enabled) => {
+                if self.memo == self.preferences.initial_memo() {
+                    self.memo = if enabled {
+                        self.preferences.default_memo.clone()
+                    } else {
+                        String::new()
+                    };
+                }
+                self.preferences.default_memo_enabled = enabled;
+                Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                )
+            }
+            Message::ArchivalRpcUrlChanged(url) => {
+                self.archival_rpc_client =
+                    archival_rpc_client(&url, &self.preferences, &self.config);
+                self.preferences.archival_rpc_url = url;
+                Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                )
+            }
+            Message::PastedKeypairChanged(content) => {
+                replace_secret(&mut self.pasted_keypair, content);
+                Command::none()
+            }
+            Message::ImportPastedKeypair => match keypair_from_json(&self.pasted_keypair) {
+                Ok(keypair) => {
+                    let load = self.activate_wallet(LoadedWallet::pasted(Arc::new(keypair)));
+                    // stay here so the key can be moved to the keystore
+                    self.screen = Screen::PasteKeypair;
+                    load
+                }
+                Err(error) => self.update(Message::FileOpened(Err(error))),
+            },
+            Message::RefreshWallet => match self.loaded_pubkey() {
+                Some(pubkey) => Command::perform(
+                    refresh_wallet(pubkey, self.rpc_client.clone()),
+                    Message::WalletRefreshed,
+                ),
+                None => Command::none(),
+            },
+            Message::WalletRefreshed(Ok(refresh)) => {
+                if Some(refresh.pubkey) != self.loaded_pubkey() {
+                    return Command::none();
+                }
+                let incoming = if self.balance_updated.is_some() {
+                    incoming_tokens(&self.token_balances, &refresh.tokens)
+                } else {
+                    Vec::new()
+                };
+                self.token_balances = refresh.tokens;
+                Command::batch(vec![
+                    self.notify(incoming),
+                    self.fetch_token_symbols(),
+                    // new mints get a price, otherwise the cache answers
+                    self.update(Message::RefreshPrices),
+                    self.update(Message::BalanceLoaded(Ok(refresh.balance))),
+                ])
+            }
+            Message::MinimizeToTrayToggled(enabled) => {
+                self.preferences.minimize_to_tray = enabled;
+                Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                )
+            }
+            Message::PollTray => {
+                if !self.tray_started {
+                    self.tray_started = true;
+                    self.tray = Tray::start().ok();
+                    self.update_tray();
+                }
+                let commands: Vec<Command<Message>> = tray_actions()
+                    .into_iter()
+                    .map(|action| match action {
+                        TrayAction::Open => Command::batch(vec![
+                            window::change_mode(window::Mode::Windowed),
+                            window::gain_focus(),
+                        ]),
+                        TrayAction::Lock => self.update(Message::LockWallet),
+                        TrayAction::Quit => window::close(),
+                    })
+                    .collect();
+                Command::batch(commands)
+            }
+            // only reached with exit_on_close_request off, i.e. the tray was
+            // enabled at startup
+            Message::CloseRequested => match self.tray {
+                Some(_) => window::change_mode(window::Mode::Hidden),
+                None => window::close(),
+            },
+            Message::MuteIncomingToggled(muted) => {
+                self.preferences.mute_incoming = muted;
+                Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                )
+            }
+            // a desktop without a notification daemon just doesn't show them
+            Message::IncomingNotified(_) => Command::none(),
+            Message::BalanceRefreshChanged(seconds) => {
+                self.preferences.balance_refresh_secs = seconds;
+                Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                )
+            }
+            Message::VanityGround(Ok(ref keypair)) => {
+                let wallet = LoadedWallet::pasted(keypair.clone());
+                Command::batch(vec![
+                    self.vanity.update(message),
+                    self.activate_wallet(wallet),
+                ])
+            }
+            Message::VanityPrefixChanged(_)
+            | Message::VanitySuffixChanged(_)
+            | Message::VanityIgnoreCaseToggled(_)
+            | Message::StartVanity
+            | Message::CancelVanity
+            | Message::VanityGround(Err(_)) => self.vanity.update(message),
+            Message::DefaultKeypairPathChanged(path) => {
+                self.preferences.default_keypair_path = path;
+                Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                )
+            }
+            Message::PickDefaultKeypair => {
+                Command::perform(pick_file(), Message::DefaultKeypairPicked)
+            }
+            Message::DefaultKeypairPicked(Ok(path)) => self.update(
+                Message::DefaultKeypairPathChanged(path.display().to_string()),
+            ),
+            Message::FallbackRpcUrlsChanged(urls) => {
+                self.preferences.fallback_rpc_urls = urls;
+                let save = Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                );
+                Command::batch(vec![save, self.rebuild_rpc_pool()])
+            }
+            Message::RpcRateLimitChanged(limit) => {
+                self.preferences.rpc_rate_limit = limit;
+                let save = Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                );
+                Command::batch(vec![save, self.rebuild_rpc_pool()])
+            }
+            Message::BroadcastRpcUrlChanged(url) => {
+                self.preferences.broadcast_rpc_url = url;
+                let save = Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                );
+                Command::batch(vec![save, self.rebuild_rpc_pool()])
+            }
+            Message::ClusterVerified(Ok(mismatch)) => {
+                self.cluster_mismatch = mismatch;
+                Command::none()
+            }
+            // an unreachable node can't take a transfer either, the status
+            // bar already reports it
+            Message::ClusterVerified(Err(_)) => Command::none(),
+            Message::CommitmentChanged(level) => {
+                self.config.commitment = level;
+                Command::batch(vec![
+                    Command::perform(save_config(self.config.clone()), Message::ConfigSaved),
+                    self.rebuild_rpc_pool(),
+                ])
+            }
+            Message::RpcUrlChanged(url) => {
+                self.config.rpc_url = url;
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            // a profile brings its own url, this one applies once none is active
+            Message::RpcUrlSubmitted if self.preferences.active_profile.is_empty() => {
+                let url = configured_rpc_url(&self.config.rpc_url);
+                (self.rpc_client, self.rpc_endpoints) =
+                    new_rpc_pool(&url, &self.preferences, &self.config);
+                self.history = Vec::new();
+                self.connection = ConnectionStatus::default();
+                self.network_performance = None;
+                self.cluster_mismatch = None;
+                Command::batch(vec![
+                    Command::perform(
+                        verify_cluster(expected_cluster("", &url), self.rpc_client.clone()),
+                        Message::ClusterVerified,
+                    ),
+                    self.match_rpc_clusters(),
+                ])
+            }
+            Message::RpcUrlSubmitted => Command::none(),
+            Message::WindowResized(width, height) => {
+                self.config.window_width = width;
+                self.config.window_height = height;
+                self.window_state_changed = true;
+                Command::none()
+            }
+            Message::WindowMoved(x, y) => {
+                self.config.window_x = Some(x);
+                self.config.window_y = Some(y);
+                self.window_state_changed = true;
+                Command::none()
+            }
+            Message::SaveWindowState => {
+                self.window_state_changed = false;
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::ThemeChanged(theme) => {
+                self.config.theme = theme;
+                self.system_dark = system_prefers_dark();
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::LanguageChanged(language) => {
+                set_language(language);
+                self.config.language = language.code().to_string();
+                self.update_tray();
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::FiatCurrencyChanged(currency) => {
+                self.config.fiat_currency = currency;
+                self.prices.clear();
+                Command::batch(vec![
+                    Command::perform(save_config(self.config.clone()), Message::ConfigSaved),
+                    self.update(Message::RefreshPrices),
+                ])
+            }
+            Message::RefreshPrices
+                if shows_fiat(&self.config.fiat_currency)
+                    && !self.offline
+                    && self
+                        .config
+                        .network_access
+                        .allows_prices(&self.config.fiat_currency)
+                    && self.prices.should_fetch(&self.config.fiat_currency) =>
+            {
+                Command::perform(
+                    fetch_prices(
+                        self.config.fiat_currency.clone(),
+                        self.held_mints(),
+                        self.config.network_access,
+                    ),
+                    Message::PricesLoaded,
+                )
+            }
+            Message::RefreshPrices => Command::none(),
+            // a reply for a currency that was switched away from is dropped
+            Message::PricesLoaded(Ok(prices)) if prices.currency == self.config.fiat_currency => {
+                self.prices.store(prices);
+                self.check_alerts()
+            }
+            Message::PricesLoaded(Ok(_)) => Command::none(),
+            // the last prices stay up until they're too old, the next poll
+            // usually gets through
+            Message::PricesLoaded(Err(_)) => Command::none(),
+            // fetched once a session, only with jupiter allowed
+            Message::RefreshTokenList => {
+                if self.token_verification.has_list()
+                    || !self.config.network_access.allows(Service::Jupiter)
+                {
+                    return Command::none();
+                }
+                Command::perform(
+                    fetch_token_list(self.config.network_access),
+                    Message::TokenListLoaded,
+                )
+            }
+            Message::TokenListLoaded(Ok(verified)) => {
+                self.token_verification.store_list(verified);
+                self.fetch_token_symbols()
+            }
+            // the badges say unchecked, opening the tab again retries
+            Message::TokenListLoaded(Err(_)) => Command::none(),
+            Message::TokenSymbolsLoaded(Ok(symbols)) => {
+                self.token_verification.store_symbols(symbols);
+                Command::none()
+            }
+            Message::TokenSymbolsLoaded(Err(_)) => Command::none(),
+            Message::ChartSeriesChanged(series) => {
+                self.chart_series = series;
+                Command::none()
+            }
+            Message::ChartRangeChanged(range) => {
+                self.chart_range = range;
+                Command::none()
+            }
+            Message::ChartPointRecorded(Ok(point)) => {
+                self.chart_points.push(point);
+                Command::none()
+            }
+            Message::AlertKindChanged(kind) => {
+                self.alert_kind = kind;
+                Command::none()
+            }
+            Message::AlertThresholdChanged(threshold) => {
+                self.alert_threshold = threshold;
+                Command::none()
+            }
+            Message::AddAlert => match parse_alert(self.alert_kind, &self.alert_threshold) {
+                Ok(alert) => {
+                    self.config.alerts.push(alert);
+                    self.alert_threshold = String::new();
+                    // a condition that already holds fires right away
+                    Command::batch(vec![
+                        self.check_alerts(),
+                        Command::perform(save_config(self.config.clone()), Message::ConfigSaved),
+                    ])
+                }
+                Err(error) => self.show_error(error),
+            },
+            Message::RemoveAlert(index) => {
+                if index < self.config.alerts.len() {
+                    self.config.alerts.remove(index);
+                }
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::AlertNotified(_) => Command::none(),
+            Message::SwapSlippageChanged(_) => {
+                let command = self.swap.update(message);
+                match parse_slippage(&self.swap.slippage) {
+                    Ok(bps) => {
+                        self.config.swap_slippage_bps = bps;
+                        Command::batch(vec![
+                            command,
+                            Command::perform(
+                                save_config(self.config.clone()),
+                                Message::ConfigSaved,
+                            ),
+                        ])
+                    }
+                    // applied once it's valid, quoting refuses it meanwhile
+                    Err(_) => command,
+                }
+            }
+            Message::GetSwapQuote => self.swap.get_quote(self.config.network_access),
+            Message::ExecuteSwap => {
+                let Some(quote) = self.swap.quote.clone() else {
+                    return Command::none();
+                };
+                if let Err(error) = self.check_cluster() {
+                    return self.show_error(error);
+                }
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                if let Err(error) = self.check_allow_list(
+                    &[signer.pubkey()],
+                    &[quote.input.mint_address(), quote.output.mint_address()],
+                ) {
+                    return self.show_error(error);
+                }
+                // SOL going out counts against the session limit like a transfer
+                if quote.input == SWAP_TOKENS[0] {
+                    if let Err(error) = self.check_spend_limit(quote.in_amount) {
+                        return self.show_error(error);
+                    }
+                }
+                let unverified = !self.swap_unverified().is_empty();
+                self.swap.execute(
+                    quote,
+                    unverified,
+                    signer,
+                    self.rpc_client.clone(),
+                    self.config.network_access,
+                )
+            }
+            Message::SwapExecuted(Ok(ref receipt)) => {
+                self.session_spent = self.session_spent.saturating_add(receipt.sol_spent());
+                Command::batch(vec![
+                    self.swap.update(message),
+                    self.toast(
+                        ToastLevel::Success,
+                        String::from(tr("Swap confirmed")),
+                        None,
+                    ),
+                    self.update(Message::RefreshWallet),
+                ])
+            }
+            Message::SwapInputChanged(_)
+            | Message::SwapOutputChanged(_)
+            | Message::FlipSwap
+            | Message::SwapAmountChanged(_)
+            | Message::CancelSwap
+            | Message::SwapQuoteLoaded(_)
+            | Message::SwapExecuted(Err(_)) => self.swap.update(message),
+            Message::DcaIntervalChanged(interval) => {
+                self.dca_interval = interval;
+                Command::none()
+            }
+            Message::AddDcaSchedule => {
+                if self.swap.input == self.swap.output {
+                    return self.show_error(Error::SwapQuoteError);
+                }
+                if let Err(error) = parse_token_amount(&self.swap.amount, self.swap.input.decimals)
+                {
+                    return self.show_error(error);
+                }
+                let id = self
+                    .config
+                    .dca_schedules
+                    .iter()
+                    .map(|schedule| schedule.id + 1)
+                    .max()
+                    .unwrap_or(0);
+                self.config.dca_schedules.push(DcaSchedule::new(
+                    id,
+                    self.swap.input,
+                    self.swap.output,
+                    self.swap.amount.trim().to_string(),
+                    self.dca_interval,
+                ));
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::ToggleDcaSchedule(id) => {
+                if let Some(schedule) = self
+                    .config
+                    .dca_schedules
+                    .iter_mut()
+                    .find(|schedule| schedule.id == id)
+                {
+                    schedule.paused = !schedule.paused;
+                    // a resumed schedule doesn't fire for the time it was paused
+                    schedule.next_run = schedule.next_run.max(unix_now());
+                }
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::RemoveDcaSchedule(id) => {
+                self.config
+                    .dca_schedules
+                    .retain(|schedule| schedule.id != id);
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::CheckDca => {
+                let now = unix_now();
+                let due: Vec<DcaSchedule> = self
+                    .config
+                    .dca_schedules
+                    .iter()
+                    .filter(|schedule| schedule.is_due(now))
+                    .filter(|schedule| !self.dca_running.iter().any(|(id, _)| *id == schedule.id))
+                    .cloned()
+                    .collect();
+                if due.is_empty() {
+                    return Command::none();
+                }
+                for schedule in self.config.dca_schedules.iter_mut() {
+                    if due.iter().any(|due| due.id == schedule.id) {
+                        schedule.advance(now);
+                    }
+                }
+
+                let mut commands = vec![Command::perform(
+                    save_config(self.config.clone()),
+                    Message::ConfigSaved,
+                )];
+                for schedule in due {
+                    match self.dca_precondition(&schedule) {
+                        Ok(signer) => {
+                            // held until the run reports back, so the next
+                            // due schedule is checked against it too
+                            let reserved = schedule.sol_amount().unwrap_or(0);
+                            self.dca_running.push((schedule.id, reserved));
+                            commands.push(Command::perform(
+                                run_dca(
+                                    schedule,
+                                    self.config.swap_slippage_bps,
+                                    signer,
+                                    self.rpc_client.clone(),
+                                    self.config.network_access,
+                                ),
+                                Message::DcaRan,
+                            ));
+                        }
+                        Err(error) => commands
+                            .push(self.update(Message::DcaRan(skipped_run(&schedule, error)))),
+                    }
+                }
+                Command::batch(commands)
+            }
+            Message::DcaRan(run) => {
+                self.dca_running.retain(|(id, _)| *id != run.schedule);
+                self.session_spent = self.session_spent.saturating_add(run.lamports);
+                let notice = match &run.result {
+                    Ok(_) => self.toast(
+                        ToastLevel::Success,
+                        tr_args(
+                            "Recurring swap done: {$summary}",
+                            &[("summary", &run.summary)],
+                        ),
+                        None,
+                    ),
+                    Err(error) => self.toast(
+                        ToastLevel::Error,
+                        tr_args(
+                            "Recurring swap failed: {$summary} ({$error})",
+                            &[("summary", &run.summary), ("error", error)],
+                        ),
+                        None,
+                    ),
+                };
+                let refresh = if run.result.is_ok() {
+                    self.update(Message::RefreshWallet)
+                } else {
+                    Command::none()
+                };
+                self.dca_history.push(run.clone());
+                Command::batch(vec![
+                    notice,
+                    refresh,
+                    Command::perform(save_dca_run(run), Message::DcaRunSaved),
+                ])
+            }
+            Message::PreviewStake => match self.loaded_pubkey() {
+                Some(owner) => self.stake.start_preview(owner, self.rpc_client.clone()),
+                None => Command::none(),
+            },
+            // same steps as a transfer: cluster, spend limit, then a second
+            // confirmation for large amounts
+            Message::ConfirmStake => {
+                let Some(preview) = self.stake.preview.clone() else {
+                    return Command::none();
+                };
+                if let Err(error) = self.check_cluster() {
+                    return self.show_error(error);
+                }
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                // the wallet stays the authority, the vote account gets the stake
+                if let Err(error) =
+                    self.check_allow_list(&[signer.pubkey(), preview.vote_account], &[])
+                {
+                    return self.show_error(error);
+                }
+                if let Err(error) = self.check_spend_limit(preview.lamports) {
+                    return self.show_error(error);
+                }
+                let large = self.is_large_amount(preview.lamports);
+                self.stake
+                    .create(preview, large, signer, self.rpc_client.clone())
+            }
+            Message::StakeCreated(Ok((stake_account, _))) => {
+                if let Some(preview) = &self.stake.preview {
+                    self.session_spent = self.session_spent.saturating_add(preview.lamports);
+                }
+                Command::batch(vec![
+                    self.stake.update(message),
+                    self.toast(
+                        ToastLevel::Success,
+                        tr_args(
+                            "Stake account {$address} created and delegated",
+                            &[("address", &stake_account)],
+                        ),
+                        None,
+                    ),
+                    self.update(Message::RefreshWallet),
+                    self.update(Message::RefreshStakeAccounts),
+                ])
+            }
+            Message::RefreshStakeAccounts => match self.loaded_pubkey() {
+                Some(pubkey) => Command::perform(
+                    fetch_stake_accounts(pubkey, self.rpc_client.clone()),
+                    Message::StakeAccountsLoaded,
+                ),
+                None => Command::none(),
+            },
+            Message::StakeAccountsLoaded(Ok((accounts, epoch))) => {
+                self.stake_accounts = accounts;
+                self.stake_epoch = Some(epoch);
+                Command::none()
+            }
+            Message::DeactivateStake(address) => {
+                if let Err(error) = self.check_cluster() {
+                    return self.show_error(error);
+                }
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                self.stake
+                    .deactivate(signer, address, self.rpc_client.clone())
+            }
+            Message::WithdrawStake(address) => {
+                let Some(account) = self.stake_account(&address) else {
+                    return Command::none();
+                };
+                if let Err(error) = self.check_cluster() {
+                    return self.show_error(error);
+                }
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                if let Err(error) = self.check_allow_list(&[signer.pubkey()], &[]) {
+                    return self.show_error(error);
+                }
+                self.stake
+                    .withdraw(signer, account, self.rpc_client.clone())
+            }
+            Message::RefreshLiquidStake => match self.loaded_pubkey() {
+                Some(pubkey) => self.liquid.refresh(pubkey, self.rpc_client.clone()),
+                None => Command::none(),
+            },
+            Message::DepositLiquid => {
+                let Some(stake) = &self.liquid.stake else {
+                    return Command::none();
+                };
+                let lamports = match parse_amount(&self.liquid.amount) {
+                    Ok(lamports) if lamports > 0 => lamports,
+                    _ => return self.show_error(Error::InvalidAmount),
+                };
+                if let Err(error) = self.check_cluster() {
+                    return self.show_error(error);
+                }
+                if let Err(error) = self.check_spend_limit(lamports) {
+                    return self.show_error(error);
+                }
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                if let Err(error) =
+                    self.check_allow_list(&[signer.pubkey()], &[stake.pool.pool_mint])
+                {
+                    return self.show_error(error);
+                }
+                let pool = stake.pool.clone();
+                self.liquid
+                    .deposit(signer, pool, lamports, self.rpc_client.clone())
+            }
+            Message::WithdrawLiquid => {
+                let Some(stake) = &self.liquid.stake else {
+                    return Command::none();
+                };
+                let tokens = match parse_amount(&self.liquid.amount) {
+                    Ok(tokens) if tokens > 0 && tokens <= stake.balance => tokens,
+                    _ => return self.show_error(Error::InvalidAmount),
+                };
+                if let Err(error) = self.check_cluster() {
+                    return self.show_error(error);
+                }
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                if let Err(error) =
+                    self.check_allow_list(&[signer.pubkey()], &[stake.pool.pool_mint])
+                {
+                    return self.show_error(error);
+                }
+                let pool = stake.pool.clone();
+                self.liquid
+                    .withdraw(signer, pool, tokens, self.rpc_client.clone())
+            }
+            Message::LiquidStakeUpdated(Ok(_)) => {
+                if let Some(lamports) = self.liquid.depositing {
+                    self.session_spent = self.session_spent.saturating_add(lamports);
+                }
+                Command::batch(vec![
+                    self.liquid.update(message),
+                    self.update(Message::RefreshLiquidStake),
+                    self.update(Message::RefreshWallet),
+                ])
+            }
+            Message::LiquidStakeLoaded(_)
+            | Message::LiquidAmountChanged(_)
+            | Message::LiquidStakeUpdated(Err(_)) => self.liquid.update(message),
+            Message::RefreshStakeRewards => {
+                let Some(epoch) = self.stake_epoch else {
+                    return Command::none();
+                };
+                self.stake_rewards_loading = true;
+                Command::perform(
+                    fetch_stake_rewards(
+                        self.stake_accounts
+                            .iter()
+                            .map(|account| account.address)
+                            .collect(),
+                        epoch,
+                        self.rpc_client.clone(),
+                    ),
+                    Message::StakeRewardsLoaded,
+                )
+            }
+            Message::StakeRewardsLoaded(result) => {
+                self.stake_rewards_loading = false;
+                match result {
+                    Ok(rewards) => {
+                        self.stake_rewards = rewards;
+                        Command::none()
+                    }
+                    Err(error) => self.show_error(error),
+                }
+            }
+            Message::ExportStakeRewards => Command::perform(
+                export_stake_rewards(self.stake_rewards.clone()),
+                Message::StakeRewardsExported,
+            ),
+            Message::StakeRewardsExported(Ok(_)) => Command::none(),
+            Message::RefreshValidators => self.validators.refresh(self.rpc_client.clone()),
+            Message::ValidatorsLoaded(_)
+            | Message::ValidatorSearchChanged(_)
+            | Message::ValidatorSortChanged(_) => self.validators.update(message),
+            Message::PickValidator(_) => Command::batch(vec![
+                self.stake.update(message),
+                self.update(Message::ShowScreen(Screen::Staking)),
+            ]),
+            Message::SplitStake(address) => {
+                let Some(account) = self.stake_account(&address) else {
+                    return Command::none();
+                };
+                if let Err(error) = self.check_cluster() {
+                    return self.show_error(error);
+                }
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                // the new account keeps the delegation of the one it's split from
+                let recipients: Vec<Pubkey> = std::iter::once(signer.pubkey())
+                    .chain(account.voter)
+                    .collect();
+                if let Err(error) = self.check_allow_list(&recipients, &[]) {
+                    return self.show_error(error);
+                }
+                self.stake.split(signer, account, self.rpc_client.clone())
+            }
+            Message::StakeSplit(Ok((stake_account, _))) => Command::batch(vec![
+                self.stake.update(message),
+                self.toast(
+                    ToastLevel::Success,
+                    tr_args(
+                        "Split off into stake account {$address}",
+                        &[("address", &stake_account)],
+                    ),
+                    None,
+                ),
+                self.update(Message::RefreshStakeAccounts),
+                self.update(Message::RefreshWallet),
+            ]),
+            Message::MergeStake(address) => {
+                let (Some(destination), Some(source)) = (
+                    self.stake_account(&address),
+                    self.stake
+                        .merge_source
+                        .and_then(|source| self.stake_account(&source)),
+                ) else {
+                    return Command::none();
+                };
+                if let Err(error) = self.check_cluster() {
+                    return self.show_error(error);
+                }
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                // the source's stake ends up delegated where the destination is
+                let recipients: Vec<Pubkey> = std::iter::once(signer.pubkey())
+                    .chain(destination.voter)
+                    .collect();
+                if let Err(error) = self.check_allow_list(&recipients, &[]) {
+                    return self.show_error(error);
+                }
+                self.stake
+                    .merge(signer, destination, source, self.rpc_client.clone())
+            }
+            Message::StakeAccountUpdated(Ok(_)) => Command::batch(vec![
+                self.stake.update(message),
+                self.update(Message::RefreshStakeAccounts),
+                self.update(Message::RefreshWallet),
+            ]),
+            Message::StakeVoteAccountChanged(_)
+            | Message::StakeAmountChanged(_)
+            | Message::StakePreviewed(_)
+            | Message::CancelStake
+            | Message::StakeCreated(Err(_))
+            | Message::StakeSplitAmountChanged(_)
+            | Message::StakeSplit(Err(_))
+            | Message::PickMergeSource(_)
+            | Message::CancelMergeStake
+            | Message::StakeAccountUpdated(Err(_)) => self.stake.update(message),
+            Message::UiScaleChanged(scale) => {
+                self.config.ui_scale = scale;
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::AccentColorChanged(color) => {
+                self.config.accent_color = color;
+                self.apply_palette()
+            }
+            Message::SuccessColorChanged(color) => {
+                self.config.success_color = color;
+                self.apply_palette()
+            }
+            Message::ErrorColorChanged(color) => {
+                self.config.error_color = color;
+                self.apply_palette()
+            }
+            Message::CheckSystemTheme => {
+                self.system_dark = system_prefers_dark();
+                Command::none()
+            }
+            Message::ExplorerChanged(explorer) => {
+                self.config.explorer = explorer;
+                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+            }
+            Message::TestRpcEndpoints => {
+                self.testing_endpoints = true;
+                Command::batch(vec![
+                    self.match_rpc_clusters(),
+                    Command::perform(
+                        benchmark_endpoints(Arc::clone(&self.rpc_endpoints)),
+                        Message::RpcEndpointsTested,
+                    ),
+                ])
+            }
+            // the endpoint list in settings picks the result up on redraw
+            Message::RpcClustersMatched(()) => Command::none(),
+            Message::RpcEndpointsTested(fastest) => {
+                self.testing_endpoints = false;
+                match fastest {
+                    Some(url) => self.toast(
+                        ToastLevel::Info,
+                        tr_args("Using {$url}", &[("url", &url)]),
+                        None,
+                    ),
+                    None => self.show_error(Error::NoRpcEndpointAnswered),
+                }
+            }
+            Message::SnapshotFolderChanged(folder) => {
+                self.preferences.snapshot_folder = folder;
+                Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                )
+            }
+            Message::ExportSnapshot => Command::perform(
+                export_snapshot(
+                    self.preferences.snapshot_folder.clone(),
+                    snapshot_wallets(self.loaded_pubkey(), &self.test_wallets.sets),
+                    self.rpc_client.clone(),
+                ),
+                Message::SnapshotExported,
+            ),
+            // one file per closed day, written by the first check after midnight utc
+            Message::CheckSnapshot => {
+                if snapshot_path(&self.preferences.snapshot_folder, &last_closed_day()).exists() {
+                    Command::none()
+                } else {
+                    self.update(Message::ExportSnapshot)
+                }
+            }
+            Message::SnapshotExported(Ok(_)) => Command::none(),
+            Message::KeystorePassphraseChanged(passphrase) => {
+                replace_secret(&mut self.keystore_passphrase, passphrase);
+                Command::none()
+            }
+            Message::KeystoreConfirmationChanged(confirmation) => {
+                replace_secret(&mut self.keystore_confirmation, confirmation);
+                Command::none()
+            }
+            Message::UnlockKeystore => Command::perform(
+                unlock_keystore(
+                    Zeroizing::new(std::mem::take(&mut self.keystore_passphrase)),
+                    self.preferences.keychain_unlock,
+                ),
+                Message::KeystoreUnlocked,
+            ),
+            Message::KeystoreUnlocked(Ok(signer)) => self.wallet_loaded(signer, keystore_path()),
+            Message::MigrateToKeystore => match self.signer() {
+                Ok(signer) => Command::perform(
+                    migrate_to_keystore(
+                        signer,
+                        Zeroizing::new(std::mem::take(&mut self.keystore_passphrase)),
+                        Zeroizing::new(std::mem::take(&mut self.keystore_confirmation)),
+                        self.preferences.keychain_unlock,
+                    ),
+                    Message::KeystoreMigrated,
+                ),
+                Err(error) => self.update(Message::KeystoreMigrated(Err(error))),
+            },
+            Message::KeychainUnlockToggled(enabled) => {
+                self.preferences.keychain_unlock = enabled;
+                let save = Command::perform(
+                    save_preferences(self.preferences.clone()),
+                    Message::PreferencesSaved,
+                );
+                if enabled {
+                    save
+                } else {
+                    Command::batch(vec![
+                        save,
+                        Command::perform(forget_keychain_passphrase(), Message::PreferencesSaved),
+                    ])
+                }
+            }
+            // back to the unlock screen with a throwaway signer in place of the key
+            Message::LockWallet if !self.can_lock() => Command::none(),
+            Message::LockWallet => {
+                // a match found after locking would load without the password
+                self.vanity.stop();
+                self.clear_active_wallet();
+                self.wallets = Vec::new();
+                self.fee_payer = None;
+                self.fee_payer_path = None;
+                self.screen = self.locked_screen();
+                Command::none()
+            }
+            Message::SelectWallet(index) => match self.wallets.get(index).cloned() {
+                Some(wallet) => self.activate_wallet(wallet),
+                None => Command::none(),
+            },
+            Message::RemoveWallet(index) => {
+                if index >= self.wallets.len() {
+                    return Command::none();
+                }
+                let removed = self.wallets.remove(index);
+                if Some(removed.pubkey) != self.loaded_pubkey() {
+                    return Command::none();
+                }
+                match self.wallets.first().cloned() {
+                    Some(wallet) => self.activate_wallet(wallet),
+                    None => {
+                        self.clear_active_wallet();
+                        Command::none()
+                    }
+                }
+            }
+            Message::WatchAddressChanged(address) => {
+                self.watch_address = address;
+                Command::none()
+            }
+            // the loaded keypair is dropped so nothing can sign by accident
+            Message::WatchAddress => match Pubkey::from_str(self.watch_address.trim()) {
+                Ok(pubkey) => {
+                    self.watch_address = String::new();
+                    self.ata.owner = pubkey.to_string();
+                    self.activate_wallet(LoadedWallet::watch_only(pubkey))
+                }
+                Err(_) => self.update(Message::FileOpened(Err(Error::InvalidPubKeyLen))),
+            },
+            Message::LaunchPasswordChanged(password) => {
+                replace_secret(&mut self.launch_password_input, password);
+                Command::none()
+            }
+            Message::LaunchConfirmationChanged(confirmation) => {
+                replace_secret(&mut self.launch_password_confirmation, confirmation);
+                Command::none()
+            }
+            Message::UnlockLaunch => match self.launch_password.clone() {
+                Some(hash) => Command::perform(
+                    unlock_launch(
+                        hash,
+                        Zeroizing::new(std::mem::take(&mut self.launch_password_input)),
+                        self.startup_file.clone(),
+                    ),
+                    Message::LaunchUnlocked,
+                ),
+                None => Command::none(),
+            },
+            Message::LaunchUnlocked(Ok(signer)) => {
+                self.wallet_loaded(signer, self.startup_file.clone())
+            }
+            Message::SetLaunchPassword => Command::perform(
+                set_launch_password(
+                    Zeroizing::new(std::mem::take(&mut self.launch_password_input)),
+                    Zeroizing::new(std::mem::take(&mut self.launch_password_confirmation)),
+                ),
+                Message::LaunchPasswordSaved,
+            ),
+            Message::RemoveLaunchPassword => match self.launch_password.clone() {
+                Some(hash) => Command::perform(
+                    remove_launch_password(
+                        hash,
+                        Zeroizing::new(std::mem::take(&mut self.launch_password_input)),
+                    ),
+                    Message::LaunchPasswordSaved,
+                ),
+                None => Command::none(),
+            },
+            Message::LaunchPasswordSaved(Ok(hash)) => {
+                self.launch_password = hash;
+                self.update_tray();
+                Command::none()
+            }
+            Message::KeystoreMigrated(Ok(signer)) => {
+                self.keystore_migrated = true;
+                self.update_tray();
+                let moved = self.toast(
+                    ToastLevel::Success,
+                    String::from(tr("Wallet moved to the encrypted keystore")),
+                    None,
+                );
+                Command::batch(vec![moved, self.wallet_loaded(signer, keystore_path())])
+            }
+            Message::BackupPassphraseChanged(passphrase) => {
+                replace_secret(&mut self.backup_passphrase, passphrase);
+                self.encrypted_backup = None;
+                Command::none()
+            }
+            Message::BackupConfirmationChanged(confirmation) => {
+                replace_secret(&mut self.backup_confirmation, confirmation);
+                self.encrypted_backup = None;
+                Command::none()
+            }
+            Message::ExportEncryptedKeypair => {
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => {
+                        return self.update(Message::EncryptedKeypairExported(Err(error)))
+                    }
+                };
+                if let Err(error) =
+                    check_new_passphrase(&self.backup_passphrase, &self.backup_confirmation)
+                {
+                    return self.update(Message::EncryptedKeypairExported(Err(error)));
+                }
+                Command::perform(
+                    export_encrypted_keypair(
+                        signer,
+                        Zeroizing::new(self.backup_passphrase.clone()),
+                    ),
+                    Message::EncryptedKeypairExported,
+                )
+            }
+            // the passphrase isn't kept around once the backup is written
+            Message::EncryptedKeypairExported(Ok(path)) => {
+                self.backup_passphrase.zeroize();
+                self.backup_confirmation.zeroize();
+                let written = tr_args(
+                    "Encrypted backup written to {$path}",
+                    &[("path", &path.display())],
+                );
+                self.encrypted_backup = Some(path);
+                self.toast(ToastLevel::Info, written, None)
+            }
+            Message::PreferencesSaved(Ok(_)) | Message::ConfigSaved(Ok(_)) => Command::none(),
+            Message::ProfileSelected(name) => {
+                match self.profiles.iter().find(|profile| profile.name == name) {
+                    Some(profile) => self.apply_profile(profile.clone()),
+                    None => Command::none(),
+                }
+            }
+            Message::ProfileNameChanged(name) => {
+                self.profile_name = name;
+                Command::none()
+            }
+            // the current endpoint, fee and keypair file become a profile, a
+            // profile with the same name is overwritten
+            Message::SaveProfile => {
+                let name = self.profile_name.trim().to_string();
+                if !valid_profile_name(&name) {
+                    return self.update(Message::ProfilesSaved(Err(Error::InvalidProfileName)));
+                }
+                let profile = Profile {
+                    name: name.clone(),
+                    rpc_url: self.rpc_endpoints.primary_url().to_string(),
+                    priority_fee: self.priority_fee.clone(),
+                    keypair_path: self.path.clone().filter(|path| *path != keystore_path()),
+                };
+                self.profiles.retain(|profile| profile.name != name);
+                self.profiles.push(profile);
+                self.profile_name = String::new();
+                self.preferences.active_profile = name;
+                Command::batch(vec![
+                    Command::perform(save_profiles(self.profiles.clone()), Message::ProfilesSaved),
+                    Command::perform(
+                        save_preferences(self.preferences.clone()),
+                        Message::PreferencesSaved,
+                    ),
+                ])
+            }
+            Message::DeleteProfile(name) => {
+                self.profiles.retain(|profile| profile.name != name);
+                Command::perform(save_profiles(self.profiles.clone()), Message::ProfilesSaved)
+            }
+            Message::ProfilesSaved(Ok(_)) => Command::none(),
+            Message::CloseReplay => {
+                self.replay = None;
+                Command::none()
+            }
+            Message::SendBatch => {
+                let prepared = self.signer().and_then(|signer| {
+                    let transfers = parse_rows(&self.batch.rows, &self.allow_list)?;
+                    let total = transfers
+                        .iter()
+                        .try_fold(0u64, |total, (_, _, lamports)| total.checked_add(*lamports))
+                        .ok_or(Error::InvalidAmount)?;
+                    self.check_spend_limit(total)?;
+                    self.check_cluster()?;
+                    let fee_payer = self
+                        .fee_payer
+                        .as_ref()
+                        .map_or(signer.pubkey(), |fee_payer| fee_payer.pubkey());
+                    let chunks = chunk_transfers(&signer.pubkey(), &fee_payer, &transfers);
+                    // without a separate fee payer every chunk's fee comes
+                    // out of the same balance as the transfers
+                    if self.fee_payer.is_none()
+                        && self.balance.unwrap_or(0) < total.saturating_add(batch_fees(&chunks))
+                    {
+                        return Err(Error::InsufficientBalance);
+                    }
+                    Ok((transfers, chunks))
+                });
+                let (transfers, chunks) = match prepared {
+                    Ok(prepared) => prepared,
+                    Err(error) => return self.update(Message::BatchPrepared(Err(error))),
+                };
+                // lookalike recipients and big amounts need the same explicit
+                // confirmation as on the send form
+                if !std::mem::take(&mut self.batch.confirmed) {
+                    let review = review_transfers(&transfers, &self.known_recipients, |lamports| {
+                        self.is_large_amount(lamports)
+                    });
+                    if !review.is_empty() {
+                        self.batch.review = Some(review);
+                        return Command::none();
+                    }
+                }
+                self.batch.review = None;
+                self.update(Message::BatchPrepared(Ok(chunks)))
+            }
+            Message::ConfirmBatch => Command::batch(vec![
+                self.batch.update(message),
+                self.update(Message::SendBatch),
+            ]),
+            Message::BatchPrepared(Ok(_)) => {
+                Command::batch(vec![self.batch.update(message), self.next_batch_chunk()])
+            }
+            Message::BatchChunkSent(ref rows, ref result) => {
+                let mut commands = Vec::new();
+                if result.is_ok() {
+                    let (recipients, sent) = self.batch.sent(rows);
+                    remember_recipients(&mut self.known_recipients, &recipients);
+                    self.session_spent = self.session_spent.saturating_add(sent);
+                    commands.push(Command::perform(
+                        save_known_recipients(self.known_recipients.clone()),
+                        Message::KnownRecipientsSaved,
+                    ));
+                }
+                commands.push(self.batch.update(message));
+                commands.push(self.next_batch_chunk());
+                Command::batch(commands)
+            }
+            Message::AddBatchRow
+            | Message::RemoveBatchRow(_)
+            | Message::BatchRowChanged(..)
+            | Message::CancelBatch => self.batch.update(message),
+            Message::ImportPayroll => {
+                Command::perform(read_payroll_file(), Message::PayrollImported)
+            }
+            Message::PayrollImported(Ok(mut rows)) => {
+                validate_rows(&mut rows, &self.allow_list);
+                self.batch.import(rows);
+                Command::none()
+            }
+            Message::ExportPayrollResults => Command::perform(
+                export_payroll_results(self.batch.rows.clone()),
+                Message::PayrollResultsExported,
+            ),
+            Message::PayrollResultsExported(Ok(_)) => Command::none(),
+            Message::AutoTopUpToggled(true) => Command::batch(vec![
+                self.faucet.update(message),
+                self.update(Message::TopUpWallets),
+            ]),
+            Message::TopUpWallets if self.faucet.running => Command::none(),
+            Message::TopUpWallets => match self.faucet.target() {
+                Ok(target) => {
+                    let mut addresses: Vec<Pubkey> = self.loaded_pubkey().into_iter().collect();
+                    if let Some(fee_payer) = &self.fee_payer {
+                        addresses.push(fee_payer.pubkey());
+                    }
+                    for (_, pubkey) in self.test_wallets.sets.iter().flat_map(|set| &set.wallets) {
+                        if !addresses.contains(pubkey) {
+                            addresses.push(*pubkey);
+                        }
+                    }
+                    self.faucet
+                        .start(addresses, target, self.rpc_client.clone())
+                }
+                Err(error) => {
+                    self.faucet.auto_top_up = false;
+                    self.show_error(error)
+                }
+            },
+            Message::WalletsToppedUp(_) => Command::batch(vec![
+                self.faucet.update(message),
+                self.run_command(AppCommand::RefreshBalance),
+            ]),
+            Message::FaucetTargetChanged(_) | Message::AutoTopUpToggled(false) => {
+                self.faucet.update(message)
+            }
+            Message::TestSetGenerated(Ok(ref set)) => {
+                let addresses: Vec<Pubkey> =
+                    set.wallets.iter().map(|(_, pubkey)| *pubkey).collect();
+                let command = self.test_wallets.update(message);
+                match self.faucet.target() {
+                    Ok(target)
+                        if self.test_wallets.airdrop
+                            && !self.faucet.running
+                            && has_faucet(self.rpc_endpoints.primary_url()) =>
+                    {
+                        Command::batch(vec![
+                            command,
+                            self.faucet
+                                .start(addresses, target, self.rpc_client.clone()),
+                        ])
+                    }
+                    _ => command,
+                }
+            }
+            Message::TestSetDeleted(Ok(ref label)) => {
+                let active_deleted = self
+                    .test_wallets
+                    .sets
+                    .iter()
+                    .filter(|set| &set.label == label)
+                    .flat_map(|set| &set.wallets)
+                    .any(|(path, _)| self.path.as_ref() == Some(path));
+                let deleted: Vec<Pubkey> = self
+                    .test_wallets
+                    .sets
+                    .iter()
+                    .filter(|set| &set.label == label)
+                    .flat_map(|set| set.wallets.iter().map(|(_, pubkey)| *pubkey))
+                    .collect();
+                self.wallets
+                    .retain(|wallet| !deleted.contains(&wallet.pubkey));
+                let command = self.test_wallets.update(message);
+                // the wallet in use was just deleted, go back to the default one
+                if active_deleted && self.can_lock() {
+                    self.screen = self.locked_screen();
+                    command
+                } else if active_deleted {
+                    Command::batch(vec![
+                        command,
+                        self.update(Message::FileOpened(Ok(default_file()))),
+                    ])
+                } else {
+                    command
+                }
+            }
+            Message::TestSetLabelChanged(_)
+            | Message::TestWalletCountChanged(_)
+            | Message::TestWalletAirdropToggled(_)
+            | Message::GenerateTestSet
+            | Message::DeleteTestSet(_)
+            | Message::TestSetGenerated(Err(_))
+            | Message::TestSetDeleted(Err(_)) => self.test_wallets.update(message),
+            Message::PreviewAta => self.ata.start_preview(self.rpc_client.clone()),
+            Message::CreateAta => match self.ata.preview.take() {
+                // the owner gets an account whose rent the wallet pays
+                Some(preview) => {
+                    if let Err(error) = self.check_allow_list(&[preview.owner], &[preview.mint]) {
+                        return self.show_error(error);
+                    }
+                    let signer = match self.signer() {
+                        Ok(signer) => signer,
+                        Err(error) => return self.show_error(error),
+                    };
+                    self.ata.create(signer, preview, self.rpc_client.clone())
+                }
+                None => Command::none(),
+            },
+            Message::AtaCreated(Ok(_)) => Command::batch(vec![
+                self.ata.update(message),
+                self.run_command(AppCommand::RefreshBalance),
+            ]),
+            Message::AtaOwnerChanged(_)
+            | Message::AtaMintChanged(_)
+            | Message::AtaOffCurveToggled(_)
+            | Message::AtaPreviewed(_)
+            | Message::AtaPreviewCancelled
+            | Message::AtaCreated(Err(_)) => self.ata.update(message),
+            Message::RefreshWsol => match self.loaded_pubkey() {
+                Some(owner) => self.wsol.refresh(owner, self.rpc_client.clone()),
+                None => Command::none(),
+            },
+            Message::WrapSol | Message::UnwrapSol => {
+                if let Err(error) = self.check_cluster() {
+                    return self.show_error(error);
+                }
+                let signer = match self.signer() {
+                    Ok(signer) => signer,
+                    Err(error) => return self.show_error(error),
+                };
+                if let Err(error) = self.check_allow_list(&[signer.pubkey()], &[NATIVE_MINT]) {
+                    return self.show_error(error);
+                }
+                if matches!(message, Message::WrapSol) {
+                    self.wsol.start_wrap(signer, self.rpc_client.clone())
+                } else {
+                    self.wsol.start_unwrap(signer, self.rpc_client.clone())
+                }
+            }
+            Message::WsolUpdated(Ok(_)) => Command::batch(vec![
+                self.wsol.update(message),
+                self.update(Message::RefreshWsol),
+                self.update(Message::RefreshWallet),
+            ]),
+            Message::WsolLoaded(_)
+            | Message::WrapAmountChanged(_)
+            | Message::WsolUpdated(Err(_)) => self.wsol.update(message),
+            Message::OpenFeePayer => Command::perform(pick_file(), Message::FeePayerOpened),
+            Message::FeePayerOpened(Ok(path)) => {
+                let fee_payer = match load_keypair_from_file(path.to_path_buf()) {
+                    Ok(fee_payer) => fee_payer,
+                    Err(error) => return self.update(Message::FeePayerOpened(Err(error))),
+                };
+                self.fee_payer = Some(fee_payer.into());
+                self.fee_payer_path = Some(path);
+                self.simulation = None;
+                self.estimate_send_fee()
+            }
+            Message::ClearFeePayer => {
+                self.fee_payer = None;
+                self.fee_payer_path = None;
+                self.simulation = None;
+                self.estimate_send_fee()
+            }
+            Message::OfflineFileWritten(Err(error))
+            | Message::TransactionFileSigned(Err(error))
+            | Message::FeePayerOpened(Err(error))
+            | Message::HistoryLoaded(Err(error))
+            | Message::DecodedTransactionExported(Err(error))
+            | Message::TransactionReplayed(Err(error))
+            | Message::AccountsDerived(Err(error))
+            | Message::PreferencesSaved(Err(error))
+            | Message::ConfigSaved(Err(error))
+            | Message::DefaultKeypairPicked(Err(error))
+            | Message::WalletRefreshed(Err(error))
+            | Message::ChartPointRecorded(Err(error))
+            | Message::DcaRunSaved(Err(error))
+            | Message::StakeAccountsLoaded(Err(error))
+            | Message::NetworkPerformanceLoaded(Err(error))
+            | Message::ProfilesSaved(Err(error))
+            | Message::SnapshotExported(Err(error))
+            | Message::EncryptedKeypairExported(Err(error))
+            | Message::KeystoreUnlocked(Err(error))
+            | Message::KeystoreMigrated(Err(error))
+            | Message::LaunchPasswordSaved(Err(error))
+            | Message::LaunchUnlocked(Err(error))
+            | Message::BatchPrepared(Err(error))
+            | Message::PayrollImported(Err(error))
+            | Message::KnownRecipientsSaved(Err(error))
+            | Message::QuickActionsSaved(Err(error))
+            | Message::SpendingUnlocked(Err(error))
+            | Message::SpendLimitApplied(Err(error))
+            | Message::MaxAmountComputed(Err(error))
+            | Message::PayrollResultsExported(Err(error))
+            | Message::StakeRewardsExported(Err(error))
+            | Message::OfflineTransactionBroadcasted(Err(error))
+            | Message::PortableModeSaved(Err(error)) => self.show_error(error),
+        }
+    }
+
+    fn send_view(&self) -> Element<'_, Message> {
+        let image_path = match self.current_frame {
+            0 => "./gif_animation/loader1.png",
+            1 => "./gif_animation/loader2.png",
+            2 => "./gif_animation/loader3.png",
+            3 => "./gif_animation/loader4.png",
+            4 => "./gif_animation/loader5.png",
+            5 => "./gif_animation/loader6.png",
+            6 => "./gif_animation/loader7.png",
+            7 => "./gif_animation/loader7.png",
+            8 => "./gif_animation/loader7.png",
+            9 => "./gif_animation/loader7.png",
+            10 => "./gif_animation/loader11.png",
+            11 => "./gif_animation/loader12.png",
+            12 => "./gif_animation/loader13.png",
+            13 => "./gif_animation/loader14.png",
+            14 => "./gif_animation/loader15.png",
+            15 => "./gif_animation/loader16.png",
+            16 => "./gif_animation/loader7.png",
+            17 => "./gif_animation/loader7.png",
+            18 => "./gif_animation/loader7.png",
+            19 => "./gif_animation/loader7.png",
+            20 => "./gif_animation/loader7.png",
+            _ => "./gif_animation/loader1.png",
+        };
+
+        // Solana sender
+
+        let some_h2 = Column::new().push(Space::with_height(20)).push(
+            text(tr("Send SOL to any wallet!!! LFG"))
+                .style(accent_color())
+                .size(14),
+        );
+
+        let address_input = text_input(tr("Put receiver address"), &self.receiver_value.0)
+            .on_input(|value| Message::TxValuesHandler((value, self.receiver_value.1.to_string())));
+
+        let amount_input = text_input(tr("Amount to send"), &self.receiver_value.1.to_string())
+            .on_input(|value| Message::TxValuesHandler((self.receiver_value.0.clone(), value)));
+
+        let amount_fiat = self
+            .amount_unit
+            .parse(&self.receiver_value.1)
+            .map(|lamports| self.fiat_value(lamports))
+            .unwrap_or_default();
+        let amount_row = row![
+            amount_input,
+            pick_list(
+                &AmountUnit::ALL[..],
+                Some(self.amount_unit),
+                Message::AmountUnitChanged
+            ),
+            button(tr("Max")).on_press(Message::SendMax),
+            text(amount_fiat).size(12)
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let keep_rent_exempt = checkbox(
+            tr("Keep the rent-exempt minimum when sending max"),
+            self.keep_rent_exempt,
+            Message::KeepRentExemptToggled,
+        )
+        .size(16)
+        .text_size(14);
+
+        let rent_warning = match self.rent_warning {
+            Some(minimum) => text(tr_args(
+                "This address has no account yet: send at least {$minimum} SOL (the rent-exempt minimum) or the transfer will fail",
+                &[("minimum", &format_sol(minimum))],
+            ))
+            .size(14)
+            .style(error_color()),
+            None => text("").size(1),
+        };
+
+        let memo_input =
+            text_input(tr("Memo (optional)"), &self.memo).on_input(Message::MemoChanged);
+
+        // deposit rules for known exchange addresses
+        let exchange_hint = match Pubkey::from_str(&self.receiver_value.0)
+            .ok()
+            .and_then(|address| find_exchange(&address))
+        {
+            Some(rule) => text(tr_args(
+                "{$exchange} deposit address: {$memo}minimum deposit {$minimum} SOL",
+                &[
+                    ("exchange", &rule.name),
+                    (
+                        "memo",
+                        &if rule.requires_memo {
+                            tr("memo required, ")
+                        } else {
+                            ""
+                        },
+                    ),
+                    (
+                        "minimum",
+                        &format!("{:.3}", rule.min_deposit as f64 / LAMPORTS_PER_SOL as f64),
+                    ),
+                ],
+            ))
+            .size(14)
+            .style(accent_color()),
+            None => text("").size(1),
+        };
+
+        let priority_fee_input = text_input(
+            tr("Priority fee (micro-lamports per compute unit)"),
+            &self.priority_fee,
+        )
+        .on_input(Message::PriorityFeeChanged);
+        let priority_fee_row = self.config.fee_presets.iter().fold(
+            row![priority_fee_input]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            |fee_row, preset| {
+                fee_row.push(
+                    button(text(preset.to_string()).size(12))
+                        .on_press(Message::PriorityFeeChanged(preset.to_string())),
+                )
+            },
+        );
+
+        let notify_recipient = checkbox(
+            tr("Notify recipient (memo ping with the payment signature)"),
+            self.notify_recipient,
+            Message::NotifyRecipientToggled,
+        )
+        .size(16)
+        .text_size(14);
+
+        // sponsored transactions: another keypair pays the fees
+        let fee_payer: Element<'_, Message> = match &self.fee_payer {
+            Some(fee_payer) => row![
+                text(tr_args(
+                    "Fee payer: {$address}",
+                    &[("address", &fee_payer.pubkey())]
+                ))
+                .size(14),
+                button(tr("Use my wallet")).on_press(Message::ClearFeePayer),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into(),
+            None => button(tr("Use a separate fee payer keypair"))
+                .on_press(Message::OpenFeePayer)
+                .into(),
+        };
+
+        let unwrap_wsol = checkbox(
+            tr("Unwrap my wSOL if my SOL balance can't cover the transfer"),
+            self.unwrap_wsol,
+            Message::UnwrapWsolToggled,
+        )
+        .size(16)
+        .text_size(14);
+
+        let use_nonce = checkbox(
+            tr("Use the durable nonce account (transaction doesn't expire)"),
+            self.use_nonce,
+            Message::UseNonceToggled,
+        )
+        .size(16)
+        .text_size(14);
+
+        let use_jito = display_jito_option(
+            self.use_jito,
+            &self.jito_tip,
+            cluster_name(self.rpc_endpoints.primary_url()) == "mainnet-beta",
+        );
+
+        let send_lamports_btn: Element<'_, Message> = if self.is_loading {
+            Image::new(image_path).width(64).height(40).into()
+        } else {
+            button(tr("Send"))
+                .on_press(Message::SimulateTransaction)
+                .into()
+        };
+
+        let poisoning_warning: Element<'_, Message> = match &self.poisoning_warning {
+            Some(lookalike) => display_poisoning_warning(&self.receiver_value.0, lookalike),
+            None => Space::with_height(0).into(),
+        };
+
+        // nothing is broadcast until the simulated result is confirmed
+        let simulation_preview: Element<'_, Message> = match &self.simulation {
+            Some(_) if self.confirming_large_transfer => column![
+                text(tr("Large transfer, please confirm"))
+                    .style(error_color())
+                    .size(16),
+                text(tr_args(
+                    "Recipient: {$address}",
+                    &[("address", &self.receiver_value.0)]
+                ))
+                .size(14),
+                text(tr_args(
+                    "Amount: {$amount} SOL",
+                    &[(
+                        "amount",
+                        &self
+                            .amount_unit
+                            .parse(&self.receiver_value.1)
+                            .map(format_sol)
+                            .unwrap_or_default()
+                    )],
+                ))
+                .size(14),
+                text(match self.fee {
+                    Some(fee) => tr_args("Fee: {$fee} SOL", &[("fee", &format_sol(fee))]),
+                    None => tr_args("Fee: {$fee}", &[("fee", &tr("unknown"))]),
+                })
+                .size(14),
+                row![
+                    button(tr("Yes, send it")).on_press(Message::LargeTransferConfirmed),
+                    button(tr("Cancel")).on_press(Message::LargeTransferCancelled),
+                ]
+                .spacing(10)
+            ]
+            .spacing(5)
+            .into(),
+            Some(preview) => {
+                let logs = preview
+                    .logs
+                    .iter()
+                    .fold(Column::new().spacing(2), |column, log| {
+                        column.push(text(log).size(12))
+                    });
+
+                // funding from wSOL is spelled out before it can be confirmed
+                let unwrap_notice = match preview.unwrapped_wsol {
+                    Some(lamports) => text(tr_args(
+                        "Your SOL doesn't cover this transfer: your whole wSOL account ({$amount} SOL) will be unwrapped in the same transaction",
+                        &[("amount", &format_sol(lamports))],
+                    ))
+                    .size(14)
+                    .style(error_color()),
+                    None => text("").size(1),
+                };
+
+                column![
+                    text(tr("Simulation preview"))
+                        .style(accent_color())
+                        .size(14),
+                    unwrap_notice,
+                    text(tr_args(
+                        "Your balance change: {$change} SOL",
+                        &[(
+                            "change",
+                            &format!(
+                                "{:.9}",
+                                preview.sender_delta as f64 / LAMPORTS_PER_SOL as f64
+                            )
+                        )],
+                    ))
+                    .size(14),
+                    text(tr_args(
+                        "Recipient balance change: {$change} SOL",
+                        &[(
+                            "change",
+                            &format!(
+                                "{:.9}",
+                                preview.recipient_delta as f64 / LAMPORTS_PER_SOL as f64
+                            )
+                        )],
+                    ))
+                    .size(14),
+                    text(tr_args(
+                        "Compute units consumed: {$units}",
+                        &[(
+                            "units",
+                            &preview
+                                .units_consumed
+                                .map(|units| units.to_string())
+                                .unwrap_or_else(|| String::from(tr("unknown")))
+                        )],
+                    ))
+                    .size(14),
+                    text(match self.eta {
+                        Some(eta) => tr_args(
+                            "ETA: ~{$confirmed}s to confirmed, ~{$finalized}s to finalized",
+                            &[
+                                ("confirmed", &format!("{:.0}", eta.confirmed_secs)),
+                                ("finalized", &format!("{:.0}", eta.finalized_secs)),
+                            ],
+                        ),
+                        None => String::from(tr("ETA: unknown")),
+                    })
+                    .size(14),
+                    text(tr("Program logs:")).style(accent_color()).size(14),
+                    logs,
+                    row![
+                        button(tr("Confirm and send")).on_press(Message::ExecuteTransaction),
+                        button(tr("Cancel")).on_press(Message::SimulationCancelled),
+                    ]
+                    .spacing(10)
+                ]
+                .spacing(5)
+                .into()
+            }
+            None => Space::with_height(0).into(),
+        };
+
+        let fee_preview = match (self.fee, self.amount_unit.parse(&self.receiver_value.1)) {
+            // live timer while the transaction is being confirmed
+            _ if self.sent_at.is_some() => {
+                let elapsed = format!(
+                    "{:.1}",
+                    self.sent_at
+                        .map(|sent_at| sent_at.elapsed().as_secs_f64())
+                        .unwrap_or(0.0)
+                );
+                text(match self.eta {
+                    Some(eta) => tr_args(
+                        "Confirming... {$elapsed}s elapsed (~{$finalized}s to finalized)",
+                        &[
+                            ("elapsed", &elapsed),
+                            ("finalized", &format!("{:.0}", eta.finalized_secs)),
+                        ],
+                    ),
+                    None => tr_args(
+                        "Confirming... {$elapsed}s elapsed",
+                        &[("elapsed", &elapsed)],
+                    ),
+                })
+            }
+            (Some(fee), Ok(amount)) => text(tr_args(
+                "Fee: {$fee} SOL | Total: {$total} SOL",
+                &[
+                    ("fee", &format_sol(fee)),
+                    ("total", &format_sol(amount.saturating_add(fee))),
+                ],
+            )),
+            (Some(fee), Err(_)) => text(tr_args("Fee: {$fee} SOL", &[("fee", &format_sol(fee))])),
+            (None, _) => text(tr("Estimating fee...")),
+        }
+        .size(14);
+
+        let send_row = row![send_lamports_btn, fee_preview]
+            .spacing(20)
+            .align_items(Alignment::Center);
+
+        let spend_limit: Element<'_, Message> = match parse_amount(&self.preferences.spend_limit) {
+            Ok(limit) => row![
+                text(tr_args(
+                    "Spent this session: {$spent} of {$limit} SOL",
+                    &[
+                        ("spent", &format_sol(self.session_spent)),
+                        ("limit", &format_sol(limit)),
+                    ],
+                ))
+                .size(14),
+                text_input(tr("Wallet password"), &self.spend_password)
+                    .on_input(Message::SpendPasswordChanged)
+                    .on_submit(Message::UnlockSpending)
+                    .password(),
+                button(tr("Unlock spending")).on_press(Message::UnlockSpending),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .into(),
+            Err(_) => Space::with_height(0).into(),
+        };
+
+        let signature: Element<'_, Message> = if self.signature.is_empty() {
+            Space::with_height(0).into()
+        } else {
+            column![
+                text(&self.signature).size(14),
+                text(self.config.explorer_url(
+                    &self.signature,
+                    cluster_name(self.rpc_endpoints.primary_url())
+                ))
+                .size(12),
+            ]
+            .into()
+        };
+
+        let acknowledged_payments = self.acknowledged_payments.iter().fold(
+            Column::new().spacing(5),
+            |column, payment_signature| {
+                column.push(
+                    text(tr_args(
+                        "Payment acknowledged: {$signature}",
+                        &[("signature", payment_signature)],
+                    ))
+                    .size(12)
+                    .style(accent_color()),
+                )
+            },
+        );
+
+        column![
+            some_h2,
+            address_input,
+            amount_row,
+            keep_rent_exempt,
+            rent_warning,
+            exchange_hint,
+            memo_input,
+            priority_fee_row,
+            notify_recipient,
+            unwrap_wsol,
+            use_nonce,
+            use_jito,
+            fee_payer,
+            send_row,
+            spend_limit,
+            poisoning_warning,
+            simulation_preview,
+            signature,
+            acknowledged_payments
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    // empty while fiat is off or no price came in yet
+    fn fiat_value(&self, lamports: u64) -> String {
+        match self.prices.sol(&self.config.fiat_currency) {
+            Some(price) if shows_fiat(&self.config.fiat_currency) => {
+                format_fiat(lamports, price, &self.config.fiat_currency)
+            }
+            _ => String::new(),
+        }
+    }
+
+    // alerts that crossed their threshold go out as system notifications
+    // and toasts, the triggered flags are saved so a restart doesn't repeat them
+    fn check_alerts(&mut self) -> Command<Message> {
+        let before = self.config.alerts.clone();
+        let balance = self
+            .loaded_pubkey()
+            .and(self.balance)
+            .map(|lamports| lamports as f64 / LAMPORTS_PER_SOL as f64);
+        let fired = evaluate_alerts(
+            &mut self.config.alerts,
+            self.prices.sol(&self.config.fiat_currency),
+            balance,
+            &self.config.fiat_currency,
+        );
+
+        let mut commands: Vec<Command<Message>> = Vec::new();
+        for body in fired {
+            commands.push(self.toast(
+                ToastLevel::Info,
+                tr_args("Alert: {$alert}", &[("alert", &body)]),
+                None,
+            ));
+            commands.push(Command::perform(notify_alert(body), Message::AlertNotified));
+        }
+        if self.config.alerts != before {
+            commands.push(Command::perform(
+                save_config(self.config.clone()),
+                Message::ConfigSaved,
+            ));
+        }
+        Command::batch(commands)
+    }
+
+    // what a scheduled swap needs at the moment it's due, checked the same way
+    // a swap from the tab is
+    fn dca_precondition(&self, schedule: &DcaSchedule) -> Result<Arc<Keypair>, Error> {
+        let signer = self.signer()?;
+        self.check_cluster()?;
+        if cluster_name(self.rpc_endpoints.primary_url()) != "mainnet-beta" {
+            return Err(Error::ClusterMismatch);
+        }
+        self.config.network_access.check(Service::Jupiter)?;
+        let mints: Vec<Pubkey> = [&schedule.input, &schedule.output]
+            .into_iter()
+            .filter_map(|symbol| swap_token(symbol))
+            .map(|token| token.mint_address())
+            .collect();
+        self.check_allow_list(&[signer.pubkey()], &mints)?;
+        self.check_spend_limit(schedule.sol_amount()?)?;
+        Ok(signer)
+    }
+
+    // the balance just loaded with the current price, the chart tab draws
+    // from these
+    fn record_chart_point(&mut self) -> Command<Message> {
+        let due = self
+            .chart_recorded
+            .map_or(true, |recorded| recorded.elapsed() >= CHART_RECORD_INTERVAL);
+        let Some(wallet) = self.loaded_pubkey() else {
+            return Command::none();
+        };
+        if !due || self.offline {
+            return Command::none();
+        }
+        self.chart_recorded = Some(Instant::now());
+        Command::perform(
+            record_chart_point(ChartPoint {
+                time: unix_now(),
+                wallet,
+                lamports: self.balance,
+                price: self.prices.sol(&self.config.fiat_currency),
+                currency: self.config.fiat_currency.clone(),
+            }),
+            Message::ChartPointRecorded,
+        )
+    }
+
+    // the on-chain symbol of held mints missing from the verified list, to tell
+    // the ones posing as a listed token apart
+    fn fetch_token_symbols(&self) -> Command<Message> {
+        let missing = self.token_verification.missing_symbols(&self.held_mints());
+        if missing.is_empty() {
+            return Command::none();
+        }
+        Command::perform(
+            fetch_token_symbols(missing, self.rpc_client.clone()),
+            Message::TokenSymbolsLoaded,
+        )
+    }
+
+    // anything but verified on either side of the swap asks for a second click
+    fn swap_unverified(&self) -> Vec<(SwapToken, MintStatus)> {
+        [self.swap.input, self.swap.output]
+            .into_iter()
+            .map(|token| (token, self.token_verification.status(token.mint)))
+            .filter(|(_, status)| *status != MintStatus::Verified)
+            .collect()
+    }
+
+    // mints of the tokens the wallet holds, priced along with sol
+    fn held_mints(&self) -> Vec<String> {
+        let mut mints: Vec<String> = self
+            .token_balances
+            .iter()
+            .filter(|row| row.asset != "SOL")
+            .map(|row| row.asset.clone())
+            .collect();
+        mints.sort();
+        mints.dedup();
+        mints
+    }
+
+    // sends the next queued batch transaction, chunks go out one at a time so
+    // every row's status updates as soon as its transaction lands
+    fn next_batch_chunk(&mut self) -> Command<Message> {
+        if self.batch.queue.is_empty() {
+            return self.run_command(AppCommand::RefreshBalance);
+        }
+
+        let signer = match self.signer() {
+            Ok(signer) => signer,
+            Err(error) => return self.show_error(error),
+        };
+        self.batch
+            .next_chunk(signer, self.fee_payer.clone(), self.rpc_client.clone())
+            .unwrap_or_else(Command::none)
+    }
+
+    // over the configured amount or share of the balance, an empty setting
+    // disables that check
+    fn is_large_transfer(&self) -> bool {
+        self.amount_unit
+            .parse(&self.receiver_value.1)
+            .is_ok_and(|amount| self.is_large_amount(amount))
+    }
+
+    // the thresholds from settings, shared by everything that moves SOL out
+    fn is_large_amount(&self, amount: u64) -> bool {
+        let over_amount = parse_amount(&self.large_transfer_sol)
+            .map(|threshold| amount > threshold)
+            .unwrap_or(false);
+        let over_percent = match (
+            self.large_transfer_percent.trim().parse::<u64>(),
+            self.balance,
+        ) {
+            (Ok(percent), Some(balance)) if balance > 0 => {
+                amount as u128 * 100 > balance as u128 * percent as u128
+            }
+            _ => false,
+        };
+
+        over_amount || over_percent
+    }
+
+    fn check_cluster(&self) -> Result<(), Error> {
+        match self.cluster_mismatch {
+            Some(_) => Err(Error::ClusterMismatch),
+            None => Ok(()),
+        }
+    }
+
+    // while the allow-list is on, everything an action moves value or
+    // authority to and every token it touches has to be listed
+    fn check_allow_list(&self, recipients: &[Pubkey], mints: &[Pubkey]) -> Result<(), Error> {
+        let Some(allow_list) = &self.allow_list else {
+            return Ok(());
+        };
+        recipients
+            .iter()
+            .try_for_each(|recipient| allow_list.check_recipient(recipient))?;
+        mints
+            .iter()
+            .try_for_each(|mint| allow_list.check_mint(mint))
+    }
+
+    // sent this session plus what the recurring swaps in flight hold
+    fn committed_spend(&self) -> u64 {
+        self.dca_running
+            .iter()
+            .fold(self.session_spent, |total, (_, reserved)| {
+                total.saturating_add(*reserved)
+            })
+    }
+
+    fn check_spend_limit(&self, amount: u64) -> Result<(), Error> {
+        match parse_amount(&self.preferences.spend_limit) {
+            Ok(limit) if self.committed_spend().saturating_add(amount) > limit => {
+                Err(Error::SpendLimitReached)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn balances_hidden(&self) -> bool {
+        self.privacy_mode && !self.balances_revealed
+    }
+
+    // the toast dismisses itself once its level's lifetime runs out
+    fn toast(
+        &mut self,
+        level: ToastLevel,
+        text: String,
+        action: Option<ToastAction>,
+    ) -> Command<Message> {
+        let id = self.toasts.push(level, text, action);
+        Command::perform(
+            async move { time::sleep(level.lifetime()).await },
+            move |_| Message::DismissToast(id),
+        )
+    }
+
+    fn apply_palette(&mut self) -> Command<Message> {
+        set_palette(Palette::from_config(&self.config));
+        Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+    }
+
+    // picks up pool settings around the same primary node
+    fn rebuild_rpc_pool(&mut self) -> Command<Message> {
+        let primary = self.rpc_endpoints.primary_url().to_string();
+        (self.rpc_client, self.rpc_endpoints) =
+            new_rpc_pool(&primary, &self.preferences, &self.config);
+        self.match_rpc_clusters()
+    }
+
+    // fallbacks join the rotation once their genesis hash matches the primary's
+    fn match_rpc_clusters(&self) -> Command<Message> {
+        Command::perform(
+            match_clusters(
+                Arc::clone(&self.rpc_endpoints),
+                expected_cluster(
+                    &self.preferences.active_profile,
+                    self.rpc_endpoints.primary_url(),
+                ),
+            ),
+            Message::RpcClustersMatched,
+        )
+    }
+
+    fn show_error(&mut self, error: Error) -> Command<Message> {
+        self.toast(
+            ToastLevel::Error,
+            tr_args("Error: {$error}", &[("error", &error)]),
+            None,
+        )
+    }
+
+    fn update_tray(&self) {
+        let Some(tray) = &self.tray else {
+            return;
+        };
+        tray.set_lock_enabled(self.can_lock());
+        let tooltip = match (self.loaded_pubkey(), self.balance) {
+            (Some(_), Some(_)) if self.privacy_mode => String::from(tr("Balance hidden")),
+            (Some(_), Some(balance)) => format!("{} SOL", format_sol(balance)),
+            _ => String::from(tr("No wallet loaded")),
+        };
+        tray.set_tooltip(tooltip);
+    }
+
+    // everything that has to happen when a different keypair becomes the signer
+    fn wallet_loaded(&mut self, signer: Arc<Keypair>, path: PathBuf) -> Command<Message> {
+        self.config.last_keypair_path = path.display().to_string();
+        let save = Command::perform(save_config(self.config.clone()), Message::ConfigSaved);
+        let activate = self.activate_wallet(LoadedWallet::from_keypair(signer, path));
+        Command::batch(vec![save, activate])
+    }
+
+    // makes a sidebar wallet the active one, adding it to the sidebar first
+    // if it's new
+    fn activate_wallet(&mut self, wallet: LoadedWallet) -> Command<Message> {
+        let wallet = remember_wallet(&mut self.wallets, wallet);
+        self.wipe_secrets();
+        self.watch_only = wallet.signer.is_none().then_some(wallet.pubkey);
+        self.path = wallet.path;
+        self.signer = wallet.signer;
+        self.balance = wallet.balance;
+        // cached history and tokens belong to the previous wallet
+        self.history = Vec::new();
+        self.token_balances = Vec::new();
+        self.balance_updated = None;
+        if matches!(
+            self.screen,
+            Screen::Unlock | Screen::LaunchLock | Screen::Welcome
+        ) {
+            self.screen = if self.offline {
+                Screen::Offline
+            } else {
+                Screen::Send
+            };
+        }
+        if self.offline {
+            return Command::none();
+        }
+        let restore = match self.restore_screen.take() {
+            Some(screen) => self.update(Message::ShowScreen(screen)),
+            None => Command::none(),
+        };
+        Command::batch(vec![
+            Command::perform(
+                refresh_wallet(wallet.pubkey, self.rpc_client.clone()),
+                Message::WalletRefreshed,
+            ),
+            Command::perform(
+                fetch_receipt_pings(wallet.pubkey, self.rpc_client.clone()),
+                Message::ReceiptsLoaded,
+            ),
+            restore,
+        ])
+    }
+
+    // switches endpoint and fees, and loads the profile's keypair unless the
+    // app is still waiting for a password
+    fn apply_profile(&mut self, profile: Profile) -> Command<Message> {
+        (self.rpc_client, self.rpc_endpoints) =
+            new_rpc_pool(&profile.rpc_url, &self.preferences, &self.config);
+        self.priority_fee = profile.priority_fee;
+        self.preferences.active_profile = profile.name;
+        self.history = Vec::new();
+        self.token_balances = Vec::new();
+        self.simulation = None;
+        self.connection = ConnectionStatus::default();
+        self.network_performance = None;
+        self.cluster_mismatch = None;
+
+        let save = Command::perform(
+            save_preferences(self.preferences.clone()),
+            Message::PreferencesSaved,
+        );
+        let verify = Command::perform(
+            verify_cluster(
+                expected_cluster(&self.preferences.active_profile, &profile.rpc_url),
+                self.rpc_client.clone(),
+            ),
+            Message::ClusterVerified,
+        );
+        let matched = self.match_rpc_clusters();
+        let load = match profile.keypair_path {
+            _ if self.is_locked() => Command::none(),
+            Some(path) => self.update(Message::FileOpened(Ok(path))),
+            None => self.run_command(AppCommand::RefreshBalance),
+        };
+        Command::batch(vec![save, verify, matched, load])
+    }
+
+    fn notify(&self, incoming: Vec<Incoming>) -> Command<Message> {
+        let Some(owner) = self.loaded_pubkey() else {
+            return Command::none();
+        };
+        if self.preferences.mute_incoming {
+            return Command::none();
+        }
+        Command::batch(incoming.into_iter().map(|incoming| {
+            Command::perform(
+                notify_incoming(owner, incoming, self.rpc_client.clone()),
+                Message::IncomingNotified,
+            )
+        }))
+    }
+
+    // nothing active, the sidebar list is left as it is
+    fn clear_active_wallet(&mut self) {
+        self.wipe_secrets();
+        self.signer = None;
+        self.path = None;
+        self.watch_only = None;
+        self.balance = None;
+        self.history = Vec::new();
+        self.token_balances = Vec::new();
+        self.balance_updated = None;
+        self.update_tray();
+    }
+
+    fn stake_account(&self, address: &Pubkey) -> Option<StakeAccount> {
+        self.stake_accounts
+            .iter()
+            .find(|account| account.address == *address)
+            .cloned()
+    }
+
+    // the address the read-only views follow: a watched pubkey or the
+    // signer's, None while neither is loaded
+    fn loaded_pubkey(&self) -> Option<Pubkey> {
+        self.watch_only
+            .or_else(|| self.signer.as_ref().map(|signer| signer.pubkey()))
+    }
+
+    // the keypair to sign with, refused for a watched address or no wallet
+    fn signer(&self) -> Result<Arc<Keypair>, Error> {
+        match &self.signer {
+            Some(signer) => Ok(Arc::clone(signer)),
+            None if self.watch_only.is_some() => Err(Error::WatchOnlyWallet),
+            None => Err(Error::NoKeypairLoaded),
+        }
+    }
+
+    fn has_signer(&self) -> bool {
+        self.signer.is_some()
+    }
+
+    fn is_locked(&self) -> bool {
+        matches!(self.screen, Screen::Unlock | Screen::LaunchLock)
+    }
+
+    // without a keystore or a startup password there's no prompt to lock
+    // behind, the key would just be loaded again on the next start
+    fn can_lock(&self) -> bool {
+        has_keystore() || self.launch_password.is_some()
+    }
+
+    // where a locked app waits: the keystore prompt, or the startup password
+    // that guards the default keypair
+    fn locked_screen(&self) -> Screen {
+        if self.launch_password.is_some() && !has_keystore() {
+            Screen::LaunchLock
+        } else {
+            Screen::Unlock
+        }
+    }
+
+    // screens where a seed phrase or key can be shown or typed in
+    fn shows_secrets(&self) -> bool {
+        matches!(
+            self.screen,
+            Screen::NewMnemonic | Screen::ImportMnemonic | Screen::PasteKeypair
+        ) && !self.is_locked()
+    }
+
+    // the window is kept out of screen captures while one of those screens is
+    // open, and let back in once it's left
+    fn sync_capture_exclusion(&mut self) {
+        let shows_secrets = self.shows_secrets();
+        if !shows_secrets {
+            self.secret_revealed = false;
+        }
+        if shows_secrets != self.secrets_on_screen {
+            self.secrets_on_screen = shows_secrets;
+            self.capture_excluded =
+                exclude_from_capture(&self.title(), shows_secrets) && shows_secrets;
+        }
+    }
+
+    // typed passphrases and seed phrases don't outlive the wallet they were for
+    fn wipe_secrets(&mut self) {
+        self.mnemonic.zeroize();
+        self.mnemonic_passphrase.zeroize();
+        self.mnemonic_backup = None;
+        self.derived_accounts = Vec::new();
+        self.backup_passphrase.zeroize();
+        self.backup_confirmation.zeroize();
+        self.keystore_passphrase.zeroize();
+        self.keystore_confirmation.zeroize();
+        self.launch_password_input.zeroize();
+        self.launch_password_confirmation.zeroize();
+        self.spend_password.zeroize();
+        self.pasted_keypair.zeroize();
+        self.allow_list_form.wipe();
+    }
+
+    fn run_command(&mut self, command: AppCommand) -> Command<Message> {
+        match command {
+            AppCommand::LoadWallet => self.update(Message::Open),
+            AppCommand::CreateWallet => self.update(Message::CreateWallet),
+            AppCommand::RefreshBalance => self.update(Message::RefreshWallet),
+            // requests always carry SOL amounts
+            AppCommand::OpenSendForm { address, amount } => {
+                self.screen = Screen::Send;
+                let amount = parse_amount(&amount)
+                    .map(|lamports| self.amount_unit.format(lamports))
+                    .unwrap_or(amount);
+                self.update(Message::TxValuesHandler((address, amount)))
+            }
+            AppCommand::ClearSendForm => {
+                self.memo = self.preferences.initial_memo();
+                self.update(Message::TxValuesHandler((String::new(), String::new())))
+            }
+            AppCommand::TopUpWallets => self.update(Message::TopUpWallets),
+        }
+    }
+
+    // owned copy of just what the async transaction helpers need
+    fn transfer_values(&self) -> Result<TransferValues, Error> {
+        Ok(TransferValues {
+            signer: self.signer()?,
+            fee_payer: self.fee_payer.clone(),
+            rpc_client: Arc::clone(&self.rpc_client),
+            balance: self.balance,
+            receiver_value: self.receiver_value.clone(),
+            amount_unit: self.amount_unit,
+            priority_fee: self.priority_fee.clone(),
+            memo: self.memo.clone(),
+            allow_list: self.allow_list.clone(),
+            use_nonce: self.use_nonce,
+            nonce_address: self.nonce.address.clone(),
+            keep_rent_exempt: self.keep_rent_exempt,
+            unwrap_wsol: self.unwrap_wsol,
+            jito: self.jito_settings(),
+        })
+    }
+
+    // nothing to estimate for until a keypair is loaded
+    fn estimate_send_fee(&self) -> Command<Message> {
+        match self.transfer_values() {
+            Ok(values) => Command::perform(estimate_fee(values), Message::FeeEstimated),
+            Err(_) => Command::none(),
+        }
+    }
+
+    // the saved tip only follows valid input, what's typed has to match it
+    fn check_jito_tip(&self) -> Result<(), Error> {
+        if self.use_jito && cluster_name(self.rpc_endpoints.primary_url()) == "mainnet-beta" {
+            self.config.network_access.check(Service::Jito)?;
+        }
+        if self.jito_settings().is_none() {
+            return Ok(());
+        }
+        match parse_amount(&self.jito_tip) {
+            Ok(lamports) if lamports >= MIN_JITO_TIP => Ok(()),
+            _ => Err(Error::JitoTipTooLow),
+        }
+    }
+
+    // only on mainnet, a leftover toggle doesn't follow the wallet to devnet,
+    // and never to a block engine that wasn't allowed
+    fn jito_settings(&self) -> Option<JitoSettings> {
+        (self.use_jito
+            && cluster_name(self.rpc_endpoints.primary_url()) == "mainnet-beta"
+            && self.config.network_access.allows(Service::Jito))
+        .then(|| JitoSettings {
+            block_engine: self.config.jito_block_engine.clone(),
+            tip_lamports: self.config.jito_tip_lamports,
+        })
     }
 }
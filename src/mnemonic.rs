@@ -0,0 +1,304 @@
+use std::{path::PathBuf, sync::Arc};
+
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use iced::{
+    widget::{button, column, container, mouse_area, row, text, text_input, Column},
+    Alignment, Element,
+};
+use rand::seq::index::sample;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    derivation_path::DerivationPath,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::{keypair::keypair_from_seed_and_derivation_path, Signer},
+};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+    errors::Error,
+    i18n::{tr, tr_args},
+    loaders::save_keypair_file,
+    palette::accent_color,
+    units::format_sol,
+    Message,
+};
+
+// how many accounts are listed to pick from
+const DERIVED_ACCOUNTS: u32 = 10;
+// words the user has to type back before a generated wallet is saved
+const BACKUP_CHECKS: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct DerivedAccount {
+    pub index: u32,
+    pub pubkey: Pubkey,
+    pub balance: u64,
+}
+
+fn seed_from_phrase(phrase: &str, passphrase: &str) -> Result<Seed, Error> {
+    let normalized = Zeroizing::new(phrase.split_whitespace().collect::<Vec<&str>>().join(" "));
+    let mnemonic = Mnemonic::from_phrase(&normalized, Language::English)
+        .map_err(|_| Error::InvalidMnemonic)?;
+    Ok(Seed::new(&mnemonic, passphrase))
+}
+
+// m/44'/501'/<index>'/0', the path phantom and solflare use
+fn derive_keypair(seed: &Seed, index: u32) -> Result<Keypair, Error> {
+    keypair_from_seed_and_derivation_path(
+        seed.as_bytes(),
+        Some(DerivationPath::new_bip44(Some(index), Some(0))),
+    )
+    .map_err(|_| Error::InvalidMnemonic)
+}
+
+pub async fn derive_accounts(
+    phrase: Zeroizing<String>,
+    passphrase: Zeroizing<String>,
+    rpc_client: Arc<RpcClient>,
+) -> Result<Vec<DerivedAccount>, Error> {
+    let seed = seed_from_phrase(&phrase, &passphrase)?;
+
+    let pubkeys = (0..DERIVED_ACCOUNTS)
+        .map(|index| derive_keypair(&seed, index).map(|keypair| keypair.pubkey()))
+        .collect::<Result<Vec<Pubkey>, Error>>()?;
+
+    let accounts = rpc_client
+        .get_multiple_accounts(&pubkeys)
+        .await
+        .map_err(|_| Error::FetchBalanceError)?;
+
+    Ok(pubkeys
+        .into_iter()
+        .zip(accounts)
+        .enumerate()
+        .map(|(index, (pubkey, account))| DerivedAccount {
+            index: index as u32,
+            pubkey,
+            balance: account.map(|account| account.lamports).unwrap_or(0),
+        })
+        .collect())
+}
+
+pub async fn import_derived_account(
+    phrase: Zeroizing<String>,
+    passphrase: Zeroizing<String>,
+    index: u32,
+) -> Result<PathBuf, Error> {
+    let seed = seed_from_phrase(&phrase, &passphrase)?;
+    save_keypair_file(derive_keypair(&seed, index)?).await
+}
+
+// a freshly generated phrase, nothing is derived or written to disk until
+// the user proves they wrote it down
+#[derive(Debug, Clone)]
+pub struct MnemonicBackup {
+    pub phrase: String,
+    // zero based word positions to ask for, in ascending order
+    pub checks: Vec<usize>,
+    pub answers: Vec<String>,
+    pub written_down: bool,
+}
+
+impl MnemonicBackup {
+    pub fn generate() -> Self {
+        let mnemonic = Mnemonic::new(MnemonicType::Words12, Language::English);
+        let words = mnemonic.phrase().split_whitespace().count();
+        let mut checks = sample(&mut rand::thread_rng(), words, BACKUP_CHECKS).into_vec();
+        checks.sort_unstable();
+
+        Self {
+            phrase: mnemonic.phrase().to_string(),
+            answers: vec![String::new(); checks.len()],
+            checks,
+            written_down: false,
+        }
+    }
+
+    pub fn phrase(&self) -> Zeroizing<String> {
+        Zeroizing::new(self.phrase.clone())
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        let words: Vec<&str> = self.phrase.split_whitespace().collect();
+        self.checks
+            .iter()
+            .zip(&self.answers)
+            .all(|(&position, answer)| answer.trim().eq_ignore_ascii_case(words[position]))
+    }
+}
+
+impl Drop for MnemonicBackup {
+    fn drop(&mut self) {
+        self.phrase.zeroize();
+        self.answers.zeroize();
+    }
+}
+
+// the generated wallet is the first account on the standard path
+pub async fn create_mnemonic_wallet(phrase: Zeroizing<String>) -> Result<PathBuf, Error> {
+    import_derived_account(phrase, Zeroizing::new(String::new()), 0).await
+}
+
+// the words are masked unless the mouse is held down on them, so a glance or a
+// screenshot of the screen doesn't give them away
+pub fn display_mnemonic_backup(
+    backup: &MnemonicBackup,
+    revealed: bool,
+    capture_excluded: bool,
+) -> Element<'static, Message> {
+    let title = text(tr("New wallet from a seed phrase"))
+        .size(14)
+        .style(accent_color());
+
+    if !backup.written_down {
+        let words = backup.phrase.split_whitespace().enumerate().fold(
+            Column::new().spacing(5),
+            |column, (position, word)| {
+                let word = if revealed { word } else { "****" };
+                column.push(text(format!("{:>2}. {}", position + 1, word)).size(14))
+            },
+        );
+        let words = mouse_area(container(words).padding(10))
+            .on_press(Message::SecretHeld(true))
+            .on_release(Message::SecretHeld(false));
+
+        let capture = if capture_excluded {
+            tr("Screenshots and screen recordings leave this window out while the phrase is on screen")
+        } else {
+            tr("This system can't keep the window out of screenshots, make sure nothing is recording the screen")
+        };
+
+        return column![
+            title,
+            text(tr("Write these words down in order and keep them offline. Anyone with them controls the wallet."))
+                .size(12),
+            text(tr("Hold the mouse button down on the words to reveal them")).size(12),
+            words,
+            text(capture).size(12),
+            row![
+                button(tr("I wrote them down")).on_press(Message::MnemonicBackupWritten),
+                button(tr("Generate another")).on_press(Message::GenerateMnemonic),
+            ]
+            .spacing(10),
+        ]
+        .spacing(10)
+        .into();
+    }
+
+    let inputs = backup.checks.iter().zip(&backup.answers).enumerate().fold(
+        Column::new().spacing(5),
+        |column, (index, (position, answer))| {
+            column.push(
+                text_input(
+                    &tr_args("Word #{$position}", &[("position", &(position + 1))]),
+                    answer,
+                )
+                .on_input(move |answer| Message::MnemonicBackupAnswerChanged(index, answer)),
+            )
+        },
+    );
+
+    let create = if backup.is_confirmed() {
+        button(tr("Create wallet")).on_press(Message::CreateMnemonicWallet)
+    } else {
+        button(tr("Create wallet"))
+    };
+
+    column![
+        title,
+        text(tr("Type the requested words to confirm your backup")).size(12),
+        inputs,
+        row![
+            create,
+            button(tr("Show the words again")).on_press(Message::MnemonicBackupShown),
+        ]
+        .spacing(10),
+    ]
+    .spacing(10)
+    .into()
+}
+
+pub fn display_mnemonic_import(
+    phrase: &str,
+    passphrase: &str,
+    accounts: &[DerivedAccount],
+) -> Element<'static, Message> {
+    let phrase_input = text_input(tr("12 or 24 word seed phrase"), phrase)
+        .on_input(Message::MnemonicChanged)
+        .password();
+    let passphrase_input = text_input(tr("BIP39 passphrase (optional)"), passphrase)
+        .on_input(Message::MnemonicPassphraseChanged)
+        .password();
+
+    let accounts_list = accounts
+        .iter()
+        .fold(Column::new().spacing(5), |column, account| {
+            column.push(
+                row![
+                    button(tr("Import")).on_press(Message::ImportDerivedAccount(account.index)),
+                    text(format!("m/44'/501'/{}'/0'", account.index)).size(12),
+                    text(account.pubkey.to_string()).size(12),
+                    text(format!("{} SOL", format_sol(account.balance)))
+                        .size(12)
+                        .style(accent_color()),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            )
+        });
+
+    column![
+        phrase_input,
+        passphrase_input,
+        button(tr("Derive addresses")).on_press(Message::DeriveAccounts),
+        accounts_list
+    ]
+    .spacing(10)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    // the bip39 test phrase at m/44'/501'/0'/0'
+    #[test]
+    fn derives_the_standard_first_account() {
+        let seed = seed_from_phrase(PHRASE, "").unwrap();
+        assert_eq!(
+            derive_keypair(&seed, 0).unwrap().pubkey().to_string(),
+            "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk"
+        );
+    }
+
+    #[test]
+    fn extra_whitespace_in_the_phrase_is_ignored() {
+        let spaced = format!("  {}\n", PHRASE.replace(' ', "   "));
+        let seed = seed_from_phrase(&spaced, "").unwrap();
+        assert_eq!(
+            derive_keypair(&seed, 0).unwrap().pubkey().to_string(),
+            "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk"
+        );
+    }
+
+    #[test]
+    fn the_passphrase_and_index_change_the_account() {
+        let seed = seed_from_phrase(PHRASE, "").unwrap();
+        let first = derive_keypair(&seed, 0).unwrap().pubkey();
+        assert_ne!(derive_keypair(&seed, 1).unwrap().pubkey(), first);
+        let protected = seed_from_phrase(PHRASE, "passphrase").unwrap();
+        assert_ne!(derive_keypair(&protected, 0).unwrap().pubkey(), first);
+    }
+
+    #[test]
+    fn a_bad_checksum_is_rejected() {
+        let phrase = PHRASE.replace("about", "abandon");
+        assert!(matches!(
+            seed_from_phrase(&phrase, ""),
+            Err(Error::InvalidMnemonic)
+        ));
+    }
+}
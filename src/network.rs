@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use iced::{
+    widget::{button, column, row, text},
+    Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use crate::{
+    errors::Error,
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color, success_color},
+    Message,
+};
+
+// slots until a landed transaction usually reaches each commitment level
+const SLOTS_TO_CONFIRMED: f64 = 10.0;
+const SLOTS_TO_FINALIZED: f64 = 32.0;
+const PERFORMANCE_SAMPLES: usize = 10;
+// 400ms is the target, sustained slower slots mean the cluster is struggling
+const DEGRADED_SLOT_TIME: f64 = 0.6;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ConfirmationEta {
+    pub confirmed_secs: f64,
+    pub finalized_secs: f64,
+}
+
+// average slot time from the latest performance samples (one sample per minute)
+pub async fn fetch_slot_time(rpc_client: Arc<RpcClient>) -> Result<f64, Error> {
+    let samples = rpc_client
+        .get_recent_performance_samples(Some(PERFORMANCE_SAMPLES))
+        .await
+        .map_err(|_| Error::FetchNetworkInfoError)?;
+
+    let (slots, secs) = samples.iter().fold((0u64, 0u64), |(slots, secs), sample| {
+        (
+            slots + sample.num_slots,
+            secs + sample.sample_period_secs as u64,
+        )
+    });
+
+    if slots == 0 {
+        return Err(Error::FetchNetworkInfoError);
+    }
+    Ok(secs as f64 / slots as f64)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NetworkPerformance {
+    pub tps: f64,
+    // None on nodes that don't split out vote transactions
+    pub non_vote_tps: Option<f64>,
+    pub slot_time: f64,
+    // micro-lamports per compute unit over the recent slots the node keeps
+    pub average_priority_fee: u64,
+    pub median_priority_fee: u64,
+}
+
+impl NetworkPerformance {
+    pub fn is_degraded(&self) -> bool {
+        self.slot_time > DEGRADED_SLOT_TIME
+    }
+}
+
+pub async fn fetch_network_performance(
+    rpc_client: Arc<RpcClient>,
+) -> Result<NetworkPerformance, Error> {
+    let samples = rpc_client
+        .get_recent_performance_samples(Some(PERFORMANCE_SAMPLES))
+        .await
+        .map_err(|_| Error::FetchNetworkInfoError)?;
+    let secs: u64 = samples
+        .iter()
+        .map(|sample| sample.sample_period_secs as u64)
+        .sum();
+    let slots: u64 = samples.iter().map(|sample| sample.num_slots).sum();
+    if secs == 0 || slots == 0 {
+        return Err(Error::FetchNetworkInfoError);
+    }
+    let transactions: u64 = samples.iter().map(|sample| sample.num_transactions).sum();
+    let non_vote_transactions: Option<u64> = samples
+        .iter()
+        .map(|sample| sample.num_non_vote_transaction)
+        .sum();
+
+    let mut fees: Vec<u64> = rpc_client
+        .get_recent_prioritization_fees(&[])
+        .await
+        .map_err(|_| Error::FetchNetworkInfoError)?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    fees.sort_unstable();
+    let average_priority_fee = match fees.len() {
+        0 => 0,
+        count => fees.iter().sum::<u64>() / count as u64,
+    };
+    let median_priority_fee = fees.get(fees.len() / 2).copied().unwrap_or(0);
+
+    Ok(NetworkPerformance {
+        tps: transactions as f64 / secs as f64,
+        non_vote_tps: non_vote_transactions.map(|count| count as f64 / secs as f64),
+        slot_time: secs as f64 / slots as f64,
+        average_priority_fee,
+        median_priority_fee,
+    })
+}
+
+pub async fn estimate_confirmation_eta(
+    rpc_client: Arc<RpcClient>,
+) -> Result<ConfirmationEta, Error> {
+    let slot_time = fetch_slot_time(rpc_client).await?;
+
+    Ok(ConfirmationEta {
+        confirmed_secs: slot_time * SLOTS_TO_CONFIRMED,
+        finalized_secs: slot_time * SLOTS_TO_FINALIZED,
+    })
+}
+
+pub fn display_network_performance(
+    performance: &Option<NetworkPerformance>,
+) -> Element<'static, Message> {
+    let title = text(tr("Network performance"))
+        .size(14)
+        .style(accent_color());
+    let refresh = button(tr("Refresh")).on_press(Message::RefreshNetworkPerformance);
+
+    let Some(performance) = performance else {
+        return column![title, text(tr("Loading...")).size(14), refresh]
+            .spacing(10)
+            .into();
+    };
+
+    let tps = match performance.non_vote_tps {
+        Some(non_vote_tps) => tr_args(
+            "{$tps} TPS ({$non_vote} excluding votes)",
+            &[
+                ("tps", &format!("{:.0}", performance.tps)),
+                ("non_vote", &format!("{:.0}", non_vote_tps)),
+            ],
+        ),
+        None => format!("{:.0} TPS", performance.tps),
+    };
+    let state = if performance.is_degraded() {
+        text(tr_args(
+            "Degraded: slots take {$millis}ms, consider a higher priority fee",
+            &[("millis", &format!("{:.0}", performance.slot_time * 1000.0))],
+        ))
+        .size(14)
+        .style(error_color())
+    } else {
+        text(tr_args(
+            "Healthy: slots take {$millis}ms",
+            &[("millis", &format!("{:.0}", performance.slot_time * 1000.0))],
+        ))
+        .size(14)
+        .style(success_color())
+    };
+    let fees = text(tr_args(
+        "Priority fees over recent slots: median {$median} / average {$average} micro-lamports per CU",
+        &[
+            ("median", &performance.median_priority_fee),
+            ("average", &performance.average_priority_fee),
+        ],
+    ))
+    .size(14);
+
+    column![
+        title,
+        state,
+        text(tps).size(14),
+        fees,
+        row![
+            button(tr("Use median priority fee")).on_press(Message::PriorityFeeChanged(
+                performance.median_priority_fee.to_string()
+            )),
+            refresh,
+        ]
+        .spacing(10)
+    ]
+    .spacing(10)
+    .into()
+}
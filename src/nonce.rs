@@ -0,0 +1,412 @@
+use std::{str::FromStr, sync::Arc};
+
+use iced::{
+    widget::{button, column, row, text, text_input, Column},
+    Alignment, Command, Element,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    account::Account,
+    account_utils::StateMut,
+    hash::Hash,
+    native_token::LAMPORTS_PER_SOL,
+    nonce::{state::Versions, State},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+};
+
+use crate::{
+    errors::{report, Error},
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    transaction::send_instructions,
+    units::parse_amount,
+    Message,
+};
+
+// offset of the authority: versions tag (4) + state tag (4)
+const AUTHORITY_OFFSET: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct NonceAccountInfo {
+    pub address: Pubkey,
+    pub authority: Pubkey,
+    pub blockhash: Hash,
+    pub lamports: u64,
+}
+
+// the nonce screen: the account being managed and the wallet's other ones
+#[derive(Debug, Default)]
+pub struct NonceForm {
+    pub address: String,
+    pub account: Option<NonceAccountInfo>,
+    pub withdraw_amount: String,
+    pub new_authority: String,
+    pub accounts: Vec<NonceAccountInfo>,
+}
+
+impl NonceForm {
+    pub fn refresh(&self, rpc_client: Arc<RpcClient>) -> Command<Message> {
+        Command::perform(
+            fetch_nonce_account(self.address.clone(), rpc_client),
+            Message::NonceAccountLoaded,
+        )
+    }
+
+    pub fn refresh_accounts(
+        &self,
+        authority: Pubkey,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        Command::perform(
+            fetch_nonce_accounts(authority, rpc_client),
+            Message::NonceAccountsLoaded,
+        )
+    }
+
+    // create, advance, withdraw and authorize, the allow list is checked before
+    pub fn submit(
+        &self,
+        action: Message,
+        signer: Arc<Keypair>,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        match action {
+            Message::CreateNonceAccount => Command::perform(
+                create_nonce_account(signer, rpc_client),
+                Message::NonceAccountCreated,
+            ),
+            Message::AdvanceNonceAccount => Command::perform(
+                advance_nonce_account(signer, rpc_client, self.address.clone()),
+                Message::NonceAccountUpdated,
+            ),
+            Message::WithdrawNonceAccount => Command::perform(
+                withdraw_nonce_account(
+                    signer,
+                    rpc_client,
+                    self.address.clone(),
+                    self.withdraw_amount.clone(),
+                ),
+                Message::NonceAccountUpdated,
+            ),
+            Message::AuthorizeNonceAccount => Command::perform(
+                authorize_nonce_account(
+                    signer,
+                    rpc_client,
+                    self.address.clone(),
+                    self.new_authority.clone(),
+                ),
+                Message::NonceAccountUpdated,
+            ),
+            _ => Command::none(),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::NonceAddressChanged(address) => {
+                self.address = address;
+                self.account = None;
+            }
+            Message::SelectNonceAccount(address) => {
+                self.address = address.to_string();
+                self.account = None;
+            }
+            Message::NonceAccountLoaded(Ok(account)) => self.account = Some(account),
+            Message::NonceAccountsLoaded(Ok(accounts)) => self.accounts = accounts,
+            Message::NonceAccountCreated(Ok((_, address))) => self.address = address.to_string(),
+            Message::NonceWithdrawAmountChanged(amount) => self.withdraw_amount = amount,
+            Message::NonceAuthorityChanged(authority) => self.new_authority = authority,
+            Message::NonceAccountUpdated(Ok(_)) => {
+                self.withdraw_amount = String::new();
+                self.new_authority = String::new();
+            }
+            Message::NonceAccountLoaded(Err(error))
+            | Message::NonceAccountsLoaded(Err(error))
+            | Message::NonceAccountCreated(Err(error))
+            | Message::NonceAccountUpdated(Err(error)) => return report(error),
+            _ => {}
+        }
+        Command::none()
+    }
+}
+
+pub async fn fetch_nonce_account(
+    address: String,
+    rpc_client: Arc<RpcClient>,
+) -> Result<NonceAccountInfo, Error> {
+    let address = Pubkey::from_str(&address).map_err(|_| Error::InvalidPubKeyLen)?;
+
+    let account = rpc_client
+        .get_account(&address)
+        .await
+        .map_err(|_| Error::FetchNonceError)?;
+
+    nonce_account_info(address, &account)
+}
+
+fn nonce_account_info(address: Pubkey, account: &Account) -> Result<NonceAccountInfo, Error> {
+    if account.owner != solana_sdk::system_program::id() {
+        return Err(Error::InvalidNonceAccount);
+    }
+
+    let versions: Versions = account.state().map_err(|_| Error::InvalidNonceAccount)?;
+    match versions.state() {
+        State::Initialized(data) => Ok(NonceAccountInfo {
+            address,
+            authority: data.authority,
+            blockhash: data.blockhash(),
+            lamports: account.lamports,
+        }),
+        State::Uninitialized => Err(Error::InvalidNonceAccount),
+    }
+}
+
+// the blockhash the nonce account holds right now, None once it's closed
+pub async fn current_nonce(
+    address: &Pubkey,
+    rpc_client: &RpcClient,
+) -> Result<Option<Hash>, Error> {
+    let account = rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await
+        .map_err(|_| Error::FetchNonceError)?
+        .value;
+
+    account
+        .map(|account| nonce_account_info(*address, &account).map(|info| info.blockhash))
+        .transpose()
+}
+
+// every nonce account the wallet is the authority of, whoever created it
+pub async fn fetch_nonce_accounts(
+    authority: Pubkey,
+    rpc_client: Arc<RpcClient>,
+) -> Result<Vec<NonceAccountInfo>, Error> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize(State::size() as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                AUTHORITY_OFFSET,
+                &authority.to_bytes(),
+            )),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = rpc_client
+        .get_program_accounts_with_config(&solana_sdk::system_program::id(), config)
+        .await
+        .map_err(|_| Error::FetchNonceError)?;
+
+    Ok(accounts
+        .into_iter()
+        .filter_map(|(address, account)| nonce_account_info(address, &account).ok())
+        .collect())
+}
+
+// creates a rent exempt nonce account with the signer as authority
+pub async fn create_nonce_account(
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+) -> Result<(String, Pubkey), Error> {
+    let nonce_keypair = Keypair::new();
+
+    let rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(State::size())
+        .await
+        .map_err(|_| Error::FetchNonceError)?;
+
+    let instructions = system_instruction::create_nonce_account(
+        &signer.pubkey(),
+        &nonce_keypair.pubkey(),
+        &signer.pubkey(),
+        rent,
+    );
+
+    let signature =
+        send_instructions(&rpc_client, &[&*signer, &nonce_keypair], &instructions).await?;
+    Ok((signature, nonce_keypair.pubkey()))
+}
+
+pub async fn advance_nonce_account(
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+    address: String,
+) -> Result<String, Error> {
+    let address = Pubkey::from_str(&address).map_err(|_| Error::InvalidPubKeyLen)?;
+    let advance_ix = system_instruction::advance_nonce_account(&address, &signer.pubkey());
+
+    send_instructions(&rpc_client, &[&*signer], &[advance_ix]).await
+}
+
+// the new authority is the only one able to advance, withdraw or authorize
+// afterwards, this wallet loses the account
+pub async fn authorize_nonce_account(
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+    address: String,
+    new_authority: String,
+) -> Result<String, Error> {
+    let address = Pubkey::from_str(&address).map_err(|_| Error::InvalidPubKeyLen)?;
+    let new_authority =
+        Pubkey::from_str(new_authority.trim()).map_err(|_| Error::InvalidPubKeyLen)?;
+    let authorize_ix =
+        system_instruction::authorize_nonce_account(&address, &signer.pubkey(), &new_authority);
+
+    send_instructions(&rpc_client, &[&*signer], &[authorize_ix]).await
+}
+
+// withdrawn lamports go back to the signer
+pub async fn withdraw_nonce_account(
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+    address: String,
+    amount: String,
+) -> Result<String, Error> {
+    let address = Pubkey::from_str(&address).map_err(|_| Error::InvalidPubKeyLen)?;
+    let lamports = parse_amount(&amount)?;
+
+    let withdraw_ix = system_instruction::withdraw_nonce_account(
+        &address,
+        &signer.pubkey(),
+        &signer.pubkey(),
+        lamports,
+    );
+
+    send_instructions(&rpc_client, &[&*signer], &[withdraw_ix]).await
+}
+
+fn display_nonce_accounts(accounts: &[NonceAccountInfo]) -> Element<'static, Message> {
+    let header = row![
+        text(tr("Your nonce accounts"))
+            .size(14)
+            .style(accent_color()),
+        button(text(tr("Refresh")).size(12)).on_press(Message::RefreshNonceAccounts),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    if accounts.is_empty() {
+        return column![
+            header,
+            text(tr("No nonce accounts with this wallet as authority")).size(12)
+        ]
+        .spacing(5)
+        .into();
+    }
+
+    let list = accounts
+        .iter()
+        .fold(Column::new().spacing(5), |column, account| {
+            column.push(
+                row![
+                    text(tr_args(
+                        "{$address}: {$balance} SOL, blockhash {$blockhash}",
+                        &[
+                            ("address", &account.address),
+                            (
+                                "balance",
+                                &format!(
+                                    "{:.9}",
+                                    account.lamports as f64 / LAMPORTS_PER_SOL as f64
+                                ),
+                            ),
+                            ("blockhash", &account.blockhash),
+                        ],
+                    ))
+                    .size(12),
+                    button(text(tr("Use")).size(12))
+                        .on_press(Message::SelectNonceAccount(account.address)),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            )
+        });
+
+    column![header, list].spacing(5).into()
+}
+
+pub fn display_nonce_account(
+    address: &str,
+    nonce_account: &Option<NonceAccountInfo>,
+    withdraw_amount: &str,
+    new_authority: &str,
+    accounts: &[NonceAccountInfo],
+) -> Element<'static, Message> {
+    let address_input =
+        text_input(tr("Nonce account address"), address).on_input(Message::NonceAddressChanged);
+
+    let actions = row![
+        button(tr("Create nonce account")).on_press(Message::CreateNonceAccount),
+        button(tr("Refresh")).on_press(Message::RefreshNonceAccount),
+        button(tr("Advance")).on_press(Message::AdvanceNonceAccount),
+    ]
+    .spacing(10);
+
+    let details = match nonce_account {
+        Some(nonce_account) => column![
+            text(nonce_account.address.to_string())
+                .size(14)
+                .style(accent_color()),
+            text(tr_args(
+                "Authority: {$authority}",
+                &[("authority", &nonce_account.authority)],
+            ))
+            .size(14),
+            text(tr_args(
+                "Stored blockhash: {$blockhash}",
+                &[("blockhash", &nonce_account.blockhash)],
+            ))
+            .size(14),
+            text(tr_args(
+                "Balance: {$balance} SOL",
+                &[(
+                    "balance",
+                    &format!(
+                        "{:.9}",
+                        nonce_account.lamports as f64 / LAMPORTS_PER_SOL as f64
+                    ),
+                )],
+            ))
+            .size(14),
+        ],
+        None => column![text(tr("No nonce account loaded")).size(14)],
+    }
+    .spacing(5);
+
+    let withdraw = row![
+        text_input(tr("SOL to withdraw"), withdraw_amount)
+            .on_input(Message::NonceWithdrawAmountChanged),
+        button(tr("Withdraw")).on_press(Message::WithdrawNonceAccount),
+    ]
+    .spacing(10);
+
+    let authorize = row![
+        text_input(tr("New authority address"), new_authority)
+            .on_input(Message::NonceAuthorityChanged),
+        button(tr("Transfer authority")).on_press(Message::AuthorizeNonceAccount),
+    ]
+    .spacing(10);
+
+    column![
+        display_nonce_accounts(accounts),
+        address_input,
+        actions,
+        details,
+        withdraw,
+        authorize
+    ]
+    .spacing(10)
+    .into()
+}
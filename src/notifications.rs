@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use notify_rust::Notification;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{EncodedTransaction, UiMessage, UiTransactionEncoding};
+
+use crate::{
+    errors::Error, history::fetch_transaction, i18n::tr_args, snapshots::SnapshotRow,
+    units::format_sol,
+};
+
+// the credit usually sits in the newest signatures, a few are enough
+const SENDER_LOOKUP_DEPTH: usize = 5;
+
+// one asset that went up since the last poll
+#[derive(Debug, Clone)]
+pub struct Incoming {
+    // "SOL" or the token mint
+    pub asset: String,
+    pub amount: String,
+    // lamports received, only known for SOL
+    pub lamports: Option<u64>,
+}
+
+impl Incoming {
+    pub fn sol(lamports: u64) -> Self {
+        Self {
+            asset: String::from("SOL"),
+            amount: format_sol(lamports),
+            lamports: Some(lamports),
+        }
+    }
+}
+
+// token amounts are compared as the ui strings the rpc returns
+pub fn incoming_tokens(before: &[SnapshotRow], after: &[SnapshotRow]) -> Vec<Incoming> {
+    after
+        .iter()
+        .filter_map(|row| {
+            let new: f64 = row.amount.parse().ok()?;
+            let old: f64 = before
+                .iter()
+                .find(|known| known.asset == row.asset)
+                .and_then(|known| known.amount.parse().ok())
+                .unwrap_or(0.0);
+            (new > old).then(|| Incoming {
+                asset: row.asset.clone(),
+                amount: format!("{}", new - old),
+                lamports: None,
+            })
+        })
+        .collect()
+}
+
+// the account that paid in a recent transaction crediting `owner` with
+// exactly `lamports`, token transfers don't touch the owner's signatures
+async fn find_sender(owner: &Pubkey, lamports: u64, rpc_client: &RpcClient) -> Option<String> {
+    let signatures = rpc_client
+        .get_signatures_for_address_with_config(
+            owner,
+            GetConfirmedSignaturesForAddress2Config {
+                before: None,
+                until: None,
+                limit: Some(SENDER_LOOKUP_DEPTH),
+                commitment: None,
+            },
+        )
+        .await
+        .ok()?;
+
+    for status in signatures.into_iter().filter(|status| status.err.is_none()) {
+        let Ok(confirmed) = fetch_transaction(
+            &status.signature,
+            UiTransactionEncoding::JsonParsed,
+            rpc_client,
+            None,
+        )
+        .await
+        else {
+            continue;
+        };
+        let Some(meta) = confirmed.transaction.meta else {
+            continue;
+        };
+        let EncodedTransaction::Json(transaction) = confirmed.transaction.transaction else {
+            continue;
+        };
+        let UiMessage::Parsed(message) = transaction.message else {
+            continue;
+        };
+
+        let change = |index: usize| {
+            meta.post_balances.get(index).copied().unwrap_or(0) as i128
+                - meta.pre_balances.get(index).copied().unwrap_or(0) as i128
+        };
+        let owner = owner.to_string();
+        let credited = message
+            .account_keys
+            .iter()
+            .position(|account| account.pubkey == owner)
+            .is_some_and(|index| change(index) == lamports as i128);
+        if !credited {
+            continue;
+        }
+
+        // whoever lost the most lamports in it sent them
+        return message
+            .account_keys
+            .iter()
+            .enumerate()
+            .filter(|(_, account)| account.pubkey != owner)
+            .min_by_key(|(index, _)| change(*index))
+            .map(|(_, account)| account.pubkey.clone());
+    }
+    None
+}
+
+// a native notification, shown by the os even while the window is minimized
+pub async fn notify_incoming(
+    owner: Pubkey,
+    incoming: Incoming,
+    rpc_client: Arc<RpcClient>,
+) -> Result<(), Error> {
+    let sender = match incoming.lamports {
+        Some(lamports) => find_sender(&owner, lamports, &rpc_client).await,
+        None => None,
+    };
+    let body = match sender {
+        Some(sender) => tr_args("From {$sender}", &[("sender", &sender)]),
+        None => tr_args("To {$owner}", &[("owner", &owner)]),
+    };
+
+    Notification::new()
+        .summary(&tr_args(
+            "Received {$amount} {$asset}",
+            &[("amount", &incoming.amount), ("asset", &incoming.asset)],
+        ))
+        .body(&body)
+        .appname("Solana Executable Application")
+        .show()
+        .map(|_| ())
+        .map_err(|_| Error::NotificationError)
+}
@@ -0,0 +1,169 @@
+use std::{path::PathBuf, sync::Arc};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use iced::{
+    widget::{button, column, text},
+    Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    clock::MAX_PROCESSING_AGE,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    system_instruction::SystemInstruction,
+    system_program,
+    transaction::Transaction,
+};
+
+use crate::{
+    errors::Error,
+    files::{pick_transaction_file, save_file},
+    i18n::tr,
+    palette::accent_color,
+    transaction::{broadcast_transaction, build_unsigned_transfer, Expiry, TransferValues},
+    Message,
+};
+
+// files hold a base64 encoded, bincode serialized transaction
+pub fn encode_transaction(tx: &Transaction) -> Result<String, Error> {
+    let bytes = bincode::serialize(tx).map_err(|_| Error::InvalidTransactionFile)?;
+    Ok(STANDARD.encode(bytes))
+}
+
+pub fn decode_transaction(content: &str) -> Result<Transaction, Error> {
+    let bytes = STANDARD
+        .decode(content.trim())
+        .map_err(|_| Error::InvalidTransactionFile)?;
+    bincode::deserialize(&bytes).map_err(|_| Error::InvalidTransactionFile)
+}
+
+pub async fn read_transaction_file() -> Result<Transaction, Error> {
+    let path = pick_transaction_file().await?;
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|_| Error::InvalidTransactionFile)?;
+    decode_transaction(&content)
+}
+
+pub async fn write_transaction_file(tx: &Transaction, file_name: &str) -> Result<PathBuf, Error> {
+    let path = save_file(file_name).await?;
+    tokio::fs::write(&path, encode_transaction(tx)?)
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(path)
+}
+
+// connected machine: build the transfer from the send form without signing it,
+// pair it with a durable nonce so it doesn't expire before being broadcast
+pub async fn export_unsigned_transfer(values: TransferValues) -> Result<PathBuf, Error> {
+    let (tx, _) = build_unsigned_transfer(&values).await?;
+    write_transaction_file(&tx, "unsigned-transaction.b64").await
+}
+
+// required signers whose signature slot is still empty
+pub fn missing_signers(tx: &Transaction) -> Vec<Pubkey> {
+    tx.signatures
+        .iter()
+        .zip(tx.message.account_keys.iter())
+        .filter(|(signature, _)| **signature == Signature::default())
+        .map(|(_, signer)| *signer)
+        .collect()
+}
+
+// air-gapped machine: no rpc access needed, adds our signature and keeps the
+// ones already present so multi-signer transactions can go from hand to hand
+pub async fn sign_transaction_file(signer: Arc<Keypair>) -> Result<(PathBuf, Vec<Pubkey>), Error> {
+    let mut tx = read_transaction_file().await?;
+    let blockhash = tx.message.recent_blockhash;
+
+    tx.try_partial_sign(&[&*signer], blockhash)
+        .map_err(|_| Error::SignerNotRequired)?;
+
+    let missing = missing_signers(&tx);
+    let file_name = if missing.is_empty() {
+        "signed-transaction.b64"
+    } else {
+        "partially-signed-transaction.b64"
+    };
+
+    let path = write_transaction_file(&tx, file_name).await?;
+    Ok((path, missing))
+}
+
+pub async fn broadcast_transaction_file(rpc_client: Arc<RpcClient>) -> Result<String, Error> {
+    let tx = read_transaction_file().await?;
+    if !missing_signers(&tx).is_empty() {
+        return Err(Error::MissingSignatures);
+    }
+    tx.verify().map_err(|_| Error::MissingSignatures)?;
+
+    // nonce transactions start with an advance instruction and last until the
+    // nonce moves on, otherwise the blockhash has to still be valid
+    let nonce_account = tx
+        .message
+        .instructions
+        .first()
+        .filter(|ix| tx.message.account_keys[ix.program_id_index as usize] == system_program::id())
+        .filter(|ix| {
+            matches!(
+                bincode::deserialize::<SystemInstruction>(&ix.data),
+                Ok(SystemInstruction::AdvanceNonceAccount)
+            )
+        })
+        .and_then(|ix| ix.accounts.first())
+        .map(|index| tx.message.account_keys[*index as usize]);
+
+    let expiry = if let Some(account) = nonce_account {
+        Expiry::Nonce {
+            account,
+            blockhash: tx.message.recent_blockhash,
+        }
+    } else {
+        let is_valid = rpc_client
+            .is_blockhash_valid(&tx.message.recent_blockhash, rpc_client.commitment())
+            .await
+            .map_err(|_| Error::FetchBlockhashError)?;
+        if !is_valid {
+            return Err(Error::BlockhashExpired);
+        }
+        let block_height = rpc_client
+            .get_block_height()
+            .await
+            .map_err(|_| Error::FetchBlockhashError)?;
+        Expiry::BlockHeight(block_height + MAX_PROCESSING_AGE as u64)
+    };
+
+    broadcast_transaction(&rpc_client, &tx, expiry).await
+}
+
+pub fn display_offline(status: &str, offline: bool) -> Element<'static, Message> {
+    let mode = if offline {
+        text(tr("Signing-only mode: the app won't contact any RPC")).style(accent_color())
+    } else {
+        text(tr("Start the app with --offline on the air-gapped machine"))
+    }
+    .size(14);
+
+    column![
+        mode,
+        text(tr(
+            "1. Connected machine: export the send form as an unsigned transaction"
+        ))
+        .size(14),
+        button(tr("Export unsigned transaction")).on_press(Message::ExportUnsignedTransaction),
+        text(tr(
+            "2. Air-gapped machine: sign the exported file with the loaded keypair"
+        ))
+        .size(14),
+        text(tr(
+            "   Partially signed files can be signed again by every other signer"
+        ))
+        .size(12),
+        button(tr("Sign transaction file")).on_press(Message::SignTransactionFile),
+        text(tr("3. Connected machine: broadcast the signed file")).size(14),
+        button(tr("Broadcast signed transaction")).on_press(Message::BroadcastTransactionFile),
+        text(status.to_string()).size(14),
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,90 @@
+use std::sync::RwLock;
+
+use iced::{theme, Color, Theme};
+
+use crate::config::AppConfig;
+
+// colors every view draws with, the views are free functions so they read
+// the current palette instead of having it passed down
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub accent: Color,
+    pub success: Color,
+    pub error: Color,
+    pub warning: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            accent: Color::from_rgb8(0x30, 0xcb, 0xf2),
+            success: Color::from_rgb8(0x14, 0xf1, 0x95),
+            error: Color::from_rgb8(0xff, 0x00, 0x00),
+            warning: Color::from_rgb8(0xff, 0xa5, 0x00),
+        }
+    }
+}
+
+impl Palette {
+    // an empty or unreadable hex keeps the default for that color
+    pub fn from_config(config: &AppConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            accent: parse_hex(&config.accent_color).unwrap_or(defaults.accent),
+            success: parse_hex(&config.success_color).unwrap_or(defaults.success),
+            error: parse_hex(&config.error_color).unwrap_or(defaults.error),
+            warning: defaults.warning,
+        }
+    }
+
+    // the light or dark base with buttons and highlights in the accent color
+    pub fn theme(&self, dark: bool) -> Theme {
+        let base = if dark { Theme::Dark } else { Theme::Light };
+        Theme::custom(theme::Palette {
+            primary: self.accent,
+            success: self.success,
+            danger: self.error,
+            ..base.palette()
+        })
+    }
+}
+
+static PALETTE: RwLock<Option<Palette>> = RwLock::new(None);
+
+// "#30cbf2" or "30cbf2"
+pub fn parse_hex(value: &str) -> Option<Color> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some(Color::from_rgb8(
+        (rgb >> 16) as u8,
+        (rgb >> 8) as u8,
+        rgb as u8,
+    ))
+}
+
+pub fn set_palette(palette: Palette) {
+    *PALETTE.write().unwrap() = Some(palette);
+}
+
+fn palette() -> Palette {
+    PALETTE.read().unwrap().unwrap_or_default()
+}
+
+pub fn accent_color() -> Color {
+    palette().accent
+}
+
+pub fn success_color() -> Color {
+    palette().success
+}
+
+pub fn error_color() -> Color {
+    palette().error
+}
+
+pub fn warning_color() -> Color {
+    palette().warning
+}
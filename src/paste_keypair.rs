@@ -0,0 +1,48 @@
+use iced::{
+    widget::{button, column, text, text_input},
+    Element,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use zeroize::Zeroizing;
+
+use crate::{
+    errors::Error,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+// the same "[12,34,...]" array the solana cli writes to id.json
+pub fn keypair_from_json(content: &str) -> Result<Keypair, Error> {
+    let bytes: Zeroizing<Vec<u8>> = Zeroizing::new(
+        serde_json::from_str(content.trim()).map_err(|_| Error::InvalidKeypairJson)?,
+    );
+    Keypair::from_bytes(&bytes).map_err(|_| Error::InvalidKeypairJson)
+}
+
+pub fn display_paste_keypair(content: &str, pasted: Option<Pubkey>) -> Element<'static, Message> {
+    let title = text(tr("Paste keypair")).size(14).style(accent_color());
+
+    let input = text_input(tr("[12,34,...] keypair bytes"), content)
+        .on_input(Message::PastedKeypairChanged)
+        .on_submit(Message::ImportPastedKeypair)
+        .password();
+
+    let status = match pasted {
+        Some(pubkey) => text(tr_args(
+            "{$pubkey} is loaded from memory only, it's gone once the app closes unless it's moved to the keystore below",
+            &[("pubkey", &pubkey)],
+        ))
+        .size(12),
+        None => text(tr("Nothing is written to disk, the text is wiped once it's imported")).size(12),
+    };
+
+    column![
+        title,
+        input,
+        button(tr("Import")).on_press(Message::ImportPastedKeypair),
+        status
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use crate::{
+    allowlist::AllowList,
+    batch::{parse_row, BatchRow, BatchStatus},
+    errors::Error,
+    files::{pick_csv_file, save_file},
+};
+
+// one `address,amount` pair per line, amounts in SOL. blank lines, `#`
+// comments and a leading header row are ignored
+pub fn parse_csv(content: &str) -> Result<Vec<BatchRow>, Error> {
+    let mut rows = Vec::new();
+
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (address, amount) = line.split_once(',').ok_or(Error::InvalidPayrollFile)?;
+        if line_number == 0 && address.trim().eq_ignore_ascii_case("address") {
+            continue;
+        }
+
+        rows.push(BatchRow {
+            address: address.trim().to_string(),
+            amount: amount.trim().to_string(),
+            status: BatchStatus::Pending,
+        });
+    }
+
+    if rows.is_empty() {
+        return Err(Error::InvalidPayrollFile);
+    }
+    Ok(rows)
+}
+
+// flags every bad row instead of stopping at the first one
+pub fn validate_rows(rows: &mut [BatchRow], allow_list: &Option<AllowList>) {
+    for row in rows.iter_mut() {
+        if let Err(error) = parse_row(row, allow_list) {
+            row.status = BatchStatus::Invalid(error);
+        }
+    }
+}
+
+pub async fn read_payroll_file() -> Result<Vec<BatchRow>, Error> {
+    let path = pick_csv_file().await?;
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|_| Error::InvalidPayrollFile)?;
+    parse_csv(&content)
+}
+
+pub fn results_csv(rows: &[BatchRow]) -> String {
+    let mut csv = String::from("address,amount,status,signature\n");
+
+    for row in rows {
+        let (status, signature) = match &row.status {
+            BatchStatus::Pending => (String::from("pending"), String::new()),
+            BatchStatus::Sending => (String::from("sending"), String::new()),
+            BatchStatus::Sent(signature) => (String::from("sent"), signature.clone()),
+            // localized messages can carry commas, they'd split the column
+            BatchStatus::Failed(error) => (
+                format!("failed: {}", error).replace(',', ";"),
+                String::new(),
+            ),
+            BatchStatus::Invalid(error) => (
+                format!("invalid: {}", error).replace(',', ";"),
+                String::new(),
+            ),
+        };
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            row.address, row.amount, status, signature
+        ));
+    }
+
+    csv
+}
+
+pub async fn export_payroll_results(rows: Vec<BatchRow>) -> Result<PathBuf, Error> {
+    let path = save_file("payroll-results.csv").await?;
+    tokio::fs::write(&path, results_csv(&rows))
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(path)
+}
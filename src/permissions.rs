@@ -0,0 +1,109 @@
+use iced::{
+    widget::{checkbox, column, text, Column},
+    Element,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{errors::Error, i18n::tr, Message};
+
+// the third-party hosts the app can talk to besides the rpc nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Service {
+    CoinGecko,
+    Pyth,
+    Jupiter,
+    Jito,
+}
+
+impl Service {
+    pub const ALL: [Service; 4] = [
+        Service::CoinGecko,
+        Service::Pyth,
+        Service::Jupiter,
+        Service::Jito,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Service::CoinGecko => "CoinGecko (api.coingecko.com): fiat prices",
+            Service::Pyth => "Pyth (hermes.pyth.network): SOL/USD when CoinGecko fails",
+            Service::Jupiter => "Jupiter (jup.ag): swap quotes, swaps, DCA and verified tokens",
+            Service::Jito => "Jito block engine: sending transfers as bundles",
+        }
+    }
+}
+
+// nothing is contacted until it's allowed here, every host learns the ip and,
+// for jupiter and jito, the wallet address
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkPermissions {
+    pub coingecko: bool,
+    pub pyth: bool,
+    pub jupiter: bool,
+    pub jito: bool,
+}
+
+impl NetworkPermissions {
+    pub fn allows(&self, service: Service) -> bool {
+        match service {
+            Service::CoinGecko => self.coingecko,
+            Service::Pyth => self.pyth,
+            Service::Jupiter => self.jupiter,
+            Service::Jito => self.jito,
+        }
+    }
+
+    pub fn set(&mut self, service: Service, allowed: bool) {
+        match service {
+            Service::CoinGecko => self.coingecko = allowed,
+            Service::Pyth => self.pyth = allowed,
+            Service::Jupiter => self.jupiter = allowed,
+            Service::Jito => self.jito = allowed,
+        }
+    }
+
+    pub fn check(&self, service: Service) -> Result<(), Error> {
+        if self.allows(service) {
+            Ok(())
+        } else {
+            Err(Error::ServiceNotAllowed)
+        }
+    }
+
+    // pyth only knows sol in usd
+    pub fn allows_prices(&self, currency: &str) -> bool {
+        self.coingecko || (self.pyth && currency == "usd")
+    }
+}
+
+pub fn display_permissions(
+    permissions: &NetworkPermissions,
+    block_engine: &str,
+) -> Element<'static, Message> {
+    let toggles = Service::ALL
+        .into_iter()
+        .fold(Column::new().spacing(10), |column, service| {
+            column.push(
+                checkbox(
+                    tr(service.label()),
+                    permissions.allows(service),
+                    move |allowed| Message::NetworkPermissionToggled(service, allowed),
+                )
+                .size(16)
+                .text_size(14),
+            )
+        });
+
+    column![
+        text(tr("Network access")).size(20),
+        text(tr(
+            "Besides the RPC nodes the app only contacts the services allowed below"
+        ))
+        .size(14),
+        toggles,
+        text(format!("{} {}", tr("Block engine:"), block_engine)).size(12),
+    ]
+    .spacing(15)
+    .into()
+}
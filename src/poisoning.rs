@@ -0,0 +1,103 @@
+use std::{fs, path::PathBuf, str::FromStr};
+
+use iced::{
+    widget::{button, column, row, text},
+    Element,
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    errors::Error,
+    files::app_config_dir,
+    i18n::{tr, tr_args},
+    palette::error_color,
+    Message,
+};
+
+const KNOWN_RECIPIENTS_FILE: &str = "recipients.txt";
+const MAX_KNOWN_RECIPIENTS: usize = 200;
+// poisoned addresses are ground to match what wallets usually show
+const MATCHING_CHARS: usize = 4;
+
+fn known_recipients_path() -> PathBuf {
+    app_config_dir().join(KNOWN_RECIPIENTS_FILE)
+}
+
+// addresses this wallet actually paid before, one per line
+pub fn load_known_recipients() -> Vec<Pubkey> {
+    fs::read_to_string(known_recipients_path())
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| Pubkey::from_str(line.trim()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// most recent first, older entries fall off the end
+pub fn remember_recipients(known: &mut Vec<Pubkey>, recipients: &[Pubkey]) {
+    for recipient in recipients {
+        known.retain(|address| address != recipient);
+        known.insert(0, *recipient);
+    }
+    known.truncate(MAX_KNOWN_RECIPIENTS);
+}
+
+pub async fn save_known_recipients(known: Vec<Pubkey>) -> Result<(), Error> {
+    let content: String = known
+        .iter()
+        .map(|address| format!("{}\n", address))
+        .collect();
+
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    tokio::fs::write(known_recipients_path(), content)
+        .await
+        .map_err(|_| Error::SaveFileError)
+}
+
+// same start and end but a different address in between, the classic
+// address poisoning lookalike
+fn is_lookalike(candidate: &str, known: &str) -> bool {
+    candidate != known
+        && candidate.len() > MATCHING_CHARS * 2
+        && known.len() > MATCHING_CHARS * 2
+        && candidate[..MATCHING_CHARS] == known[..MATCHING_CHARS]
+        && candidate[candidate.len() - MATCHING_CHARS..] == known[known.len() - MATCHING_CHARS..]
+}
+
+pub fn find_lookalike(candidate: &Pubkey, known: &[Pubkey]) -> Option<Pubkey> {
+    if known.contains(candidate) {
+        return None;
+    }
+    let candidate = candidate.to_string();
+    known
+        .iter()
+        .find(|address| is_lookalike(&candidate, &address.to_string()))
+        .copied()
+}
+
+pub fn display_poisoning_warning(recipient: &str, lookalike: &Pubkey) -> Element<'static, Message> {
+    column![
+        text(tr("Possible address poisoning!"))
+            .size(16)
+            .style(error_color()),
+        text(tr_args("You typed:   {$recipient}", &[("recipient", &recipient)]))
+            .size(14)
+            .style(error_color()),
+        text(tr_args("You paid before: {$lookalike}", &[("lookalike", &lookalike)])).size(14),
+        text(tr("Both start and end the same but they are different addresses. Lookalike addresses are planted in wallet history to be copied by mistake, compare every character before sending."))
+            .size(14),
+        row![
+            button(tr("Use the address I paid before"))
+                .on_press(Message::UseKnownRecipient(*lookalike)),
+            button(tr("I checked it, continue")).on_press(Message::PoisoningAcknowledged),
+            button(tr("Cancel")).on_press(Message::PoisoningCancelled),
+        ]
+        .spacing(10)
+    ]
+    .spacing(5)
+    .into()
+}
@@ -0,0 +1,186 @@
+use iced::{
+    widget::{button, column, container, row, text, Column},
+    Alignment, Element, Length,
+};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+use crate::{
+    fiat::shows_fiat,
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color},
+    prices::PriceCache,
+    snapshots::SnapshotRow,
+    token_list::{display_mint_status, MintStatus, TokenVerification},
+    Message,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holding {
+    // "SOL" or the token mint
+    pub asset: String,
+    pub amount: f64,
+    // None when the price feed has no price for the asset
+    pub value: Option<f64>,
+}
+
+// sol first, then the tokens by value, the ones without a price last; token
+// accounts of the same mint are added up
+pub fn holdings(
+    lamports: u64,
+    tokens: &[SnapshotRow],
+    prices: &PriceCache,
+    currency: &str,
+) -> Vec<Holding> {
+    let sol = lamports as f64 / LAMPORTS_PER_SOL as f64;
+    let mut token_holdings: Vec<Holding> = Vec::new();
+    for token in tokens {
+        let amount = token.amount.parse::<f64>().unwrap_or(0.0);
+        match token_holdings
+            .iter_mut()
+            .find(|holding| holding.asset == token.asset)
+        {
+            Some(holding) => holding.amount += amount,
+            None => token_holdings.push(Holding {
+                asset: token.asset.clone(),
+                amount,
+                value: None,
+            }),
+        }
+    }
+    for holding in &mut token_holdings {
+        holding.value = prices
+            .token(&holding.asset, currency)
+            .map(|price| price * holding.amount);
+    }
+    token_holdings.sort_by(|a, b| b.value.unwrap_or(-1.0).total_cmp(&a.value.unwrap_or(-1.0)));
+
+    let mut all = vec![Holding {
+        asset: String::from("SOL"),
+        amount: sol,
+        value: prices.sol(currency).map(|price| price * sol),
+    }];
+    all.extend(token_holdings);
+    all
+}
+
+fn short_mint(asset: &str) -> String {
+    if asset.len() > 12 {
+        format!("{}...{}", &asset[..4], &asset[asset.len() - 4..])
+    } else {
+        asset.to_string()
+    }
+}
+
+pub fn display_portfolio(
+    holdings: &[Holding],
+    verification: &TokenVerification,
+    currency: &str,
+    hide_balances: bool,
+) -> Element<'static, Message> {
+    let fiat_off = !shows_fiat(currency);
+    let currency = currency.to_uppercase();
+    let total_value: f64 = holdings.iter().filter_map(|holding| holding.value).sum();
+    let unpriced = holdings
+        .iter()
+        .filter(|holding| holding.value.is_none())
+        .count();
+
+    let header = row![
+        text(tr("Portfolio")).size(14).style(accent_color()),
+        button(text(tr("Refresh")).size(12)).on_press(Message::RefreshWallet),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let total = if hide_balances {
+        text(tr_args("Total: {$total}", &[("total", &"****")])).size(20)
+    } else {
+        text(tr_args(
+            "Total: {$total}",
+            &[("total", &format!("{:.2} {}", total_value, currency))],
+        ))
+        .size(20)
+    };
+
+    let columns = row![
+        text(tr("Asset")).size(12).width(Length::Fixed(160.0)),
+        text(tr("Status")).size(12).width(Length::Fixed(160.0)),
+        text(tr("Amount")).size(12).width(Length::Fixed(160.0)),
+        text(tr_args("Value ({$currency})", &[("currency", &currency)]))
+            .size(12)
+            .width(Length::Fixed(120.0)),
+        text(tr("Allocation")).size(12),
+    ]
+    .spacing(10);
+
+    let list = holdings
+        .iter()
+        .fold(Column::new().spacing(2), |column, holding| {
+            let (amount, value, allocation) = match (hide_balances, holding.value) {
+                (true, _) => (String::from("****"), String::from("****"), String::new()),
+                (false, Some(value)) => (
+                    holding.amount.to_string(),
+                    format!("{:.2}", value),
+                    if total_value > 0.0 {
+                        format!("{:.1}%", value / total_value * 100.0)
+                    } else {
+                        String::from("-")
+                    },
+                ),
+                (false, None) => (
+                    holding.amount.to_string(),
+                    String::from("-"),
+                    String::from("-"),
+                ),
+            };
+            column.push(
+                row![
+                    text(short_mint(&holding.asset))
+                        .size(12)
+                        .width(Length::Fixed(160.0)),
+                    container(display_mint_status(&verification.status(&holding.asset)))
+                        .width(Length::Fixed(160.0)),
+                    text(amount).size(12).width(Length::Fixed(160.0)),
+                    text(value).size(12).width(Length::Fixed(120.0)),
+                    text(allocation).size(12),
+                ]
+                .spacing(10),
+            )
+        });
+
+    // allocation only counts what has a price, say so when something is left out
+    let note = if fiat_off {
+        text(tr("Pick a fiat currency in Settings to see values")).size(12)
+    } else if unpriced > 0 {
+        text(tr_args(
+            "{$count} asset(s) have no price and aren't part of the total",
+            &[("count", &unpriced)],
+        ))
+        .size(12)
+    } else {
+        text("").size(1)
+    };
+
+    // airdropped tokens posing as a listed one are the usual way into a
+    // phishing site, they're called out above the list
+    let imitations = holdings
+        .iter()
+        .filter_map(|holding| match verification.status(&holding.asset) {
+            MintStatus::Imitates(symbol) => Some((holding.asset.clone(), symbol)),
+            _ => None,
+        })
+        .fold(Column::new().spacing(2), |column, (mint, symbol)| {
+            column.push(
+                text(tr_args(
+                    "{$mint} isn't the real {$symbol}, don't send it, swap it or follow links that come with it",
+                    &[("mint", &mint), ("symbol", &symbol)],
+                ))
+                .size(12)
+                .style(error_color()),
+            )
+        });
+
+    column![header, total, imitations, columns, list, note]
+        .spacing(10)
+        .into()
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+
+use crate::{
+    errors::Error,
+    i18n::tr_args,
+    permissions::{NetworkPermissions, Service},
+};
+
+// how often the subscription asks for new prices
+pub const PRICE_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+// coingecko's free tier allows a few calls a minute, a click on refresh or a
+// wallet switch right after a poll is answered from the cache
+const MIN_FETCH_GAP: Duration = Duration::from_secs(30);
+// prices older than this aren't shown anymore
+const MAX_PRICE_AGE: Duration = Duration::from_secs(10 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const COINGECKO_API: &str = "https://api.coingecko.com/api/v3/simple";
+const PYTH_HERMES_URL: &str = "https://hermes.pyth.network/v2/updates/price/latest";
+const PYTH_SOL_USD_FEED: &str = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceProvider {
+    CoinGecko,
+    // sol in usd only
+    Pyth,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prices {
+    pub currency: String,
+    pub sol: f64,
+    // by token mint, tokens the provider doesn't know are missing
+    pub tokens: HashMap<String, f64>,
+    pub provider: PriceProvider,
+    pub fetched: Instant,
+}
+
+// the last prices and when they were asked for, kept between polls
+#[derive(Debug, Default)]
+pub struct PriceCache {
+    latest: Option<Prices>,
+    requested: Option<Instant>,
+}
+
+impl PriceCache {
+    // false when the cache is still fresh enough for `currency`, otherwise
+    // the request is counted and the caller fetches
+    pub fn should_fetch(&mut self, currency: &str) -> bool {
+        let fresh = self.latest.as_ref().map(|prices| prices.currency.as_str()) == Some(currency)
+            && self
+                .requested
+                .map_or(false, |requested| requested.elapsed() < MIN_FETCH_GAP);
+        if fresh {
+            return false;
+        }
+        self.requested = Some(Instant::now());
+        true
+    }
+
+    pub fn store(&mut self, prices: Prices) {
+        self.latest = Some(prices);
+    }
+
+    pub fn clear(&mut self) {
+        self.latest = None;
+        self.requested = None;
+    }
+
+    fn current(&self, currency: &str) -> Option<&Prices> {
+        self.latest.as_ref().filter(|prices| {
+            prices.currency == currency && prices.fetched.elapsed() < MAX_PRICE_AGE
+        })
+    }
+
+    pub fn sol(&self, currency: &str) -> Option<f64> {
+        self.current(currency).map(|prices| prices.sol)
+    }
+
+    pub fn token(&self, mint: &str, currency: &str) -> Option<f64> {
+        self.current(currency)
+            .and_then(|prices| prices.tokens.get(mint).copied())
+    }
+
+    // "CoinGecko, 12s ago" for the status line
+    pub fn source(&self, currency: &str) -> Option<String> {
+        self.current(currency).map(|prices| {
+            let provider = match prices.provider {
+                PriceProvider::CoinGecko => "CoinGecko",
+                PriceProvider::Pyth => "Pyth",
+            };
+            tr_args(
+                "{$provider}, {$seconds}s ago",
+                &[
+                    ("provider", &provider),
+                    ("seconds", &prices.fetched.elapsed().as_secs()),
+                ],
+            )
+        })
+    }
+}
+
+async fn get_json(client: &reqwest::Client, url: &str) -> Option<Value> {
+    let body = client.get(url).send().await.ok()?.text().await.ok()?;
+    serde_json::from_str(&body).ok()
+}
+
+async fn from_coingecko(
+    client: &reqwest::Client,
+    currency: &str,
+    mints: &[String],
+) -> Option<Prices> {
+    let sol = get_json(
+        client,
+        &format!(
+            "{}/price?ids=solana&vs_currencies={}",
+            COINGECKO_API, currency
+        ),
+    )
+    .await?["solana"][currency]
+        .as_f64()?;
+
+    // token prices are a bonus, sol alone is still worth showing
+    let mut tokens = HashMap::new();
+    if !mints.is_empty() {
+        if let Some(body) = get_json(
+            client,
+            &format!(
+                "{}/token_price/solana?contract_addresses={}&vs_currencies={}",
+                COINGECKO_API,
+                mints.join(","),
+                currency
+            ),
+        )
+        .await
+        {
+            for mint in mints {
+                let price = body[mint.as_str()][currency]
+                    .as_f64()
+                    .or_else(|| body[mint.to_lowercase().as_str()][currency].as_f64());
+                if let Some(price) = price {
+                    tokens.insert(mint.clone(), price);
+                }
+            }
+        }
+    }
+
+    Some(Prices {
+        currency: currency.to_string(),
+        sol,
+        tokens,
+        provider: PriceProvider::CoinGecko,
+        fetched: Instant::now(),
+    })
+}
+
+// pyth publishes the price as an integer and a power of ten
+async fn from_pyth(client: &reqwest::Client) -> Option<Prices> {
+    let body = get_json(
+        client,
+        &format!("{}?ids[]={}", PYTH_HERMES_URL, PYTH_SOL_USD_FEED),
+    )
+    .await?;
+    let price = &body["parsed"][0]["price"];
+    let mantissa = price["price"].as_str()?.parse::<i64>().ok()?;
+    let exponent = price["expo"].as_i64()?;
+
+    Some(Prices {
+        currency: String::from("usd"),
+        sol: mantissa as f64 * 10f64.powi(exponent as i32),
+        tokens: HashMap::new(),
+        provider: PriceProvider::Pyth,
+        fetched: Instant::now(),
+    })
+}
+
+// coingecko first, pyth when it's down or rate limiting us and the currency
+// is usd; a provider that isn't allowed is never asked
+pub async fn fetch_prices(
+    currency: String,
+    mints: Vec<String>,
+    permissions: NetworkPermissions,
+) -> Result<Prices, Error> {
+    if !permissions.allows_prices(&currency) {
+        return Err(Error::ServiceNotAllowed);
+    }
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|_| Error::FetchPriceError)?;
+
+    if permissions.allows(Service::CoinGecko) {
+        if let Some(prices) = from_coingecko(&client, &currency, &mints).await {
+            return Ok(prices);
+        }
+    }
+    if currency == "usd" && permissions.allows(Service::Pyth) {
+        if let Some(prices) = from_pyth(&client).await {
+            return Ok(prices);
+        }
+    }
+    Err(Error::FetchPriceError)
+}
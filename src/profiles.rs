@@ -0,0 +1,141 @@
+use std::{fs, path::PathBuf};
+
+use iced::{
+    widget::{button, column, row, text, text_input, Column},
+    Alignment, Element,
+};
+
+use crate::{
+    errors::Error,
+    files::app_config_dir,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+const PROFILES_FILE: &str = "profiles.txt";
+
+// a named setup the app can switch to in one step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub rpc_url: String,
+    pub priority_fee: String,
+    // left empty to keep whatever wallet is loaded
+    pub keypair_path: Option<PathBuf>,
+}
+
+// one "[<name>]" header per profile followed by its "<key>=<value>" lines
+pub fn load_profiles() -> Vec<Profile> {
+    let content = fs::read_to_string(app_config_dir().join(PROFILES_FILE)).unwrap_or_default();
+
+    let mut profiles: Vec<Profile> = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            profiles.push(Profile {
+                name: name.to_string(),
+                rpc_url: String::new(),
+                priority_fee: String::new(),
+                keypair_path: None,
+            });
+            continue;
+        }
+        let Some(profile) = profiles.last_mut() else {
+            continue;
+        };
+        match line.split_once('=') {
+            Some(("rpc_url", url)) => profile.rpc_url = url.to_string(),
+            Some(("priority_fee", fee)) => profile.priority_fee = fee.to_string(),
+            Some(("keypair_path", path)) if !path.is_empty() => {
+                profile.keypair_path = Some(PathBuf::from(path))
+            }
+            _ => {}
+        }
+    }
+
+    // a profile without an endpoint can't be switched to
+    profiles.retain(|profile| !profile.rpc_url.is_empty());
+    profiles
+}
+
+pub async fn save_profiles(profiles: Vec<Profile>) -> Result<(), Error> {
+    let mut content = String::new();
+    for profile in &profiles {
+        content.push_str(&format!(
+            "[{}]\nrpc_url={}\npriority_fee={}\nkeypair_path={}\n",
+            profile.name,
+            profile.rpc_url,
+            profile.priority_fee,
+            profile
+                .keypair_path
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default()
+        ));
+    }
+
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    tokio::fs::write(app_config_dir().join(PROFILES_FILE), content)
+        .await
+        .map_err(|_| Error::SaveFileError)
+}
+
+// names end up in "[...]" headers, keep them on one line without brackets
+pub fn valid_profile_name(name: &str) -> bool {
+    !name.trim().is_empty() && !name.contains(['[', ']', '\n'])
+}
+
+pub fn display_profiles(
+    profiles: &[Profile],
+    active: &str,
+    name: &str,
+) -> Element<'static, Message> {
+    let title = text(tr("Profiles")).size(14).style(accent_color());
+
+    let list = profiles
+        .iter()
+        .fold(Column::new().spacing(5), |column, profile| {
+            let label = if active == profile.name {
+                tr_args("{$name} (active)", &[("name", &profile.name)])
+            } else {
+                profile.name.clone()
+            };
+            let wallet = profile
+                .keypair_path
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| String::from(tr("current wallet")));
+
+            column.push(
+                row![
+                    button(text(label).size(12))
+                        .on_press(Message::ProfileSelected(profile.name.clone())),
+                    text(format!("{} | {}", profile.rpc_url, wallet)).size(12),
+                    button(text(tr("Delete")).size(12))
+                        .on_press(Message::DeleteProfile(profile.name.clone())),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            )
+        });
+
+    let name_input = text_input(tr("Profile name, e.g. Dev (devnet, test key)"), name)
+        .on_input(Message::ProfileNameChanged);
+
+    column![
+        title,
+        list,
+        row![
+            name_input,
+            button(tr("Save current setup as profile")).on_press(Message::SaveProfile),
+        ]
+        .spacing(10)
+    ]
+    .spacing(10)
+    .into()
+}
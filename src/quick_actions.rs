@@ -0,0 +1,84 @@
+use std::{fs, path::PathBuf};
+
+use iced::{
+    widget::{button, row, text, Row},
+    Alignment, Element,
+};
+
+use crate::{commands::parse_command, errors::Error, files::app_config_dir, Message};
+
+const QUICK_ACTIONS_FILE: &str = "quick_actions.txt";
+
+// a palette command pinned to the main screen as a one click tile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickAction {
+    pub label: String,
+    pub command_line: String,
+}
+
+fn quick_actions_path() -> PathBuf {
+    app_config_dir().join(QUICK_ACTIONS_FILE)
+}
+
+// one "<label>=<command line>" entry per line
+pub fn load_quick_actions() -> Vec<QuickAction> {
+    fs::read_to_string(quick_actions_path())
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .filter(|(_, command_line)| parse_command(command_line).is_some())
+                .map(|(label, command_line)| QuickAction {
+                    label: label.trim().to_string(),
+                    command_line: command_line.trim().to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub async fn save_quick_actions(actions: Vec<QuickAction>) -> Result<(), Error> {
+    let content: String = actions
+        .iter()
+        .map(|action| format!("{}={}\n", action.label, action.command_line))
+        .collect();
+
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    tokio::fs::write(quick_actions_path(), content)
+        .await
+        .map_err(|_| Error::SaveFileError)
+}
+
+// the label defaults to the command itself, "label: command" sets a custom one
+pub fn quick_action_from_query(query: &str) -> Option<QuickAction> {
+    let (label, command_line) = match query.split_once(':') {
+        Some((label, command_line)) => (label.trim(), command_line.trim()),
+        None => (query.trim(), query.trim()),
+    };
+    parse_command(command_line)?;
+
+    Some(QuickAction {
+        label: label.replace('=', "-"),
+        command_line: command_line.to_string(),
+    })
+}
+
+pub fn display_quick_actions(actions: &[QuickAction]) -> Element<'static, Message> {
+    actions
+        .iter()
+        .enumerate()
+        .fold(Row::new().spacing(10), |tiles, (index, action)| {
+            tiles.push(
+                row![
+                    button(text(action.label.clone()).size(14))
+                        .on_press(Message::RunQuickAction(index)),
+                    button(text("x").size(14)).on_press(Message::UnpinQuickAction(index)),
+                ]
+                .spacing(2)
+                .align_items(Alignment::Center),
+            )
+        })
+        .into()
+}
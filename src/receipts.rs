@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use futures::future::join_all;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction::{self, SystemInstruction},
+    system_program,
+};
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::{
+    errors::Error,
+    history::fetch_transaction,
+    transaction::{memo_instruction, send_instructions},
+};
+
+// memo layout: "ack:<recipient>:<payment signature>"
+pub const RECEIPT_PREFIX: &str = "ack:";
+
+// sends a zero lamports transfer to the recipient with a memo pointing to the payment,
+// so the ping shows up in the recipient's signature history
+pub async fn send_receipt_ping(
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+    to: Pubkey,
+    payment_signature: String,
+) -> Result<String, Error> {
+    let signer_pubkey = signer.pubkey();
+
+    let memo = format!("{}{}:{}", RECEIPT_PREFIX, to, payment_signature);
+    let instructions = [
+        system_instruction::transfer(&signer_pubkey, &to, 0),
+        memo_instruction(&memo, &signer_pubkey),
+    ];
+
+    send_instructions(&rpc_client, &[&*signer], &instructions).await
+}
+
+// payment signatures acknowledged through pings addressed to `owner`; anyone
+// can write such a memo, so a ping only counts when its sender is the one who
+// paid `owner` in the transaction it points to
+pub async fn fetch_receipt_pings(
+    owner: Pubkey,
+    rpc_client: Arc<RpcClient>,
+) -> Result<Vec<String>, Error> {
+    let signatures = rpc_client
+        .get_signatures_for_address(&owner)
+        .await
+        .map_err(|_| Error::FetchReceiptsError)?;
+
+    let pings: Vec<(String, String)> = signatures
+        .into_iter()
+        .filter(|status| status.err.is_none())
+        .filter_map(|status| {
+            let payment_signature = parse_receipt_memo(status.memo.as_deref()?, &owner)?;
+            Some((status.signature, payment_signature))
+        })
+        .collect();
+
+    let verified = join_all(
+        pings
+            .iter()
+            .map(|(ping, payment)| verify_receipt_ping(ping, payment, &owner, &rpc_client)),
+    )
+    .await;
+
+    Ok(pings
+        .into_iter()
+        .zip(verified)
+        .filter(|(_, verified)| *verified)
+        .map(|((_, payment), _)| payment)
+        .collect())
+}
+
+async fn verify_receipt_ping(
+    ping_signature: &str,
+    payment_signature: &str,
+    owner: &Pubkey,
+    rpc_client: &RpcClient,
+) -> bool {
+    let Some(ping) = transfers_to(ping_signature, owner, rpc_client).await else {
+        return false;
+    };
+    let Some(payment) = transfers_to(payment_signature, owner, rpc_client).await else {
+        return false;
+    };
+    ping.iter().any(|(sender, _)| {
+        payment
+            .iter()
+            .any(|(payer, lamports)| payer == sender && *lamports > 0)
+    })
+}
+
+// (sender, lamports) of every system transfer to `payee` in a transaction
+// that landed without errors; None when it can't be found or failed
+async fn transfers_to(
+    signature: &str,
+    payee: &Pubkey,
+    rpc_client: &RpcClient,
+) -> Option<Vec<(Pubkey, u64)>> {
+    let confirmed = fetch_transaction(signature, UiTransactionEncoding::Base64, rpc_client, None)
+        .await
+        .ok()?;
+    if confirmed.transaction.meta?.err.is_some() {
+        return None;
+    }
+    let tx = confirmed.transaction.transaction.decode()?;
+    let keys = tx.message.static_account_keys();
+    let account = |index: Option<&u8>| index.and_then(|index| keys.get(*index as usize));
+
+    Some(
+        tx.message
+            .instructions()
+            .iter()
+            .filter(|ix| keys.get(ix.program_id_index as usize) == Some(&system_program::id()))
+            .filter_map(|ix| {
+                let SystemInstruction::Transfer { lamports } =
+                    bincode::deserialize(&ix.data).ok()?
+                else {
+                    return None;
+                };
+                let from = account(ix.accounts.first())?;
+                let to = account(ix.accounts.get(1))?;
+                (to == payee).then_some((*from, lamports))
+            })
+            .collect(),
+    )
+}
+
+// the rpc returns memos as "[<len>] <memo>", so look for the prefix anywhere
+fn parse_receipt_memo(memo: &str, owner: &Pubkey) -> Option<String> {
+    let start = memo.find(RECEIPT_PREFIX)?;
+    let mut parts = memo[start + RECEIPT_PREFIX.len()..].trim().splitn(2, ':');
+
+    let recipient = parts.next()?;
+    let payment_signature = parts.next()?;
+
+    if recipient != owner.to_string() {
+        return None;
+    }
+    Some(payment_signature.to_string())
+}
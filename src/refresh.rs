@@ -0,0 +1,96 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use iced::{
+    widget::{button, column, row, text, Column},
+    Alignment, Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    errors::Error,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    snapshots::{token_rows, SnapshotRow},
+    Message,
+};
+
+// anything shorter would mostly hit rpc rate limits
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+// the settings field holds seconds, empty or invalid disables polling
+pub fn refresh_interval(seconds: &str) -> Option<Duration> {
+    let seconds: u64 = seconds.trim().parse().ok()?;
+    (seconds > 0).then(|| Duration::from_secs(seconds).max(MIN_REFRESH_INTERVAL))
+}
+
+#[derive(Debug, Clone)]
+pub struct WalletRefresh {
+    // the wallet that was polled, a switch in the meantime drops the result
+    pub pubkey: Pubkey,
+    pub balance: u64,
+    pub tokens: Vec<SnapshotRow>,
+}
+
+pub async fn refresh_wallet(
+    pubkey: Pubkey,
+    rpc_client: Arc<RpcClient>,
+) -> Result<WalletRefresh, Error> {
+    let balance = rpc_client
+        .get_balance(&pubkey)
+        .await
+        .map_err(|_| Error::FetchBalanceError)?;
+    let tokens = token_rows("main", &pubkey, &rpc_client).await?;
+    Ok(WalletRefresh {
+        pubkey,
+        balance,
+        tokens,
+    })
+}
+
+fn elapsed_label(updated: Option<Instant>) -> String {
+    let Some(updated) = updated else {
+        return String::from(tr("Not updated yet"));
+    };
+    match updated.elapsed().as_secs() {
+        0..=4 => String::from(tr("Updated just now")),
+        seconds @ 5..=59 => tr_args("Updated {$seconds}s ago", &[("seconds", &seconds)]),
+        seconds @ 60..=3599 => tr_args("Updated {$minutes}m ago", &[("minutes", &(seconds / 60))]),
+        seconds => tr_args("Updated {$hours}h ago", &[("hours", &(seconds / 3600))]),
+    }
+}
+
+pub fn display_token_balances(
+    tokens: &[SnapshotRow],
+    updated: Option<Instant>,
+    hide_balances: bool,
+) -> Element<'static, Message> {
+    let header = row![
+        text(elapsed_label(updated)).size(12),
+        button(text(tr("Refresh")).size(12)).on_press(Message::RefreshWallet),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let list = tokens
+        .iter()
+        .fold(Column::new().spacing(2), |column, token| {
+            let amount = if hide_balances {
+                String::from("****")
+            } else {
+                token.amount.clone()
+            };
+            column.push(text(format!("{} {}", amount, token.asset)).size(12))
+        });
+
+    let title = if tokens.is_empty() {
+        text("").size(1)
+    } else {
+        text(tr("Tokens")).size(14).style(accent_color())
+    };
+
+    column![header, title, list].spacing(5).into()
+}
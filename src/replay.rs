@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use iced::{
+    widget::{button, column, text, Column},
+    Element,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::{
+    errors::Error,
+    history::fetch_transaction,
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color},
+    Message,
+};
+
+#[derive(Debug, Clone)]
+pub struct AccountDiff {
+    pub address: Pubkey,
+    // balance change when the transaction originally landed
+    pub original_change: i128,
+    pub current_lamports: u64,
+    pub simulated_lamports: u64,
+    pub data_changed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    pub signature: String,
+    pub original_error: Option<String>,
+    pub original_logs: Vec<String>,
+    pub simulated_error: Option<String>,
+    pub simulated_logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    pub accounts: Vec<AccountDiff>,
+}
+
+// re-runs a landed transaction against the current state: a fresh blockhash
+// is swapped in and signatures aren't checked, nothing is broadcast
+pub async fn replay_transaction(
+    signature: String,
+    rpc_client: Arc<RpcClient>,
+    archival_client: Option<Arc<RpcClient>>,
+) -> Result<ReplayReport, Error> {
+    let confirmed = fetch_transaction(
+        &signature,
+        UiTransactionEncoding::Base64,
+        &rpc_client,
+        archival_client.as_deref(),
+    )
+    .await?;
+
+    let meta = confirmed
+        .transaction
+        .meta
+        .ok_or(Error::FetchTransactionError)?;
+    let tx = confirmed
+        .transaction
+        .transaction
+        .decode()
+        .ok_or(Error::FetchTransactionError)?;
+
+    // accounts loaded from lookup tables aren't included
+    let addresses: Vec<Pubkey> = tx.message.static_account_keys().to_vec();
+
+    let current_accounts = rpc_client
+        .get_multiple_accounts(&addresses)
+        .await
+        .map_err(|_| Error::FetchAccountError)?;
+
+    let simulate_cfg = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(rpc_client.commitment()),
+        encoding: Some(UiTransactionEncoding::Base64),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: addresses
+                .iter()
+                .map(|address| address.to_string())
+                .collect(),
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+
+    let result = rpc_client
+        .simulate_transaction_with_config(&tx, simulate_cfg)
+        .await
+        .map_err(|_| Error::SimulationError(String::from(tr("RPC request failed"))))?
+        .value;
+
+    let simulated_accounts: Vec<Option<Account>> = result
+        .accounts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|account| account.and_then(|account| account.decode::<Account>()))
+        .collect();
+
+    let accounts = addresses
+        .iter()
+        .enumerate()
+        .map(|(index, address)| {
+            let current = current_accounts.get(index).cloned().flatten();
+            let simulated = simulated_accounts.get(index).cloned().flatten();
+            let pre_balance = meta.pre_balances.get(index).copied().unwrap_or(0);
+            let post_balance = meta.post_balances.get(index).copied().unwrap_or(0);
+
+            AccountDiff {
+                address: *address,
+                original_change: post_balance as i128 - pre_balance as i128,
+                current_lamports: current
+                    .as_ref()
+                    .map(|account| account.lamports)
+                    .unwrap_or(0),
+                simulated_lamports: simulated
+                    .as_ref()
+                    .map(|account| account.lamports)
+                    .unwrap_or(0),
+                data_changed: match (&current, &simulated) {
+                    (Some(current), Some(simulated)) => current.data != simulated.data,
+                    (None, None) => false,
+                    _ => true,
+                },
+            }
+        })
+        .collect();
+
+    Ok(ReplayReport {
+        signature,
+        original_error: meta.err.map(|err| err.to_string()),
+        original_logs: Option::<Vec<String>>::from(meta.log_messages).unwrap_or_default(),
+        simulated_error: result.err.map(|err| err.to_string()),
+        simulated_logs: result.logs.unwrap_or_default(),
+        units_consumed: result.units_consumed,
+        accounts,
+    })
+}
+
+fn display_logs(title: &str, error: &Option<String>, logs: &[String]) -> Column<'static, Message> {
+    let status = match error {
+        Some(error) => text(tr_args(
+            "{$title}: failed with {$error}",
+            &[("title", &title), ("error", &error)],
+        ))
+        .size(14)
+        .style(error_color()),
+        None => text(tr_args("{$title}: success", &[("title", &title)]))
+            .size(14)
+            .style(accent_color()),
+    };
+
+    logs.iter()
+        .fold(Column::new().spacing(2).push(status), |column, log| {
+            column.push(text(log.clone()).size(12))
+        })
+}
+
+pub fn display_replay(report: &ReplayReport) -> Element<'static, Message> {
+    let accounts = report
+        .accounts
+        .iter()
+        .fold(Column::new().spacing(2), |column, diff| {
+            let simulated_change = diff.simulated_lamports as i128 - diff.current_lamports as i128;
+            column.push(
+                text(format!(
+                    "{}  {}{}",
+                    diff.address,
+                    tr_args(
+                        "then: {$then}  now: {$now}",
+                        &[
+                            ("then", &format!("{:+}", diff.original_change)),
+                            ("now", &format!("{:+}", simulated_change)),
+                        ],
+                    ),
+                    if diff.data_changed {
+                        tr(" (data changes)")
+                    } else {
+                        ""
+                    }
+                ))
+                .size(12),
+            )
+        });
+
+    column![
+        text(tr_args(
+            "Replay of {$signature}",
+            &[("signature", &report.signature)]
+        ))
+        .size(14)
+        .style(accent_color()),
+        text(tr(
+            "Balance changes in lamports, when it landed vs against the current state"
+        ))
+        .size(12),
+        accounts,
+        display_logs(
+            tr("Original run"),
+            &report.original_error,
+            &report.original_logs
+        ),
+        display_logs(
+            &tr_args(
+                "Replay ({$units} compute units)",
+                &[(
+                    "units",
+                    &report
+                        .units_consumed
+                        .map(|units| units.to_string())
+                        .unwrap_or_else(|| String::from(tr("unknown"))),
+                )],
+            ),
+            &report.simulated_error,
+            &report.simulated_logs
+        ),
+        button(tr("Close replay")).on_press(Message::CloseReplay),
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,173 @@
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+
+use iced::{
+    widget::{button, column, row, text, Column},
+    Alignment, Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{clock::Epoch, pubkey::Pubkey};
+
+use crate::{
+    errors::Error,
+    files::save_file,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    rpc::with_retry,
+    units::format_sol,
+    Message,
+};
+
+// each epoch is a separate, fairly slow query on most nodes
+const REWARD_EPOCHS: u64 = 10;
+
+#[derive(Debug, Clone)]
+pub struct StakeReward {
+    pub epoch: Epoch,
+    pub stake_account: Pubkey,
+    pub lamports: u64,
+    pub post_balance: u64,
+    pub commission: Option<u8>,
+}
+
+// rewards for an epoch are paid at the start of the next one, so the
+// running epoch has none yet; newest epoch first
+pub async fn fetch_stake_rewards(
+    stake_accounts: Vec<Pubkey>,
+    current_epoch: Epoch,
+    rpc_client: Arc<RpcClient>,
+) -> Result<Vec<StakeReward>, Error> {
+    let mut rewards = Vec::new();
+    if stake_accounts.is_empty() {
+        return Ok(rewards);
+    }
+
+    for epoch in (current_epoch.saturating_sub(REWARD_EPOCHS)..current_epoch).rev() {
+        let epoch_rewards =
+            with_retry(|| rpc_client.get_inflation_reward(&stake_accounts, Some(epoch)))
+                .await
+                .map_err(|_| Error::FetchRewardsError)?;
+        rewards.extend(stake_accounts.iter().zip(epoch_rewards).filter_map(
+            |(stake_account, reward)| {
+                let reward = reward?;
+                Some(StakeReward {
+                    epoch: reward.epoch,
+                    stake_account: *stake_account,
+                    lamports: reward.amount,
+                    post_balance: reward.post_balance,
+                    commission: reward.commission,
+                })
+            },
+        ));
+    }
+    Ok(rewards)
+}
+
+fn epoch_totals(rewards: &[StakeReward]) -> BTreeMap<Epoch, (u64, usize)> {
+    rewards.iter().fold(BTreeMap::new(), |mut totals, reward| {
+        let (lamports, accounts) = totals.entry(reward.epoch).or_insert((0, 0));
+        *lamports += reward.lamports;
+        *accounts += 1;
+        totals
+    })
+}
+
+pub fn rewards_csv(rewards: &[StakeReward]) -> String {
+    let mut csv = String::from("epoch,stake_account,reward_sol,post_balance_sol,commission\n");
+
+    for reward in rewards {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            reward.epoch,
+            reward.stake_account,
+            format_sol(reward.lamports),
+            format_sol(reward.post_balance),
+            reward
+                .commission
+                .map(|commission| commission.to_string())
+                .unwrap_or_default()
+        ));
+    }
+
+    csv
+}
+
+pub async fn export_stake_rewards(rewards: Vec<StakeReward>) -> Result<PathBuf, Error> {
+    let path = save_file("staking-rewards.csv").await?;
+    tokio::fs::write(&path, rewards_csv(&rewards))
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(path)
+}
+
+pub fn display_rewards(rewards: &[StakeReward], loading: bool) -> Element<'static, Message> {
+    let load = button(
+        text(if loading {
+            tr("Loading...")
+        } else {
+            tr("Load rewards")
+        })
+        .size(12),
+    );
+    let load = if loading {
+        load
+    } else {
+        load.on_press(Message::RefreshStakeRewards)
+    };
+    let export = button(text(tr("Export CSV")).size(12));
+    let export = if rewards.is_empty() {
+        export
+    } else {
+        export.on_press(Message::ExportStakeRewards)
+    };
+    let header = row![
+        text(tr("Staking rewards")).size(14).style(accent_color()),
+        load,
+        export,
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    if rewards.is_empty() {
+        return column![
+            header,
+            text(tr_args(
+                "Rewards of the last {$epochs} epochs for the accounts above",
+                &[("epochs", &REWARD_EPOCHS)],
+            ))
+            .size(12)
+        ]
+        .spacing(10)
+        .into();
+    }
+
+    let totals = epoch_totals(rewards);
+    let table = totals.iter().rev().fold(
+        Column::new().spacing(2),
+        |column, (epoch, (lamports, accounts))| {
+            column.push(
+                text(tr_args(
+                    "Epoch {$epoch}: +{$amount} SOL across {$accounts} account(s)",
+                    &[
+                        ("epoch", epoch),
+                        ("amount", &format_sol(*lamports)),
+                        ("accounts", accounts),
+                    ],
+                ))
+                .size(12),
+            )
+        },
+    );
+    let total: u64 = totals.values().map(|(lamports, _)| lamports).sum();
+
+    column![
+        header,
+        table,
+        text(tr_args(
+            "Total over {$epochs} epochs: +{$amount} SOL",
+            &[("epochs", &totals.len()), ("amount", &format_sol(total))],
+        ))
+        .size(14),
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,733 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU32, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use iced::{
+    widget::{button, column, pick_list, row, text, text_input, Column},
+    Alignment, Element,
+};
+use rand::Rng;
+use serde_json::{json, Value};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    nonblocking::rpc_client::RpcClient,
+    rpc_request::RpcRequest,
+};
+use solana_rpc_client::{
+    http_sender::HttpSender,
+    rpc_client::RpcClientConfig,
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+use tokio::time;
+
+use crate::{
+    config::{load_config, AppConfig},
+    files::solana_cli_config,
+    genesis::genesis_hash,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    settings::{load_preferences, Preferences},
+    Message,
+};
+
+// used when the solana cli has no json_rpc_url configured
+const DEFAULT_RPC_URL: &str = "https://api.devnet.solana.com";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+// background fetchers poll every few seconds, keep their connections warm
+// instead of paying a new tls handshake each time
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const POOL_MAX_IDLE_PER_HOST: usize = 4;
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+const HTTP2_KEEPALIVE: Duration = Duration::from_secs(20);
+// consecutive failures before a node is only tried after the healthy ones
+const UNHEALTHY_AFTER: u32 = 3;
+// every node already had its turn within one attempt, this is the budget for
+// going around the pool again
+const RETRY_ATTEMPTS: u32 = 4;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(4);
+// public endpoints answer 429 well before this, a burst of two seconds worth
+// covers a wallet switch fetching everything at once
+const DEFAULT_REQUESTS_PER_SEC: u32 = 10;
+const BURST_SECONDS: f64 = 2.0;
+pub const COMMITMENT_LEVELS: [&str; 3] = ["processed", "confirmed", "finalized"];
+
+// the url from the app config, otherwise the endpoint `solana config get`
+// points at, so the app and the cli agree
+pub fn configured_rpc_url(configured: &str) -> String {
+    if !configured.trim().is_empty() {
+        return configured.trim().to_string();
+    }
+    solana_cli_config()
+        .map(|config| config.json_rpc_url.clone())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_RPC_URL.to_string())
+}
+
+pub fn default_rpc_url() -> String {
+    configured_rpc_url(&load_config().rpc_url)
+}
+
+// named after the public endpoints, anything else is shown as custom
+pub fn cluster_name(rpc_url: &str) -> &'static str {
+    if rpc_url.contains("devnet") {
+        "devnet"
+    } else if rpc_url.contains("testnet") {
+        "testnet"
+    } else if rpc_url.contains("mainnet") {
+        "mainnet-beta"
+    } else if rpc_url.contains("localhost") || rpc_url.contains("127.0.0.1") {
+        "localnet"
+    } else {
+        "custom"
+    }
+}
+
+// the primary url plus every fallback from settings; which of them serve the
+// primary's cluster is only known once they answered getGenesisHash, see
+// `match_clusters`
+fn pool_urls(url: &str, fallback_urls: &str) -> Vec<String> {
+    let mut urls = vec![url.trim().to_string()];
+    for fallback in fallback_urls
+        .split([',', ' ', '\n'])
+        .map(str::trim)
+        .filter(|fallback| !fallback.is_empty())
+    {
+        if !urls.iter().any(|known| known == fallback) {
+            urls.push(fallback.to_string());
+        }
+    }
+    urls
+}
+
+// a relay on another cluster would drop every transaction; relays usually
+// have their own urls, those can't be told apart and are trusted
+fn broadcast_url(url: &str, setting: &str) -> Option<String> {
+    let setting = setting.trim();
+    if setting.is_empty() {
+        return None;
+    }
+    let cluster = cluster_name(setting);
+    (cluster == "custom" || cluster == cluster_name(url)).then(|| setting.to_string())
+}
+
+// anything but the two faster levels keeps the finalized default
+pub fn commitment_config(setting: &str) -> CommitmentConfig {
+    match setting {
+        "processed" => CommitmentConfig::processed(),
+        "confirmed" => CommitmentConfig::confirmed(),
+        _ => CommitmentConfig::finalized(),
+    }
+}
+
+// history lookups don't take processed, confirmed is the closest level
+pub fn history_commitment(rpc_client: &RpcClient) -> CommitmentConfig {
+    if rpc_client.commitment().is_at_least_confirmed() {
+        rpc_client.commitment()
+    } else {
+        CommitmentConfig::confirmed()
+    }
+}
+
+// empty or unparsable falls back to the default, 0 turns the limiter off
+fn requests_per_sec(setting: &str) -> u32 {
+    setting.trim().parse().unwrap_or(DEFAULT_REQUESTS_PER_SEC)
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+// token bucket shared by every request of the pool, callers over the budget
+// wait their turn instead of hitting the node
+struct RateLimiter {
+    per_sec: u32,
+    bucket: Mutex<Bucket>,
+    queued: AtomicUsize,
+}
+
+impl RateLimiter {
+    fn new(per_sec: u32) -> Self {
+        Self {
+            per_sec,
+            bucket: Mutex::new(Bucket {
+                tokens: per_sec as f64 * BURST_SECONDS,
+                refilled: Instant::now(),
+            }),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    // None once a token was taken, otherwise how long until the next one
+    fn take(&self) -> Option<Duration> {
+        let per_sec = self.per_sec as f64;
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        bucket.tokens = (bucket.tokens
+            + now.duration_since(bucket.refilled).as_secs_f64() * per_sec)
+            .min(per_sec * BURST_SECONDS);
+        bucket.refilled = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - bucket.tokens) / per_sec))
+        }
+    }
+
+    async fn acquire(&self) {
+        if self.per_sec == 0 {
+            return;
+        }
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        let _queued = QueuedGuard(&self.queued);
+        while let Some(wait) = self.take() {
+            time::sleep(wait).await;
+        }
+    }
+}
+
+// takes the request off the queue count even when the future waiting for a
+// token is dropped, e.g. a screen change cancelling the fetch
+struct QueuedGuard<'a>(&'a AtomicUsize);
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .http2_keep_alive_interval(HTTP2_KEEPALIVE)
+        .http2_keep_alive_while_idle(true)
+        .build()
+        .expect("Cannot build the rpc http client")
+}
+
+// whether a fallback is on the primary's chain, going by the genesis hash;
+// the primary is the reference and always matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClusterCheck {
+    Pending,
+    Same,
+    Other,
+}
+
+// result of the last "Test endpoints" run for a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Latency {
+    Untested,
+    Failed,
+    Measured(Duration),
+}
+
+pub struct Endpoint {
+    pub url: String,
+    sender: HttpSender,
+    failures: AtomicU32,
+    latency: Mutex<Latency>,
+    cluster: Mutex<ClusterCheck>,
+}
+
+impl Endpoint {
+    pub fn latency(&self) -> Latency {
+        *self.latency.lock().unwrap()
+    }
+
+    pub fn cluster(&self) -> ClusterCheck {
+        *self.cluster.lock().unwrap()
+    }
+
+    async fn genesis_hash(&self) -> Option<String> {
+        self.sender
+            .send(RpcRequest::GetGenesisHash, Value::Null)
+            .await
+            .ok()?
+            .as_str()
+            .map(str::to_string)
+    }
+
+    // getHealth then getLatestBlockhash, a node that's behind or can't hand
+    // out a blockhash is no use for sending
+    async fn measure(&self) -> Latency {
+        let started = Instant::now();
+        let answered = self
+            .sender
+            .send(RpcRequest::GetHealth, Value::Null)
+            .await
+            .is_ok()
+            && self
+                .sender
+                .send(RpcRequest::GetLatestBlockhash, json!([]))
+                .await
+                .is_ok();
+        let latency = if answered {
+            Latency::Measured(started.elapsed())
+        } else {
+            Latency::Failed
+        };
+        *self.latency.lock().unwrap() = latency;
+        latency
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.failures() < UNHEALTHY_AFTER
+    }
+}
+
+// every node configured for the cluster, the first is the one the user picked
+pub struct RpcEndpoints {
+    pub endpoints: Vec<Endpoint>,
+    // send-only node (e.g. an mev protected relay), reads never go there
+    pub broadcast: Option<Endpoint>,
+    active: AtomicUsize,
+    limiter: RateLimiter,
+}
+
+impl RpcEndpoints {
+    pub fn primary_url(&self) -> &str {
+        &self.endpoints[0].url
+    }
+
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    // requests waiting on the rate limiter right now
+    pub fn queued(&self) -> usize {
+        self.limiter.queued.load(Ordering::Relaxed)
+    }
+
+    fn select(&self, index: usize) {
+        if index < self.endpoints.len() {
+            self.endpoints[index].failures.store(0, Ordering::Relaxed);
+            self.active.store(index, Ordering::Relaxed);
+        }
+    }
+
+    // the node that answered last, then the healthy ones in order, nodes
+    // that keep failing go last but are still tried; fallbacks not known to
+    // be on the primary's cluster are left out
+    fn attempt_order(&self) -> Vec<usize> {
+        let active = self.active();
+        let mut order = vec![active];
+        let others = (0..self.endpoints.len()).filter(|index| {
+            *index != active && self.endpoints[*index].cluster() == ClusterCheck::Same
+        });
+        let (healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            others.partition(|index| self.endpoints[*index].is_healthy());
+        order.extend(healthy);
+        order.extend(unhealthy);
+        order
+    }
+}
+
+// connection errors, timeouts and http errors (429, 5xx) mean the node is
+// the problem; an rpc error answer would be the same on every node
+fn is_transport_error(error: &ClientError) -> bool {
+    matches!(
+        error.kind(),
+        ClientErrorKind::Reqwest(_) | ClientErrorKind::Io(_)
+    )
+}
+
+// doubles per attempt up to the cap, the jitter keeps the background
+// fetchers from retrying in lockstep
+fn retry_delay(attempt: u32) -> Duration {
+    let cap = RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RETRY_MAX_DELAY);
+    rand::thread_rng().gen_range(cap / 2..=cap)
+}
+
+// repeats `call` while it fails on the transport, anything the node answered
+// (even an error) is returned right away
+pub async fn with_retry<T, F, Fut>(mut call: F) -> ClientResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ClientResult<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match call().await {
+            Err(error) if is_transport_error(&error) && attempt + 1 < RETRY_ATTEMPTS => {
+                time::sleep(retry_delay(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+struct FailoverSender(Arc<RpcEndpoints>);
+
+#[async_trait]
+impl RpcSender for FailoverSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        // transactions go to the send-only node first; when it can't be
+        // reached they fall back to the pool rather than not going out at all
+        if let (RpcRequest::SendTransaction, Some(broadcast)) = (request, &self.0.broadcast) {
+            match broadcast.sender.send(request, params.clone()).await {
+                Err(error) if is_transport_error(&error) => {
+                    broadcast.failures.fetch_add(1, Ordering::Relaxed);
+                }
+                result => {
+                    broadcast.failures.store(0, Ordering::Relaxed);
+                    return result;
+                }
+            }
+        }
+        self.0.limiter.acquire().await;
+        let mut last_error = None;
+        for index in self.0.attempt_order() {
+            let endpoint = &self.0.endpoints[index];
+            match endpoint.sender.send(request, params.clone()).await {
+                Err(error) if is_transport_error(&error) => {
+                    endpoint.failures.fetch_add(1, Ordering::Relaxed);
+                    last_error = Some(error);
+                }
+                result => {
+                    endpoint.failures.store(0, Ordering::Relaxed);
+                    self.0.active.store(index, Ordering::Relaxed);
+                    return result;
+                }
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| ClientErrorKind::Custom(String::from("No RPC endpoint")).into()))
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.0.endpoints[self.0.active()]
+            .sender
+            .get_transport_stats()
+    }
+
+    // the node currently answering, websockets and cluster checks follow it
+    fn url(&self) -> String {
+        self.0.endpoints[self.0.active()].url.clone()
+    }
+}
+
+// settings are passed in rather than read back, a change is saved in the
+// background and may not be on disk yet
+fn build_client(
+    urls: Vec<String>,
+    broadcast_url: Option<String>,
+    preferences: &Preferences,
+    config: &AppConfig,
+) -> (Arc<RpcClient>, Arc<RpcEndpoints>) {
+    let http_client = http_client();
+    let endpoint = |url: String| Endpoint {
+        sender: HttpSender::new_with_client(url.clone(), http_client.clone()),
+        url,
+        failures: AtomicU32::new(0),
+        latency: Mutex::new(Latency::Untested),
+        cluster: Mutex::new(ClusterCheck::Pending),
+    };
+    let endpoints = Arc::new(RpcEndpoints {
+        endpoints: urls.into_iter().map(endpoint).collect(),
+        broadcast: broadcast_url.map(endpoint),
+        active: AtomicUsize::new(0),
+        limiter: RateLimiter::new(requests_per_sec(&preferences.rpc_rate_limit)),
+    });
+    if let Some(primary) = endpoints.endpoints.first() {
+        *primary.cluster.lock().unwrap() = ClusterCheck::Same;
+    }
+
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        FailoverSender(Arc::clone(&endpoints)),
+        RpcClientConfig::with_commitment(commitment_config(&config.commitment)),
+    ));
+    (rpc_client, endpoints)
+}
+
+// measures every node at once and moves the pool to the fastest one that
+// answered, the primary stays the primary for the next rebuild
+pub async fn benchmark_endpoints(endpoints: Arc<RpcEndpoints>) -> Option<String> {
+    let latencies = join_all(endpoints.endpoints.iter().map(Endpoint::measure)).await;
+    let (fastest, _) = latencies
+        .iter()
+        .enumerate()
+        .filter_map(|(index, latency)| match latency {
+            Latency::Measured(elapsed)
+                if endpoints.endpoints[index].cluster() == ClusterCheck::Same =>
+            {
+                Some((index, *elapsed))
+            }
+            _ => None,
+        })
+        .min_by_key(|(_, elapsed)| *elapsed)?;
+    endpoints.select(fastest);
+    Some(endpoints.endpoints[fastest].url.clone())
+}
+
+// asks every node for its genesis hash and lets the fallbacks that share the
+// primary's into the rotation; when the primary is down the hash of the
+// cluster its profile or url names stands in, so failover still works then
+pub async fn match_clusters(endpoints: Arc<RpcEndpoints>, expected: Option<&'static str>) {
+    let hashes = join_all(endpoints.endpoints.iter().map(Endpoint::genesis_hash)).await;
+    let Some(reference) = hashes
+        .first()
+        .cloned()
+        .flatten()
+        .or_else(|| expected.and_then(genesis_hash).map(str::to_string))
+    else {
+        return;
+    };
+
+    for (endpoint, hash) in endpoints.endpoints.iter().zip(hashes).skip(1) {
+        *endpoint.cluster.lock().unwrap() = match hash {
+            Some(hash) if hash == reference => ClusterCheck::Same,
+            Some(_) => ClusterCheck::Other,
+            // tried again on the next rebuild or endpoint test
+            None => ClusterCheck::Pending,
+        };
+    }
+}
+
+// the client plus a handle on its nodes for the health list in settings
+pub fn new_rpc_pool(
+    url: &str,
+    preferences: &Preferences,
+    config: &AppConfig,
+) -> (Arc<RpcClient>, Arc<RpcEndpoints>) {
+    build_client(
+        pool_urls(url, &preferences.fallback_rpc_urls),
+        broadcast_url(url, &preferences.broadcast_rpc_url),
+        preferences,
+        config,
+    )
+}
+
+// the one rpc client every subsystem shares, clone the Arc instead of
+// building new clients
+pub fn new_rpc_client(url: &str) -> Arc<RpcClient> {
+    new_rpc_pool(url, &load_preferences(), &load_config()).0
+}
+
+// deep history lookups go to a separate, optional node, regular fallbacks
+// don't keep the old ledger so it isn't pooled
+pub fn archival_rpc_client(
+    url: &str,
+    preferences: &Preferences,
+    config: &AppConfig,
+) -> Option<Arc<RpcClient>> {
+    let url = url.trim();
+    if url.is_empty() {
+        None
+    } else {
+        Some(build_client(vec![url.to_string()], None, preferences, config).0)
+    }
+}
+
+pub fn display_rpc_endpoints(
+    endpoints: &RpcEndpoints,
+    preferences: &Preferences,
+    commitment: &str,
+    testing: bool,
+) -> Element<'static, Message> {
+    let title = text(tr("RPC endpoints")).size(14).style(accent_color());
+    let test = if testing {
+        button(text(tr("Testing...")))
+    } else {
+        button(text(tr("Test endpoints"))).on_press(Message::TestRpcEndpoints)
+    };
+    let header = row![title, test].spacing(10).align_items(Alignment::Center);
+
+    let fallbacks = text_input(
+        tr("Fallback RPC URLs, comma separated (only those on the primary's cluster are used)"),
+        &preferences.fallback_rpc_urls,
+    )
+    .on_input(Message::FallbackRpcUrlsChanged);
+    let broadcast = text_input(
+        tr("Send-only RPC URL, e.g. a protected relay (transactions go there, reads stay on the nodes below)"),
+        &preferences.broadcast_rpc_url,
+    )
+    .on_input(Message::BroadcastRpcUrlChanged);
+    let rate_limit = text_input(
+        &tr_args(
+            "Requests per second (default {$default}, 0 for no limit)",
+            &[("default", &DEFAULT_REQUESTS_PER_SEC)],
+        ),
+        &preferences.rpc_rate_limit,
+    )
+    .on_input(Message::RpcRateLimitChanged);
+
+    // balances, blockhashes and send confirmations all follow this
+    let selected = COMMITMENT_LEVELS
+        .into_iter()
+        .find(|level| *level == commitment)
+        .unwrap_or("finalized");
+    let commitment = row![
+        text(tr("Commitment")).size(14),
+        pick_list(&COMMITMENT_LEVELS[..], Some(selected), |level| {
+            Message::CommitmentChanged(level.to_string())
+        }),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let active = endpoints.active();
+    let list = endpoints.endpoints.iter().enumerate().fold(
+        Column::new().spacing(5),
+        |column, (index, endpoint)| {
+            let state = match (index == active, endpoint.cluster(), endpoint.is_healthy()) {
+                (true, _, _) => String::from(tr("in use")),
+                (false, ClusterCheck::Pending, _) => String::from(tr("checking cluster")),
+                (false, ClusterCheck::Other, _) => String::from(tr("other cluster, not used")),
+                (false, ClusterCheck::Same, true) => String::from(tr("standby")),
+                (false, ClusterCheck::Same, false) => tr_args(
+                    "failing ({$failures} errors in a row)",
+                    &[("failures", &endpoint.failures())],
+                ),
+            };
+            let latency = match endpoint.latency() {
+                Latency::Untested => String::new(),
+                Latency::Failed => format!(", {}", tr("test failed")),
+                Latency::Measured(elapsed) => format!(", {} ms", elapsed.as_millis()),
+            };
+            column.push(text(format!("{} - {}{}", endpoint.url, state, latency)).size(12))
+        },
+    );
+
+    let list = match &endpoints.broadcast {
+        Some(broadcast) => list.push(
+            text(format!(
+                "{} - {}{}",
+                broadcast.url,
+                tr("send only"),
+                if broadcast.is_healthy() {
+                    String::new()
+                } else {
+                    format!(
+                        ", {}",
+                        tr_args(
+                            "failing ({$failures} errors in a row)",
+                            &[("failures", &broadcast.failures())],
+                        )
+                    )
+                }
+            ))
+            .size(12),
+        ),
+        // set but on another cluster than the nodes above
+        None if !preferences.broadcast_rpc_url.trim().is_empty() => list.push(
+            text(tr(
+                "The send-only URL is for another cluster, transactions go to the nodes above",
+            ))
+            .size(12),
+        ),
+        None => list,
+    };
+
+    column![header, fallbacks, broadcast, rate_limit, commitment, list]
+        .spacing(10)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_urls_keep_the_primary_first_and_drop_duplicates() {
+        assert_eq!(
+            pool_urls(
+                " https://api.devnet.solana.com ",
+                "https://a.example, https://api.devnet.solana.com\nhttps://b.example  https://a.example,,",
+            ),
+            vec![
+                "https://api.devnet.solana.com",
+                "https://a.example",
+                "https://b.example"
+            ]
+        );
+    }
+
+    // every fallback is kept, the cluster check happens once they answered
+    #[test]
+    fn pool_urls_keep_fallbacks_of_any_cluster() {
+        assert_eq!(
+            pool_urls(
+                "https://api.devnet.solana.com",
+                "https://api.mainnet-beta.solana.com"
+            ),
+            vec![
+                "https://api.devnet.solana.com",
+                "https://api.mainnet-beta.solana.com"
+            ]
+        );
+        assert_eq!(
+            pool_urls("https://api.devnet.solana.com", ""),
+            vec!["https://api.devnet.solana.com"]
+        );
+    }
+
+    #[test]
+    fn the_limiter_allows_a_burst_then_waits() {
+        let limiter = RateLimiter::new(5);
+        for _ in 0..(5.0 * BURST_SECONDS) as usize {
+            assert_eq!(limiter.take(), None);
+        }
+        let wait = limiter.take().unwrap();
+        assert!(wait > Duration::ZERO && wait <= Duration::from_millis(200));
+    }
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn a_disabled_limiter_never_queues() {
+        let limiter = RateLimiter::new(0);
+        runtime().block_on(async {
+            for _ in 0..100 {
+                limiter.acquire().await;
+            }
+        });
+        assert_eq!(limiter.queued.load(Ordering::Relaxed), 0);
+    }
+
+    // a fetch cancelled while waiting for a token leaves the queue
+    #[test]
+    fn a_dropped_request_leaves_the_queue() {
+        let limiter = RateLimiter::new(1);
+        while limiter.take().is_none() {}
+        runtime().block_on(async {
+            let waited = time::timeout(Duration::from_millis(50), limiter.acquire()).await;
+            assert!(waited.is_err());
+        });
+        assert_eq!(limiter.queued.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn rate_limit_setting_falls_back_to_the_default() {
+        assert_eq!(requests_per_sec(""), DEFAULT_REQUESTS_PER_SEC);
+        assert_eq!(requests_per_sec("abc"), DEFAULT_REQUESTS_PER_SEC);
+        assert_eq!(requests_per_sec(" 0 "), 0);
+        assert_eq!(requests_per_sec("25"), 25);
+    }
+}
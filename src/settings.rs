@@ -0,0 +1,321 @@
+use iced::{
+    widget::{button, checkbox, column, row, text, text_input},
+    Element,
+};
+
+use std::fs;
+
+use crate::{
+    errors::Error,
+    files::app_config_dir,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+pub const PREFERENCES_FILE: &str = "preferences.txt";
+
+// settings that survive a restart
+#[derive(Debug, Clone, Default)]
+pub struct Preferences {
+    pub default_memo: String,
+    pub default_memo_enabled: bool,
+    // queried for history the primary node has pruned, empty to disable
+    pub archival_rpc_url: String,
+    // end-of-day balance csvs go here, empty to disable
+    pub snapshot_folder: String,
+    // the keystore passphrase is kept in the os keychain
+    pub keychain_unlock: bool,
+    // name of the profile applied on startup, empty for none
+    pub active_profile: String,
+    // overrides the cli's keypair_path and id.json, empty to follow them
+    pub default_keypair_path: String,
+    // seconds between balance and token polls, empty to rely on the websocket
+    pub balance_refresh_secs: String,
+    // no desktop notification when the active wallet receives something
+    pub mute_incoming: bool,
+    // closing the window hides it to the tray, read once at startup
+    pub minimize_to_tray: bool,
+    // extra nodes the rpc client fails over to, once their genesis hash
+    // matches the primary's
+    pub fallback_rpc_urls: String,
+    // requests per second sent to the rpc pool, empty for the default, 0 for no limit
+    pub rpc_rate_limit: String,
+    // transactions are sent only through this node when set, empty to send
+    // through the rpc pool
+    pub broadcast_rpc_url: String,
+    // SOL that can leave the wallet before the password is asked again,
+    // empty to disable
+    pub spend_limit: String,
+}
+
+impl Preferences {
+    // what the memo field starts with for a new payment
+    pub fn initial_memo(&self) -> String {
+        if self.default_memo_enabled {
+            self.default_memo.clone()
+        } else {
+            String::new()
+        }
+    }
+}
+
+// one "<key>=<value>" entry per line
+pub fn load_preferences() -> Preferences {
+    let content = fs::read_to_string(app_config_dir().join(PREFERENCES_FILE)).unwrap_or_default();
+
+    let mut preferences = Preferences::default();
+    for line in content.lines() {
+        match line.split_once('=') {
+            Some(("default_memo", memo)) => preferences.default_memo = memo.to_string(),
+            Some(("default_memo_enabled", enabled)) => {
+                preferences.default_memo_enabled = enabled == "true"
+            }
+            Some(("archival_rpc_url", url)) => preferences.archival_rpc_url = url.to_string(),
+            Some(("snapshot_folder", folder)) => preferences.snapshot_folder = folder.to_string(),
+            Some(("keychain_unlock", enabled)) => preferences.keychain_unlock = enabled == "true",
+            Some(("active_profile", name)) => preferences.active_profile = name.to_string(),
+            Some(("default_keypair_path", path)) => {
+                preferences.default_keypair_path = path.to_string()
+            }
+            Some(("balance_refresh_secs", seconds)) => {
+                preferences.balance_refresh_secs = seconds.to_string()
+            }
+            Some(("mute_incoming", muted)) => preferences.mute_incoming = muted == "true",
+            Some(("minimize_to_tray", enabled)) => preferences.minimize_to_tray = enabled == "true",
+            Some(("fallback_rpc_urls", urls)) => preferences.fallback_rpc_urls = urls.to_string(),
+            Some(("rpc_rate_limit", limit)) => preferences.rpc_rate_limit = limit.to_string(),
+            Some(("broadcast_rpc_url", url)) => preferences.broadcast_rpc_url = url.to_string(),
+            Some(("spend_limit", limit)) => preferences.spend_limit = limit.to_string(),
+            _ => {}
+        }
+    }
+    preferences
+}
+
+pub async fn save_preferences(preferences: Preferences) -> Result<(), Error> {
+    let content = format!(
+        "default_memo={}\ndefault_memo_enabled={}\narchival_rpc_url={}\nsnapshot_folder={}\nkeychain_unlock={}\nactive_profile={}\ndefault_keypair_path={}\nbalance_refresh_secs={}\nmute_incoming={}\nminimize_to_tray={}\nfallback_rpc_urls={}\nrpc_rate_limit={}\nbroadcast_rpc_url={}\nspend_limit={}\n",
+        preferences.default_memo.replace('\n', " "),
+        preferences.default_memo_enabled,
+        preferences.archival_rpc_url.trim(),
+        preferences.snapshot_folder.trim(),
+        preferences.keychain_unlock,
+        preferences.active_profile,
+        preferences.default_keypair_path.trim(),
+        preferences.balance_refresh_secs.trim(),
+        preferences.mute_incoming,
+        preferences.minimize_to_tray,
+        preferences.fallback_rpc_urls.trim(),
+        preferences.rpc_rate_limit.trim(),
+        preferences.broadcast_rpc_url.trim(),
+        preferences.spend_limit.trim()
+    );
+
+    tokio::fs::create_dir_all(app_config_dir())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    tokio::fs::write(app_config_dir().join(PREFERENCES_FILE), content)
+        .await
+        .map_err(|_| Error::SaveFileError)
+}
+
+pub fn display_settings(
+    portable: bool,
+    privacy_mode: bool,
+    large_transfer_sol: &str,
+    large_transfer_percent: &str,
+    preferences: &Preferences,
+) -> Element<'static, Message> {
+    let privacy_title = text(tr("Privacy")).size(14).style(accent_color());
+
+    let privacy = checkbox(
+        tr("Privacy mode: hide balances until revealed with Ctrl+H"),
+        privacy_mode,
+        Message::PrivacyModeToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    let transfers_title = text(tr("Transfers")).size(14).style(accent_color());
+
+    let large_transfer_sol = text_input(
+        tr("Ask for confirmation above this amount in SOL (empty to disable)"),
+        large_transfer_sol,
+    )
+    .on_input(Message::LargeTransferSolChanged);
+
+    let large_transfer_percent = text_input(
+        tr("Ask for confirmation above this % of the balance (empty to disable)"),
+        large_transfer_percent,
+    )
+    .on_input(Message::LargeTransferPercentChanged);
+
+    let default_memo_enabled = checkbox(
+        tr("Attach a default memo to outgoing payments (can be edited per send)"),
+        preferences.default_memo_enabled,
+        Message::DefaultMemoToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    let default_memo = text_input(
+        tr("Default memo, e.g. an invoice prefix or team name"),
+        &preferences.default_memo,
+    )
+    .on_input(Message::DefaultMemoChanged);
+
+    let balances_title = text(tr("Balances")).size(14).style(accent_color());
+
+    let balance_refresh = text_input(
+        tr("Re-poll the balance and token accounts every N seconds (empty to rely on live updates)"),
+        &preferences.balance_refresh_secs,
+    )
+    .on_input(Message::BalanceRefreshChanged);
+
+    let mute_incoming = checkbox(
+        tr("Mute desktop notifications for incoming transfers"),
+        preferences.mute_incoming,
+        Message::MuteIncomingToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    let minimize_to_tray = checkbox(
+        tr("Keep running in the system tray when the window is closed (applies on restart)"),
+        preferences.minimize_to_tray,
+        Message::MinimizeToTrayToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    let history_title = text(tr("History")).size(14).style(accent_color());
+
+    let archival_rpc_url = text_input(
+        tr("Archival RPC URL for history older than the main node keeps (empty to disable)"),
+        &preferences.archival_rpc_url,
+    )
+    .on_input(Message::ArchivalRpcUrlChanged);
+
+    let bookkeeping_title = text(tr("Bookkeeping")).size(14).style(accent_color());
+
+    let snapshot_folder = text_input(
+        tr("Folder for daily end-of-day balance CSVs (empty to disable)"),
+        &preferences.snapshot_folder,
+    )
+    .on_input(Message::SnapshotFolderChanged);
+
+    let snapshot_now = button(tr("Export balances now")).on_press(Message::ExportSnapshot);
+
+    let snapshot_headless = text(tr(
+        "Run with --export-balances <folder> to export without opening the window",
+    ))
+    .size(12);
+
+    let storage_title = text(tr("Storage")).size(14).style(accent_color());
+
+    let default_keypair = row![
+        text_input(
+            tr("Keypair loaded on startup (empty to follow the Solana CLI config)"),
+            &preferences.default_keypair_path,
+        )
+        .on_input(Message::DefaultKeypairPathChanged),
+        button(tr("Browse")).on_press(Message::PickDefaultKeypair),
+    ]
+    .spacing(10);
+
+    let data_dir = text(tr_args(
+        "App data directory: {$path}",
+        &[("path", &app_config_dir().display())],
+    ))
+    .size(14);
+
+    let portable_mode = checkbox(
+        tr("Portable mode: keep app data next to the executable (applies on restart)"),
+        portable,
+        Message::PortableModeToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    let overrides = text(tr("Use --data-dir <path> to store app data somewhere else")).size(12);
+
+    column![
+        privacy_title,
+        privacy,
+        transfers_title,
+        large_transfer_sol,
+        large_transfer_percent,
+        default_memo_enabled,
+        default_memo,
+        balances_title,
+        balance_refresh,
+        mute_incoming,
+        minimize_to_tray,
+        history_title,
+        archival_rpc_url,
+        bookkeeping_title,
+        snapshot_folder,
+        snapshot_now,
+        snapshot_headless,
+        storage_title,
+        default_keypair,
+        data_dir,
+        portable_mode,
+        overrides
+    ]
+    .spacing(10)
+    .into()
+}
+
+// a limit only means something if lifting it takes more than editing a field,
+// so changing or clearing one needs the wallet's password
+pub fn display_spend_limit(
+    limit: &str,
+    input: &str,
+    password: &str,
+    guarded: bool,
+) -> Element<'static, Message> {
+    let title = text(tr("Spend limit")).size(14).style(accent_color());
+
+    if !guarded {
+        return column![
+            title,
+            text(tr(
+                "Set a startup password or move the wallet to a keystore to use a spend limit"
+            ))
+            .size(12),
+        ]
+        .spacing(10)
+        .into();
+    }
+
+    let current = if limit.trim().is_empty() {
+        String::from(tr("No limit set"))
+    } else {
+        tr_args(
+            "Current limit: {$limit} SOL per session",
+            &[("limit", &limit.trim())],
+        )
+    };
+    let input = text_input(tr("New limit in SOL (empty to disable)"), input)
+        .on_input(Message::SpendLimitChanged)
+        .on_submit(Message::ApplySpendLimit);
+    let password = text_input(tr("Wallet password"), password)
+        .on_input(Message::SpendPasswordChanged)
+        .on_submit(Message::ApplySpendLimit)
+        .password();
+
+    column![
+        title,
+        text(current).size(12),
+        row![
+            input,
+            password,
+            button(tr("Apply")).on_press(Message::ApplySpendLimit)
+        ]
+        .spacing(10),
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,202 @@
+use std::{
+    env,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use solana_account_decoder::UiAccountData;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+use crate::{
+    errors::Error,
+    files::default_file,
+    loaders::load_keypair_from_file,
+    rpc::new_rpc_client,
+    test_wallets::{load_test_sets, TestSet},
+    token_accounts::{TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    units::format_sol,
+};
+
+// how often the app checks whether the last day still needs a snapshot
+pub const SNAPSHOT_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// one asset held by one wallet at the end of the day
+#[derive(Debug, Clone)]
+pub struct SnapshotRow {
+    pub wallet: String,
+    pub address: Pubkey,
+    // "SOL" or the token mint
+    pub asset: String,
+    pub amount: String,
+}
+
+// YYYY-MM-DD in utc, days since the epoch to a civil date
+pub fn utc_date(unix_seconds: u64) -> String {
+    let days = (unix_seconds / SECONDS_PER_DAY) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// the day that closed most recently, a snapshot taken now stands for its end
+pub fn last_closed_day() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    utc_date(now.saturating_sub(SECONDS_PER_DAY))
+}
+
+pub fn snapshot_path(folder: &str, day: &str) -> PathBuf {
+    PathBuf::from(folder.trim()).join(format!("balances-{}.csv", day))
+}
+
+// the loaded wallet plus every test set, each address only once
+pub fn snapshot_wallets(signer: Option<Pubkey>, test_sets: &[TestSet]) -> Vec<(String, Pubkey)> {
+    let mut wallets: Vec<(String, Pubkey)> = Vec::new();
+    if let Some(signer) = signer {
+        wallets.push((String::from("main"), signer));
+    }
+    for set in test_sets {
+        for (index, (_, pubkey)) in set.wallets.iter().enumerate() {
+            if wallets.iter().all(|(_, known)| known != pubkey) {
+                wallets.push((format!("{}-{:03}", set.label, index + 1), *pubkey));
+            }
+        }
+    }
+    wallets
+}
+
+// headless runs have no loaded wallet, fall back to the default keypair
+pub fn default_wallet() -> Option<Pubkey> {
+    load_keypair_from_file(default_file())
+        .ok()
+        .map(|keypair| keypair.pubkey())
+}
+
+pub async fn token_rows(
+    wallet: &str,
+    owner: &Pubkey,
+    rpc_client: &RpcClient,
+) -> Result<Vec<SnapshotRow>, Error> {
+    let mut rows = Vec::new();
+
+    for program in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+        let accounts = rpc_client
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program))
+            .await
+            .map_err(|_| Error::FetchAccountError)?;
+
+        for keyed in accounts {
+            let UiAccountData::Json(parsed) = keyed.account.data else {
+                continue;
+            };
+            let info = &parsed.parsed["info"];
+            let (Some(mint), Some(amount)) = (
+                info["mint"].as_str(),
+                info["tokenAmount"]["uiAmountString"].as_str(),
+            ) else {
+                continue;
+            };
+
+            rows.push(SnapshotRow {
+                wallet: wallet.to_string(),
+                address: *owner,
+                asset: mint.to_string(),
+                amount: amount.to_string(),
+            });
+        }
+    }
+
+    Ok(rows)
+}
+
+pub async fn fetch_snapshot(
+    wallets: Vec<(String, Pubkey)>,
+    rpc_client: Arc<RpcClient>,
+) -> Result<Vec<SnapshotRow>, Error> {
+    let mut rows = Vec::new();
+
+    for (wallet, address) in wallets {
+        let lamports = rpc_client
+            .get_balance(&address)
+            .await
+            .map_err(|_| Error::FetchBalanceError)?;
+        rows.push(SnapshotRow {
+            wallet: wallet.clone(),
+            address,
+            asset: String::from("SOL"),
+            amount: format_sol(lamports),
+        });
+        rows.extend(token_rows(&wallet, &address, &rpc_client).await?);
+    }
+
+    Ok(rows)
+}
+
+// fiat_value stays empty until the app has a price source
+pub fn snapshot_csv(day: &str, rows: &[SnapshotRow]) -> String {
+    let mut csv = String::from("date,wallet,address,asset,amount,fiat_value\n");
+
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},\n",
+            day, row.wallet, row.address, row.asset, row.amount
+        ));
+    }
+
+    csv
+}
+
+pub async fn export_snapshot(
+    folder: String,
+    wallets: Vec<(String, Pubkey)>,
+    rpc_client: Arc<RpcClient>,
+) -> Result<PathBuf, Error> {
+    if folder.trim().is_empty() {
+        return Err(Error::SaveFileError);
+    }
+    let day = last_closed_day();
+    let rows = fetch_snapshot(wallets, rpc_client).await?;
+    let path = snapshot_path(&folder, &day);
+
+    tokio::fs::create_dir_all(folder.trim())
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    tokio::fs::write(&path, snapshot_csv(&day, &rows))
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(path)
+}
+
+// `--export-balances <folder>` runs one export without opening the window
+pub fn headless_export_folder() -> Option<String> {
+    let mut args = env::args();
+    args.find(|arg| arg == "--export-balances")?;
+    args.next()
+}
+
+pub fn run_headless_export(folder: String, rpc_url: &str) -> Result<PathBuf, Error> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|_| Error::SaveFileError)?;
+    let wallets = snapshot_wallets(default_wallet(), &load_test_sets());
+
+    runtime.block_on(export_snapshot(folder, wallets, new_rpc_client(rpc_url)))
+}
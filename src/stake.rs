@@ -0,0 +1,738 @@
+use std::{str::FromStr, sync::Arc};
+
+use iced::{
+    widget::{button, column, row, text, text_input, Column},
+    Alignment, Command, Element,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    clock::Epoch,
+    message::Message as TransactionMessage,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    stake::{
+        self, instruction as stake_instruction,
+        state::{Authorized, Lockup, StakeStateV2},
+    },
+    system_instruction, vote,
+};
+
+use crate::{
+    chart::unix_now,
+    errors::{report, Error},
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color, success_color, warning_color},
+    rpc::with_retry,
+    transaction::send_instructions,
+    units::{format_sol, parse_amount},
+    Message, Screen,
+};
+
+// offset of the withdraw authority: state tag (4) + rent exempt reserve (8) +
+// stake authority (32)
+const WITHDRAWER_OFFSET: usize = 44;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StakeStatus {
+    // funded but never delegated, withdrawable right away
+    Undelegated,
+    Activating,
+    Active,
+    // withdrawable once `inactive_from` starts
+    Deactivating { inactive_from: Epoch },
+    Inactive,
+}
+
+impl StakeStatus {
+    // cooldown normally completes at the first epoch after the deactivation;
+    // when a lot of stake leaves at once the cluster spreads it over more
+    // epochs and refuses an early withdraw, which only costs the fee
+    fn new(activation_epoch: Epoch, deactivation_epoch: Epoch, current_epoch: Epoch) -> Self {
+        if deactivation_epoch == Epoch::MAX {
+            if activation_epoch >= current_epoch {
+                StakeStatus::Activating
+            } else {
+                StakeStatus::Active
+            }
+        } else if deactivation_epoch >= current_epoch {
+            StakeStatus::Deactivating {
+                inactive_from: deactivation_epoch + 1,
+            }
+        } else {
+            StakeStatus::Inactive
+        }
+    }
+
+    pub fn can_withdraw(&self) -> bool {
+        matches!(self, StakeStatus::Undelegated | StakeStatus::Inactive)
+    }
+
+    pub fn can_deactivate(&self) -> bool {
+        matches!(self, StakeStatus::Activating | StakeStatus::Active)
+    }
+
+    // a cooling down account can't take part in a merge until it's inactive
+    fn is_inactive(&self) -> bool {
+        matches!(self, StakeStatus::Undelegated | StakeStatus::Inactive)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StakeAccount {
+    pub address: Pubkey,
+    pub lamports: u64,
+    pub voter: Option<Pubkey>,
+    pub status: StakeStatus,
+}
+
+impl StakeAccount {
+    // the stake program's merge rules for accounts sharing authorities and
+    // lockup, which all accounts listed here do
+    pub fn can_merge_from(&self, source: &StakeAccount) -> bool {
+        if self.address == source.address {
+            return false;
+        }
+        match (self.status, source.status) {
+            (to, from) if to.is_inactive() && from.is_inactive() => true,
+            (StakeStatus::Activating, from) if from.is_inactive() => true,
+            (StakeStatus::Activating, StakeStatus::Activating)
+            | (StakeStatus::Active, StakeStatus::Active) => self.voter == source.voter,
+            _ => false,
+        }
+    }
+}
+
+// everything checked before anything is signed, shown for confirmation
+#[derive(Debug, Clone)]
+pub struct StakePreview {
+    pub vote_account: Pubkey,
+    // goes into the new stake account, rent included
+    pub lamports: u64,
+    pub rent: u64,
+    pub minimum_delegation: u64,
+    pub fee: u64,
+}
+
+impl StakePreview {
+    pub fn delegated(&self) -> u64 {
+        self.lamports.saturating_sub(self.rent)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StakeForm {
+    pub vote_account: String,
+    pub amount: String,
+    pub preview: Option<StakePreview>,
+    // the large amount step of the shared confirmation
+    pub confirming_large: bool,
+    pub busy: bool,
+    pub signature: String,
+    pub split_amount: String,
+    // picked with Merge, the next account clicked receives it
+    pub merge_source: Option<Pubkey>,
+}
+
+impl StakeForm {
+    pub fn clear_preview(&mut self) {
+        self.preview = None;
+        self.confirming_large = false;
+    }
+
+    pub fn start_preview(&mut self, owner: Pubkey, rpc_client: Arc<RpcClient>) -> Command<Message> {
+        self.busy = true;
+        self.signature = String::new();
+        Command::perform(
+            preview_stake(
+                owner,
+                self.vote_account.clone(),
+                self.amount.clone(),
+                rpc_client,
+            ),
+            Message::StakePreviewed,
+        )
+    }
+
+    // the app checks the cluster, allow list and spend limit first, a large
+    // amount then waits for a second click
+    pub fn create(
+        &mut self,
+        preview: StakePreview,
+        large: bool,
+        signer: Arc<Keypair>,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        if large && !self.confirming_large {
+            self.confirming_large = true;
+            return Command::none();
+        }
+        self.confirming_large = false;
+        self.busy = true;
+        Command::perform(
+            create_stake_account(signer, preview, rpc_client),
+            Message::StakeCreated,
+        )
+    }
+
+    pub fn deactivate(
+        &mut self,
+        signer: Arc<Keypair>,
+        address: Pubkey,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        self.busy = true;
+        Command::perform(
+            deactivate_stake(signer, address, rpc_client),
+            Message::StakeAccountUpdated,
+        )
+    }
+
+    pub fn withdraw(
+        &mut self,
+        signer: Arc<Keypair>,
+        account: StakeAccount,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        self.busy = true;
+        Command::perform(
+            withdraw_stake(signer, account, rpc_client),
+            Message::StakeAccountUpdated,
+        )
+    }
+
+    pub fn split(
+        &mut self,
+        signer: Arc<Keypair>,
+        account: StakeAccount,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        self.busy = true;
+        Command::perform(
+            split_stake(signer, account, self.split_amount.clone(), rpc_client),
+            Message::StakeSplit,
+        )
+    }
+
+    pub fn merge(
+        &mut self,
+        signer: Arc<Keypair>,
+        destination: StakeAccount,
+        source: StakeAccount,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        self.merge_source = None;
+        self.busy = true;
+        Command::perform(
+            merge_stake(signer, destination, source, rpc_client),
+            Message::StakeAccountUpdated,
+        )
+    }
+
+    // the app counts a created stake against the spend limit from the preview
+    // before handing the result over
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::StakeVoteAccountChanged(vote_account) => {
+                self.vote_account = vote_account;
+                self.clear_preview();
+            }
+            Message::PickValidator(vote_account) => {
+                self.vote_account = vote_account.to_string();
+                self.clear_preview();
+            }
+            Message::StakeAmountChanged(amount) => {
+                self.amount = amount;
+                self.clear_preview();
+            }
+            Message::CancelStake => self.clear_preview(),
+            Message::StakeSplitAmountChanged(amount) => self.split_amount = amount,
+            Message::PickMergeSource(address) => self.merge_source = Some(address),
+            Message::CancelMergeStake => self.merge_source = None,
+            Message::StakePreviewed(result) => {
+                self.busy = false;
+                match result {
+                    Ok(preview) => self.preview = Some(preview),
+                    Err(error) => return report(error),
+                }
+            }
+            Message::StakeCreated(result) => {
+                self.busy = false;
+                match result {
+                    Ok((_, signature)) => {
+                        self.preview = None;
+                        self.signature = signature;
+                        self.amount = String::new();
+                    }
+                    Err(error) => return report(error),
+                }
+            }
+            Message::StakeSplit(result) => {
+                self.busy = false;
+                match result {
+                    Ok((_, signature)) => {
+                        self.signature = signature;
+                        self.split_amount = String::new();
+                    }
+                    Err(error) => return report(error),
+                }
+            }
+            Message::StakeAccountUpdated(result) => {
+                self.busy = false;
+                match result {
+                    Ok(signature) => self.signature = signature,
+                    Err(error) => return report(error),
+                }
+            }
+            _ => {}
+        }
+        Command::none()
+    }
+}
+
+// the amount has to cover the stake account rent plus the cluster's minimum
+// delegation, and the wallet has to cover the amount plus the fee
+pub async fn preview_stake(
+    owner: Pubkey,
+    vote_account: String,
+    amount: String,
+    rpc_client: Arc<RpcClient>,
+) -> Result<StakePreview, Error> {
+    let vote_account =
+        Pubkey::from_str(vote_account.trim()).map_err(|_| Error::InvalidVoteAccount)?;
+    let lamports = parse_amount(&amount)?;
+
+    let is_vote_account = rpc_client
+        .get_account_with_commitment(&vote_account, rpc_client.commitment())
+        .await
+        .map_err(|_| Error::FetchAccountError)?
+        .value
+        .is_some_and(|account| account.owner == vote::program::id());
+    if !is_vote_account {
+        return Err(Error::InvalidVoteAccount);
+    }
+
+    let rent =
+        with_retry(|| rpc_client.get_minimum_balance_for_rent_exemption(StakeStateV2::size_of()))
+            .await
+            .map_err(|_| Error::FetchAccountError)?;
+    let minimum_delegation = with_retry(|| rpc_client.get_stake_minimum_delegation())
+        .await
+        .map_err(|_| Error::FetchAccountError)?;
+    if lamports < rent.saturating_add(minimum_delegation) {
+        return Err(Error::StakeBelowMinimum);
+    }
+
+    // the same instructions with a placeholder stake account, the fee only
+    // depends on the signature count
+    let blockhash = with_retry(|| rpc_client.get_latest_blockhash())
+        .await
+        .map_err(|_| Error::FetchBlockhashError)?;
+    let message = TransactionMessage::new_with_blockhash(
+        &stake_instruction::create_account_and_delegate_stake(
+            &owner,
+            &Pubkey::new_unique(),
+            &vote_account,
+            &Authorized::auto(&owner),
+            &Lockup::default(),
+            lamports,
+        ),
+        Some(&owner),
+        &blockhash,
+    );
+    let fee = with_retry(|| rpc_client.get_fee_for_message(&message))
+        .await
+        .map_err(|_| Error::FetchFeeError)?;
+    let balance = with_retry(|| rpc_client.get_balance(&owner))
+        .await
+        .map_err(|_| Error::FetchBalanceError)?;
+    if balance < lamports.saturating_add(fee) {
+        return Err(Error::InsufficientBalance);
+    }
+
+    Ok(StakePreview {
+        vote_account,
+        lamports,
+        rent,
+        minimum_delegation,
+        fee,
+    })
+}
+
+// a fresh keypair holds the stake, the wallet is both stake and withdraw
+// authority so it can deactivate and withdraw later without that keypair
+pub async fn create_stake_account(
+    signer: Arc<Keypair>,
+    preview: StakePreview,
+    rpc_client: Arc<RpcClient>,
+) -> Result<(Pubkey, String), Error> {
+    let stake_account = Keypair::new();
+    let owner = signer.pubkey();
+    let instructions = stake_instruction::create_account_and_delegate_stake(
+        &owner,
+        &stake_account.pubkey(),
+        &preview.vote_account,
+        &Authorized::auto(&owner),
+        &Lockup::default(),
+        preview.lamports,
+    );
+    let signature =
+        send_instructions(&rpc_client, &[&*signer, &stake_account], &instructions).await?;
+    Ok((stake_account.pubkey(), signature))
+}
+
+// stake accounts the wallet can withdraw from, whoever created them
+pub async fn fetch_stake_accounts(
+    owner: Pubkey,
+    rpc_client: Arc<RpcClient>,
+) -> Result<(Vec<StakeAccount>, Epoch), Error> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            WITHDRAWER_OFFSET,
+            &owner.to_bytes(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = rpc_client
+        .get_program_accounts_with_config(&stake::program::id(), config)
+        .await
+        .map_err(|_| Error::FetchAccountError)?;
+    let current_epoch = rpc_client
+        .get_epoch_info()
+        .await
+        .map_err(|_| Error::FetchNetworkInfoError)?
+        .epoch;
+
+    let mut stake_accounts: Vec<StakeAccount> = accounts
+        .into_iter()
+        .filter_map(|(address, account)| {
+            let (voter, status) = match bincode::deserialize(&account.data).ok()? {
+                StakeStateV2::Stake(_, stake, _) => (
+                    Some(stake.delegation.voter_pubkey),
+                    StakeStatus::new(
+                        stake.delegation.activation_epoch,
+                        stake.delegation.deactivation_epoch,
+                        current_epoch,
+                    ),
+                ),
+                StakeStateV2::Initialized(_) => (None, StakeStatus::Undelegated),
+                _ => return None,
+            };
+            Some(StakeAccount {
+                address,
+                lamports: account.lamports,
+                voter,
+                status,
+            })
+        })
+        .collect();
+    stake_accounts.sort_by(|a, b| b.lamports.cmp(&a.lamports));
+    Ok((stake_accounts, current_epoch))
+}
+
+pub async fn deactivate_stake(
+    signer: Arc<Keypair>,
+    stake_account: Pubkey,
+    rpc_client: Arc<RpcClient>,
+) -> Result<String, Error> {
+    let instruction = stake_instruction::deactivate_stake(&stake_account, &signer.pubkey());
+    send_instructions(&rpc_client, &[&*signer], &[instruction]).await
+}
+
+// everything, rent included, goes back to the wallet and the account closes
+pub async fn withdraw_stake(
+    signer: Arc<Keypair>,
+    stake_account: StakeAccount,
+    rpc_client: Arc<RpcClient>,
+) -> Result<String, Error> {
+    if !stake_account.status.can_withdraw() {
+        return Err(Error::StakeStillActive);
+    }
+    let owner = signer.pubkey();
+    let instruction = stake_instruction::withdraw(
+        &stake_account.address,
+        &owner,
+        &owner,
+        stake_account.lamports,
+        None,
+    );
+    send_instructions(&rpc_client, &[&*signer], &[instruction]).await
+}
+
+// the new account's address comes from the wallet and a seed, so there's no
+// extra keypair to keep; the wallet pays its rent up front as the stake
+// program requires and stays the authority of both accounts
+pub async fn split_stake(
+    signer: Arc<Keypair>,
+    stake_account: StakeAccount,
+    amount: String,
+    rpc_client: Arc<RpcClient>,
+) -> Result<(Pubkey, String), Error> {
+    let lamports = parse_amount(&amount)?;
+    if lamports == 0 || lamports >= stake_account.lamports {
+        return Err(Error::InvalidAmount);
+    }
+
+    let rent =
+        with_retry(|| rpc_client.get_minimum_balance_for_rent_exemption(StakeStateV2::size_of()))
+            .await
+            .map_err(|_| Error::FetchAccountError)?;
+    // both halves of a delegated account have to stay delegatable
+    let minimum_delegation = if stake_account.voter.is_some() {
+        with_retry(|| rpc_client.get_stake_minimum_delegation())
+            .await
+            .map_err(|_| Error::FetchAccountError)?
+    } else {
+        0
+    };
+    let remaining = stake_account.lamports - lamports;
+    if lamports < minimum_delegation || remaining < rent.saturating_add(minimum_delegation) {
+        return Err(Error::StakeBelowMinimum);
+    }
+
+    let owner = signer.pubkey();
+    let seed = format!("split-{}", unix_now());
+    let split_account = Pubkey::create_with_seed(&owner, &seed, &stake::program::id())
+        .map_err(|_| Error::TransactionError)?;
+    let mut instructions = vec![system_instruction::transfer(&owner, &split_account, rent)];
+    instructions.extend(stake_instruction::split_with_seed(
+        &stake_account.address,
+        &owner,
+        lamports,
+        &split_account,
+        &owner,
+        &seed,
+    ));
+    let signature = send_instructions(&rpc_client, &[&*signer], &instructions).await?;
+    Ok((split_account, signature))
+}
+
+// the source account is closed and its lamports land in the destination
+pub async fn merge_stake(
+    signer: Arc<Keypair>,
+    destination: StakeAccount,
+    source: StakeAccount,
+    rpc_client: Arc<RpcClient>,
+) -> Result<String, Error> {
+    if !destination.can_merge_from(&source) {
+        return Err(Error::StakeNotMergeable);
+    }
+    let instructions =
+        stake_instruction::merge(&destination.address, &source.address, &signer.pubkey());
+    send_instructions(&rpc_client, &[&*signer], &instructions).await
+}
+
+pub fn display_stake_accounts(
+    accounts: &[StakeAccount],
+    current_epoch: Option<Epoch>,
+    form: &StakeForm,
+) -> Element<'static, Message> {
+    let busy = form.busy;
+    let merge_source = form
+        .merge_source
+        .and_then(|address| accounts.iter().find(|account| account.address == address));
+
+    let header = row![
+        text(tr("Stake accounts")).size(14).style(accent_color()),
+        button(text(tr("Refresh")).size(12)).on_press(Message::RefreshStakeAccounts),
+        text(
+            current_epoch
+                .map(|epoch| tr_args("Current epoch {$epoch}", &[("epoch", &epoch)]))
+                .unwrap_or_default()
+        )
+        .size(12),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    if accounts.is_empty() {
+        return column![
+            header,
+            text(tr("No stake accounts for this wallet")).size(12)
+        ]
+        .spacing(10)
+        .into();
+    }
+
+    let split_amount = text_input(tr("Amount to split off (SOL)"), &form.split_amount)
+        .on_input(Message::StakeSplitAmountChanged);
+    let merge_note = if merge_source.is_some() {
+        text(tr(
+            "Pick the account to merge into, only compatible accounts can be picked",
+        ))
+        .size(12)
+    } else {
+        text(tr(
+            "Merging needs both accounts inactive, or delegated to the same validator",
+        ))
+        .size(12)
+    };
+
+    let list = accounts
+        .iter()
+        .fold(Column::new().spacing(5), |column, account| {
+            let (status, color) = match account.status {
+                StakeStatus::Undelegated => (String::from(tr("not delegated")), warning_color()),
+                StakeStatus::Activating => (String::from(tr("activating")), warning_color()),
+                StakeStatus::Active => (String::from(tr("active")), success_color()),
+                StakeStatus::Deactivating { inactive_from } => (
+                    tr_args(
+                        "cooling down, withdrawable from epoch {$epoch}",
+                        &[("epoch", &inactive_from)],
+                    ),
+                    warning_color(),
+                ),
+                StakeStatus::Inactive => (String::from(tr("inactive")), accent_color()),
+            };
+            let voter = account
+                .voter
+                .map(|voter| tr_args(", validator {$voter}", &[("voter", &voter)]))
+                .unwrap_or_default();
+
+            let deactivate = button(text(tr("Deactivate")).size(12));
+            let deactivate = if account.status.can_deactivate() && !busy {
+                deactivate.on_press(Message::DeactivateStake(account.address))
+            } else {
+                deactivate
+            };
+            let withdraw = button(text(tr("Withdraw")).size(12));
+            let withdraw = if account.status.can_withdraw() && !busy {
+                withdraw.on_press(Message::WithdrawStake(account.address))
+            } else {
+                withdraw
+            };
+            let split = button(text(tr("Split")).size(12));
+            let split = if !form.split_amount.trim().is_empty() && !busy {
+                split.on_press(Message::SplitStake(account.address))
+            } else {
+                split
+            };
+            let merge = match merge_source {
+                Some(source) if source.address == account.address => {
+                    button(text(tr("Cancel merge")).size(12)).on_press(Message::CancelMergeStake)
+                }
+                Some(source) if account.can_merge_from(source) && !busy => {
+                    button(text(tr("Merge here")).size(12))
+                        .on_press(Message::MergeStake(account.address))
+                }
+                Some(_) => button(text(tr("Merge here")).size(12)),
+                None if !busy => button(text(tr("Merge")).size(12))
+                    .on_press(Message::PickMergeSource(account.address)),
+                None => button(text(tr("Merge")).size(12)),
+            };
+
+            column.push(
+                column![
+                    text(format!(
+                        "{}: {} SOL{}",
+                        account.address,
+                        format_sol(account.lamports),
+                        voter
+                    ))
+                    .size(12),
+                    row![
+                        text(status).size(12).style(color),
+                        deactivate,
+                        withdraw,
+                        split,
+                        merge
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+                ]
+                .spacing(2),
+            )
+        });
+
+    column![header, split_amount, merge_note, list]
+        .spacing(10)
+        .into()
+}
+
+pub fn display_stake_form(form: &StakeForm) -> Element<'static, Message> {
+    let title = text(tr("Stake SOL")).size(14).style(accent_color());
+    let vote_account = row![
+        text_input(tr("Validator vote account"), &form.vote_account)
+            .on_input(Message::StakeVoteAccountChanged),
+        button(tr("Browse validators")).on_press(Message::ShowScreen(Screen::Validators)),
+    ]
+    .spacing(10);
+    let amount = text_input(tr("Amount to stake (SOL, rent included)"), &form.amount)
+        .on_input(Message::StakeAmountChanged)
+        .on_submit(Message::PreviewStake);
+
+    let review: Element<'static, Message> = match &form.preview {
+        None => button(tr("Review")).on_press(Message::PreviewStake).into(),
+        Some(preview) => {
+            let mut details = column![
+                text(tr_args(
+                    "Validator: {$vote_account}",
+                    &[("vote_account", &preview.vote_account)]
+                ))
+                .size(14),
+                text(tr_args(
+                    "Delegated: {$delegated} SOL (minimum {$minimum} SOL)",
+                    &[
+                        ("delegated", &format_sol(preview.delegated())),
+                        ("minimum", &format_sol(preview.minimum_delegation)),
+                    ],
+                ))
+                .size(14),
+                text(tr_args(
+                    "Stake account rent: {$rent} SOL, fee: {$fee} SOL",
+                    &[
+                        ("rent", &format_sol(preview.rent)),
+                        ("fee", &format_sol(preview.fee)),
+                    ],
+                ))
+                .size(14),
+            ]
+            .spacing(5);
+            details = if form.busy {
+                details.push(button(tr("Staking...")))
+            } else if form.confirming_large {
+                details
+                    .push(
+                        text(tr("Large stake, please confirm"))
+                            .size(16)
+                            .style(error_color()),
+                    )
+                    .push(
+                        row![
+                            button(tr("Yes, stake it")).on_press(Message::ConfirmStake),
+                            button(tr("Cancel")).on_press(Message::CancelStake),
+                        ]
+                        .spacing(10),
+                    )
+            } else {
+                details.push(
+                    row![
+                        button(tr("Confirm and stake")).on_press(Message::ConfirmStake),
+                        button(tr("Cancel")).on_press(Message::CancelStake),
+                    ]
+                    .spacing(10),
+                )
+            };
+            details.into()
+        }
+    };
+
+    column![
+        title,
+        vote_account,
+        amount,
+        review,
+        text(form.signature.clone()).size(14)
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,143 @@
+use std::{sync::Arc, time::Duration};
+
+use futures::{SinkExt, StreamExt};
+use iced::{
+    subscription,
+    widget::{row, text},
+    Element, Subscription,
+};
+use solana_cli_config::Config;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_pubsub_client::nonblocking::pubsub_client::PubsubClient;
+use tokio::time;
+
+use crate::{
+    epoch::{format_countdown, EpochProgress},
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color, success_color, warning_color},
+    rpc::cluster_name,
+    Message,
+};
+
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+// slots are polled at this pace while the websocket is down
+const SLOT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const RECONNECT_ATTEMPT_EVERY: u32 = 2;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConnectionStatus {
+    pub slot: Option<u64>,
+    // None until the first health check answered
+    pub rpc_healthy: Option<bool>,
+    pub websocket_connected: bool,
+    pub epoch: Option<EpochProgress>,
+}
+
+pub async fn check_rpc_health(rpc_client: Arc<RpcClient>) -> bool {
+    rpc_client.get_health().await.is_ok()
+}
+
+// follows the tip through slotSubscribe and reports whether the socket is up,
+// polling get_slot between reconnect attempts
+pub fn slot_updates(rpc_client: Arc<RpcClient>) -> Subscription<Message> {
+    subscription::channel(
+        (String::from("slots"), rpc_client.url()),
+        16,
+        move |mut output| async move {
+            let ws_url = Config::compute_websocket_url(&rpc_client.url());
+            let mut polls = 0;
+
+            loop {
+                if polls % RECONNECT_ATTEMPT_EVERY == 0 {
+                    if let Ok(pubsub_client) = PubsubClient::new(&ws_url).await {
+                        if let Ok((mut slots, _unsubscribe)) = pubsub_client.slot_subscribe().await
+                        {
+                            output.send(Message::WebsocketStateChanged(true)).await.ok();
+                            while let Some(info) = slots.next().await {
+                                output.send(Message::SlotUpdated(info.slot)).await.ok();
+                            }
+                        }
+                    }
+                    output
+                        .send(Message::WebsocketStateChanged(false))
+                        .await
+                        .ok();
+                }
+
+                if let Ok(slot) = rpc_client.get_slot().await {
+                    output.send(Message::SlotUpdated(slot)).await.ok();
+                }
+                polls += 1;
+                time::sleep(SLOT_POLL_INTERVAL).await;
+            }
+        },
+    )
+}
+
+pub fn display_status_bar(
+    rpc_url: &str,
+    status: &ConnectionStatus,
+    // requests held back by the rate limiter
+    queued: usize,
+    // where the fiat prices came from, None while fiat is off
+    price_source: Option<String>,
+    offline: bool,
+) -> Element<'static, Message> {
+    if offline {
+        return text(tr("Offline mode, no network connection is used"))
+            .size(12)
+            .into();
+    }
+
+    let slot = match status.slot {
+        Some(slot) => tr_args("Slot {$slot}", &[("slot", &slot)]),
+        None => String::from(tr("Slot -")),
+    };
+    let (health, health_color) = match status.rpc_healthy {
+        Some(true) => (tr("RPC healthy"), success_color()),
+        Some(false) => (tr("RPC unhealthy"), error_color()),
+        None => (tr("RPC checking"), accent_color()),
+    };
+    let (websocket, websocket_color) = if status.websocket_connected {
+        (tr("WebSocket connected"), success_color())
+    } else {
+        (tr("WebSocket disconnected"), error_color())
+    };
+
+    // stakers time delegation changes around the epoch boundary
+    let epoch = match status.epoch {
+        Some(epoch) => tr_args(
+            "Epoch {$epoch} {$percent}%, next in {$countdown}",
+            &[
+                ("epoch", &epoch.epoch),
+                ("percent", &format!("{:.1}", epoch.percent(status.slot))),
+                ("countdown", &format_countdown(epoch.remaining(status.slot))),
+            ],
+        ),
+        None => String::from(tr("Epoch -")),
+    };
+
+    let mut bar = row![
+        text(cluster_name(rpc_url)).size(12).style(accent_color()),
+        text(rpc_url.to_string()).size(12),
+        text(slot).size(12),
+        text(epoch).size(12),
+        text(health).size(12).style(health_color),
+        text(websocket).size(12).style(websocket_color),
+    ]
+    .spacing(20);
+    if queued > 0 {
+        bar = bar.push(
+            text(tr_args(
+                "Throttled, {$queued} requests queued",
+                &[("queued", &queued)],
+            ))
+            .size(12)
+            .style(warning_color()),
+        );
+    }
+    if let Some(source) = price_source {
+        bar = bar.push(text(tr_args("Prices: {$source}", &[("source", &source)])).size(12));
+    }
+    bar.into()
+}
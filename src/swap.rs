@@ -0,0 +1,907 @@
+use std::{fmt, str::FromStr, sync::Arc, time::Duration};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use iced::{
+    widget::{button, column, pick_list, row, text, text_input, Column},
+    Alignment, Command, Element, Length,
+};
+use serde_json::{json, Value};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+    rpc_request::TokenAccountsFilter,
+};
+use solana_sdk::{
+    account::Account, message::VersionedMessage, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, transaction::VersionedTransaction,
+};
+
+use crate::{
+    errors::{report, Error},
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color, success_color, warning_color},
+    permissions::{NetworkPermissions, Service},
+    token_accounts::{associated_token_address, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID},
+    token_list::{display_mint_status, MintStatus},
+    transaction::broadcast_versioned_transaction,
+    wsol::NATIVE_MINT,
+    Message,
+};
+
+const JUPITER_QUOTE_URL: &str = "https://quote-api.jup.ag/v6/quote";
+const JUPITER_SWAP_URL: &str = "https://quote-api.jup.ag/v6/swap";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+pub const DEFAULT_SLIPPAGE_BPS: u16 = 50;
+pub const SLIPPAGE_PRESETS: [u16; 3] = [10, 50, 100];
+// past this a typo is more likely than intent
+const MAX_SLIPPAGE_BPS: u16 = 5_000;
+// the quote is shown in the warning color from here
+const HIGH_SLIPPAGE_BPS: u16 = 300;
+const HIGH_IMPACT_PCT: f64 = 1.0;
+// and from here the swap needs a second click
+const CONFIRM_IMPACT_PCT: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapToken {
+    pub symbol: &'static str,
+    pub mint: &'static str,
+    pub decimals: u32,
+}
+
+impl SwapToken {
+    // the listed mints are all valid addresses
+    pub fn mint_address(&self) -> Pubkey {
+        Pubkey::from_str(self.mint).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for SwapToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol)
+    }
+}
+
+// jupiter routes native SOL through the wrapped mint and wraps/unwraps it
+// inside the swap transaction
+pub const SWAP_TOKENS: [SwapToken; 5] = [
+    SwapToken {
+        symbol: "SOL",
+        mint: "So11111111111111111111111111111111111111112",
+        decimals: 9,
+    },
+    SwapToken {
+        symbol: "USDC",
+        mint: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        decimals: 6,
+    },
+    SwapToken {
+        symbol: "USDT",
+        mint: "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY2CVrdXomJ2QTG",
+        decimals: 6,
+    },
+    SwapToken {
+        symbol: "JUP",
+        mint: "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+        decimals: 6,
+    },
+    SwapToken {
+        symbol: "BONK",
+        mint: "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263",
+        decimals: 5,
+    },
+];
+
+#[derive(Debug, Clone)]
+pub struct SwapQuote {
+    pub input: SwapToken,
+    pub output: SwapToken,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    // the least the swap pays out once slippage is applied
+    pub min_out_amount: u64,
+    pub slippage_bps: u16,
+    pub price_impact_pct: f64,
+    // the amms the route goes through, in order
+    pub route: Vec<String>,
+    // sent back as is to get the transaction
+    response: Value,
+}
+
+// what a landed swap took from the wallet, as it was sent rather than as the
+// form reads by the time the confirmation comes back
+#[derive(Debug, Clone)]
+pub struct SwapReceipt {
+    pub signature: String,
+    pub input: SwapToken,
+    pub in_amount: u64,
+}
+
+impl SwapReceipt {
+    // SOL the swap took as its input, what counts against the spend limit
+    pub fn sol_spent(&self) -> u64 {
+        if self.input == SWAP_TOKENS[0] {
+            self.in_amount
+        } else {
+            0
+        }
+    }
+}
+
+// what the swap tab shows, a quote is dropped whenever what it was asked for
+// changes
+#[derive(Debug, Clone)]
+pub struct SwapForm {
+    pub input: SwapToken,
+    pub output: SwapToken,
+    pub amount: String,
+    pub quote: Option<SwapQuote>,
+    // as typed, applied once it parses
+    pub slippage: String,
+    // a high impact quote waits for a second click
+    pub confirming_impact: bool,
+    // and so does a token missing from jupiter's verified list
+    pub confirming_unverified: bool,
+    pub quoting: bool,
+    pub sending: bool,
+    pub signature: String,
+}
+
+impl SwapForm {
+    pub fn new(slippage_bps: u16) -> Self {
+        Self {
+            slippage: slippage_bps.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn clear_quote(&mut self) {
+        self.quote = None;
+        self.confirming_impact = false;
+        self.confirming_unverified = false;
+    }
+
+    pub fn get_quote(&mut self, permissions: NetworkPermissions) -> Command<Message> {
+        let slippage_bps = match parse_slippage(&self.slippage) {
+            Ok(bps) => bps,
+            Err(error) => return report(error),
+        };
+        self.quoting = true;
+        self.signature = String::new();
+        Command::perform(
+            fetch_swap_quote(
+                self.input,
+                self.output,
+                self.amount.clone(),
+                slippage_bps,
+                permissions,
+            ),
+            Message::SwapQuoteLoaded,
+        )
+    }
+
+    // the app's cluster, allow list and spend limit checks come first, then
+    // an unverified token or a high impact quote each wait for another click
+    pub fn execute(
+        &mut self,
+        quote: SwapQuote,
+        unverified: bool,
+        signer: Arc<Keypair>,
+        rpc_client: Arc<RpcClient>,
+        permissions: NetworkPermissions,
+    ) -> Command<Message> {
+        if unverified && !self.confirming_unverified {
+            self.confirming_unverified = true;
+            return Command::none();
+        }
+        if quote.needs_confirmation() && !self.confirming_impact {
+            self.confirming_impact = true;
+            return Command::none();
+        }
+        self.confirming_impact = false;
+        self.confirming_unverified = false;
+        self.sending = true;
+        Command::perform(
+            execute_swap(quote, signer, rpc_client, permissions),
+            Message::SwapExecuted,
+        )
+    }
+
+    // a quote only holds for what it was asked for
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::SwapInputChanged(token) => self.input = token,
+            Message::SwapOutputChanged(token) => self.output = token,
+            Message::FlipSwap => (self.input, self.output) = (self.output, self.input),
+            Message::SwapAmountChanged(amount) => self.amount = amount,
+            Message::SwapSlippageChanged(slippage) => self.slippage = slippage,
+            Message::CancelSwap => {}
+            Message::SwapQuoteLoaded(result) => {
+                self.quoting = false;
+                return match result {
+                    Ok(quote) => {
+                        self.quote = Some(quote);
+                        Command::none()
+                    }
+                    Err(error) => report(error),
+                };
+            }
+            Message::SwapExecuted(result) => {
+                self.sending = false;
+                return match result {
+                    Ok(receipt) => {
+                        self.quote = None;
+                        self.signature = receipt.signature;
+                        self.amount = String::new();
+                        Command::none()
+                    }
+                    Err(error) => report(error),
+                };
+            }
+            _ => return Command::none(),
+        }
+        self.clear_quote();
+        Command::none()
+    }
+}
+
+impl Default for SwapForm {
+    fn default() -> Self {
+        Self {
+            input: SWAP_TOKENS[0],
+            output: SWAP_TOKENS[1],
+            amount: String::new(),
+            quote: None,
+            slippage: DEFAULT_SLIPPAGE_BPS.to_string(),
+            confirming_impact: false,
+            confirming_unverified: false,
+            quoting: false,
+            sending: false,
+            signature: String::new(),
+        }
+    }
+}
+
+impl SwapQuote {
+    pub fn is_high_impact(&self) -> bool {
+        self.price_impact_pct >= HIGH_IMPACT_PCT || self.slippage_bps >= HIGH_SLIPPAGE_BPS
+    }
+
+    pub fn needs_confirmation(&self) -> bool {
+        self.price_impact_pct >= CONFIRM_IMPACT_PCT
+    }
+}
+
+// whole basis points, 0 lets any price through and is refused
+pub fn parse_slippage(slippage: &str) -> Result<u16, Error> {
+    match slippage.trim().parse::<u16>() {
+        Ok(bps) if bps > 0 && bps <= MAX_SLIPPAGE_BPS => Ok(bps),
+        _ => Err(Error::InvalidSlippage),
+    }
+}
+
+// "1.5" with 6 decimals is 1500000, more decimals than the token has is refused
+pub fn parse_token_amount(amount: &str, decimals: u32) -> Result<u64, Error> {
+    let (whole, fraction) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+    let digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || !digits(whole)
+        || !digits(fraction)
+        || fraction.len() > decimals as usize
+    {
+        return Err(Error::InvalidSwapAmount);
+    }
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| Error::InvalidSwapAmount)?
+    };
+    let fraction: u64 = format!("{:0<width$}", fraction, width = decimals as usize)
+        .parse()
+        .unwrap_or(0);
+    whole
+        .checked_mul(10u64.pow(decimals))
+        .and_then(|whole| whole.checked_add(fraction))
+        .filter(|amount| *amount > 0)
+        .ok_or(Error::InvalidSwapAmount)
+}
+
+pub fn format_token_amount(amount: u64, decimals: u32) -> String {
+    let unit = 10u64.pow(decimals);
+    let fraction = format!("{:0>width$}", amount % unit, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        (amount / unit).to_string()
+    } else {
+        format!("{}.{}", amount / unit, fraction)
+    }
+}
+
+fn http_client() -> Result<reqwest::Client, Error> {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|_| Error::SwapQuoteError)
+}
+
+fn amount_field(value: &Value) -> Option<u64> {
+    value.as_str()?.parse().ok()
+}
+
+pub async fn fetch_swap_quote(
+    input: SwapToken,
+    output: SwapToken,
+    amount: String,
+    slippage_bps: u16,
+    permissions: NetworkPermissions,
+) -> Result<SwapQuote, Error> {
+    permissions.check(Service::Jupiter)?;
+    if input == output {
+        return Err(Error::SwapQuoteError);
+    }
+    let amount = parse_token_amount(&amount, input.decimals)?;
+    let url = format!(
+        "{}?inputMint={}&outputMint={}&amount={}&slippageBps={}",
+        JUPITER_QUOTE_URL, input.mint, output.mint, amount, slippage_bps
+    );
+    let body = http_client()?
+        .get(url)
+        .send()
+        .await
+        .map_err(|_| Error::SwapQuoteError)?
+        .text()
+        .await
+        .map_err(|_| Error::SwapQuoteError)?;
+    let response: Value = serde_json::from_str(&body).map_err(|_| Error::SwapQuoteError)?;
+
+    let route = response["routePlan"]
+        .as_array()
+        .map(|steps| {
+            steps
+                .iter()
+                .filter_map(|step| step["swapInfo"]["label"].as_str())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(SwapQuote {
+        input,
+        output,
+        in_amount: amount_field(&response["inAmount"]).ok_or(Error::SwapQuoteError)?,
+        out_amount: amount_field(&response["outAmount"]).ok_or(Error::SwapQuoteError)?,
+        min_out_amount: amount_field(&response["otherAmountThreshold"])
+            .ok_or(Error::SwapQuoteError)?,
+        slippage_bps,
+        price_impact_pct: response["priceImpactPct"]
+            .as_str()
+            .and_then(|impact| impact.parse::<f64>().ok())
+            .unwrap_or(0.0)
+            * 100.0,
+        route,
+        response,
+    })
+}
+
+// lamports and, for token accounts, the mint and amount of a wallet account
+#[derive(Debug, Clone, Copy, Default)]
+struct Holding {
+    lamports: u64,
+    token: Option<(Pubkey, u64)>,
+}
+
+impl Holding {
+    fn from_account(account: Option<&Account>) -> Self {
+        let Some(account) = account else {
+            return Self::default();
+        };
+        let is_token_account =
+            account.owner == TOKEN_PROGRAM_ID || account.owner == TOKEN_2022_PROGRAM_ID;
+        let token = match account.data.get(..72) {
+            Some(data) if is_token_account => {
+                let mint = Pubkey::try_from(&data[..32]).ok();
+                let amount = data[64..72].try_into().ok().map(u64::from_le_bytes);
+                mint.zip(amount)
+            }
+            _ => None,
+        };
+        Self {
+            lamports: account.lamports,
+            token,
+        }
+    }
+}
+
+// the wallet, every token account it holds and the input and output accounts
+// the swap may open for it
+async fn wallet_accounts(
+    owner: &Pubkey,
+    quote: &SwapQuote,
+    rpc_client: &RpcClient,
+) -> Result<Vec<Pubkey>, Error> {
+    let mut addresses = vec![*owner];
+    for program in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+        let accounts = rpc_client
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program))
+            .await
+            .map_err(|_| Error::FetchAccountError)?;
+        addresses.extend(
+            accounts
+                .iter()
+                .filter_map(|keyed| Pubkey::from_str(&keyed.pubkey).ok()),
+        );
+    }
+    for token in [quote.input, quote.output] {
+        let address = associated_token_address(owner, &token.mint_address(), &TOKEN_PROGRAM_ID);
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+    Ok(addresses)
+}
+
+// the swap may only take the quoted input and the network fee from the
+// wallet; SOL is counted across the wallet and all its token accounts, so
+// wrapping, opening or closing one of them nets out while lamports going
+// anywhere else don't
+fn check_outflow(
+    quote: &SwapQuote,
+    before: &[Holding],
+    after: &[Holding],
+    fee: u64,
+) -> Result<(), Error> {
+    let lamports = |holdings: &[Holding]| -> u128 {
+        holdings
+            .iter()
+            .map(|holding| holding.lamports as u128)
+            .sum()
+    };
+    let sol_in = if quote.input.mint_address() == NATIVE_MINT {
+        quote.in_amount
+    } else {
+        0
+    };
+    let sol_out = lamports(before).saturating_sub(lamports(after));
+    if sol_out > sol_in as u128 + fee as u128 {
+        return Err(Error::SwapOutflowMismatch);
+    }
+
+    // wrapped SOL is already part of the lamports above
+    let tokens = |holdings: &[Holding], mint: &Pubkey| -> u128 {
+        holdings
+            .iter()
+            .filter_map(|holding| holding.token)
+            .filter(|(held, _)| held == mint)
+            .map(|(_, amount)| amount as u128)
+            .sum()
+    };
+    before
+        .iter()
+        .filter_map(|holding| holding.token)
+        .map(|(mint, _)| mint)
+        .filter(|mint| *mint != NATIVE_MINT)
+        .try_for_each(|mint| {
+            let allowed = if mint == quote.input.mint_address() {
+                quote.in_amount
+            } else {
+                0
+            };
+            let spent = tokens(before, &mint).saturating_sub(tokens(after, &mint));
+            if spent > allowed as u128 {
+                Err(Error::SwapOutflowMismatch)
+            } else {
+                Ok(())
+            }
+        })
+}
+
+async fn message_fee(message: &VersionedMessage, rpc_client: &RpcClient) -> Result<u64, Error> {
+    match message {
+        VersionedMessage::Legacy(message) => rpc_client.get_fee_for_message(message).await,
+        VersionedMessage::V0(message) => rpc_client.get_fee_for_message(message).await,
+    }
+    .map_err(|_| Error::FetchFeeError)
+}
+
+// jupiter builds a v0 transaction for the quote; it's only signed once it's
+// known to charge the loaded wallet's fees and, simulated against the wallet
+// and its token accounts, to take nothing beyond the quoted input and the fee
+pub async fn execute_swap(
+    quote: SwapQuote,
+    signer: Arc<Keypair>,
+    rpc_client: Arc<RpcClient>,
+    permissions: NetworkPermissions,
+) -> Result<SwapReceipt, Error> {
+    permissions.check(Service::Jupiter)?;
+    let request = json!({
+        "quoteResponse": quote.response,
+        "userPublicKey": signer.pubkey().to_string(),
+        "wrapAndUnwrapSol": true,
+        "dynamicComputeUnitLimit": true,
+    });
+    let body = http_client()?
+        .post(JUPITER_SWAP_URL)
+        .header("Content-Type", "application/json")
+        .body(request.to_string())
+        .send()
+        .await
+        .map_err(|_| Error::SwapError)?
+        .text()
+        .await
+        .map_err(|_| Error::SwapError)?;
+    let response: Value = serde_json::from_str(&body).map_err(|_| Error::SwapError)?;
+
+    let bytes = response["swapTransaction"]
+        .as_str()
+        .and_then(|encoded| STANDARD.decode(encoded).ok())
+        .ok_or(Error::SwapError)?;
+    let last_valid_block_height = response["lastValidBlockHeight"]
+        .as_u64()
+        .ok_or(Error::SwapError)?;
+    let unsigned: VersionedTransaction =
+        bincode::deserialize(&bytes).map_err(|_| Error::SwapError)?;
+    if unsigned.message.static_account_keys().first() != Some(&signer.pubkey()) {
+        return Err(Error::SwapPayerMismatch);
+    }
+
+    let addresses = wallet_accounts(&signer.pubkey(), &quote, &rpc_client).await?;
+    let before: Vec<Holding> = rpc_client
+        .get_multiple_accounts_with_commitment(&addresses, rpc_client.commitment())
+        .await
+        .map_err(|_| Error::FetchAccountError)?
+        .value
+        .iter()
+        .map(|account| Holding::from_account(account.as_ref()))
+        .collect();
+    let fee = message_fee(&unsigned.message, &rpc_client).await?;
+
+    let simulate_cfg = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        commitment: Some(rpc_client.commitment()),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: addresses.iter().map(Pubkey::to_string).collect(),
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let simulation = rpc_client
+        .simulate_transaction_with_config(&unsigned, simulate_cfg)
+        .await
+        .map_err(|_| Error::SimulationError(String::from(tr("RPC request failed"))))?
+        .value;
+    if let Some(err) = simulation.err {
+        return Err(Error::SimulationError(err.to_string()));
+    }
+
+    // accounts come back in the same order as the requested addresses
+    let after: Vec<Holding> = simulation
+        .accounts
+        .ok_or(Error::SwapOutflowMismatch)?
+        .iter()
+        .map(|account| {
+            let account = account
+                .as_ref()
+                .and_then(|account| account.decode::<Account>());
+            Holding::from_account(account.as_ref())
+        })
+        .collect();
+    if after.len() != before.len() {
+        return Err(Error::SwapOutflowMismatch);
+    }
+    check_outflow(&quote, &before, &after, fee)?;
+
+    let tx = VersionedTransaction::try_new(unsigned.message, &[&*signer])
+        .map_err(|_| Error::SwapError)?;
+
+    let signature =
+        broadcast_versioned_transaction(&rpc_client, &tx, last_valid_block_height).await?;
+    Ok(SwapReceipt {
+        signature,
+        input: quote.input,
+        in_amount: quote.in_amount,
+    })
+}
+
+fn display_quote(quote: &SwapQuote) -> Element<'static, Message> {
+    let route = if quote.route.is_empty() {
+        String::from(tr("direct"))
+    } else {
+        quote.route.join(" -> ")
+    };
+    column![
+        text(format!(
+            "{} {} -> {} {}",
+            format_token_amount(quote.in_amount, quote.input.decimals),
+            quote.input.symbol,
+            format_token_amount(quote.out_amount, quote.output.decimals),
+            quote.output.symbol
+        ))
+        .size(16),
+        text(tr_args(
+            "Minimum received: {$amount} {$symbol}",
+            &[
+                (
+                    "amount",
+                    &format_token_amount(quote.min_out_amount, quote.output.decimals),
+                ),
+                ("symbol", &quote.output.symbol),
+            ],
+        ))
+        .size(12),
+        text(tr_args(
+            "Price impact: {$impact}%, slippage tolerance: {$slippage}%",
+            &[
+                ("impact", &format!("{:.3}", quote.price_impact_pct)),
+                ("slippage", &(f64::from(quote.slippage_bps) / 100.0)),
+            ],
+        ))
+        .size(12)
+        .style(if quote.is_high_impact() {
+            warning_color()
+        } else {
+            success_color()
+        }),
+        text(tr_args("Route: {$route}", &[("route", &route)])).size(12),
+    ]
+    .spacing(5)
+    .into()
+}
+
+// `unverified` holds the side(s) of the pair missing from jupiter's verified
+// list, the rest get a verified badge
+pub fn display_swap(
+    form: &SwapForm,
+    mainnet: bool,
+    unverified: &[(SwapToken, MintStatus)],
+) -> Element<'static, Message> {
+    let title = text(tr("Swap with Jupiter")).size(14).style(accent_color());
+    if !mainnet {
+        return column![
+            title,
+            text(tr(
+                "Jupiter only routes on mainnet-beta, switch the RPC or profile to swap"
+            ))
+            .size(12)
+            .style(error_color()),
+        ]
+        .spacing(10)
+        .into();
+    }
+
+    let pair = row![
+        pick_list(
+            &SWAP_TOKENS[..],
+            Some(form.input),
+            Message::SwapInputChanged
+        ),
+        button("⇄").on_press(Message::FlipSwap),
+        pick_list(
+            &SWAP_TOKENS[..],
+            Some(form.output),
+            Message::SwapOutputChanged
+        ),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let status = |token: SwapToken| {
+        unverified
+            .iter()
+            .find(|(listed, _)| *listed == token)
+            .map_or(MintStatus::Verified, |(_, status)| status.clone())
+    };
+    let badges = [form.input, form.output]
+        .into_iter()
+        .fold(row![].spacing(20), |badges, token| {
+            badges.push(
+                row![
+                    text(token.symbol).size(12),
+                    display_mint_status(&status(token))
+                ]
+                .spacing(5),
+            )
+        });
+
+    let amount = text_input(
+        &tr_args(
+            "Amount of {$symbol} to swap",
+            &[("symbol", &form.input.symbol)],
+        ),
+        &form.amount,
+    )
+    .on_input(Message::SwapAmountChanged)
+    .on_submit(Message::GetSwapQuote);
+
+    let slippage = SLIPPAGE_PRESETS.iter().fold(
+        row![
+            text(tr("Slippage (bps)")).size(14),
+            text_input("50", &form.slippage)
+                .on_input(Message::SwapSlippageChanged)
+                .width(Length::Fixed(80.0)),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        |row, bps| {
+            row.push(
+                button(text(format!("{}%", f64::from(*bps) / 100.0)).size(12))
+                    .on_press(Message::SwapSlippageChanged(bps.to_string())),
+            )
+        },
+    );
+
+    let quote_button = if form.quoting {
+        button(tr("Getting quote..."))
+    } else {
+        button(tr("Get quote")).on_press(Message::GetSwapQuote)
+    };
+
+    let mut view = column![title, pair, badges, amount, slippage, quote_button].spacing(10);
+    if let Some(quote) = &form.quote {
+        view = view.push(display_quote(quote));
+        view = view.push(if form.sending {
+            Element::from(button(tr("Swapping...")))
+        } else if form.confirming_unverified && !form.confirming_impact {
+            let warnings = unverified.iter().fold(
+                Column::new().spacing(2),
+                |warnings, (token, status)| {
+                    let warning = match status {
+                        MintStatus::Imitates(symbol) => tr_args(
+                            "{$symbol} ({$mint}) isn't on Jupiter's verified list and poses as {$imitated}",
+                            &[
+                                ("symbol", &token.symbol),
+                                ("mint", &token.mint),
+                                ("imitated", symbol),
+                            ],
+                        ),
+                        MintStatus::Unchecked => tr_args(
+                            "{$symbol} ({$mint}) couldn't be checked against Jupiter's verified list",
+                            &[("symbol", &token.symbol), ("mint", &token.mint)],
+                        ),
+                        _ => tr_args(
+                            "{$symbol} ({$mint}) isn't on Jupiter's verified list",
+                            &[("symbol", &token.symbol), ("mint", &token.mint)],
+                        ),
+                    };
+                    warnings.push(text(warning).size(12).style(error_color()))
+                },
+            );
+            column![
+                warnings,
+                row![
+                    button(tr("Swap anyway")).on_press(Message::ExecuteSwap),
+                    button(tr("Cancel")).on_press(Message::CancelSwap),
+                ]
+                .spacing(10),
+            ]
+            .spacing(5)
+            .into()
+        } else if form.confirming_impact {
+            column![
+                text(tr_args(
+                    "This route moves the price by {$impact}%, you may get much less than the market rate",
+                    &[("impact", &format!("{:.2}", quote.price_impact_pct))],
+                ))
+                .size(12)
+                .style(error_color()),
+                row![
+                    button(tr("Swap anyway")).on_press(Message::ExecuteSwap),
+                    button(tr("Cancel")).on_press(Message::CancelSwap),
+                ]
+                .spacing(10),
+            ]
+            .spacing(5)
+            .into()
+        } else {
+            button(tr("Swap")).on_press(Message::ExecuteSwap).into()
+        });
+    }
+    if !form.signature.is_empty() {
+        view = view.push(
+            text(tr_args(
+                "Swap confirmed: {$signature}",
+                &[("signature", &form.signature)],
+            ))
+            .size(12)
+            .style(success_color()),
+        );
+    }
+    view.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEE: u64 = 5_000;
+
+    fn quote(input: SwapToken, output: SwapToken, in_amount: u64) -> SwapQuote {
+        SwapQuote {
+            input,
+            output,
+            in_amount,
+            out_amount: 0,
+            min_out_amount: 0,
+            slippage_bps: DEFAULT_SLIPPAGE_BPS,
+            price_impact_pct: 0.0,
+            route: Vec::new(),
+            response: Value::Null,
+        }
+    }
+
+    fn sol(lamports: u64) -> Holding {
+        Holding {
+            lamports,
+            token: None,
+        }
+    }
+
+    fn token(token: SwapToken, amount: u64) -> Holding {
+        Holding {
+            lamports: 2_039_280,
+            token: Some((token.mint_address(), amount)),
+        }
+    }
+
+    #[test]
+    fn a_swap_taking_the_quoted_sol_passes() {
+        let quote = quote(SWAP_TOKENS[0], SWAP_TOKENS[1], 1_000_000);
+        // the output account is opened with rent from the wallet
+        let before = [sol(10_000_000), Holding::default()];
+        let after = [
+            sol(10_000_000 - 1_000_000 - FEE - 2_039_280),
+            token(SWAP_TOKENS[1], 150_000),
+        ];
+        assert!(check_outflow(&quote, &before, &after, FEE).is_ok());
+    }
+
+    #[test]
+    fn sol_leaving_beyond_the_quote_is_refused() {
+        let quote = quote(SWAP_TOKENS[0], SWAP_TOKENS[1], 1_000_000);
+        let before = [sol(10_000_000)];
+        let after = [sol(10_000_000 - 1_000_000 - FEE - 1)];
+        assert!(matches!(
+            check_outflow(&quote, &before, &after, FEE),
+            Err(Error::SwapOutflowMismatch)
+        ));
+    }
+
+    #[test]
+    fn tokens_beyond_the_quote_are_refused() {
+        let quote = quote(SWAP_TOKENS[1], SWAP_TOKENS[0], 100);
+        let before = [sol(10_000_000), token(SWAP_TOKENS[1], 1_000)];
+        let honest = [sol(10_000_000 - FEE + 50), token(SWAP_TOKENS[1], 900)];
+        assert!(check_outflow(&quote, &before, &honest, FEE).is_ok());
+        let drained = [sol(10_000_000 - FEE + 50), token(SWAP_TOKENS[1], 899)];
+        assert!(check_outflow(&quote, &before, &drained, FEE).is_err());
+    }
+
+    #[test]
+    fn other_tokens_held_must_not_move() {
+        let quote = quote(SWAP_TOKENS[0], SWAP_TOKENS[1], 1_000_000);
+        let before = [sol(10_000_000), token(SWAP_TOKENS[4], 500)];
+        let after = [sol(10_000_000 - 1_000_000 - FEE), token(SWAP_TOKENS[4], 0)];
+        assert!(check_outflow(&quote, &before, &after, FEE).is_err());
+    }
+
+    // closing a token account to someone else's address takes its rent along
+    #[test]
+    fn closing_a_wallet_account_elsewhere_is_refused() {
+        let quote = quote(SWAP_TOKENS[0], SWAP_TOKENS[1], 1_000_000);
+        let before = [sol(10_000_000), token(SWAP_TOKENS[4], 0)];
+        let after = [sol(10_000_000 - 1_000_000 - FEE), Holding::default()];
+        assert!(check_outflow(&quote, &before, &after, FEE).is_err());
+    }
+
+    #[test]
+    fn editing_the_form_drops_the_quote() {
+        let mut form = SwapForm::default();
+        form.quote = Some(quote(SWAP_TOKENS[0], SWAP_TOKENS[1], 1_000_000));
+        form.confirming_impact = true;
+
+        let _ = form.update(Message::SwapAmountChanged(String::from("2")));
+
+        assert!(form.quote.is_none());
+        assert!(!form.confirming_impact);
+        assert_eq!(form.amount, "2");
+    }
+}
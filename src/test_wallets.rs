@@ -0,0 +1,229 @@
+use std::{fs, path::PathBuf};
+
+use iced::{
+    widget::{button, checkbox, column, row, text, text_input, Column},
+    Alignment, Command, Element,
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{read_keypair_file, write_keypair_file, Keypair},
+    signer::Signer,
+};
+
+use crate::{
+    errors::{report, Error},
+    files::app_config_dir,
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+const TEST_WALLETS_DIR: &str = "test-wallets";
+const MAX_TEST_WALLETS: usize = 100;
+
+// a labeled folder of throwaway keypairs
+#[derive(Debug, Clone)]
+pub struct TestSet {
+    pub label: String,
+    pub wallets: Vec<(PathBuf, Pubkey)>,
+}
+
+// the test wallets screen: the generated sets and the form for a new one
+#[derive(Debug)]
+pub struct TestWallets {
+    pub sets: Vec<TestSet>,
+    pub label: String,
+    pub count: String,
+    // top up the new set from the faucet once it's generated
+    pub airdrop: bool,
+}
+
+impl Default for TestWallets {
+    fn default() -> Self {
+        Self {
+            sets: load_test_sets(),
+            label: String::new(),
+            count: String::from("5"),
+            airdrop: true,
+        }
+    }
+}
+
+impl TestWallets {
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::TestSetLabelChanged(label) => self.label = label,
+            Message::TestWalletCountChanged(count) => self.count = count,
+            Message::TestWalletAirdropToggled(airdrop) => self.airdrop = airdrop,
+            Message::GenerateTestSet => {
+                return Command::perform(
+                    generate_test_set(self.label.clone(), self.count.clone()),
+                    Message::TestSetGenerated,
+                )
+            }
+            Message::TestSetGenerated(Ok(set)) => {
+                self.label = String::new();
+                self.sets.push(set);
+            }
+            Message::DeleteTestSet(label) => {
+                return Command::perform(delete_test_set(label), Message::TestSetDeleted)
+            }
+            Message::TestSetDeleted(Ok(label)) => self.sets.retain(|set| set.label != label),
+            Message::TestSetGenerated(Err(error)) | Message::TestSetDeleted(Err(error)) => {
+                return report(error)
+            }
+            _ => {}
+        }
+        Command::none()
+    }
+}
+
+fn test_wallets_dir() -> PathBuf {
+    app_config_dir().join(TEST_WALLETS_DIR)
+}
+
+// the label becomes a folder name, keep it to something every os accepts
+fn valid_label(label: &str) -> bool {
+    !label.is_empty()
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn load_test_set(label: String, dir: PathBuf) -> Option<TestSet> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .collect();
+    paths.sort();
+
+    let wallets = paths
+        .into_iter()
+        .filter_map(|path| {
+            let pubkey = read_keypair_file(&path).ok()?.pubkey();
+            Some((path, pubkey))
+        })
+        .collect();
+
+    Some(TestSet { label, wallets })
+}
+
+pub fn load_test_sets() -> Vec<TestSet> {
+    let Ok(entries) = fs::read_dir(test_wallets_dir()) else {
+        return Vec::new();
+    };
+
+    let mut sets: Vec<TestSet> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let label = entry.file_name().to_string_lossy().to_string();
+            load_test_set(label, entry.path())
+        })
+        .collect();
+    sets.sort_by(|a, b| a.label.cmp(&b.label));
+    sets
+}
+
+pub async fn generate_test_set(label: String, count: String) -> Result<TestSet, Error> {
+    let label = label.trim().to_string();
+    if !valid_label(&label) {
+        return Err(Error::InvalidTestSetLabel);
+    }
+    let count = count
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|count| (1..=MAX_TEST_WALLETS).contains(count))
+        .ok_or(Error::InvalidTestWalletCount)?;
+
+    let dir = test_wallets_dir().join(&label);
+    if dir.exists() {
+        return Err(Error::InvalidTestSetLabel);
+    }
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+
+    let mut wallets = Vec::new();
+    for index in 0..count {
+        let keypair = Keypair::new();
+        let path = dir.join(format!("wallet-{:03}.json", index + 1));
+        write_keypair_file(&keypair, &path).map_err(|_| Error::SaveFileError)?;
+        wallets.push((path, keypair.pubkey()));
+    }
+
+    Ok(TestSet { label, wallets })
+}
+
+pub async fn delete_test_set(label: String) -> Result<String, Error> {
+    if !valid_label(&label) {
+        return Err(Error::InvalidTestSetLabel);
+    }
+    tokio::fs::remove_dir_all(test_wallets_dir().join(&label))
+        .await
+        .map_err(|_| Error::SaveFileError)?;
+    Ok(label)
+}
+
+pub fn display_test_wallets(
+    sets: &[TestSet],
+    label: &str,
+    count: &str,
+    airdrop: bool,
+    active: Option<&PathBuf>,
+) -> Element<'static, Message> {
+    let form = row![
+        text_input(tr("Set label"), label).on_input(Message::TestSetLabelChanged),
+        text_input(tr("Wallets"), count).on_input(Message::TestWalletCountChanged),
+        checkbox(
+            tr("Airdrop to each"),
+            airdrop,
+            Message::TestWalletAirdropToggled
+        )
+        .size(16)
+        .text_size(14),
+        button(tr("Generate")).on_press(Message::GenerateTestSet),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    let list = sets.iter().fold(Column::new().spacing(15), |column, set| {
+        let wallets =
+            set.wallets
+                .iter()
+                .fold(Column::new().spacing(2), |column, (path, pubkey)| {
+                    let switch = if Some(path) == active {
+                        button(tr("Active"))
+                    } else {
+                        button(tr("Use")).on_press(Message::FileOpened(Ok(path.clone())))
+                    };
+                    column.push(
+                        row![switch, text(pubkey.to_string()).size(12)]
+                            .spacing(10)
+                            .align_items(Alignment::Center),
+                    )
+                });
+
+        column.push(
+            column![
+                row![
+                    text(tr_args(
+                        "Test set \"{$label}\" ({$count} wallets)",
+                        &[("label", &set.label), ("count", &set.wallets.len())],
+                    ))
+                    .size(14)
+                    .style(accent_color()),
+                    button(tr("Delete set")).on_press(Message::DeleteTestSet(set.label.clone())),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+                wallets
+            ]
+            .spacing(5),
+        )
+    });
+
+    column![form, list].spacing(10).into()
+}
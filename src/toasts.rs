@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use iced::{
+    widget::{button, container, row, text, Column},
+    Alignment, Color, Element,
+};
+
+use crate::{
+    palette::{accent_color, error_color, success_color},
+    Message,
+};
+
+const SUCCESS_LIFETIME: Duration = Duration::from_secs(6);
+const INFO_LIFETIME: Duration = Duration::from_secs(5);
+// errors stay up longer, they usually need reading
+const ERROR_LIFETIME: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Success,
+    Info,
+    Error,
+}
+
+impl ToastLevel {
+    pub fn lifetime(&self) -> Duration {
+        match self {
+            ToastLevel::Success => SUCCESS_LIFETIME,
+            ToastLevel::Info => INFO_LIFETIME,
+            ToastLevel::Error => ERROR_LIFETIME,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            ToastLevel::Success => success_color(),
+            ToastLevel::Info => accent_color(),
+            ToastLevel::Error => error_color(),
+        }
+    }
+}
+
+// a button on the toast, e.g. copying the signature it reports
+#[derive(Debug, Clone)]
+pub struct ToastAction {
+    pub label: String,
+    pub message: Message,
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: u64,
+    pub level: ToastLevel,
+    pub text: String,
+    pub action: Option<ToastAction>,
+}
+
+// newest last, each toast removes itself when its lifetime runs out
+#[derive(Debug, Clone, Default)]
+pub struct Toasts {
+    next_id: u64,
+    pub items: Vec<Toast>,
+}
+
+impl Toasts {
+    // a repeated message replaces the older copy instead of stacking up
+    pub fn push(&mut self, level: ToastLevel, text: String, action: Option<ToastAction>) -> u64 {
+        self.items
+            .retain(|toast| toast.level != level || toast.text != text);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.items.push(Toast {
+            id,
+            level,
+            text,
+            action,
+        });
+        id
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.items.retain(|toast| toast.id != id);
+    }
+}
+
+pub fn display_toasts(toasts: &Toasts) -> Element<'static, Message> {
+    toasts
+        .items
+        .iter()
+        .fold(Column::new().spacing(5), |column, toast| {
+            let mut content = row![text(&toast.text).size(14).style(toast.level.color())]
+                .spacing(10)
+                .align_items(Alignment::Center);
+            if let Some(action) = &toast.action {
+                content = content
+                    .push(button(text(&action.label).size(12)).on_press(action.message.clone()));
+            }
+            content =
+                content.push(button(text("x").size(12)).on_press(Message::DismissToast(toast.id)));
+
+            column.push(container(content).padding(5))
+        })
+        .into()
+}
@@ -0,0 +1,275 @@
+use std::{str::FromStr, sync::Arc};
+
+use iced::{
+    widget::{button, checkbox, column, row, text, text_input},
+    Command, Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use crate::{
+    errors::{report, Error},
+    i18n::{tr, tr_args},
+    palette::{accent_color, error_color},
+    transaction::send_instructions,
+    Message,
+};
+
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+// associated token program instruction that doesn't fail if the account exists
+const CREATE_IDEMPOTENT: u8 = 1;
+
+#[derive(Debug, Clone)]
+pub struct AtaPreview {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub address: Pubkey,
+    pub exists: bool,
+    pub owner_off_curve: bool,
+}
+
+// the create associated token account form on the token accounts screen
+#[derive(Debug, Default)]
+pub struct AtaForm {
+    pub owner: String,
+    pub mint: String,
+    pub allow_off_curve: bool,
+    pub preview: Option<AtaPreview>,
+    pub signature: String,
+}
+
+impl AtaForm {
+    pub fn start_preview(&self, rpc_client: Arc<RpcClient>) -> Command<Message> {
+        Command::perform(
+            preview_associated_token_account(
+                self.owner.clone(),
+                self.mint.clone(),
+                self.allow_off_curve,
+                rpc_client,
+            ),
+            Message::AtaPreviewed,
+        )
+    }
+
+    pub fn create(
+        &self,
+        signer: Arc<Keypair>,
+        preview: AtaPreview,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        Command::perform(
+            create_associated_token_account_from_preview(signer, preview, rpc_client),
+            Message::AtaCreated,
+        )
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            // a changed input makes the shown preview stale
+            Message::AtaOwnerChanged(owner) => {
+                self.owner = owner;
+                self.preview = None;
+            }
+            Message::AtaMintChanged(mint) => {
+                self.mint = mint;
+                self.preview = None;
+            }
+            Message::AtaOffCurveToggled(allow_off_curve) => {
+                self.allow_off_curve = allow_off_curve;
+                self.preview = None;
+            }
+            Message::AtaPreviewCancelled => self.preview = None,
+            Message::AtaPreviewed(Ok(preview)) => {
+                self.signature = String::new();
+                self.preview = Some(preview);
+            }
+            Message::AtaCreated(Ok(signature)) => self.signature = signature,
+            Message::AtaPreviewed(Err(error)) | Message::AtaCreated(Err(error)) => {
+                return report(error)
+            }
+            _ => {}
+        }
+        Command::none()
+    }
+}
+
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn create_associated_token_account(payer: &Pubkey, preview: &AtaPreview) -> Instruction {
+    Instruction::new_with_bytes(
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        &[CREATE_IDEMPOTENT],
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(preview.address, false),
+            AccountMeta::new_readonly(preview.owner, false),
+            AccountMeta::new_readonly(preview.mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(preview.token_program, false),
+        ],
+    )
+}
+
+// tokens sent to an ata whose owner can't sign are lost: the owner has to be
+// a wallet (on curve, owned by the system program) unless a pda owner was
+// explicitly acknowledged
+pub async fn preview_associated_token_account(
+    owner: String,
+    mint: String,
+    allow_off_curve: bool,
+    rpc_client: Arc<RpcClient>,
+) -> Result<AtaPreview, Error> {
+    let owner = Pubkey::from_str(owner.trim()).map_err(|_| Error::InvalidPubKeyLen)?;
+    let mint = Pubkey::from_str(mint.trim()).map_err(|_| Error::InvalidMint)?;
+
+    let owner_off_curve = !owner.is_on_curve();
+    if owner_off_curve && !allow_off_curve {
+        return Err(Error::OwnerOffCurve);
+    }
+
+    let owner_account = rpc_client
+        .get_account_with_commitment(&owner, rpc_client.commitment())
+        .await
+        .map_err(|_| Error::FetchAccountError)?
+        .value;
+    if let Some(owner_account) = owner_account {
+        if !owner_off_curve && owner_account.owner != system_program::id() {
+            return Err(Error::OwnerNotSystemAccount);
+        }
+    }
+
+    // the mint's owner tells which token program the ata belongs to
+    let token_program = rpc_client
+        .get_account_with_commitment(&mint, rpc_client.commitment())
+        .await
+        .map_err(|_| Error::FetchAccountError)?
+        .value
+        .map(|account| account.owner)
+        .filter(|program| *program == TOKEN_PROGRAM_ID || *program == TOKEN_2022_PROGRAM_ID)
+        .ok_or(Error::InvalidMint)?;
+
+    let address = associated_token_address(&owner, &mint, &token_program);
+    let exists = rpc_client
+        .get_account_with_commitment(&address, rpc_client.commitment())
+        .await
+        .map_err(|_| Error::FetchAccountError)?
+        .value
+        .is_some();
+
+    Ok(AtaPreview {
+        owner,
+        mint,
+        token_program,
+        address,
+        exists,
+        owner_off_curve,
+    })
+}
+
+pub async fn create_associated_token_account_from_preview(
+    signer: Arc<Keypair>,
+    preview: AtaPreview,
+    rpc_client: Arc<RpcClient>,
+) -> Result<String, Error> {
+    let instruction = create_associated_token_account(&signer.pubkey(), &preview);
+    send_instructions(&rpc_client, &[&*signer], &[instruction]).await
+}
+
+pub fn display_token_accounts(
+    owner: &str,
+    mint: &str,
+    allow_off_curve: bool,
+    preview: &Option<AtaPreview>,
+    signature: &str,
+) -> Element<'static, Message> {
+    let owner_input =
+        text_input(tr("Owner wallet address"), owner).on_input(Message::AtaOwnerChanged);
+    let mint_input = text_input(tr("Token mint address"), mint).on_input(Message::AtaMintChanged);
+
+    let off_curve = checkbox(
+        tr("The owner is a program derived address (PDA), I know it can't sign"),
+        allow_off_curve,
+        Message::AtaOffCurveToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    let preview_details: Element<'static, Message> = match preview {
+        Some(preview) => column![
+            text(tr("Associated token account preview"))
+                .size(14)
+                .style(accent_color()),
+            text(tr_args(
+                "Address: {$address}",
+                &[("address", &preview.address)]
+            ))
+            .size(14),
+            text(tr_args(
+                "Token program: {$program}",
+                &[(
+                    "program",
+                    &if preview.token_program == TOKEN_2022_PROGRAM_ID {
+                        "Token-2022"
+                    } else {
+                        "Token"
+                    },
+                )],
+            ))
+            .size(14),
+            if preview.owner_off_curve {
+                text(tr("Owner is off curve (PDA)"))
+                    .size(14)
+                    .style(error_color())
+            } else {
+                text(tr("Owner is a regular wallet")).size(14)
+            },
+            if preview.exists {
+                text(tr("This account already exists")).size(14)
+            } else {
+                text("").size(1)
+            },
+            row![
+                if preview.exists {
+                    button(tr("Create account"))
+                } else {
+                    button(tr("Create account")).on_press(Message::CreateAta)
+                },
+                button(tr("Cancel")).on_press(Message::AtaPreviewCancelled),
+            ]
+            .spacing(10)
+        ]
+        .spacing(5)
+        .into(),
+        None => button(tr("Preview address"))
+            .on_press(Message::PreviewAta)
+            .into(),
+    };
+
+    column![
+        owner_input,
+        mint_input,
+        off_curve,
+        preview_details,
+        text(signature.to_string()).size(14)
+    ]
+    .spacing(10)
+    .into()
+}
@@ -0,0 +1,285 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
+
+use iced::{widget::text, Element};
+use serde_json::Value;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    errors::Error,
+    i18n::{tr, tr_args},
+    palette::{error_color, success_color, warning_color},
+    permissions::{NetworkPermissions, Service},
+    Message,
+};
+
+// jupiter's strict list, the mints it has verified
+const JUPITER_STRICT_LIST_URL: &str = "https://token.jup.ag/strict";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+// key, update authority and mint come before the name and symbol
+const METADATA_NAME_OFFSET: usize = 1 + 32 + 32;
+// get_multiple_accounts takes at most 100 addresses
+const ACCOUNTS_PER_REQUEST: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MintStatus {
+    Verified,
+    Unverified,
+    // not on the list but calls itself like a listed token, the usual scam
+    // airdrop
+    Imitates(String),
+    // the list isn't loaded, jupiter isn't allowed or didn't answer
+    Unchecked,
+}
+
+// verified mints by address with their symbol, plus the symbols unverified
+// mints give themselves on chain
+#[derive(Debug, Default)]
+pub struct TokenVerification {
+    verified: Option<HashMap<String, String>>,
+    symbols: HashMap<String, String>,
+}
+
+impl TokenVerification {
+    pub fn has_list(&self) -> bool {
+        self.verified.is_some()
+    }
+
+    pub fn store_list(&mut self, verified: HashMap<String, String>) {
+        self.verified = Some(verified);
+    }
+
+    pub fn store_symbols(&mut self, symbols: Vec<(String, String)>) {
+        self.symbols.extend(symbols);
+    }
+
+    // native SOL isn't a mint and needs no list
+    pub fn status(&self, mint: &str) -> MintStatus {
+        let Some(verified) = &self.verified else {
+            return MintStatus::Unchecked;
+        };
+        if mint == "SOL" || verified.contains_key(mint) {
+            return MintStatus::Verified;
+        }
+        match self.symbols.get(mint) {
+            Some(symbol) => match imitated_symbol(symbol, verified) {
+                Some(imitated) => MintStatus::Imitates(imitated),
+                None => MintStatus::Unverified,
+            },
+            None => MintStatus::Unverified,
+        }
+    }
+
+    // unverified mints whose on-chain symbol hasn't been looked up yet
+    pub fn missing_symbols(&self, mints: &[String]) -> Vec<Pubkey> {
+        let Some(verified) = &self.verified else {
+            return Vec::new();
+        };
+        let mut missing: Vec<Pubkey> = mints
+            .iter()
+            .filter(|mint| !verified.contains_key(*mint) && !self.symbols.contains_key(*mint))
+            .filter_map(|mint| Pubkey::from_str(mint).ok())
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+}
+
+// "U$DC", "usdc " or "USDС" with a cyrillic С all read as USDC
+fn normalize_symbol(symbol: &str) -> String {
+    symbol
+        .chars()
+        .filter_map(|c| match c {
+            '$' => Some('S'),
+            '0' => Some('O'),
+            'А' | 'а' => Some('A'),
+            'В' | 'в' => Some('B'),
+            'С' | 'с' => Some('C'),
+            'Е' | 'е' => Some('E'),
+            'Н' | 'н' => Some('H'),
+            'К' | 'к' => Some('K'),
+            'М' | 'м' => Some('M'),
+            'О' | 'о' => Some('O'),
+            'Р' | 'р' => Some('P'),
+            'Т' | 'т' => Some('T'),
+            'Х' | 'х' => Some('X'),
+            'У' | 'у' => Some('Y'),
+            c if c.is_ascii_alphanumeric() => Some(c.to_ascii_uppercase()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn imitated_symbol(symbol: &str, verified: &HashMap<String, String>) -> Option<String> {
+    let normalized = normalize_symbol(symbol);
+    if normalized.is_empty() {
+        return None;
+    }
+    verified
+        .values()
+        .find(|listed| normalize_symbol(listed) == normalized)
+        .cloned()
+}
+
+pub async fn fetch_token_list(
+    permissions: NetworkPermissions,
+) -> Result<HashMap<String, String>, Error> {
+    permissions.check(Service::Jupiter)?;
+    let body = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|_| Error::FetchTokenListError)?
+        .get(JUPITER_STRICT_LIST_URL)
+        .send()
+        .await
+        .map_err(|_| Error::FetchTokenListError)?
+        .text()
+        .await
+        .map_err(|_| Error::FetchTokenListError)?;
+    let tokens: Value = serde_json::from_str(&body).map_err(|_| Error::FetchTokenListError)?;
+
+    let verified: HashMap<String, String> = tokens
+        .as_array()
+        .ok_or(Error::FetchTokenListError)?
+        .iter()
+        .filter_map(|token| {
+            Some((
+                token["address"].as_str()?.to_string(),
+                token["symbol"].as_str()?.to_string(),
+            ))
+        })
+        .collect();
+    if verified.is_empty() {
+        return Err(Error::FetchTokenListError);
+    }
+    Ok(verified)
+}
+
+fn metadata_address(mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(METADATA_PROGRAM_ID).unwrap_or_default();
+    Pubkey::find_program_address(
+        &[b"metadata", program_id.as_ref(), mint.as_ref()],
+        &program_id,
+    )
+    .0
+}
+
+// a borsh string, the u32 length then the bytes, padded with zeros on chain
+fn read_string(data: &[u8], offset: usize) -> Option<(String, usize)> {
+    let length_bytes: [u8; 4] = data.get(offset..offset + 4)?.try_into().ok()?;
+    let length = u32::from_le_bytes(length_bytes) as usize;
+    let bytes = data.get(offset + 4..offset + 4 + length)?;
+    let value = String::from_utf8_lossy(bytes)
+        .trim_end_matches('\0')
+        .trim()
+        .to_string();
+    Some((value, offset + 4 + length))
+}
+
+fn metadata_symbol(data: &[u8]) -> Option<String> {
+    let (_, symbol_offset) = read_string(data, METADATA_NAME_OFFSET)?;
+    read_string(data, symbol_offset).map(|(symbol, _)| symbol)
+}
+
+// the symbol each mint's metaplex metadata gives it, mints without metadata
+// get an empty one so they aren't looked up again. Token-2022 mints keeping
+// their metadata in an extension are left as plain unverified
+pub async fn fetch_token_symbols(
+    mints: Vec<Pubkey>,
+    rpc_client: Arc<RpcClient>,
+) -> Result<Vec<(String, String)>, Error> {
+    let mut symbols = Vec::new();
+    for chunk in mints.chunks(ACCOUNTS_PER_REQUEST) {
+        let addresses: Vec<Pubkey> = chunk.iter().map(metadata_address).collect();
+        let accounts = rpc_client
+            .get_multiple_accounts(&addresses)
+            .await
+            .map_err(|_| Error::FetchAccountError)?;
+        for (mint, account) in chunk.iter().zip(accounts) {
+            let symbol = account
+                .and_then(|account| metadata_symbol(&account.data))
+                .unwrap_or_default();
+            symbols.push((mint.to_string(), symbol));
+        }
+    }
+    Ok(symbols)
+}
+
+pub fn display_mint_status(status: &MintStatus) -> Element<'static, Message> {
+    match status {
+        MintStatus::Verified => text(tr("verified")).size(12).style(success_color()),
+        MintStatus::Unverified => text(tr("unverified")).size(12).style(warning_color()),
+        MintStatus::Imitates(symbol) => text(tr_args(
+            "unverified, posing as {$symbol}",
+            &[("symbol", symbol)],
+        ))
+        .size(12)
+        .style(error_color()),
+        MintStatus::Unchecked => text(tr("unchecked")).size(12),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const FAKE: &str = "Fake1111111111111111111111111111111111111111";
+
+    fn verification() -> TokenVerification {
+        let mut verification = TokenVerification::default();
+        verification.store_list(HashMap::from([(USDC.to_string(), "USDC".to_string())]));
+        verification
+    }
+
+    #[test]
+    fn nothing_is_verified_before_the_list_loads() {
+        assert_eq!(
+            TokenVerification::default().status(USDC),
+            MintStatus::Unchecked
+        );
+    }
+
+    #[test]
+    fn listed_mints_and_sol_are_verified() {
+        let verification = verification();
+        assert_eq!(verification.status(USDC), MintStatus::Verified);
+        assert_eq!(verification.status("SOL"), MintStatus::Verified);
+    }
+
+    #[test]
+    fn a_lookalike_symbol_imitates_the_listed_token() {
+        let mut verification = verification();
+        verification.store_symbols(vec![(FAKE.to_string(), "U$DС ".to_string())]);
+        assert_eq!(
+            verification.status(FAKE),
+            MintStatus::Imitates("USDC".to_string())
+        );
+    }
+
+    #[test]
+    fn an_unlisted_mint_with_its_own_symbol_is_only_unverified() {
+        let mut verification = verification();
+        verification.store_symbols(vec![(FAKE.to_string(), "MEOW".to_string())]);
+        assert_eq!(verification.status(FAKE), MintStatus::Unverified);
+        assert!(verification
+            .missing_symbols(&[FAKE.to_string(), USDC.to_string()])
+            .is_empty());
+    }
+
+    #[test]
+    fn reads_the_symbol_from_metaplex_metadata() {
+        let mut data = vec![4u8; METADATA_NAME_OFFSET];
+        for value in ["USD Coin", "USDC"] {
+            let mut padded = value.as_bytes().to_vec();
+            padded.resize(10, 0);
+            data.extend((padded.len() as u32).to_le_bytes());
+            data.extend(padded);
+        }
+        assert_eq!(metadata_symbol(&data), Some("USDC".to_string()));
+        assert_eq!(metadata_symbol(&data[..METADATA_NAME_OFFSET + 2]), None);
+    }
+}
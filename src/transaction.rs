@@ -1,121 +1,732 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc, time::Duration};
 
-use solana_client::rpc_config::RpcSendTransactionConfig;
+use futures::StreamExt;
+use solana_account_decoder::UiAccountEncoding;
+use solana_cli_config::Config;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_client::SerializableTransaction,
+    rpc_config::{
+        RpcSendTransactionConfig, RpcSignatureSubscribeConfig,
+        RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+    },
+    rpc_response::{Response, RpcSignatureResult},
+};
+use solana_pubsub_client::nonblocking::pubsub_client::PubsubClient;
 use solana_sdk::{
-    commitment_config::{CommitmentConfig, CommitmentLevel},
-    native_token::LAMPORTS_PER_SOL,
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey,
     pubkey::Pubkey,
+    signature::{Keypair, Signature},
     signer::Signer,
     system_instruction,
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
 };
 use solana_transaction_status::UiTransactionEncoding;
+use tokio::time;
 
-use crate::{Error, SolExecApp};
+use crate::{
+    allowlist::AllowList,
+    exchanges::check_deposit,
+    i18n::tr,
+    jito::{send_bundle, tip_instruction, JitoSettings},
+    nonce::{current_nonce, fetch_nonce_account},
+    rpc::with_retry,
+    units::AmountUnit,
+    wsol::{close_wsol_instruction, fetch_wsol_lamports, unwraps_wsol, NATIVE_MINT},
+    Error,
+};
 
-fn parse_amount(amount_str: &str) -> Result<u64, Error> {
-    let parts: Vec<&str> = amount_str.split('.').collect();
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TuNEL3vEoXQEtyVnpFbmQ4");
 
-    let lamports = match parts.len() {
-        // take only integer part
-        1 => {
-            let valid_integer = parts[0].parse::<u64>().unwrap_or(0);
-            valid_integer
-                .checked_mul(LAMPORTS_PER_SOL)
-                .ok_or(Error::InvalidAmount)?
-        }
-        // take integer + decimal part
-        2 => {
-            let integer = parts[0].parse::<u64>().unwrap_or(0);
-
-            // take decimal, example: 0.125, so decimal part is literal 125
-            let fraction = &format!("{:0<9}", parts[1])[..9];
-            let fraction_value = fraction.parse::<u64>().unwrap_or(0);
-
-            // convert both parts in lamports and add them
-            let whole_amount = integer
-                .checked_mul(LAMPORTS_PER_SOL)
-                .ok_or(Error::InvalidAmount)?;
-
-            whole_amount
-                .checked_add(fraction_value)
-                .ok_or(Error::InvalidAmount)?
-        }
-        // if the input has more than one dot, an error throws
-        _ => return Err(Error::InvalidAmount),
-    };
+// the send form as the transfer helpers see it, built per request so the
+// rest of the app state (and its secrets) never moves into a future
+#[derive(Clone)]
+pub struct TransferValues {
+    pub signer: Arc<Keypair>,
+    pub fee_payer: Option<Arc<Keypair>>,
+    pub rpc_client: Arc<RpcClient>,
+    pub balance: Option<u64>,
+    pub receiver_value: (String, String),
+    pub amount_unit: AmountUnit,
+    pub priority_fee: String,
+    pub memo: String,
+    pub allow_list: Option<AllowList>,
+    pub use_nonce: bool,
+    pub nonce_address: String,
+    pub keep_rent_exempt: bool,
+    pub unwrap_wsol: bool,
+    // sent to the block engine with this tip instead of through the rpc
+    pub jito: Option<JitoSettings>,
+}
+
+impl TransferValues {
+    pub fn fee_payer_pubkey(&self) -> Pubkey {
+        self.fee_payer
+            .as_ref()
+            .map(|fee_payer| fee_payer.pubkey())
+            .unwrap_or_else(|| self.signer.pubkey())
+    }
+}
+
+// the memo program requires every account passed to be a signer
+pub fn memo_instruction(memo: &str, signer: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        MEMO_PROGRAM_ID,
+        memo.as_bytes(),
+        vec![AccountMeta::new_readonly(*signer, true)],
+    )
+}
 
-    Ok(lamports)
+// when a sent transaction can no longer land: its blockhash is too old, or
+// the durable nonce it used has been advanced (or its account closed)
+#[derive(Debug, Clone, Copy)]
+pub enum Expiry {
+    BlockHeight(u64),
+    Nonce { account: Pubkey, blockhash: Hash },
 }
 
-pub async fn transfer_sol(values: SolExecApp) -> Result<String, Error> {
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_REBROADCASTS: usize = 2;
+
+// signs with every keypair (the first one pays the fees), sends and confirms
+// through the same state machine as transfers, so an expired blockhash is
+// replaced and the transaction signed again instead of waited on forever
+pub async fn send_instructions(
+    rpc_client: &RpcClient,
+    signers: &[&Keypair],
+    instructions: &[Instruction],
+) -> Result<String, Error> {
+    let payer = signers.first().ok_or(Error::TransactionError)?.pubkey();
+    let mut rebroadcasts = 0;
+    let mut state = SendState::Building;
+
+    loop {
+        state = match state {
+            SendState::Building => {
+                let (blockhash, last_valid_block_height) = with_retry(|| {
+                    rpc_client.get_latest_blockhash_with_commitment(rpc_client.commitment())
+                })
+                .await
+                .map_err(|_| Error::FetchBlockhashError)?;
+                let mut tx = Transaction::new_with_payer(instructions, Some(&payer));
+                tx.try_sign(signers, blockhash)
+                    .map_err(|_| Error::TransactionError)?;
+                SendState::Sending {
+                    tx,
+                    expiry: Expiry::BlockHeight(last_valid_block_height),
+                }
+            }
+            SendState::Sending { tx, expiry } => {
+                send_and_confirm(rpc_client, &tx, expiry, None).await?
+            }
+            SendState::Committed(signature) => return Ok(signature.to_string()),
+            SendState::Expired if rebroadcasts < MAX_REBROADCASTS => {
+                rebroadcasts += 1;
+                SendState::Building
+            }
+            SendState::Expired => return Err(Error::BlockhashExpired),
+        };
+    }
+}
+
+// a plain transfer plus the compute budget instructions stays well below this
+pub const TRANSFER_COMPUTE_UNITS: u32 = 1_000;
+// micro-lamports per compute unit, about 0.1 SOL at the transfer's limit;
+// anything above is a typo rather than a bid
+pub const MAX_PRIORITY_FEE: u64 = 100_000_000_000;
+
+fn parse_priority_fee(priority_fee_str: &str) -> Result<u64, Error> {
+    if priority_fee_str.trim().is_empty() {
+        return Ok(0);
+    }
+    priority_fee_str
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|fee| *fee <= MAX_PRIORITY_FEE)
+        .ok_or(Error::InvalidPriorityFee)
+}
+
+// priority fee in lamports: compute unit limit * micro-lamports per compute unit
+fn priority_fee_lamports(micro_lamports_per_cu: u64) -> u64 {
+    let micro_lamports = micro_lamports_per_cu.saturating_mul(TRANSFER_COMPUTE_UNITS as u64);
+    micro_lamports.div_ceil(1_000_000)
+}
+
+fn transfer_instructions(
+    from: &Pubkey,
+    to: &Pubkey,
+    lamports: u64,
+    micro_lamports_per_cu: u64,
+    memo: &str,
+) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    if micro_lamports_per_cu > 0 {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
+            TRANSFER_COMPUTE_UNITS,
+        ));
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+            micro_lamports_per_cu,
+        ));
+    }
+    instructions.push(system_instruction::transfer(from, to, lamports));
+    if !memo.trim().is_empty() {
+        instructions.push(memo_instruction(memo.trim(), from));
+    }
+    instructions
+}
+
+// base fee reported by the cluster for a plain transfer, the priority part is
+// computed locally so it's never counted twice
+async fn fetch_base_fee(
+    rpc_client: &RpcClient,
+    sender: &Pubkey,
+    payer: &Pubkey,
+    blockhash: &Hash,
+) -> Result<u64, Error> {
+    let transfer_ix = system_instruction::transfer(sender, sender, 0);
+    let message = Message::new_with_blockhash(&[transfer_ix], Some(payer), blockhash);
+
+    with_retry(|| rpc_client.get_fee_for_message(&message))
+        .await
+        .map_err(|_| Error::FetchFeeError)
+}
+
+pub async fn estimate_fee(values: TransferValues) -> Result<u64, Error> {
     let signer_pubkey = values.signer.pubkey();
+    let micro_lamports_per_cu = parse_priority_fee(&values.priority_fee)?;
+
+    let blockhash = with_retry(|| values.rpc_client.get_latest_blockhash())
+        .await
+        .map_err(|_| Error::FetchBlockhashError)?;
+
+    let payer = values.fee_payer_pubkey();
+    let base_fee = fetch_base_fee(&values.rpc_client, &signer_pubkey, &payer, &blockhash).await?;
+
+    Ok(base_fee
+        .saturating_add(priority_fee_lamports(micro_lamports_per_cu))
+        .saturating_add(jito_tip(&values)))
+}
+
+fn jito_tip(values: &TransferValues) -> u64 {
+    values
+        .jito
+        .as_ref()
+        .map_or(0, |settings| settings.tip_lamports)
+}
+
+// a transfer that creates the recipient account has to leave it rent exempt,
+// returns the rent-exempt minimum when the amount falls short of it
+pub async fn recipient_rent_shortfall(
+    to: Pubkey,
+    lamports: u64,
+    rpc_client: Arc<RpcClient>,
+) -> Result<Option<u64>, Error> {
+    let recipient_balance = rpc_client
+        .get_balance(&to)
+        .await
+        .map_err(|_| Error::FetchBalanceError)?;
+    if recipient_balance > 0 {
+        return Ok(None);
+    }
+
+    let minimum = rpc_client
+        .get_minimum_balance_for_rent_exemption(0)
+        .await
+        .map_err(|_| Error::FetchBalanceError)?;
+
+    Ok((lamports < minimum).then_some(minimum))
+}
+
+// largest amount the send form can transfer: the balance minus the fees this
+// wallet pays and, if asked to, the rent-exempt minimum it has to keep
+pub async fn max_sendable(values: TransferValues) -> Result<u64, Error> {
+    let balance = values
+        .rpc_client
+        .get_balance(&values.signer.pubkey())
+        .await
+        .map_err(|_| Error::FetchBalanceError)?;
+
+    let rent_reserve = if values.keep_rent_exempt {
+        values
+            .rpc_client
+            .get_minimum_balance_for_rent_exemption(0)
+            .await
+            .map_err(|_| Error::FetchBalanceError)?
+    } else {
+        0
+    };
+
+    // a separate fee payer covers the fees
+    let fees = if values.fee_payer.is_some() {
+        0
+    } else {
+        estimate_fee(values).await?
+    };
+
+    balance
+        .checked_sub(fees)
+        .and_then(|amount| amount.checked_sub(rent_reserve))
+        .filter(|amount| *amount > 0)
+        .ok_or(Error::InsufficientBalance)
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationPreview {
+    pub sender_delta: i128,
+    pub recipient_delta: i128,
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+    // lamports released by closing the wSOL account, if the transfer needs it
+    pub unwrapped_wsol: Option<u64>,
+}
+
+// what a confirmed transfer paid, as it was built rather than as the form
+// reads by the time the confirmation comes back
+#[derive(Debug, Clone)]
+pub struct SentTransfer {
+    pub signature: String,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+}
+
+// recipient and lamports from the form values
+fn transfer_target(values: &TransferValues) -> Result<(Pubkey, u64), Error> {
     let to_address_str = &values.receiver_value.0;
 
     if to_address_str.as_bytes().len() < 32 {
         return Err(Error::InvalidPubKeyLen);
     }
 
-    let to = Pubkey::from_str(&values.receiver_value.0).unwrap();
-    let lamports = values.receiver_value.1;
-    let amount_as_u64 = parse_amount(&lamports)?;
+    let to = Pubkey::from_str(to_address_str).map_err(|_| Error::InvalidPubKeyLen)?;
+    let amount_as_u64 = values.amount_unit.parse(&values.receiver_value.1)?;
 
-    if amount_as_u64 <= 0 {
+    if amount_as_u64 == 0 {
         return Err(Error::InvalidAmount);
-    } else if values.balance.unwrap_or(0) < amount_as_u64 {
-        return Err(Error::InsufficientBalance);
     }
+    Ok((to, amount_as_u64))
+}
 
-    let transfer_ix = system_instruction::transfer(&signer_pubkey, &to, amount_as_u64);
-    let mut tx = Transaction::new_with_payer(&[transfer_ix], Some(&signer_pubkey));
+// validates the form values and returns the unsigned transfer with the point
+// it expires at
+pub async fn build_unsigned_transfer(
+    values: &TransferValues,
+) -> Result<(Transaction, Expiry), Error> {
+    let signer_pubkey = values.signer.pubkey();
+    let (to, amount_as_u64) = transfer_target(values)?;
+    let micro_lamports_per_cu = parse_priority_fee(&values.priority_fee)?;
 
-    let send_cfg = RpcSendTransactionConfig {
-        skip_preflight: true,
-        preflight_commitment: Some(CommitmentLevel::Confirmed),
-        encoding: Some(UiTransactionEncoding::Base64),
-        max_retries: Some(3),
-        min_context_slot: None,
-    };
+    if let Some(allow_list) = &values.allow_list {
+        allow_list.check_recipient(&to)?;
+    }
+    check_deposit(&to, amount_as_u64, &values.memo)?;
 
-    let blockhash_result = values
-        .rpc_client
-        .get_latest_blockhash_with_commitment(values.rpc_client.commitment())
-        .await;
+    let mut instructions = transfer_instructions(
+        &signer_pubkey,
+        &to,
+        amount_as_u64,
+        micro_lamports_per_cu,
+        &values.memo,
+    );
+
+    let blockhash_result = with_retry(|| {
+        values
+            .rpc_client
+            .get_latest_blockhash_with_commitment(values.rpc_client.commitment())
+    })
+    .await;
 
-    let blockhash = if let Ok((blockhash_info, _)) = blockhash_result {
+    let (blockhash, last_valid_block_height) = if let Ok(blockhash_info) = blockhash_result {
         blockhash_info
     } else {
         return Err(Error::FetchBlockhashError);
     };
 
-    tx.sign(&[&values.signer], blockhash);
+    let payer = values.fee_payer_pubkey();
+    let base_fee = fetch_base_fee(&values.rpc_client, &signer_pubkey, &payer, &blockhash).await?;
+    let fees = base_fee
+        .checked_add(priority_fee_lamports(micro_lamports_per_cu))
+        .and_then(|fees| fees.checked_add(jito_tip(values)))
+        .ok_or(Error::InvalidAmount)?;
+
+    // with a separate fee payer the sender only needs to cover the amount
+    let sender_cost = if payer == signer_pubkey {
+        amount_as_u64
+            .checked_add(fees)
+            .ok_or(Error::InvalidAmount)?
+    } else {
+        let payer_balance = with_retry(|| values.rpc_client.get_balance(&payer))
+            .await
+            .map_err(|_| Error::FetchBalanceError)?;
+        if payer_balance < fees {
+            return Err(Error::InsufficientBalance);
+        }
+        amount_as_u64
+    };
+
+    // when allowed, unwrapping the wSOL account covers a shortfall in the
+    // same transaction
+    let balance = values.balance.unwrap_or(0);
+    if balance < sender_cost {
+        let wsol_lamports = if values.unwrap_wsol {
+            fetch_wsol_lamports(&signer_pubkey, &values.rpc_client).await?
+        } else {
+            0
+        };
+        if balance.saturating_add(wsol_lamports) < sender_cost {
+            return Err(Error::InsufficientBalance);
+        }
+        if let Some(allow_list) = &values.allow_list {
+            allow_list.check_mint(&NATIVE_MINT)?;
+        }
+        instructions.insert(0, close_wsol_instruction(&signer_pubkey));
+    }
+
+    if let Some(settings) = &values.jito {
+        instructions.push(tip_instruction(&payer, settings));
+    }
 
-    let signature_result = values
+    // a durable nonce replaces the recent blockhash and only expires once the
+    // nonce moves on, the advance instruction has to be the first one
+    let (blockhash, expiry) = if values.use_nonce {
+        let nonce_account =
+            fetch_nonce_account(values.nonce_address.clone(), values.rpc_client.clone()).await?;
+        instructions.insert(
+            0,
+            system_instruction::advance_nonce_account(&nonce_account.address, &signer_pubkey),
+        );
+        (
+            nonce_account.blockhash,
+            Expiry::Nonce {
+                account: nonce_account.address,
+                blockhash: nonce_account.blockhash,
+            },
+        )
+    } else {
+        (blockhash, Expiry::BlockHeight(last_valid_block_height))
+    };
+
+    let mut tx = Transaction::new_with_payer(&instructions, Some(&payer));
+    tx.message.recent_blockhash = blockhash;
+    Ok((tx, expiry))
+}
+
+async fn build_transfer(values: &TransferValues) -> Result<(Transaction, Expiry), Error> {
+    let (mut tx, expiry) = build_unsigned_transfer(values).await?;
+    let blockhash = tx.message.recent_blockhash;
+
+    let mut signers = vec![values.signer.as_ref()];
+    if let Some(fee_payer) = &values.fee_payer {
+        signers.push(fee_payer.as_ref());
+    }
+    tx.try_sign(&signers, blockhash)
+        .map_err(|_| Error::TransactionError)?;
+    Ok((tx, expiry))
+}
+
+pub async fn simulate_transfer(values: TransferValues) -> Result<SimulationPreview, Error> {
+    let (tx, _) = build_transfer(&values).await?;
+
+    let signer_pubkey = values.signer.pubkey();
+    let to = Pubkey::from_str(&values.receiver_value.0).map_err(|_| Error::InvalidPubKeyLen)?;
+
+    let recipient_balance = values
         .rpc_client
-        .send_transaction_with_config(&tx, send_cfg)
-        .await;
+        .get_balance(&to)
+        .await
+        .map_err(|_| Error::FetchBalanceError)?;
 
-    let signature = if let Ok(signature_data) = signature_result {
-        signature_data
+    let unwrapped_wsol = if unwraps_wsol(&tx) {
+        Some(fetch_wsol_lamports(&signer_pubkey, &values.rpc_client).await?)
     } else {
-        return Err(Error::TransactionError);
+        None
     };
 
+    let simulate_cfg = RpcSimulateTransactionConfig {
+        sig_verify: true,
+        commitment: Some(values.rpc_client.commitment()),
+        encoding: Some(UiTransactionEncoding::Base64),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: vec![signer_pubkey.to_string(), to.to_string()],
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+
+    let result = values
+        .rpc_client
+        .simulate_transaction_with_config(&tx, simulate_cfg)
+        .await
+        .map_err(|_| Error::SimulationError(String::from(tr("RPC request failed"))))?
+        .value;
+
+    if let Some(err) = result.err {
+        return Err(Error::SimulationError(err.to_string()));
+    }
+
+    // accounts come back in the same order as the requested addresses
+    let post_balances: Vec<u64> = result
+        .accounts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|account| account.map(|account| account.lamports).unwrap_or(0))
+        .collect();
+
+    let sender_post = post_balances.first().copied().unwrap_or(0);
+    let recipient_post = post_balances.get(1).copied().unwrap_or(0);
+
+    Ok(SimulationPreview {
+        sender_delta: sender_post as i128 - values.balance.unwrap_or(0) as i128,
+        recipient_delta: recipient_post as i128 - recipient_balance as i128,
+        units_consumed: result.units_consumed,
+        logs: result.logs.unwrap_or_default(),
+        unwrapped_wsol,
+    })
+}
+
+// states of the send-and-confirm flow, a transaction that expired can never
+// land, so it's safe to rebuild it with a fresh blockhash or nonce
+enum SendState {
+    Building,
+    Sending { tx: Transaction, expiry: Expiry },
+    // reached the client's commitment level
+    Committed(Signature),
+    Expired,
+}
+
+pub async fn transfer_sol(values: TransferValues) -> Result<SentTransfer, Error> {
+    let (recipient, lamports) = transfer_target(&values)?;
+    let mut rebroadcasts = 0;
+    let mut state = SendState::Building;
+
     loop {
-        let commitment_config = CommitmentConfig::finalized();
-        let confirmed = values
-            .rpc_client
-            .confirm_transaction_with_commitment(&signature, commitment_config)
-            .await;
-        let result = if let Ok(result) = confirmed {
-            result
-        } else {
-            return Err(Error::TransactionError);
+        state = match state {
+            SendState::Building => {
+                let (tx, expiry) = build_transfer(&values).await?;
+                SendState::Sending { tx, expiry }
+            }
+            SendState::Sending { tx, expiry } => {
+                send_and_confirm(&values.rpc_client, &tx, expiry, values.jito.as_ref()).await?
+            }
+            SendState::Committed(signature) => {
+                return Ok(SentTransfer {
+                    signature: signature.to_string(),
+                    sender: values.signer.pubkey(),
+                    recipient,
+                    lamports,
+                })
+            }
+            SendState::Expired if rebroadcasts < MAX_REBROADCASTS => {
+                rebroadcasts += 1;
+                SendState::Building
+            }
+            SendState::Expired => return Err(Error::BlockhashExpired),
         };
-        if result.value {
-            break;
+    }
+}
+
+// sends an already signed transaction, used for files signed somewhere else
+pub async fn broadcast_transaction(
+    rpc_client: &RpcClient,
+    tx: &Transaction,
+    expiry: Expiry,
+) -> Result<String, Error> {
+    match send_and_confirm(rpc_client, tx, expiry, None).await? {
+        SendState::Committed(signature) => Ok(signature.to_string()),
+        _ => Err(expired_error(&expiry)),
+    }
+}
+
+// v0 transactions, built elsewhere (e.g. by an aggregator) and signed here
+pub async fn broadcast_versioned_transaction(
+    rpc_client: &RpcClient,
+    tx: &VersionedTransaction,
+    last_valid_block_height: u64,
+) -> Result<String, Error> {
+    let expiry = Expiry::BlockHeight(last_valid_block_height);
+    match send_and_confirm(rpc_client, tx, expiry, None).await? {
+        SendState::Committed(signature) => Ok(signature.to_string()),
+        _ => Err(Error::BlockhashExpired),
+    }
+}
+
+fn expired_error(expiry: &Expiry) -> Error {
+    match expiry {
+        Expiry::BlockHeight(_) => Error::BlockhashExpired,
+        Expiry::Nonce { .. } => Error::NonceAdvanced,
+    }
+}
+
+async fn has_expired(rpc_client: &RpcClient, expiry: &Expiry) -> Result<bool, Error> {
+    match expiry {
+        Expiry::BlockHeight(last_valid_block_height) => {
+            let block_height = rpc_client
+                .get_block_height()
+                .await
+                .map_err(|_| Error::TransactionError)?;
+            Ok(block_height > *last_valid_block_height)
+        }
+        // read at the client's commitment, the same level a rebuild reads the
+        // nonce at, so the rebuilt transaction never reuses the old one
+        Expiry::Nonce { account, blockhash } => {
+            let current = current_nonce(account, rpc_client).await?;
+            Ok(current != Some(*blockhash))
         }
     }
+}
+
+async fn send_and_confirm(
+    rpc_client: &RpcClient,
+    tx: &impl SerializableTransaction,
+    expiry: Expiry,
+    jito: Option<&JitoSettings>,
+) -> Result<SendState, Error> {
+    let send_cfg = RpcSendTransactionConfig {
+        skip_preflight: true,
+        preflight_commitment: Some(rpc_client.commitment().commitment),
+        encoding: Some(UiTransactionEncoding::Base64),
+        max_retries: Some(3),
+        min_context_slot: None,
+    };
+
+    let signature = *tx.get_signature();
+    let ws_url = Config::compute_websocket_url(&rpc_client.url());
+
+    // subscribe before broadcasting so the notification can't be missed
+    let pubsub_client = PubsubClient::new(&ws_url).await.ok();
+    let subscription = match &pubsub_client {
+        Some(pubsub_client) => pubsub_client
+            .signature_subscribe(
+                &signature,
+                Some(RpcSignatureSubscribeConfig {
+                    commitment: Some(rpc_client.commitment()),
+                    enable_received_notification: Some(false),
+                }),
+            )
+            .await
+            .ok(),
+        None => None,
+    };
+
+    match jito {
+        Some(settings) => {
+            send_bundle(settings, tx).await?;
+        }
+        None => {
+            rpc_client
+                .send_transaction_with_config(tx, send_cfg)
+                .await
+                .map_err(|_| Error::TransactionError)?;
+        }
+    }
+
+    let (mut notifications, unsubscribe) = match subscription {
+        Some((notifications, unsubscribe)) => (Some(notifications), Some(unsubscribe)),
+        None => (None, None),
+    };
+
+    let state = loop {
+        // wait for the websocket notification, or just wait when there's no socket
+        let notification = match notifications.as_mut() {
+            Some(notifications) => time::timeout(POLL_INTERVAL, notifications.next())
+                .await
+                .ok()
+                .flatten(),
+            None => {
+                time::sleep(POLL_INTERVAL).await;
+                None
+            }
+        };
+
+        if let Some(Response {
+            value: RpcSignatureResult::ProcessedSignature(processed),
+            ..
+        }) = notification
+        {
+            if processed.err.is_some() {
+                break Err(Error::TransactionError);
+            }
+            break Ok(SendState::Committed(signature));
+        }
+
+        // polling fallback, also covers a socket that dropped silently
+        let status = rpc_client
+            .get_signature_status_with_commitment(&signature, rpc_client.commitment())
+            .await
+            .map_err(|_| Error::TransactionError)?;
+
+        match status {
+            Some(Ok(())) => break Ok(SendState::Committed(signature)),
+            Some(Err(_)) => break Err(Error::TransactionError),
+            None => {}
+        }
+
+        // only a missing status is checked against the expiry; landing
+        // advances the nonce too, so that's told apart by the status below
+        if has_expired(rpc_client, &expiry).await? {
+            let status = rpc_client
+                .get_signature_status_with_commitment(&signature, CommitmentConfig::processed())
+                .await
+                .map_err(|_| Error::TransactionError)?;
+
+            // landed right before expiring, keep waiting for the commitment
+            if status.is_none() {
+                break Ok(SendState::Expired);
+            }
+        }
+    };
+
+    if let Some(unsubscribe) = unsubscribe {
+        unsubscribe().await;
+    }
 
-    Ok(signature.to_string())
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_fee_rounds_up_to_whole_lamports() {
+        assert_eq!(priority_fee_lamports(0), 0);
+        // a fraction of a lamport is still paid as a whole one
+        assert_eq!(priority_fee_lamports(1), 1);
+        assert_eq!(priority_fee_lamports(1_000), 1);
+        assert_eq!(priority_fee_lamports(1_001), 2);
+        assert_eq!(priority_fee_lamports(10_000), 10);
+    }
+
+    #[test]
+    fn the_largest_accepted_fee_is_a_tenth_of_a_sol() {
+        assert_eq!(priority_fee_lamports(MAX_PRIORITY_FEE), 100_000_000);
+        assert_eq!(
+            parse_priority_fee(&MAX_PRIORITY_FEE.to_string()).unwrap(),
+            MAX_PRIORITY_FEE
+        );
+        assert!(matches!(
+            parse_priority_fee(&(MAX_PRIORITY_FEE + 1).to_string()),
+            Err(Error::InvalidPriorityFee)
+        ));
+    }
+
+    #[test]
+    fn priority_fee_saturates_instead_of_overflowing() {
+        assert_eq!(
+            priority_fee_lamports(u64::MAX),
+            u64::MAX.div_ceil(1_000_000)
+        );
+    }
+
+    #[test]
+    fn an_empty_priority_fee_is_zero() {
+        assert_eq!(parse_priority_fee("  ").unwrap(), 0);
+        assert!(parse_priority_fee("-1").is_err());
+    }
 }
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use tray_icon::{
+    menu::{Menu, MenuEvent, MenuItem},
+    Icon, TrayIcon, TrayIconBuilder, TrayIconEvent,
+};
+
+use crate::{errors::Error, i18n::tr};
+
+pub const TRAY_OPEN: &str = "open";
+pub const TRAY_LOCK: &str = "lock";
+pub const TRAY_QUIT: &str = "quit";
+const ICON_SIZE: u32 = 32;
+// how often menu picks and icon clicks are picked up
+pub const TRAY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// what the user picked in the tray since the last poll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    Open,
+    Lock,
+    Quit,
+}
+
+// a plain square in the app's accent color, there's no icon file to ship
+fn tray_icon() -> Result<Icon, Error> {
+    let rgba = [0x30, 0xcb, 0xf2, 0xff].repeat((ICON_SIZE * ICON_SIZE) as usize);
+    Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE).map_err(|_| Error::TrayError)
+}
+
+// Lock starts disabled, it's only offered once there's a prompt to lock behind
+fn build_tray() -> Result<(TrayIcon, MenuItem), Error> {
+    let lock = MenuItem::with_id(TRAY_LOCK, tr("Lock"), false, None);
+    let menu = Menu::new();
+    menu.append_items(&[
+        &MenuItem::with_id(TRAY_OPEN, tr("Open"), true, None),
+        &lock,
+        &MenuItem::with_id(TRAY_QUIT, tr("Quit"), true, None),
+    ])
+    .map_err(|_| Error::TrayError)?;
+
+    let icon = TrayIconBuilder::new()
+        .with_menu(Box::new(menu))
+        .with_tooltip("Solana Executable Application")
+        .with_icon(tray_icon()?)
+        .build()
+        .map_err(|_| Error::TrayError)?;
+    Ok((icon, lock))
+}
+
+#[cfg(target_os = "linux")]
+enum TrayUpdate {
+    Tooltip(String),
+    LockEnabled(bool),
+}
+
+// gtk needs its own thread and main loop on linux, the icon lives there and
+// tooltips and menu states are handed over through a channel
+#[cfg(target_os = "linux")]
+pub struct Tray {
+    updates: std::sync::mpsc::Sender<TrayUpdate>,
+}
+
+#[cfg(target_os = "linux")]
+impl Tray {
+    pub fn start() -> Result<Self, Error> {
+        use std::{sync::mpsc, thread, time::Duration};
+
+        let (updates, received) = mpsc::channel::<TrayUpdate>();
+        let (ready, started) = mpsc::channel::<Result<(), Error>>();
+        thread::spawn(move || {
+            if gtk::init().is_err() {
+                ready.send(Err(Error::TrayError)).ok();
+                return;
+            }
+            let (tray, lock) = match build_tray() {
+                Ok(tray) => tray,
+                Err(error) => {
+                    ready.send(Err(error)).ok();
+                    return;
+                }
+            };
+            ready.send(Ok(())).ok();
+            gtk::glib::timeout_add_local(Duration::from_millis(500), move || {
+                while let Ok(update) = received.try_recv() {
+                    match update {
+                        TrayUpdate::Tooltip(text) => {
+                            tray.set_tooltip(Some(text)).ok();
+                        }
+                        TrayUpdate::LockEnabled(enabled) => lock.set_enabled(enabled),
+                    }
+                }
+                gtk::glib::ControlFlow::Continue
+            });
+            gtk::main();
+        });
+
+        started.recv().map_err(|_| Error::TrayError)??;
+        Ok(Self { updates })
+    }
+
+    pub fn set_tooltip(&self, text: String) {
+        self.updates.send(TrayUpdate::Tooltip(text)).ok();
+    }
+
+    pub fn set_lock_enabled(&self, enabled: bool) {
+        self.updates.send(TrayUpdate::LockEnabled(enabled)).ok();
+    }
+}
+
+// windows and macos drive the icon from the window's event loop
+#[cfg(not(target_os = "linux"))]
+pub struct Tray {
+    icon: TrayIcon,
+    lock: MenuItem,
+}
+
+#[cfg(not(target_os = "linux"))]
+impl Tray {
+    pub fn start() -> Result<Self, Error> {
+        build_tray().map(|(icon, lock)| Self { icon, lock })
+    }
+
+    pub fn set_tooltip(&self, text: String) {
+        self.icon.set_tooltip(Some(text)).ok();
+    }
+
+    pub fn set_lock_enabled(&self, enabled: bool) {
+        self.lock.set_enabled(enabled);
+    }
+}
+
+// menu picks and clicks on the icon queue up until the app asks for them
+pub fn tray_actions() -> Vec<TrayAction> {
+    let mut actions = Vec::new();
+    while let Ok(event) = MenuEvent::receiver().try_recv() {
+        match event.id.as_ref() {
+            TRAY_OPEN => actions.push(TrayAction::Open),
+            TRAY_LOCK => actions.push(TrayAction::Lock),
+            TRAY_QUIT => actions.push(TrayAction::Quit),
+            _ => {}
+        }
+    }
+    while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+        if event.click_type == tray_icon::ClickType::Left {
+            actions.push(TrayAction::Open);
+        }
+    }
+    actions
+}
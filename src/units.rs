@@ -0,0 +1,259 @@
+use std::fmt;
+
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+use crate::errors::Error;
+
+// digits after the dot, one lamport is 0.000000001 SOL
+pub const SOL_DECIMALS: usize = 9;
+
+fn parse_digits(digits: &str) -> Result<u64, Error> {
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::InvalidAmount);
+    }
+    digits.parse::<u64>().map_err(|_| Error::InvalidAmount)
+}
+
+// parses a SOL amount typed by the user into lamports, anything that can't be
+// represented exactly is rejected instead of being rounded
+pub fn parse_amount(amount_str: &str) -> Result<u64, Error> {
+    let parts: Vec<&str> = amount_str.trim().split('.').collect();
+
+    let lamports = match parts.len() {
+        // take only integer part
+        1 => {
+            let valid_integer = parse_digits(parts[0])?;
+            valid_integer
+                .checked_mul(LAMPORTS_PER_SOL)
+                .ok_or(Error::InvalidAmount)?
+        }
+        // take integer + decimal part
+        2 => {
+            if parts[0].is_empty() && parts[1].is_empty() {
+                return Err(Error::InvalidAmount);
+            }
+            if parts[1].len() > SOL_DECIMALS {
+                return Err(Error::InvalidAmount);
+            }
+
+            let integer = if parts[0].is_empty() {
+                0
+            } else {
+                parse_digits(parts[0])?
+            };
+
+            // take decimal, example: 0.125, so decimal part is literal 125000000
+            let fraction = format!("{:0<9}", parts[1]);
+            let fraction_value = parse_digits(&fraction)?;
+
+            // convert both parts in lamports and add them
+            let whole_amount = integer
+                .checked_mul(LAMPORTS_PER_SOL)
+                .ok_or(Error::InvalidAmount)?;
+
+            whole_amount
+                .checked_add(fraction_value)
+                .ok_or(Error::InvalidAmount)?
+        }
+        // if the input has more than one dot, an error throws
+        _ => return Err(Error::InvalidAmount),
+    };
+
+    Ok(lamports)
+}
+
+// exact SOL representation of a lamports amount, without trailing zeros
+pub fn format_sol(lamports: u64) -> String {
+    let whole = lamports / LAMPORTS_PER_SOL;
+    let fraction = lamports % LAMPORTS_PER_SOL;
+
+    if fraction == 0 {
+        return whole.to_string();
+    }
+
+    let fraction = format!("{:09}", fraction);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+// what the send form's amount is typed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmountUnit {
+    #[default]
+    Sol,
+    Lamports,
+}
+
+impl AmountUnit {
+    pub const ALL: [AmountUnit; 2] = [AmountUnit::Sol, AmountUnit::Lamports];
+
+    pub fn parse(&self, amount: &str) -> Result<u64, Error> {
+        match self {
+            AmountUnit::Sol => parse_amount(amount),
+            AmountUnit::Lamports => parse_digits(amount.trim()),
+        }
+    }
+
+    pub fn format(&self, lamports: u64) -> String {
+        match self {
+            AmountUnit::Sol => format_sol(lamports),
+            AmountUnit::Lamports => lamports.to_string(),
+        }
+    }
+
+    // as stored in the config file
+    pub fn code(&self) -> &'static str {
+        match self {
+            AmountUnit::Sol => "sol",
+            AmountUnit::Lamports => "lamports",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "lamports" => AmountUnit::Lamports,
+            _ => AmountUnit::Sol,
+        }
+    }
+}
+
+impl fmt::Display for AmountUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmountUnit::Sol => write!(f, "SOL"),
+            AmountUnit::Lamports => write!(f, "lamports"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // lamports -> SOL string pairs, checked in both directions
+    const SNAPSHOTS: &[(u64, &str)] = &[
+        (0, "0"),
+        (1, "0.000000001"),
+        (10, "0.00000001"),
+        (999_999_999, "0.999999999"),
+        (LAMPORTS_PER_SOL, "1"),
+        (LAMPORTS_PER_SOL + 1, "1.000000001"),
+        (1_500_000_000, "1.5"),
+        (125_000_000, "0.125"),
+        (u64::MAX - 1, "18446744073.709551614"),
+        (u64::MAX, "18446744073.709551615"),
+    ];
+
+    #[test]
+    fn format_matches_snapshots() {
+        for (lamports, sol) in SNAPSHOTS {
+            assert_eq!(
+                format_sol(*lamports),
+                *sol,
+                "formatting {} lamports",
+                lamports
+            );
+        }
+    }
+
+    #[test]
+    fn parse_matches_snapshots() {
+        for (lamports, sol) in SNAPSHOTS {
+            assert_eq!(parse_amount(sol).unwrap(), *lamports, "parsing {}", sol);
+        }
+    }
+
+    #[test]
+    fn lamports_round_trip_exactly() {
+        let boundaries = [0, 1, 2, LAMPORTS_PER_SOL - 1, LAMPORTS_PER_SOL, u64::MAX];
+        let dust = (0..1_000).map(|i| i * 7_919);
+        let near_max = (0..1_000).map(|i| u64::MAX - i * 104_729);
+
+        for lamports in boundaries.into_iter().chain(dust).chain(near_max) {
+            assert_eq!(parse_amount(&format_sol(lamports)).unwrap(), lamports);
+        }
+    }
+
+    #[test]
+    fn typed_amounts_error_or_round_trip() {
+        let inputs = [
+            "",
+            ".",
+            "1",
+            "1.",
+            ".5",
+            "0.5",
+            "00.50",
+            "1.000000001",
+            "1.0000000001",
+            "1..0",
+            "1.2.3",
+            "-1",
+            "+1",
+            "1e9",
+            "abc",
+            "1.x",
+            " 2.5 ",
+            "1 000",
+            "18446744073.709551615",
+            "18446744073.709551616",
+            "18446744074",
+            "99999999999999999999",
+        ];
+
+        for input in inputs {
+            if let Ok(lamports) = parse_amount(input) {
+                // the canonical form parses back to the very same amount
+                let formatted = format_sol(lamports);
+                assert_eq!(
+                    parse_amount(&formatted).unwrap(),
+                    lamports,
+                    "input {:?}",
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn units_read_the_same_amount_differently() {
+        assert_eq!(AmountUnit::Sol.parse("1.5").unwrap(), 1_500_000_000);
+        assert_eq!(AmountUnit::Lamports.parse("1500").unwrap(), 1_500);
+        assert!(AmountUnit::Lamports.parse("1.5").is_err());
+        assert!(AmountUnit::Lamports.parse("").is_err());
+
+        for unit in AmountUnit::ALL {
+            for (lamports, _) in SNAPSHOTS {
+                assert_eq!(unit.parse(&unit.format(*lamports)).unwrap(), *lamports);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_what_cant_be_represented() {
+        let invalid = [
+            "",
+            ".",
+            "1..0",
+            "-1",
+            "+1",
+            "1e9",
+            "abc",
+            "1.x",
+            "1 000",
+            // below one lamport
+            "0.0000000001",
+            // above u64::MAX lamports
+            "18446744073.709551616",
+            "18446744074",
+            "99999999999999999999",
+        ];
+
+        for input in invalid {
+            assert!(
+                parse_amount(input).is_err(),
+                "{:?} should be rejected",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use std::{collections::HashMap, fmt, str::FromStr, sync::Arc};
+
+use iced::{
+    widget::{button, column, pick_list, row, text, text_input, Column},
+    Alignment, Command, Element,
+};
+use solana_account_decoder::parse_config::{parse_config, ConfigAccountType};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{config, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+
+use crate::{
+    errors::{report, Error},
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+// a few thousand validators are too many rows to lay out, search narrows it
+const SHOWN_VALIDATORS: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct Validator {
+    pub vote_account: Pubkey,
+    pub identity: Pubkey,
+    // from the validator info the operator published on chain, if any
+    pub name: Option<String>,
+    pub commission: u8,
+    pub activated_stake: u64,
+    // yearly, in percent
+    pub apy: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidatorSort {
+    Stake,
+    Apy,
+    Commission,
+    Name,
+}
+
+// the validators screen: the fetched list and how it's narrowed and sorted
+#[derive(Debug)]
+pub struct ValidatorList {
+    pub validators: Vec<Validator>,
+    pub search: String,
+    pub sort: ValidatorSort,
+    pub loading: bool,
+}
+
+impl Default for ValidatorList {
+    fn default() -> Self {
+        Self {
+            validators: Vec::new(),
+            search: String::new(),
+            sort: ValidatorSort::Stake,
+            loading: false,
+        }
+    }
+}
+
+impl ValidatorList {
+    // one fetch at a time, a few thousand vote accounts take a while
+    pub fn refresh(&mut self, rpc_client: Arc<RpcClient>) -> Command<Message> {
+        if self.loading {
+            return Command::none();
+        }
+        self.loading = true;
+        Command::perform(fetch_validators(rpc_client), Message::ValidatorsLoaded)
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::ValidatorsLoaded(result) => {
+                self.loading = false;
+                match result {
+                    Ok(validators) => {
+                        self.validators = validators;
+                        Command::none()
+                    }
+                    Err(error) => report(error),
+                }
+            }
+            Message::ValidatorSearchChanged(search) => {
+                self.search = search;
+                Command::none()
+            }
+            Message::ValidatorSortChanged(sort) => {
+                self.sort = sort;
+                Command::none()
+            }
+            _ => Command::none(),
+        }
+    }
+}
+
+impl ValidatorSort {
+    pub const ALL: [ValidatorSort; 4] = [
+        ValidatorSort::Stake,
+        ValidatorSort::Apy,
+        ValidatorSort::Commission,
+        ValidatorSort::Name,
+    ];
+}
+
+impl fmt::Display for ValidatorSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidatorSort::Stake => write!(f, "{}", tr("Most stake")),
+            ValidatorSort::Apy => write!(f, "{}", tr("Highest APY")),
+            ValidatorSort::Commission => write!(f, "{}", tr("Lowest commission")),
+            ValidatorSort::Name => write!(f, "{}", tr("Name")),
+        }
+    }
+}
+
+// identity -> name, from the config program's validator info accounts; the
+// first key is the validator info id, the second the identity that signed it
+async fn fetch_validator_names(rpc_client: &RpcClient) -> Result<HashMap<Pubkey, String>, Error> {
+    let accounts = rpc_client
+        .get_program_accounts(&config::program::id())
+        .await
+        .map_err(|_| Error::FetchValidatorsError)?;
+
+    Ok(accounts
+        .into_iter()
+        .filter_map(|(address, account)| {
+            let ConfigAccountType::ValidatorInfo(info) =
+                parse_config(&account.data, &address).ok()?
+            else {
+                return None;
+            };
+            let identity = Pubkey::from_str(&info.keys.get(1)?.pubkey).ok()?;
+            let name = info.config_data.get("name")?.as_str()?.trim().to_string();
+            (!name.is_empty()).then_some((identity, name))
+        })
+        .collect())
+}
+
+// the estimate is the inflation paid to stakers spread over the staked
+// supply, minus commission, scaled by how many vote credits the validator
+// earned last epoch next to the best one; it ignores compounding and mev
+pub async fn fetch_validators(rpc_client: Arc<RpcClient>) -> Result<Vec<Validator>, Error> {
+    let vote_accounts = rpc_client
+        .get_vote_accounts()
+        .await
+        .map_err(|_| Error::FetchValidatorsError)?
+        .current;
+    let inflation = rpc_client
+        .get_inflation_rate()
+        .await
+        .map_err(|_| Error::FetchValidatorsError)?;
+    let supply = rpc_client
+        .get_supply()
+        .await
+        .map_err(|_| Error::FetchValidatorsError)?
+        .value
+        .total;
+    // names are a nicety, the list still loads without them
+    let names = fetch_validator_names(&rpc_client).await.unwrap_or_default();
+
+    let total_stake: u64 = vote_accounts
+        .iter()
+        .map(|account| account.activated_stake)
+        .sum();
+    let staking_yield = if total_stake > 0 {
+        inflation.validator * supply as f64 / total_stake as f64 * 100.0
+    } else {
+        0.0
+    };
+    let last_epoch_credits = |credits: &[(u64, u64, u64)]| {
+        credits.last().map_or(0, |(_, credits, previous)| {
+            credits.saturating_sub(*previous)
+        })
+    };
+    let best_credits = vote_accounts
+        .iter()
+        .map(|account| last_epoch_credits(&account.epoch_credits))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    Ok(vote_accounts
+        .into_iter()
+        .filter_map(|account| {
+            let vote_account = Pubkey::from_str(&account.vote_pubkey).ok()?;
+            let identity = Pubkey::from_str(&account.node_pubkey).ok()?;
+            let performance =
+                last_epoch_credits(&account.epoch_credits) as f64 / best_credits as f64;
+            Some(Validator {
+                vote_account,
+                identity,
+                name: names.get(&identity).cloned(),
+                commission: account.commission,
+                activated_stake: account.activated_stake,
+                apy: staking_yield * (1.0 - account.commission as f64 / 100.0) * performance,
+            })
+        })
+        .collect())
+}
+
+fn matches_search(validator: &Validator, search: &str) -> bool {
+    let search = search.trim().to_lowercase();
+    search.is_empty()
+        || validator
+            .name
+            .as_ref()
+            .is_some_and(|name| name.to_lowercase().contains(&search))
+        || validator
+            .vote_account
+            .to_string()
+            .to_lowercase()
+            .contains(&search)
+        || validator
+            .identity
+            .to_string()
+            .to_lowercase()
+            .contains(&search)
+}
+
+fn sorted_validators<'a>(
+    validators: &'a [Validator],
+    search: &str,
+    sort: ValidatorSort,
+) -> Vec<&'a Validator> {
+    let mut shown: Vec<&Validator> = validators
+        .iter()
+        .filter(|validator| matches_search(validator, search))
+        .collect();
+    match sort {
+        ValidatorSort::Stake => shown.sort_by(|a, b| b.activated_stake.cmp(&a.activated_stake)),
+        ValidatorSort::Apy => shown.sort_by(|a, b| b.apy.total_cmp(&a.apy)),
+        ValidatorSort::Commission => shown.sort_by(|a, b| {
+            a.commission
+                .cmp(&b.commission)
+                .then(b.activated_stake.cmp(&a.activated_stake))
+        }),
+        // unnamed validators go last
+        ValidatorSort::Name => shown.sort_by(|a, b| match (&a.name, &b.name) {
+            (Some(a), Some(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        }),
+    }
+    shown
+}
+
+pub fn display_validators(
+    validators: &[Validator],
+    search: &str,
+    sort: ValidatorSort,
+    loading: bool,
+) -> Element<'static, Message> {
+    let controls = row![
+        text(tr("Validators")).size(14).style(accent_color()),
+        text_input(tr("Search by name or address"), search)
+            .on_input(Message::ValidatorSearchChanged),
+        pick_list(
+            &ValidatorSort::ALL[..],
+            Some(sort),
+            Message::ValidatorSortChanged
+        ),
+        button(
+            text(if loading {
+                tr("Loading...")
+            } else {
+                tr("Refresh")
+            })
+            .size(12)
+        )
+        .on_press(Message::RefreshValidators),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    if validators.is_empty() {
+        let note = if loading {
+            tr("Fetching vote accounts...")
+        } else {
+            tr("No validators loaded")
+        };
+        return column![controls, text(note).size(12)].spacing(10).into();
+    }
+
+    let shown = sorted_validators(validators, search, sort);
+    let summary = text(tr_args(
+        "{$shown} of {$total} validators, showing the first {$limit}. APY is an estimate from inflation, commission and last epoch's vote credits",
+        &[
+            ("shown", &shown.len()),
+            ("total", &validators.len()),
+            ("limit", &shown.len().min(SHOWN_VALIDATORS)),
+        ],
+    ))
+    .size(12);
+
+    let list = shown.into_iter().take(SHOWN_VALIDATORS).fold(
+        Column::new().spacing(5),
+        |column, validator| {
+            column.push(
+                row![
+                    text(tr_args(
+                        "{$name} ({$vote_account}): {$commission}% commission, {$stake} SOL staked, ~{$apy}% APY",
+                        &[
+                            ("name", &validator.name.as_deref().unwrap_or(tr("Unnamed"))),
+                            ("vote_account", &validator.vote_account),
+                            ("commission", &validator.commission),
+                            (
+                                "stake",
+                                &format!(
+                                    "{:.0}",
+                                    validator.activated_stake as f64 / LAMPORTS_PER_SOL as f64
+                                ),
+                            ),
+                            ("apy", &format!("{:.2}", validator.apy)),
+                        ],
+                    ))
+                    .size(12),
+                    button(text(tr("Stake with")).size(12))
+                        .on_press(Message::PickValidator(validator.vote_account)),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            )
+        },
+    );
+
+    column![controls, summary, list].spacing(10).into()
+}
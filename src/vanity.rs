@@ -0,0 +1,249 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Instant,
+};
+
+use futures::channel::oneshot;
+use iced::{
+    widget::{button, checkbox, column, row, text, text_input},
+    Command, Element,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+use crate::{
+    errors::{report, Error},
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    Message,
+};
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+// attempts are counted locally and added to the shared total in batches
+const ATTEMPTS_PER_UPDATE: u64 = 1_000;
+
+#[derive(Debug, Clone, Default)]
+pub struct VanityPattern {
+    pub prefix: String,
+    pub suffix: String,
+    pub ignore_case: bool,
+}
+
+impl VanityPattern {
+    // characters outside base58 would never match, the search would run forever
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.prefix.is_empty() && self.suffix.is_empty() {
+            return Err(Error::InvalidVanityPattern);
+        }
+        let valid = |c: char| {
+            BASE58_ALPHABET.contains(c)
+                || (self.ignore_case
+                    && (BASE58_ALPHABET.contains(c.to_ascii_lowercase())
+                        || BASE58_ALPHABET.contains(c.to_ascii_uppercase())))
+        };
+        if self.prefix.chars().chain(self.suffix.chars()).all(valid) {
+            Ok(())
+        } else {
+            Err(Error::InvalidVanityPattern)
+        }
+    }
+
+    fn matches(&self, address: &str) -> bool {
+        if self.ignore_case {
+            let address = address.to_lowercase();
+            address.starts_with(&self.prefix.to_lowercase())
+                && address.ends_with(&self.suffix.to_lowercase())
+        } else {
+            address.starts_with(&self.prefix) && address.ends_with(&self.suffix)
+        }
+    }
+}
+
+// shared with the worker threads, the view reads it on every frame
+#[derive(Debug, Clone)]
+pub struct VanitySearch {
+    pub attempts: Arc<AtomicU64>,
+    pub cancel: Arc<AtomicBool>,
+    pub started: Instant,
+}
+
+impl VanitySearch {
+    pub fn start() -> Self {
+        Self {
+            attempts: Arc::new(AtomicU64::new(0)),
+            cancel: Arc::new(AtomicBool::new(false)),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn stop(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    fn rate(&self) -> u64 {
+        let seconds = self.started.elapsed().as_secs_f64();
+        if seconds < 0.5 {
+            return 0;
+        }
+        (self.attempts.load(Ordering::Relaxed) as f64 / seconds) as u64
+    }
+}
+
+// the vanity address screen
+#[derive(Debug, Default)]
+pub struct VanityGrinder {
+    pub pattern: VanityPattern,
+    // set while the grinder threads run
+    pub search: Option<VanitySearch>,
+    pub found: Option<Pubkey>,
+}
+
+impl VanityGrinder {
+    pub fn stop(&mut self) {
+        if let Some(search) = self.search.take() {
+            search.stop();
+        }
+    }
+
+    // the app loads the ground keypair, only its address is kept here
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::VanityPrefixChanged(prefix) => self.pattern.prefix = prefix.trim().to_string(),
+            Message::VanitySuffixChanged(suffix) => self.pattern.suffix = suffix.trim().to_string(),
+            Message::VanityIgnoreCaseToggled(enabled) => self.pattern.ignore_case = enabled,
+            Message::StartVanity if self.search.is_some() => {}
+            Message::StartVanity => {
+                if let Err(error) = self.pattern.validate() {
+                    return report(error);
+                }
+                let search = VanitySearch::start();
+                self.search = Some(search.clone());
+                self.found = None;
+                return Command::perform(
+                    grind_vanity(self.pattern.clone(), search),
+                    Message::VanityGround,
+                );
+            }
+            Message::CancelVanity => {
+                if let Some(search) = &self.search {
+                    search.stop();
+                }
+            }
+            Message::VanityGround(result) => {
+                self.search = None;
+                if let Ok(keypair) = result {
+                    self.found = Some(keypair.pubkey());
+                }
+            }
+            _ => {}
+        }
+        Command::none()
+    }
+}
+
+// one worker per core, the first match stops the others; a cancelled
+// search comes back as VanityCancelled
+pub async fn grind_vanity(
+    pattern: VanityPattern,
+    search: VanitySearch,
+) -> Result<Arc<Keypair>, Error> {
+    let (sender, receiver) = oneshot::channel();
+
+    thread::spawn(move || {
+        let found: Mutex<Option<Keypair>> = Mutex::new(None);
+        let workers = thread::available_parallelism()
+            .map(NonZeroUsize::get)
+            .unwrap_or(1);
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    let mut attempts = 0;
+                    while !search.cancel.load(Ordering::Relaxed) {
+                        let keypair = Keypair::new();
+                        attempts += 1;
+                        if attempts == ATTEMPTS_PER_UPDATE {
+                            search.attempts.fetch_add(attempts, Ordering::Relaxed);
+                            attempts = 0;
+                        }
+                        if pattern.matches(&keypair.pubkey().to_string()) {
+                            search.stop();
+                            if let Ok(mut found) = found.lock() {
+                                found.get_or_insert(keypair);
+                            }
+                        }
+                    }
+                    search.attempts.fetch_add(attempts, Ordering::Relaxed);
+                });
+            }
+        });
+
+        sender.send(found.into_inner().ok().flatten()).ok();
+    });
+
+    receiver
+        .await
+        .ok()
+        .flatten()
+        .map(Arc::new)
+        .ok_or(Error::VanityCancelled)
+}
+
+pub fn display_vanity(
+    pattern: &VanityPattern,
+    search: &Option<VanitySearch>,
+    found: Option<Pubkey>,
+) -> Element<'static, Message> {
+    let title = text(tr("Vanity address")).size(14).style(accent_color());
+
+    let inputs = row![
+        text_input(tr("Prefix"), &pattern.prefix).on_input(Message::VanityPrefixChanged),
+        text_input(tr("Suffix"), &pattern.suffix).on_input(Message::VanitySuffixChanged),
+    ]
+    .spacing(10);
+
+    let ignore_case = checkbox(
+        tr("Ignore case (matches sooner)"),
+        pattern.ignore_case,
+        Message::VanityIgnoreCaseToggled,
+    )
+    .size(16)
+    .text_size(14);
+
+    let status = match (search, found) {
+        (Some(search), _) => column![
+            text(tr_args(
+                "{$attempts} attempts, {$rate} per second",
+                &[
+                    ("attempts", &search.attempts.load(Ordering::Relaxed)),
+                    ("rate", &search.rate()),
+                ],
+            ))
+            .size(12),
+            button(tr("Cancel")).on_press(Message::CancelVanity),
+        ]
+        .spacing(10),
+        (None, Some(pubkey)) => column![
+            text(tr_args(
+                "Found {$pubkey}, it's loaded from memory only, move it to the keystore below to keep it",
+                &[("pubkey", &pubkey)],
+            ))
+            .size(12),
+            button(tr("Search again")).on_press(Message::StartVanity),
+        ]
+        .spacing(10),
+        (None, None) => column![
+            text(tr("Every extra character makes the search about 58 times longer")).size(12),
+            button(tr("Start")).on_press(Message::StartVanity),
+        ]
+        .spacing(10),
+    };
+
+    column![title, inputs, ignore_case, status]
+        .spacing(10)
+        .into()
+}
@@ -0,0 +1,212 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use iced::{
+    widget::{button, column, row, text, Column},
+    Alignment, Element,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+use crate::{
+    commands::AppCommand,
+    files::solana_config_dir,
+    i18n::{tr, tr_args},
+    loaders::load_keypair_from_file,
+    palette::accent_color,
+    units::format_sol,
+    Message, Screen,
+};
+
+// one entry in the sidebar, either a keypair that can sign or a watched address
+#[derive(Debug, Clone)]
+pub struct LoadedWallet {
+    pub pubkey: Pubkey,
+    pub signer: Option<Arc<Keypair>>,
+    pub path: Option<PathBuf>,
+    // last balance fetched while this wallet was active
+    pub balance: Option<u64>,
+}
+
+impl LoadedWallet {
+    pub fn from_keypair(signer: Arc<Keypair>, path: PathBuf) -> Self {
+        Self {
+            pubkey: signer.pubkey(),
+            signer: Some(signer),
+            path: Some(path),
+            balance: None,
+        }
+    }
+
+    // pasted keys only live in memory
+    pub fn pasted(signer: Arc<Keypair>) -> Self {
+        Self {
+            pubkey: signer.pubkey(),
+            signer: Some(signer),
+            path: None,
+            balance: None,
+        }
+    }
+
+    pub fn watch_only(pubkey: Pubkey) -> Self {
+        Self {
+            pubkey,
+            signer: None,
+            path: None,
+            balance: None,
+        }
+    }
+}
+
+// a wallet loaded twice replaces its entry but keeps the cached balance
+pub fn remember_wallet(wallets: &mut Vec<LoadedWallet>, wallet: LoadedWallet) -> LoadedWallet {
+    match wallets
+        .iter_mut()
+        .find(|known| known.pubkey == wallet.pubkey)
+    {
+        Some(known) => {
+            let balance = known.balance;
+            *known = LoadedWallet { balance, ..wallet };
+            known.clone()
+        }
+        None => {
+            wallets.push(wallet.clone());
+            wallet
+        }
+    }
+}
+
+// every *.json keypair in the solana config directory, only the pubkey is
+// kept, the key itself is read again when the wallet is picked
+pub fn discover_keypairs() -> Vec<(PathBuf, Pubkey)> {
+    let Ok(entries) = fs::read_dir(solana_config_dir()) else {
+        return Vec::new();
+    };
+
+    let mut keypairs: Vec<(PathBuf, Pubkey)> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let pubkey = load_keypair_from_file(path.clone()).ok()?.pubkey();
+            Some((path, pubkey))
+        })
+        .collect();
+    keypairs.sort();
+    keypairs
+}
+
+// first run, or the default keypair was moved: nothing is generated behind
+// the user's back, they pick where the wallet comes from
+pub fn display_welcome(missing: &Path) -> Element<'static, Message> {
+    let title = text(tr("Set up a wallet")).size(14).style(accent_color());
+
+    column![
+        title,
+        text(tr_args(
+            "No keypair was found at {$path}",
+            &[("path", &missing.display())]
+        ))
+        .size(12),
+        row![
+            button(tr("Load keypair")).on_press(Message::RunCommand(AppCommand::LoadWallet)),
+            button(tr("Create new wallet")).on_press(Message::RunCommand(AppCommand::CreateWallet)),
+            button(tr("Create with seed phrase")).on_press(Message::GenerateMnemonic),
+            button(tr("Import seed phrase")).on_press(Message::ShowScreen(Screen::ImportMnemonic)),
+            button(tr("Paste keypair")).on_press(Message::ShowScreen(Screen::PasteKeypair)),
+        ]
+        .spacing(10),
+        text(tr(
+            "Or watch an address above to look at a wallet without its key"
+        ))
+        .size(12),
+        row![
+            text(tr(
+                "A keypair stored somewhere else can be loaded on every start from"
+            ))
+            .size(12),
+            button(text(tr("Settings")).size(12)).on_press(Message::ShowScreen(Screen::Settings)),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center),
+    ]
+    .spacing(10)
+    .into()
+}
+
+fn short_address(pubkey: &Pubkey) -> String {
+    let address = pubkey.to_string();
+    format!("{}..{}", &address[..4], &address[address.len() - 4..])
+}
+
+pub fn display_wallets(
+    wallets: &[LoadedWallet],
+    discovered: &[(PathBuf, Pubkey)],
+    active: Option<Pubkey>,
+    hide_balances: bool,
+) -> Element<'static, Message> {
+    let title = text(tr("Wallets")).size(14).style(accent_color());
+
+    let list =
+        wallets
+            .iter()
+            .enumerate()
+            .fold(Column::new().spacing(5), |column, (index, wallet)| {
+                let label = match (&wallet.signer, active == Some(wallet.pubkey)) {
+                    (Some(_), true) => format!("> {}", short_address(&wallet.pubkey)),
+                    (None, true) => format!(
+                        "> {}",
+                        tr_args(
+                            "{$address} (watch)",
+                            &[("address", &short_address(&wallet.pubkey))]
+                        )
+                    ),
+                    (Some(_), false) => short_address(&wallet.pubkey),
+                    (None, false) => tr_args(
+                        "{$address} (watch)",
+                        &[("address", &short_address(&wallet.pubkey))],
+                    ),
+                };
+                let balance = match wallet.balance {
+                    Some(_) if hide_balances => String::from("****"),
+                    Some(balance) => format!("{} SOL", format_sol(balance)),
+                    None => String::from("-"),
+                };
+
+                column.push(
+                    row![
+                        button(text(label).size(12)).on_press(Message::SelectWallet(index)),
+                        text(balance).size(12),
+                        button(text("x").size(12)).on_press(Message::RemoveWallet(index)),
+                    ]
+                    .spacing(5)
+                    .align_items(Alignment::Center),
+                )
+            });
+
+    // found on disk but not loaded yet
+    let found = discovered
+        .iter()
+        .filter(|(_, pubkey)| wallets.iter().all(|wallet| wallet.pubkey != *pubkey))
+        .fold(Column::new().spacing(5), |column, (path, pubkey)| {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            column.push(
+                button(text(format!("{} {}", file_name, short_address(pubkey))).size(12))
+                    .on_press(Message::FileOpened(Ok(path.clone()))),
+            )
+        });
+
+    column![
+        title,
+        list,
+        text(tr("In the Solana config directory")).size(12),
+        found
+    ]
+    .spacing(10)
+    .width(260)
+    .into()
+}
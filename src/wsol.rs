@@ -0,0 +1,229 @@
+use std::sync::Arc;
+
+use iced::{
+    widget::{button, column, row, text, text_input},
+    Alignment, Command, Element,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction,
+    transaction::Transaction,
+};
+
+use crate::{
+    errors::{report, Error},
+    i18n::{tr, tr_args},
+    palette::accent_color,
+    token_accounts::{
+        associated_token_address, create_associated_token_account, AtaPreview, TOKEN_PROGRAM_ID,
+    },
+    transaction::send_instructions,
+    units::{format_sol, parse_amount},
+    Message,
+};
+
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+// spl token CloseAccount instruction index
+const CLOSE_ACCOUNT: u8 = 9;
+// makes the token amount of a native account match its lamports
+const SYNC_NATIVE: u8 = 17;
+
+// the wSOL panel on the token accounts screen
+#[derive(Debug, Default)]
+pub struct WsolForm {
+    // what closing the wSOL account returns, None until checked
+    pub lamports: Option<u64>,
+    pub amount: String,
+    pub busy: bool,
+    pub signature: String,
+}
+
+impl WsolForm {
+    pub fn refresh(&self, owner: Pubkey, rpc_client: Arc<RpcClient>) -> Command<Message> {
+        Command::perform(
+            async move { fetch_wsol_lamports(&owner, &rpc_client).await },
+            Message::WsolLoaded,
+        )
+    }
+
+    // the cluster, signer and allow-list checks are the app's, done before
+    pub fn start_wrap(
+        &mut self,
+        signer: Arc<Keypair>,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        let lamports = match parse_amount(&self.amount) {
+            Ok(lamports) if lamports > 0 => lamports,
+            _ => return report(Error::InvalidAmount),
+        };
+        self.busy = true;
+        self.signature = String::new();
+        Command::perform(wrap_sol(signer, lamports, rpc_client), Message::WsolUpdated)
+    }
+
+    pub fn start_unwrap(
+        &mut self,
+        signer: Arc<Keypair>,
+        rpc_client: Arc<RpcClient>,
+    ) -> Command<Message> {
+        self.busy = true;
+        self.signature = String::new();
+        Command::perform(unwrap_sol(signer, rpc_client), Message::WsolUpdated)
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::WsolLoaded(result) => match result {
+                Ok(lamports) => {
+                    self.lamports = Some(lamports);
+                    Command::none()
+                }
+                Err(error) => report(error),
+            },
+            Message::WrapAmountChanged(amount) => {
+                self.amount = amount;
+                Command::none()
+            }
+            Message::WsolUpdated(result) => {
+                self.busy = false;
+                match result {
+                    Ok(signature) => {
+                        self.signature = signature;
+                        self.amount = String::new();
+                        Command::none()
+                    }
+                    Err(error) => report(error),
+                }
+            }
+            _ => Command::none(),
+        }
+    }
+}
+
+pub fn wsol_account(owner: &Pubkey) -> Pubkey {
+    associated_token_address(owner, &NATIVE_MINT, &TOKEN_PROGRAM_ID)
+}
+
+// everything closing the wSOL account gives back: the wrapped amount plus
+// the account rent
+pub async fn fetch_wsol_lamports(owner: &Pubkey, rpc_client: &RpcClient) -> Result<u64, Error> {
+    Ok(rpc_client
+        .get_account_with_commitment(&wsol_account(owner), rpc_client.commitment())
+        .await
+        .map_err(|_| Error::FetchAccountError)?
+        .value
+        .map(|account| account.lamports)
+        .unwrap_or(0))
+}
+
+// closing a native token account unwraps all of it back into the owner's SOL
+pub fn close_wsol_instruction(owner: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        TOKEN_PROGRAM_ID,
+        &[CLOSE_ACCOUNT],
+        vec![
+            AccountMeta::new(wsol_account(owner), false),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+fn sync_native_instruction(owner: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        TOKEN_PROGRAM_ID,
+        &[SYNC_NATIVE],
+        vec![AccountMeta::new(wsol_account(owner), false)],
+    )
+}
+
+// creates the wSOL account when missing, moves the lamports in and syncs
+// them, adding to an existing wrapped balance
+pub async fn wrap_sol(
+    signer: Arc<Keypair>,
+    lamports: u64,
+    rpc_client: Arc<RpcClient>,
+) -> Result<String, Error> {
+    let owner = signer.pubkey();
+    let account = AtaPreview {
+        owner,
+        mint: NATIVE_MINT,
+        token_program: TOKEN_PROGRAM_ID,
+        address: wsol_account(&owner),
+        exists: false,
+        owner_off_curve: false,
+    };
+    let instructions = [
+        create_associated_token_account(&owner, &account),
+        system_instruction::transfer(&owner, &account.address, lamports),
+        sync_native_instruction(&owner),
+    ];
+    send_instructions(&rpc_client, &[&*signer], &instructions).await
+}
+
+pub async fn unwrap_sol(signer: Arc<Keypair>, rpc_client: Arc<RpcClient>) -> Result<String, Error> {
+    let owner = signer.pubkey();
+    if fetch_wsol_lamports(&owner, &rpc_client).await? == 0 {
+        return Err(Error::NoWrappedSol);
+    }
+    send_instructions(&rpc_client, &[&*signer], &[close_wsol_instruction(&owner)]).await
+}
+
+pub fn unwraps_wsol(tx: &Transaction) -> bool {
+    tx.message
+        .instructions
+        .iter()
+        .any(|instruction| *instruction.program_id(&tx.message.account_keys) == TOKEN_PROGRAM_ID)
+}
+
+// `lamports` is what closing the account gives back, None until loaded
+pub fn display_wsol(
+    lamports: Option<u64>,
+    amount: &str,
+    busy: bool,
+    signature: &str,
+) -> Element<'static, Message> {
+    let balance = match lamports {
+        Some(0) => String::from(tr("No wrapped SOL")),
+        Some(lamports) => tr_args(
+            "Unwrapping returns {$amount} SOL (wrapped amount plus the account rent)",
+            &[("amount", &format_sol(lamports))],
+        ),
+        None => String::from(tr("Checking wrapped SOL...")),
+    };
+
+    let actions = row![
+        text_input(tr("Amount of SOL to wrap"), amount)
+            .on_input(Message::WrapAmountChanged)
+            .on_submit(Message::WrapSol),
+        if busy {
+            button(tr("Wrap"))
+        } else {
+            button(tr("Wrap")).on_press(Message::WrapSol)
+        },
+        if busy || lamports.unwrap_or(0) == 0 {
+            button(tr("Unwrap all"))
+        } else {
+            button(tr("Unwrap all")).on_press(Message::UnwrapSol)
+        },
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center);
+
+    column![
+        text(tr("Wrapped SOL (wSOL)"))
+            .size(14)
+            .style(accent_color()),
+        text(balance).size(14),
+        actions,
+        text(signature.to_string()).size(14),
+    ]
+    .spacing(10)
+    .into()
+}